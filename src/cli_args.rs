@@ -6,11 +6,12 @@ use walkdir::WalkDir;
 
 use fozzy::{
     ArtifactCommand, CiOptions, CorpusCommand, ExitStatus, ExploreOptions, FlakeBudget,
-    FozzyDuration, FsBackend, FuzzMode, FuzzOptions, FuzzTarget, HttpBackend, InitTemplate,
-    InitTestType, MapCommand, MapSuitesOptions, MemoryCommand, MemoryOptions, ProcBackend,
-    ProfileCaptureLevel, ProfileCommand, ProfileExportFormat, RecordCollisionPolicy, ReportCommand,
-    Reporter, RunOptions, RunSummary, ScenarioPath, ScheduleStrategy, ShrinkCoveragePolicy,
-    ShrinkMinimize, TopologyProfile, TracePath,
+    FozzyDuration, FsBackend, FuzzCommand, FuzzMode, FuzzOptions, FuzzTarget, HistoryCommand,
+    HttpBackend, InitTemplate, InitTestType, MapCommand, MapSuitesOptions, MemoryCommand,
+    MemoryOptions, ProcBackend, ProfileCaptureLevel, ProfileCommand, ProfileExportFormat,
+    RecordCollisionPolicy, ReportCommand, Reporter, RunOptions, RunSummary, ScenarioPath,
+    ScheduleStrategy, ShardStrategy, ShrinkCoveragePolicy, ShrinkMinimize, ShrinkPreserve,
+    TestShard, TopologyProfile, TraceEncoding, TracePath,
 };
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -18,6 +19,10 @@ pub(crate) enum ExecutionReporter {
     Pretty,
     Junit,
     Html,
+    Tap,
+    Sarif,
+    Jsonl,
+    Github,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -31,6 +36,10 @@ impl From<ExecutionReporter> for Reporter {
             ExecutionReporter::Pretty => Reporter::Pretty,
             ExecutionReporter::Junit => Reporter::Junit,
             ExecutionReporter::Html => Reporter::Html,
+            ExecutionReporter::Tap => Reporter::Tap,
+            ExecutionReporter::Sarif => Reporter::Sarif,
+            ExecutionReporter::Jsonl => Reporter::Jsonl,
+            ExecutionReporter::Github => Reporter::Github,
         }
     }
 }
@@ -83,6 +92,11 @@ pub(crate) struct Cli {
     #[arg(long, global = true)]
     http_backend: Option<HttpBackend>,
 
+    /// Encoding of recorded traces (`json`, `binary`, `binary-deflate`); overrides
+    /// `FOZZY_TRACE_ENCODING` and `trace_encoding` in fozzy.toml.
+    #[arg(long, global = true)]
+    trace_encoding: Option<TraceEncoding>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -123,14 +137,26 @@ pub(crate) enum Command {
         #[arg(long)]
         jobs: Option<usize>,
 
+        /// Run only shard `i` of `n` (1-based) of the filtered scenario set.
+        #[arg(long, value_name = "I/N")]
+        shard: Option<TestShard>,
+
+        /// How `--shard` partitions scenarios: round-robin by path, or by previous run durations.
+        #[arg(long, default_value = "count", requires = "shard")]
+        shard_strategy: ShardStrategy,
+
         /// Per-test timeout.
         #[arg(long)]
         timeout: Option<FozzyDuration>,
 
-        /// Name filter expression (substring match; v1).
+        /// Scenario filter expression, e.g. `tag:smoke and not tag:slow` (a bare word matches a path substring).
         #[arg(long)]
         filter: Option<String>,
 
+        /// Print the scenarios the globs and `--filter` select, and why, without running them.
+        #[arg(long, conflicts_with = "watch")]
+        list: bool,
+
         /// Reporter artifact format (`pretty`, `junit`, or `html`). Use global `--json` for machine-readable stdout.
         /// Reporter artifact format (`pretty`, `junit`, or `html`). Use global `--json` for machine-readable stdout.
         #[arg(long, default_value = "pretty")]
@@ -148,6 +174,22 @@ pub(crate) enum Command {
         #[arg(long)]
         fail_fast: bool,
 
+        /// Retry a failing scenario up to N times (first with its seed, then fresh seeds); overrides the scenario's `retries`.
+        #[arg(long, value_name = "N")]
+        retries: Option<u32>,
+
+        /// File listing scenario paths or globs (one per line) whose failures are reported but do not fail the run.
+        #[arg(long, value_name = "FILE")]
+        quarantine: Option<PathBuf>,
+
+        /// Keep running: re-run the affected scenarios whenever a scenario, its grammar or fixture files, or a `--watch-src` file changes.
+        #[arg(long, conflicts_with = "shard")]
+        watch: bool,
+
+        /// Source globs to watch with `--watch`; a change re-runs the scenarios attributed to that file.
+        #[arg(long = "watch-src", value_name = "GLOB", requires = "watch")]
+        watch_src: Vec<String>,
+
         /// Enable deterministic memory tracking capability.
         #[arg(long)]
         mem_track: bool,
@@ -204,6 +246,14 @@ pub(crate) enum Command {
         #[arg(long, default_value = "append")]
         record_collision: RecordCollisionPolicy,
 
+        /// Keep running: re-run the scenario whenever it, its grammar or fixture files, or a `--watch-src` file changes.
+        #[arg(long)]
+        watch: bool,
+
+        /// Source globs to watch with `--watch`.
+        #[arg(long = "watch-src", value_name = "GLOB", requires = "watch")]
+        watch_src: Vec<String>,
+
         #[arg(long)]
         mem_track: bool,
         #[arg(long)]
@@ -227,8 +277,13 @@ pub(crate) enum Command {
     },
 
     /// Coverage-guided or property-based fuzzing
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Fuzz {
-        target: String,
+        #[command(subcommand)]
+        command: Option<FuzzCommand>,
+
+        #[arg(required = true)]
+        target: Option<String>,
 
         #[arg(long)]
         det: bool,
@@ -395,6 +450,39 @@ pub(crate) enum Command {
         /// Reporter artifact format (`pretty`, `junit`, or `html`). Use global `--json` for machine-readable stdout.
         #[arg(long, default_value = "pretty")]
         reporter: ExecutionReporter,
+
+        /// Open the interactive replay debugger (commands are read from stdin).
+        #[arg(long)]
+        debug: bool,
+
+        /// Run debugger commands from this file instead of stdin (implies --debug).
+        #[arg(long)]
+        debug_script: Option<PathBuf>,
+
+        /// Resume from the nearest recorded snapshot at or before this step.
+        #[arg(long, conflicts_with_all = ["from_time", "debug", "debug_script", "overrides"])]
+        from_step: Option<usize>,
+
+        /// Resume from the nearest recorded snapshot at or before this virtual time.
+        #[arg(long, conflicts_with_all = ["debug", "debug_script", "overrides"])]
+        from_time: Option<FozzyDuration>,
+
+        /// Override a recorded decision (`<kind>#<n>:<edit>` or `@<index>:<edit>`), e.g.
+        /// `http_request#3:status_code=500`, `net_drop#1:flip`, `proc_spawn#1:timeout`.
+        #[arg(long = "override", value_name = "SPEC", conflicts_with_all = ["debug", "debug_script"])]
+        overrides: Vec<fozzy::DecisionOverride>,
+
+        /// After the last override, keep following the trace (`scripted`) or run on the live backends (`live`).
+        #[arg(long = "continue", default_value = "scripted", requires = "overrides")]
+        continuation: fozzy::Continuation,
+
+        /// Where to write the counterfactual trace (defaults to the run artifacts directory).
+        #[arg(long, requires = "overrides")]
+        record: Option<PathBuf>,
+
+        /// Behavior when --record target exists: error, overwrite, or append with numeric suffix.
+        #[arg(long, default_value = "error", requires = "overrides")]
+        record_collision: RecordCollisionPolicy,
     },
 
     /// Inspect and verify trace-file integrity/versioning
@@ -419,6 +507,18 @@ pub(crate) enum Command {
         #[arg(long, default_value = "all")]
         minimize: ShrinkMinimize,
 
+        /// What a reduction must keep: the failure status, or also the original finding.
+        #[arg(long, default_value = "status")]
+        preserve: ShrinkPreserve,
+
+        /// Keep only reductions with a finding whose title or message matches this regex.
+        #[arg(long)]
+        finding_match: Option<String>,
+
+        /// Number of candidates evaluated in parallel (the shrunk trace is the same for any value).
+        #[arg(long)]
+        jobs: Option<usize>,
+
         /// Use global `--json` for machine-readable output.
         #[arg(long, default_value = "pretty")]
         reporter: PrettyReporter,
@@ -442,6 +542,12 @@ pub(crate) enum Command {
         command: ReportCommand,
     },
 
+    /// Query the run history index: pass rate, trends, first failure, flake rate
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+
     /// Inspect memory artifacts and summaries
     Memory {
         #[command(subcommand)]
@@ -497,6 +603,12 @@ pub(crate) enum Command {
         /// Maximum allowed p99 latency delta percent vs --perf-baseline.
         #[arg(long = "max-p99-delta-pct")]
         max_p99_delta_pct: Option<f64>,
+        /// Fail unless the trace carries a valid signature from the --signing-key key.
+        #[arg(long, requires = "signing_key")]
+        require_signed: bool,
+        /// Key file used to validate (keyed) or pin (ed25519) the trace signature.
+        #[arg(long)]
+        signing_key: Option<PathBuf>,
     },
 
     /// Run strict deterministic gate checks with optional scoped targeting.
@@ -510,6 +622,12 @@ pub(crate) enum Command {
         /// Substring scope matcher applied to scenario paths (comma-separated).
         #[arg(long, value_delimiter = ',')]
         scope: Vec<String>,
+        /// Scenario filter expression applied to in-scope scenarios (see `fozzy test --filter`).
+        #[arg(long)]
+        filter: Option<String>,
+        /// Print the in-scope scenarios the filter selects, and why, without running the gate.
+        #[arg(long)]
+        list: bool,
         /// Deterministic seed for reproducible runs.
         #[arg(long)]
         seed: Option<u64>,
@@ -561,10 +679,14 @@ pub(crate) enum Command {
         #[arg(long)]
         allow_expected_failures: bool,
 
-        /// Run only scenarios whose path contains this substring.
+        /// Run only scenarios this filter expression selects (see `fozzy test --filter`).
         #[arg(long)]
         scenario_filter: Option<String>,
 
+        /// Print the scenarios `--scenario-filter` selects, and why, without running the gate.
+        #[arg(long)]
+        list: bool,
+
         /// Skip specific full steps (comma-separated list).
         #[arg(long, value_delimiter = ',')]
         skip_steps: Vec<String>,
@@ -593,8 +715,53 @@ pub(crate) enum Command {
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum TraceCommand {
-    /// Verify checksum/integrity and schema warnings for a .fozzy trace
-    Verify { path: PathBuf },
+    /// Verify checksum/integrity, signature and schema warnings for a .fozzy trace
+    Verify {
+        path: PathBuf,
+        /// Key file to validate (keyed) or pin (ed25519) the trace signature
+        #[arg(long)]
+        signing_key: Option<PathBuf>,
+    },
+    /// Align two traces and report the first divergent decision (exits 1 when they diverge)
+    Diff { left: PathBuf, right: PathBuf },
+    /// Export a trace (path, run id or alias) as Chrome trace events or a Perfetto trace
+    Export {
+        trace: String,
+        #[arg(long)]
+        format: fozzy::TraceExportFormat,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Redact secrets from a trace using the `[redact]` rules in fozzy.toml
+    Redact {
+        path: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Migrate older traces to the current schema (or `--check` which still replay)
+    Upgrade {
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        #[arg(long, required_unless_present = "check", conflicts_with = "check")]
+        out: Option<PathBuf>,
+        /// Report replayability of traces (files or directories) without writing
+        #[arg(long)]
+        check: bool,
+    },
+    /// Sign a trace with a keyed-blake3 secret or an ed25519 key file
+    Sign {
+        path: PathBuf,
+        #[arg(long)]
+        key: PathBuf,
+        /// Output path (defaults to signing in place)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Generate an ed25519 signing key (`<out>`) and its public key (`<out>.pub`)
+    Keygen {
+        #[arg(long)]
+        out: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize)]
@@ -662,6 +829,8 @@ pub(crate) struct GateReport {
     #[serde(rename = "scenarioRoot")]
     scenario_root: String,
     scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<String>,
     #[serde(rename = "matchedScenarios")]
     matched_scenarios: Vec<String>,
     steps: Vec<FullStepResult>,
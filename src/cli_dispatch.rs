@@ -32,11 +32,18 @@ pub(super) fn run_command(
             det,
             seed,
             jobs,
+            shard,
+            shard_strategy,
             timeout,
             filter,
+            list,
             reporter,
             record,
             fail_fast,
+            retries,
+            quarantine,
+            watch,
+            watch_src,
             record_collision,
             mem_track,
             mem_limit_mb,
@@ -47,6 +54,11 @@ pub(super) fn run_command(
             leak_budget,
             mem_artifacts,
         } => {
+            if *list {
+                let report = fozzy::list_test_scenarios(config, globs, filter.as_deref())?;
+                logger.print_scenario_selection(&report)?;
+                return Ok(ExitCode::SUCCESS);
+            }
             let memory = resolve_memory_options(
                 config,
                 *mem_track,
@@ -58,27 +70,67 @@ pub(super) fn run_command(
                 *fail_on_leak,
                 *leak_budget,
             );
-            let run = fozzy::run_tests(
-                config,
-                globs,
-                &RunOptions {
-                    det: *det,
-                    seed: *seed,
-                    timeout: timeout.map(|d| d.0),
-                    reporter: (*reporter).into(),
-                    record_trace_to: record.clone(),
-                    filter: filter.clone(),
-                    jobs: *jobs,
-                    fail_fast: *fail_fast,
-                    record_collision: *record_collision,
-                    profile_capture: fozzy::ProfileCaptureLevel::Baseline,
-                    proc_backend,
-                    fs_backend,
-                    http_backend,
-                    memory,
-                },
-            )?;
-            logger.print_run_summary(&run.summary)?;
+            let opt = RunOptions {
+                det: *det,
+                seed: *seed,
+                timeout: timeout.map(|d| d.0),
+                reporter: (*reporter).into(),
+                record_trace_to: record.clone(),
+                filter: filter.clone(),
+                jobs: *jobs,
+                shard: *shard,
+                shard_strategy: *shard_strategy,
+                fail_fast: *fail_fast,
+                retries: *retries,
+                quarantine: quarantine.clone(),
+                record_collision: *record_collision,
+                profile_capture: fozzy::ProfileCaptureLevel::Baseline,
+                proc_backend,
+                fs_backend,
+                http_backend,
+                memory,
+            };
+            if *watch {
+                let watch = WatchOptions {
+                    scenarios: globs.clone(),
+                    sources: watch_src.clone(),
+                };
+                if let Some(filter) = filter {
+                    filter.parse::<ScenarioFilter>()?;
+                }
+                return watch_and_rerun(config, logger, &watch, |scenarios| {
+                    let scenarios: Vec<String> = match filter {
+                        Some(filter) => {
+                            let selection = fozzy::select_scenarios(
+                                scenarios,
+                                Some(filter),
+                                &config.runs_dir(),
+                            )?;
+                            for warning in &selection.warnings {
+                                logger.print_warning(warning);
+                            }
+                            selection.selected_paths()
+                        }
+                        None => scenarios.to_vec(),
+                    }
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                    if scenarios.is_empty() {
+                        return Ok(None);
+                    }
+                    let run = fozzy::run_tests(config, &scenarios, &opt)?;
+                    for warning in &run.warnings {
+                        logger.print_warning(warning);
+                    }
+                    Ok(Some(run.summary))
+                });
+            }
+            let run = fozzy::run_tests(config, globs, &opt)?;
+            for warning in &run.warnings {
+                logger.print_warning(warning);
+            }
+            logger.print_reported_run(&run.summary, (*reporter).into())?;
             enforce_strict_run(cli, &run.summary)?;
             Ok(exit_code_for_status(run.summary.status))
         }
@@ -90,6 +142,8 @@ pub(super) fn run_command(
             reporter,
             record,
             record_collision,
+            watch,
+            watch_src,
             mem_track,
             mem_limit_mb,
             mem_fail_after,
@@ -111,31 +165,58 @@ pub(super) fn run_command(
                 *fail_on_leak,
                 *leak_budget,
             );
-            let run = fozzy::run_scenario(
-                config,
-                ScenarioPath::new(scenario.clone()),
-                &RunOptions {
-                    det: *det,
-                    seed: *seed,
-                    timeout: timeout.map(|d| d.0),
-                    reporter: (*reporter).into(),
-                    record_trace_to: record.clone(),
-                    filter: None,
-                    jobs: None,
-                    fail_fast: false,
-                    record_collision: *record_collision,
-                    profile_capture: *profile_capture,
-                    proc_backend,
-                    fs_backend,
-                    http_backend,
-                    memory,
-                },
-            )?;
+            let opt = RunOptions {
+                det: *det,
+                seed: *seed,
+                timeout: timeout.map(|d| d.0),
+                reporter: (*reporter).into(),
+                record_trace_to: record.clone(),
+                filter: None,
+                jobs: None,
+                shard: None,
+                shard_strategy: fozzy::ShardStrategy::Count,
+                fail_fast: false,
+                retries: None,
+                quarantine: None,
+                record_collision: *record_collision,
+                profile_capture: *profile_capture,
+                proc_backend,
+                fs_backend,
+                http_backend,
+                memory,
+            };
+            if *watch {
+                let watch = WatchOptions {
+                    scenarios: vec![scenario.display().to_string()],
+                    sources: watch_src.clone(),
+                };
+                return watch_and_rerun(config, logger, &watch, |_| {
+                    let run =
+                        fozzy::run_scenario(config, ScenarioPath::new(scenario.clone()), &opt)?;
+                    for warning in &run.warnings {
+                        logger.print_warning(warning);
+                    }
+                    Ok(Some(run.summary))
+                });
+            }
+            let run = fozzy::run_scenario(config, ScenarioPath::new(scenario.clone()), &opt)?;
+            for warning in &run.warnings {
+                logger.print_warning(warning);
+            }
             logger.print_run_summary(&run.summary)?;
             enforce_strict_run(cli, &run.summary)?;
             Ok(exit_code_for_status(run.summary.status))
         }
         Command::Fuzz {
+            command: Some(command),
+            ..
+        } => {
+            let out = fozzy::fuzz_command(config, command)?;
+            logger.print_serialized(&out)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Fuzz {
+            command: None,
             target,
             det,
             mode,
@@ -172,7 +253,10 @@ pub(super) fn run_command(
                 *fail_on_leak,
                 *leak_budget,
             );
-            let target: FuzzTarget = target.parse()?;
+            let target: FuzzTarget = target
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("fuzz requires a target"))?
+                .parse()?;
             let run = fozzy::fuzz(
                 config,
                 &target,
@@ -195,7 +279,7 @@ pub(super) fn run_command(
                     memory,
                 },
             )?;
-            logger.print_run_summary(&run.summary)?;
+            logger.print_reported_run(&run.summary, (*reporter).into())?;
             enforce_strict_run(cli, &run.summary)?;
             Ok(exit_code_for_status(run.summary.status))
         }
@@ -254,7 +338,7 @@ pub(super) fn run_command(
                     memory,
                 },
             )?;
-            logger.print_run_summary(&run.summary)?;
+            logger.print_reported_run(&run.summary, (*reporter).into())?;
             enforce_strict_run(cli, &run.summary)?;
             Ok(exit_code_for_status(run.summary.status))
         }
@@ -268,7 +352,46 @@ pub(super) fn run_command(
             profile_export_format,
             profile_export_out,
             reporter,
+            debug,
+            debug_script,
+            from_step,
+            from_time,
+            overrides,
+            continuation,
+            record,
+            record_collision,
         } => {
+            if !overrides.is_empty() {
+                let run = fozzy::whatif_replay_trace(
+                    config,
+                    TracePath::new(trace.clone()),
+                    &fozzy::WhatIfOptions {
+                        overrides: overrides.clone(),
+                        continuation: *continuation,
+                        out_trace_path: record.clone(),
+                        record_collision: *record_collision,
+                        proc_backend,
+                        fs_backend,
+                        http_backend,
+                        reporter: (*reporter).into(),
+                    },
+                )?;
+                logger.print_run_summary(&run.summary)?;
+                enforce_strict_run(cli, &run.summary)?;
+                return Ok(exit_code_for_status(run.summary.status));
+            }
+            if *debug || debug_script.is_some() {
+                let report = fozzy::debug_replay_trace(
+                    config,
+                    TracePath::new(trace.clone()),
+                    debug_script.as_deref(),
+                    !cli.json,
+                )?;
+                if cli.json {
+                    logger.print_serialized(&report)?;
+                }
+                return Ok(ExitCode::SUCCESS);
+            }
             let run = fozzy::replay_trace(
                 config,
                 TracePath::new(trace.clone()),
@@ -282,6 +405,9 @@ pub(super) fn run_command(
                         *profile_capture
                     },
                     reporter: (*reporter).into(),
+                    start: from_step
+                        .map(fozzy::ReplayStart::Step)
+                        .or(from_time.map(|d| fozzy::ReplayStart::Time(d.0))),
                 },
             )?;
             if let (Some(format), Some(out)) = (profile_export_format, profile_export_out.as_ref())
@@ -307,11 +433,20 @@ pub(super) fn run_command(
         }
         Command::Trace { command } => {
             match command {
-                TraceCommand::Verify { path } => {
-                    let out = fozzy::verify_trace_file(path)?;
+                TraceCommand::Verify { path, signing_key } => {
+                    let key = signing_key
+                        .as_deref()
+                        .map(fozzy::TraceSigningKey::load)
+                        .transpose()?;
+                    let out = fozzy::verify_trace_file_with_key(path, key.as_ref())?;
+                    let signature_invalid = out
+                        .signature
+                        .as_ref()
+                        .is_some_and(|s| s.valid == Some(false));
                     if strict_enabled(cli)
                         && (!out.checksum_present
                             || !out.checksum_valid
+                            || signature_invalid
                             || !out.warnings.is_empty())
                     {
                         let mut reasons = Vec::new();
@@ -321,6 +456,9 @@ pub(super) fn run_command(
                         if !out.checksum_valid {
                             reasons.push("checksum invalid".to_string());
                         }
+                        if signature_invalid {
+                            reasons.push("signature invalid".to_string());
+                        }
                         if !out.warnings.is_empty() {
                             reasons.push(format!("warnings: {}", out.warnings.join("; ")));
                         }
@@ -331,6 +469,59 @@ pub(super) fn run_command(
                     }
                     logger.print_serialized(&out)?;
                 }
+                TraceCommand::Diff { left, right } => {
+                    let out = fozzy::diff_traces(left, right)?;
+                    logger.print_serialized(&out)?;
+                    if !out.identical {
+                        return Ok(ExitCode::from(1));
+                    }
+                }
+                TraceCommand::Export { trace, format, out } => {
+                    let report = fozzy::export_trace(config, trace, *format, out)?;
+                    logger.print_serialized(&report)?;
+                }
+                TraceCommand::Redact { path, out } => {
+                    let report = fozzy::redact_trace_file(config, path, out)?;
+                    logger.print_serialized(&report)?;
+                }
+                TraceCommand::Upgrade { inputs, out, check } => {
+                    if *check {
+                        let report = fozzy::check_trace_upgrades(inputs)?;
+                        if strict_enabled(cli) && report.unsupported > 0 {
+                            let failed: Vec<String> = report
+                                .traces
+                                .iter()
+                                .filter(|t| !t.replayable)
+                                .map(|t| t.path.clone())
+                                .collect();
+                            return Err(anyhow::anyhow!(
+                                "strict mode: {} trace(s) cannot be replayed or upgraded ({})",
+                                failed.len(),
+                                failed.join(", ")
+                            ));
+                        }
+                        logger.print_serialized(&report)?;
+                    } else {
+                        let [input] = inputs.as_slice() else {
+                            return Err(anyhow::anyhow!(
+                                "trace upgrade takes exactly one input trace (use --check for many)"
+                            ));
+                        };
+                        let out = out.as_deref().expect("clap requires --out without --check");
+                        let report = fozzy::upgrade_trace_file(input, out)?;
+                        logger.print_serialized(&report)?;
+                    }
+                }
+                TraceCommand::Sign { path, key, out } => {
+                    let key = fozzy::TraceSigningKey::load(key)?;
+                    let out = out.as_deref().unwrap_or(path);
+                    let report = fozzy::sign_trace_file(path, out, &key)?;
+                    logger.print_serialized(&report)?;
+                }
+                TraceCommand::Keygen { out } => {
+                    let report = fozzy::generate_signing_key(out)?;
+                    logger.print_serialized(&report)?;
+                }
             }
             Ok(ExitCode::SUCCESS)
         }
@@ -340,6 +531,9 @@ pub(super) fn run_command(
             budget,
             aggressive,
             minimize,
+            preserve,
+            finding_match,
+            jobs,
             reporter: _,
         } => {
             let result = fozzy::shrink_trace(
@@ -350,6 +544,9 @@ pub(super) fn run_command(
                     budget: budget.map(|d| d.0),
                     aggressive: *aggressive,
                     minimize: *minimize,
+                    preserve: *preserve,
+                    finding_match: finding_match.clone(),
+                    jobs: *jobs,
                 },
             )?;
             logger.print_run_summary(&result.result.summary)?;
@@ -371,6 +568,11 @@ pub(super) fn run_command(
             logger.print_serialized(&out)?;
             Ok(ExitCode::SUCCESS)
         }
+        Command::History { command } => {
+            let out = fozzy::history_command(config, command)?;
+            logger.print_serialized(&out)?;
+            Ok(ExitCode::SUCCESS)
+        }
         Command::Memory { command } => {
             let out = fozzy::memory_command(config, command)?;
             logger.print_serialized(&out)?;
@@ -382,6 +584,17 @@ pub(super) fn run_command(
             Ok(ExitCode::SUCCESS)
         }
         Command::Map { command } => {
+            if let MapCommand::Suites {
+                scenario_root,
+                filter,
+                list: true,
+                ..
+            } = command
+            {
+                let report = fozzy::map_suites_selection(config, scenario_root, filter.as_deref())?;
+                logger.print_scenario_selection(&report)?;
+                return Ok(ExitCode::SUCCESS);
+            }
             let out = match command {
                 MapCommand::Suites { .. } => run_map_with_timeout(config, command)?,
                 _ => fozzy::map_command(config, command)?,
@@ -435,6 +648,8 @@ pub(super) fn run_command(
             flake_budget,
             perf_baseline,
             max_p99_delta_pct,
+            require_signed,
+            signing_key,
         } => {
             let out = fozzy::ci_evaluate(
                 config,
@@ -444,6 +659,8 @@ pub(super) fn run_command(
                     flake_budget_pct: *flake_budget,
                     perf_baseline: perf_baseline.clone(),
                     max_p99_delta_pct: *max_p99_delta_pct,
+                    require_signed: *require_signed,
+                    signing_key: signing_key.clone(),
                     strict: strict_enabled(cli),
                 },
             )?;
@@ -458,14 +675,27 @@ pub(super) fn run_command(
             profile,
             scenario_root,
             scope,
+            filter,
+            list,
             seed,
             doctor_runs,
         } => {
+            if *list {
+                let report = cli_workflows::list_gate_scenarios(
+                    config,
+                    scenario_root,
+                    scope,
+                    filter.as_deref(),
+                )?;
+                logger.print_scenario_selection(&report)?;
+                return Ok(ExitCode::SUCCESS);
+            }
             let report = cli_workflows::run_gate_command(
                 config,
                 *profile,
                 scenario_root,
                 scope,
+                filter.as_deref(),
                 *seed,
                 *doctor_runs,
                 strict_enabled(cli),
@@ -568,6 +798,7 @@ pub(super) fn run_command(
             explore_nodes,
             allow_expected_failures,
             scenario_filter,
+            list,
             skip_steps,
             required_steps,
             require_topology_coverage,
@@ -575,6 +806,15 @@ pub(super) fn run_command(
             topology_profile,
             topology_shrink_policy,
         } => {
+            if *list {
+                let report = cli_workflows::list_full_scenarios(
+                    config,
+                    scenario_root,
+                    scenario_filter.as_deref(),
+                )?;
+                logger.print_scenario_selection(&report)?;
+                return Ok(ExitCode::SUCCESS);
+            }
             let report = cli_workflows::run_full_command(
                 config,
                 scenario_root,
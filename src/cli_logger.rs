@@ -1,5 +1,5 @@
 use anyhow::Result;
-use fozzy::{RunSummary, UsageDoc};
+use fozzy::{Reporter, RunSummary, ScenarioSelectionReport, UsageDoc, WatchCycleReport};
 use serde::Serialize;
 use serde_json::Value;
 
@@ -24,6 +24,20 @@ impl CliLogger {
         Ok(())
     }
 
+    /// Prints a `test`/`fuzz`/`explore` summary for the selected reporter. The JSON Lines
+    /// reporter has already streamed the run to stdout, ending with `run_finished`.
+    pub fn print_reported_run(&self, summary: &RunSummary, reporter: Reporter) -> Result<()> {
+        match reporter {
+            Reporter::Jsonl => Ok(()),
+            Reporter::Github if !self.json => {
+                self.print_run_summary(summary)?;
+                print!("{}", fozzy::render_github_annotations(summary));
+                Ok(())
+            }
+            _ => self.print_run_summary(summary),
+        }
+    }
+
     pub fn print_run_summary(&self, summary: &RunSummary) -> Result<()> {
         if self.json {
             self.print_json(summary)?;
@@ -69,6 +83,33 @@ impl CliLogger {
                 tests.failed,
                 tests.skipped
             ));
+            if tests.flaky > 0 || tests.quarantined > 0 {
+                out.push_str(&format!(
+                    "{} flaky={} quarantined={}\n",
+                    self.style("retries", "90"),
+                    tests.flaky,
+                    tests.quarantined
+                ));
+            }
+        }
+        for retry in &summary.retries {
+            let (passed, failed) = retry.seeds_by_outcome();
+            let verdict = match retry.classification {
+                Some(fozzy::FailureClassification::Flaky) => self.style("flaky", "33;1"),
+                Some(fozzy::FailureClassification::Deterministic) => {
+                    self.style("deterministic", "31;1")
+                }
+                None => self.style("not retried", "90"),
+            };
+            out.push_str(&format!(
+                "  {verdict}{} {} passed seeds {passed:?} failed seeds {failed:?}\n",
+                if retry.quarantined {
+                    " (quarantined)"
+                } else {
+                    ""
+                },
+                retry.path
+            ));
         }
 
         if let Some(mem) = &summary.memory {
@@ -111,6 +152,106 @@ impl CliLogger {
         Ok(())
     }
 
+    /// One line per `--watch` cycle (compact JSON with `--json`), plus any findings.
+    pub fn print_watch_cycle(&self, report: &WatchCycleReport) -> Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string(report)?);
+            return Ok(());
+        }
+
+        let status = match report.status {
+            fozzy::ExitStatus::Pass => self.style("PASS", "32;1"),
+            fozzy::ExitStatus::Fail => self.style("FAIL", "31;1"),
+            fozzy::ExitStatus::Timeout => self.style("TIMEOUT", "33;1"),
+            fozzy::ExitStatus::Crash => self.style("CRASH", "31;1"),
+            fozzy::ExitStatus::Error => self.style("ERROR", "31;1"),
+        };
+        let mut out = format!(
+            "{} {status}",
+            self.style(&format!("watch #{}", report.cycle), "36;1")
+        );
+        if let Some(tests) = &report.tests {
+            out.push_str(&format!(
+                " pass={} fail={} skip={}",
+                tests.passed, tests.failed, tests.skipped
+            ));
+        }
+        out.push_str(&format!(" {}ms", report.duration_ms));
+        if report.changed.is_empty() {
+            out.push_str(&format!(
+                " {} {}",
+                self.style("ran", "90"),
+                report.scenarios.len()
+            ));
+        } else {
+            out.push_str(&format!(
+                " {} {} {} {}",
+                self.style("changed", "90"),
+                report.changed.join(", "),
+                self.style("ran", "90"),
+                report.scenarios.join(", ")
+            ));
+        }
+        for finding in &report.findings {
+            out.push_str(&format!(
+                "\n  - [{}] {}: {}",
+                format!("{:?}", finding.kind).to_lowercase(),
+                finding.title,
+                finding.message
+            ));
+        }
+        println!("{out}");
+        Ok(())
+    }
+
+    /// `--list`: one line per candidate scenario, `+` selected and `-` filtered out.
+    pub fn print_scenario_selection(&self, report: &ScenarioSelectionReport) -> Result<()> {
+        for warning in &report.warnings {
+            self.print_warning(warning);
+        }
+        if self.json {
+            self.print_json(report)?;
+            return Ok(());
+        }
+
+        let mut out = format!(
+            "{} {}/{} selected\n",
+            self.style("scenarios", "36;1"),
+            report.selected,
+            report.total
+        );
+        if let Some(filter) = &report.filter {
+            out.push_str(&format!("{} {filter}\n", self.style("filter", "90")));
+        }
+        for entry in &report.scenarios {
+            let marker = if entry.selected {
+                self.style("+", "32;1")
+            } else {
+                self.style("-", "90")
+            };
+            out.push_str(&format!("{marker} {}", entry.path));
+            let mut meta = Vec::new();
+            if !entry.meta.tags.is_empty() {
+                meta.push(format!("tags={}", entry.meta.tags.join(",")));
+            }
+            if let Some(owner) = &entry.meta.owner {
+                meta.push(format!("owner={owner}"));
+            }
+            if let Some(tier) = &entry.meta.tier {
+                meta.push(format!("tier={tier}"));
+            }
+            if !meta.is_empty() {
+                out.push_str(&format!(" {}", self.style(&meta.join(" "), "90")));
+            }
+            if let Some(reason) = &entry.reason {
+                out.push_str(&format!("\n    {reason}"));
+            }
+            out.push('\n');
+        }
+        println!("{}", out.trim_end());
+        Ok(())
+    }
+
     pub fn print_usage(&self, doc: &UsageDoc) -> Result<()> {
         if self.json {
             self.print_json(doc)?;
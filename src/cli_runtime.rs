@@ -100,6 +100,26 @@ mod tests {
     }
 }
 
+/// Runs `run` for every `--watch` cycle and prints a one-line summary of each. A
+/// failing cycle is reported and watching continues; only setup errors return.
+pub(super) fn watch_and_rerun(
+    config: &Config,
+    logger: &CliLogger,
+    opt: &WatchOptions,
+    mut run: impl FnMut(&[PathBuf]) -> FozzyResult<Option<RunSummary>>,
+) -> anyhow::Result<ExitCode> {
+    fozzy::watch_scenarios(config, opt, |cycle| match run(&cycle.scenarios) {
+        Ok(Some(summary)) => {
+            if let Err(err) = logger.print_watch_cycle(&WatchCycleReport::new(cycle, &summary)) {
+                logger.print_warning(&format!("failed to print watch cycle: {err:#}"));
+            }
+        }
+        Ok(None) => {}
+        Err(err) => logger.print_warning(&format!("watch cycle {} failed: {err}", cycle.cycle)),
+    })?;
+    Ok(ExitCode::SUCCESS)
+}
+
 pub(super) fn strict_enabled(cli: &Cli) -> bool {
     cli.strict || !cli.unsafe_mode
 }
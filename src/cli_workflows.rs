@@ -13,7 +13,8 @@ mod topology;
 pub(crate) use checks::*;
 pub(crate) use profile::*;
 pub(crate) use runner::{
-    run_full_command, run_gate_command, selected_init_test_types, shrink_status_matches,
+    list_full_scenarios, list_gate_scenarios, run_full_command, run_gate_command,
+    selected_init_test_types, shrink_status_matches,
 };
 pub(crate) use topology::*;
 
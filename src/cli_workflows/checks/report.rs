@@ -261,18 +261,22 @@ pub(crate) fn trace_summary_identity_status(
     let trace_exists = std::fs::metadata(trace_path)
         .map(|metadata| metadata.is_file() && metadata.len() > 0)
         .unwrap_or(false);
-    let trace_content_matches = std::fs::read(trace_path)
-        .ok()
-        .and_then(|bytes| serde_json::from_slice::<fozzy::TraceFile>(&bytes).ok())
-        .is_some_and(|trace| {
-            trace.summary.identity.run_id == summary.identity.run_id
-                && trace.summary.identity.seed == summary.identity.seed
-                && trace.summary.mode == summary.mode
-                && trace.summary.status == summary.status
-                && trace.summary.identity.trace_path == summary.identity.trace_path
-                && trace.summary.identity.report_path == summary.identity.report_path
-                && trace.summary.identity.artifacts_dir == summary.identity.artifacts_dir
-        });
+    let trace = if fozzy::is_binary_trace_file(trace_path) {
+        fozzy::TraceFile::read_json(trace_path).ok()
+    } else {
+        std::fs::read(trace_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<fozzy::TraceFile>(&bytes).ok())
+    };
+    let trace_content_matches = trace.is_some_and(|trace| {
+        trace.summary.identity.run_id == summary.identity.run_id
+            && trace.summary.identity.seed == summary.identity.seed
+            && trace.summary.mode == summary.mode
+            && trace.summary.status == summary.status
+            && trace.summary.identity.trace_path == summary.identity.trace_path
+            && trace.summary.identity.report_path == summary.identity.report_path
+            && trace.summary.identity.artifacts_dir == summary.identity.artifacts_dir
+    });
     (
         trace_exists && trace_content_matches,
         format!(
@@ -8,6 +8,6 @@ mod gate;
 mod shared;
 
 pub(crate) use full::run_full_command;
-pub(crate) use gate::run_gate_command;
+pub(crate) use gate::{list_gate_scenarios, run_gate_command};
 use shared::*;
-pub(crate) use shared::{selected_init_test_types, shrink_status_matches};
+pub(crate) use shared::{list_full_scenarios, selected_init_test_types, shrink_status_matches};
@@ -258,6 +258,7 @@ fn run_corpus(state: &mut FullRunState, config: &Config) {
         &CorpusCommand::Minimize {
             dir: corpus_dir.clone(),
             budget: None,
+            target: None,
         },
     ) {
         Ok(value) => {
@@ -331,7 +332,11 @@ fn run_host_backends(
                     record_trace_to: Some(host_trace.clone()),
                     filter: None,
                     jobs: None,
+                    shard: None,
+                    shard_strategy: crate::ShardStrategy::Count,
                     fail_fast: false,
+                    retries: None,
+                    quarantine: None,
                     record_collision: RecordCollisionPolicy::Error,
                     profile_capture: ProfileCaptureLevel::Baseline,
                     proc_backend: fozzy::ProcBackend::Host,
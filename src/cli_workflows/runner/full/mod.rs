@@ -33,6 +33,9 @@ pub(crate) fn run_full_command(
     topology_shrink_policy: ShrinkCoveragePolicy,
     stream_json_events: bool,
 ) -> anyhow::Result<FullReport> {
+    if let Some(filter) = scenario_filter {
+        filter.parse::<fozzy::ScenarioFilter>()?;
+    }
     let seed = Some(resolved_workflow_seed(seed));
     let mut state = FullRunState::new(strict, unsafe_mode, scenario_root, stream_json_events);
     state.start_phase(
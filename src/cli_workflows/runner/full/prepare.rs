@@ -75,16 +75,7 @@ pub(super) fn prepare_scenarios(
             return None;
         }
     };
-    if let Some(filter) = scenario_filter {
-        if !filter.is_empty() {
-            discovered
-                .steps
-                .retain(|path| path.to_string_lossy().contains(filter));
-            discovered
-                .distributed
-                .retain(|path| path.to_string_lossy().contains(filter));
-        }
-    }
+    filter_discovered_scenarios(&mut discovered, scenario_filter, &config.runs_dir());
 
     let parse_error_count = discovered.parse_errors.len();
     state.push(
@@ -236,6 +227,7 @@ fn push_topology_step(
             only_required: false,
             only_uncovered: false,
             max_matched_scenarios: 25,
+            filter: None,
         };
         match run_with_timeout("topology_coverage", TOPOLOGY_TIMEOUT, move || {
             Ok(fozzy::map_suites(&options)?)
@@ -90,7 +90,11 @@ pub(super) fn run_deterministic_surface(
                     record_trace_to: None,
                     filter: None,
                     jobs: None,
+                    shard: None,
+                    shard_strategy: crate::ShardStrategy::Count,
                     fail_fast: false,
+                    retries: None,
+                    quarantine: None,
                     record_collision: RecordCollisionPolicy::Error,
                     profile_capture: ProfileCaptureLevel::Baseline,
                     proc_backend: config.proc_backend,
@@ -149,7 +153,11 @@ pub(super) fn run_deterministic_surface(
                     record_trace_to: Some(trace_path),
                     filter: None,
                     jobs: None,
+                    shard: None,
+                    shard_strategy: crate::ShardStrategy::Count,
                     fail_fast: false,
+                    retries: None,
+                    quarantine: None,
                     record_collision: RecordCollisionPolicy::Overwrite,
                     profile_capture: ProfileCaptureLevel::Baseline,
                     proc_backend: config.proc_backend,
@@ -280,6 +288,8 @@ pub(super) fn run_trace_surface(
                     flake_budget_pct: None,
                     perf_baseline: None,
                     max_p99_delta_pct: None,
+                    require_signed: false,
+                    signing_key: None,
                     strict,
                 },
             )?)
@@ -317,6 +327,9 @@ pub(super) fn run_trace_surface(
                     budget: None,
                     aggressive: false,
                     minimize: ShrinkMinimize::All,
+                    preserve: fozzy::ShrinkPreserve::Status,
+                    finding_match: None,
+                    jobs: None,
                 },
             )?)
         }
@@ -400,6 +413,7 @@ fn run_replay_step(
                     dump_events: false,
                     profile_capture: ProfileCaptureLevel::Baseline,
                     reporter: Reporter::Json,
+                    start: None,
                 },
             )?)
         }
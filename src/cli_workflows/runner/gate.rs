@@ -20,15 +20,53 @@ pub(crate) fn clean_tree_step_status(check: &GitWorktreeCheck) -> (FullStepStatu
     }
 }
 
+/// Lowercased, non-empty `--scope` tokens.
+fn scope_tokens(scopes: &[String]) -> Vec<String> {
+    scopes
+        .iter()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn in_scope(path: &Path, scope_tokens: &[String]) -> bool {
+    if scope_tokens.is_empty() {
+        return true;
+    }
+    let key = path.to_string_lossy().to_ascii_lowercase();
+    scope_tokens.iter().any(|token| key.contains(token))
+}
+
+/// `fozzy gate --list`: the in-scope step scenarios and the filter's verdict on each.
+pub(crate) fn list_gate_scenarios(
+    config: &Config,
+    scenario_root: &Path,
+    scopes: &[String],
+    filter: Option<&str>,
+) -> FozzyResult<ScenarioSelectionReport> {
+    let scope_tokens = scope_tokens(scopes);
+    let paths: Vec<PathBuf> = discover_scenarios(scenario_root)
+        .steps
+        .into_iter()
+        .filter(|path| in_scope(path, &scope_tokens))
+        .collect();
+    fozzy::select_scenarios(&paths, filter, &config.runs_dir())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run_gate_command(
     config: &Config,
     profile: GateProfile,
     scenario_root: &Path,
     scopes: &[String],
+    filter: Option<&str>,
     seed: Option<u64>,
     doctor_runs: u32,
     strict: bool,
 ) -> anyhow::Result<GateReport> {
+    if let Some(filter) = filter {
+        filter.parse::<fozzy::ScenarioFilter>()?;
+    }
     let seed = Some(resolved_workflow_seed(seed));
     let mut steps = Vec::<FullStepResult>::new();
     let mut push = |name: &str, status: FullStepStatus, detail: String| {
@@ -82,23 +120,27 @@ pub(crate) fn run_gate_command(
         );
     }
 
-    let scope_tokens: Vec<String> = scopes
-        .iter()
-        .map(|s| s.trim().to_ascii_lowercase())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let scope_tokens = scope_tokens(scopes);
     let mut targets: Vec<PathBuf> = discovered
         .steps
         .iter()
-        .filter(|p| {
-            if scope_tokens.is_empty() {
-                return true;
-            }
-            let key = p.to_string_lossy().to_ascii_lowercase();
-            scope_tokens.iter().any(|token| key.contains(token))
-        })
+        .filter(|p| in_scope(p, &scope_tokens))
         .cloned()
         .collect();
+    let mut discovered = FullScenarioDiscovery {
+        steps: targets,
+        distributed: Vec::new(),
+        parse_errors: Vec::new(),
+    };
+    filter_discovered_scenarios(&mut discovered, filter, &config.runs_dir());
+    if !discovered.parse_errors.is_empty() {
+        push(
+            "scenario_filter",
+            FullStepStatus::Failed,
+            discovered.parse_errors.join(" | "),
+        );
+    }
+    targets = discovered.steps;
     targets.sort();
     let matched_scenarios: Vec<String> = targets
         .iter()
@@ -109,7 +151,7 @@ pub(crate) fn run_gate_command(
         push(
             "scope_match",
             FullStepStatus::Failed,
-            "no step scenarios matched requested scope".to_string(),
+            "no step scenarios matched requested scope and filter".to_string(),
         );
         return Ok(GateReport {
             schema_version: "fozzy.gate_report.v1".to_string(),
@@ -117,6 +159,7 @@ pub(crate) fn run_gate_command(
             strict,
             scenario_root: scenario_root.display().to_string(),
             scopes: scope_tokens,
+            filter: filter.map(str::to_string),
             matched_scenarios,
             steps,
         });
@@ -181,7 +224,11 @@ pub(crate) fn run_gate_command(
             record_trace_to: None,
             filter: None,
             jobs: None,
+            shard: None,
+            shard_strategy: crate::ShardStrategy::Count,
             fail_fast: false,
+            retries: None,
+            quarantine: None,
             record_collision: RecordCollisionPolicy::Error,
             profile_capture: ProfileCaptureLevel::Baseline,
             proc_backend: config.proc_backend,
@@ -224,7 +271,11 @@ pub(crate) fn run_gate_command(
             record_trace_to: Some(trace_path.clone()),
             filter: None,
             jobs: None,
+            shard: None,
+            shard_strategy: crate::ShardStrategy::Count,
             fail_fast: false,
+            retries: None,
+            quarantine: None,
             record_collision: RecordCollisionPolicy::Overwrite,
             profile_capture: ProfileCaptureLevel::Baseline,
             proc_backend: config.proc_backend,
@@ -286,6 +337,7 @@ pub(crate) fn run_gate_command(
                 dump_events: false,
                 profile_capture: ProfileCaptureLevel::Baseline,
                 reporter: Reporter::Json,
+                start: None,
             },
         ) {
             Ok(replay) => {
@@ -310,6 +362,8 @@ pub(crate) fn run_gate_command(
                 flake_budget_pct: None,
                 perf_baseline: None,
                 max_p99_delta_pct: None,
+                require_signed: false,
+                signing_key: None,
                 strict,
             },
         );
@@ -404,6 +458,7 @@ pub(crate) fn run_gate_command(
         strict,
         scenario_root: scenario_root.display().to_string(),
         scopes: scope_tokens,
+        filter: filter.map(str::to_string),
         matched_scenarios,
         steps,
     };
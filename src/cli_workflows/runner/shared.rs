@@ -58,6 +58,37 @@ pub(crate) fn discover_scenarios(root: &Path) -> FullScenarioDiscovery {
     out
 }
 
+/// Narrows discovered scenarios to those `filter` selects. Invalid scenario metadata is
+/// recorded as a parse error so discovery fails with the offending file named.
+pub(crate) fn filter_discovered_scenarios(
+    discovered: &mut FullScenarioDiscovery,
+    filter: Option<&str>,
+    runs_dir: &Path,
+) {
+    let Some(filter) = filter.filter(|filter| !filter.trim().is_empty()) else {
+        return;
+    };
+    for paths in [&mut discovered.steps, &mut discovered.distributed] {
+        match fozzy::select_scenarios(paths, Some(filter), runs_dir) {
+            Ok(report) => *paths = report.selected_paths(),
+            Err(err) => discovered.parse_errors.push(err.to_string()),
+        }
+    }
+}
+
+/// `fozzy full --list`: the executable scenarios under `root` and the filter's verdict.
+pub(crate) fn list_full_scenarios(
+    config: &Config,
+    root: &Path,
+    filter: Option<&str>,
+) -> FozzyResult<ScenarioSelectionReport> {
+    let discovered = discover_scenarios(root);
+    let mut paths = discovered.steps;
+    paths.extend(discovered.distributed);
+    paths.sort();
+    fozzy::select_scenarios(&paths, filter, &config.runs_dir())
+}
+
 pub(crate) fn git_clean_tree_check() -> anyhow::Result<GitWorktreeCheck> {
     let out = ProcessCommand::new("git")
         .args(["status", "--porcelain"])
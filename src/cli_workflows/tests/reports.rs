@@ -24,6 +24,7 @@ pub(super) fn sample_run_summary(status: ExitStatus) -> RunSummary {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     std::fs::write(
         &report_path,
@@ -20,6 +20,10 @@ const ARTIFACT_FILE_SPECS: &[(&str, ArtifactKind)] = &[
     ("manifest.json", ArtifactKind::Manifest),
     ("report.html", ArtifactKind::Report),
     ("junit.xml", ArtifactKind::Report),
+    ("report.tap", ArtifactKind::Report),
+    ("report.sarif", ArtifactKind::Report),
+    ("report.jsonl", ArtifactKind::Report),
+    ("github-annotations.txt", ArtifactKind::Report),
 ];
 
 pub(crate) fn artifact_file_specs() -> &'static [(&'static str, ArtifactKind)] {
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
 
-use crate::{FozzyError, FozzyResult, RunManifest, RunSummary, TraceFile};
+use crate::{FozzyError, FozzyResult, Reporter, RunManifest, RunSummary, TraceFile};
 
 fn validate_required_bundle_files(files: &[PathBuf], run: &str) -> FozzyResult<()> {
     let present: BTreeSet<String> = files
@@ -202,8 +202,12 @@ pub(crate) fn validate_manifest_integrity(files: &[PathBuf], run: &str) -> Fozzy
                     "invalid manifest for {run:?}: declared trace artifact mismatch"
                 )));
             }
-            let trace_bytes = std::fs::read(actual_trace)?;
-            let trace: TraceFile = serde_json::from_slice(&trace_bytes).map_err(|e| {
+            let trace: TraceFile = if crate::is_binary_trace_file(actual_trace) {
+                TraceFile::read_json(actual_trace)
+            } else {
+                serde_json::from_slice(&std::fs::read(actual_trace)?).map_err(FozzyError::from)
+            }
+            .map_err(|e| {
                 FozzyError::InvalidArgument(format!(
                     "invalid trace for {run:?}: {} ({e})",
                     actual_trace.display()
@@ -581,24 +585,26 @@ fn validate_reporter_artifacts(
     run: &str,
     summary: &RunSummary,
 ) -> FozzyResult<()> {
-    if let Some(html_path) = find_artifact_path(files, "report.html") {
-        let actual = std::fs::read(html_path)?;
-        let expected = crate::render_html(summary).into_bytes();
-        if actual != expected {
-            return Err(FozzyError::InvalidArgument(format!(
-                "invalid html report for {run:?}: {} does not match summary rendering",
-                html_path.display()
-            )));
-        }
-    }
-
-    if let Some(junit_path) = find_artifact_path(files, "junit.xml") {
-        let actual = std::fs::read(junit_path)?;
-        let expected = crate::render_junit_xml(summary).into_bytes();
-        if actual != expected {
+    for (reporter, label) in [
+        (Reporter::Html, "html report"),
+        (Reporter::Junit, "junit report"),
+        (Reporter::Tap, "tap report"),
+        (Reporter::Sarif, "sarif report"),
+        (Reporter::Jsonl, "jsonl event report"),
+        (Reporter::Github, "github annotations"),
+    ] {
+        let Some(name) = crate::reporter_artifact_name(reporter) else {
+            continue;
+        };
+        let Some(path) = find_artifact_path(files, name) else {
+            continue;
+        };
+        let actual = std::fs::read(path)?;
+        let expected = crate::render_reporter(summary, reporter).unwrap_or_default();
+        if actual != expected.into_bytes() {
             return Err(FozzyError::InvalidArgument(format!(
-                "invalid junit report for {run:?}: {} does not match summary rendering",
-                junit_path.display()
+                "invalid {label} for {run:?}: {} does not match summary rendering",
+                path.display()
             )));
         }
     }
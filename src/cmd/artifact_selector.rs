@@ -11,6 +11,10 @@ pub(crate) fn resolve_artifact_selector_view(
     selector: &str,
 ) -> FozzyResult<Option<ArtifactSelectorView>> {
     let input = std::path::PathBuf::from(crate::normalize_run_or_trace_selector(selector));
+    if crate::split_archive_selector(&input).is_some() {
+        let trace = crate::TraceFile::read_json(&input)?;
+        return Ok(Some(ArtifactSelectorView::DirectTrace { trace }));
+    }
     if input.exists() && input.is_file() && crate::is_trace_path(&input) {
         let trace = crate::read_cached_trace_file(&input)?;
         return Ok(Some(ArtifactSelectorView::DirectTrace { trace }));
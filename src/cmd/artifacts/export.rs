@@ -109,6 +109,7 @@ pub(super) fn export_gate_bundle(config: &Config, run: &str, out: &Path) -> Fozz
             dump_events: false,
             profile_capture: crate::ProfileCaptureLevel::Baseline,
             reporter: crate::Reporter::Json,
+            start: None,
         },
     )?;
     let ci = crate::ci_evaluate(
@@ -119,6 +120,8 @@ pub(super) fn export_gate_bundle(config: &Config, run: &str, out: &Path) -> Fozz
             flake_budget_pct: None,
             perf_baseline: None,
             max_p99_delta_pct: None,
+            require_signed: false,
+            signing_key: None,
             strict: true,
         },
     )?;
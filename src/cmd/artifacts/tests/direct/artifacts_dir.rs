@@ -37,8 +37,11 @@ fn direct_trace_uses_declared_artifacts_dir_and_lists_detached_profile_files() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let resolved =
@@ -92,8 +95,11 @@ fn direct_trace_uses_manifest_only_declared_artifacts_dir() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let resolved =
@@ -169,8 +175,11 @@ fn direct_trace_ignores_untrusted_declared_artifacts_dir() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let resolved = resolve_artifacts_dir(&cfg, &trace.to_string_lossy()).expect("resolve");
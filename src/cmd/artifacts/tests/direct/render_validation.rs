@@ -44,8 +44,11 @@ fn artifacts_list_rejects_events_artifact_with_mismatched_trace() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, "r1").expect_err("list must fail");
@@ -93,8 +96,11 @@ fn direct_trace_rejects_manifest_only_timeline_with_mismatched_trace() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
@@ -144,8 +150,11 @@ fn artifacts_list_rejects_report_html_with_mismatched_summary_rendering() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, "r1").expect_err("list must fail");
@@ -188,8 +197,11 @@ fn direct_trace_rejects_manifest_only_junit_with_mismatched_summary_rendering()
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
@@ -39,8 +39,11 @@ fn artifacts_list_rejects_profile_artifacts_with_mismatched_run_identity() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, "r1").expect_err("list must fail");
@@ -86,8 +89,11 @@ fn direct_trace_rejects_manifest_only_profile_timeline_with_mismatched_identity(
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
@@ -149,8 +155,11 @@ fn artifacts_list_rejects_memory_leaks_with_mismatched_summary() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, "r1").expect_err("list must fail");
@@ -206,8 +215,11 @@ fn direct_trace_rejects_manifest_only_memory_timeline_with_mismatched_summary()
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
@@ -266,8 +278,11 @@ fn artifacts_list_rejects_memory_graph_with_mismatched_summary() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, "r1").expect_err("list must fail");
@@ -323,8 +338,11 @@ fn direct_trace_rejects_manifest_only_memory_delta_with_mismatched_summary() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
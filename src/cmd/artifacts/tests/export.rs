@@ -47,8 +47,11 @@ fn export_missing_input_returns_error_and_does_not_create_zip() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let err = export_artifacts(&cfg, "does-not-exist-input.fozzy", &out).expect_err("must fail");
     assert!(err.to_string().contains("not found"));
@@ -77,8 +80,11 @@ fn export_empty_run_errors() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let err = export_artifacts(&cfg, run_id, &out).expect_err("must fail");
     assert!(err.to_string().contains("no artifacts found"));
@@ -117,8 +123,11 @@ fn pack_includes_runtime_metadata_files() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     export_reproducer_pack(&cfg, "r1", &out).expect("pack");
     let file = std::fs::File::open(&out).expect("zip");
@@ -166,8 +175,11 @@ fn pack_dir_rejects_symlink_target_overwrite() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let outside = root.join("outside.json");
@@ -217,8 +229,11 @@ fn pack_dir_failure_atomic_on_symlink_error() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let outside = root.join("outside.json");
@@ -277,8 +292,11 @@ fn pack_zip_is_byte_deterministic_for_same_run() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let out_a = root.join("a.zip");
@@ -314,8 +332,11 @@ fn export_and_pack_reject_incomplete_run_directory() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -384,8 +405,11 @@ fn resolve_artifacts_dir_supports_latest_last_pass_last_fail_aliases() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let latest = resolve_artifacts_dir(&cfg, "latest").expect("latest");
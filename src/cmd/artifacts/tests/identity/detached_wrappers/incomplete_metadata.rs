@@ -25,8 +25,11 @@ fn export_and_pack_allow_run_dirs_without_trace_or_events() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -75,8 +78,11 @@ fn direct_trace_export_and_pack_reject_incomplete_declared_detached_metadata() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -135,8 +141,11 @@ fn direct_trace_list_rejects_incomplete_declared_detached_metadata() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
@@ -187,8 +196,14 @@ fn direct_trace_list_rejects_unchecked_sibling_artifacts() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     }
     .write_json(&trace_path)
     .expect("trace");
@@ -211,8 +226,11 @@ fn direct_trace_list_rejects_unchecked_sibling_artifacts() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = artifacts_list(&cfg, &trace_path.to_string_lossy()).expect_err("list must fail");
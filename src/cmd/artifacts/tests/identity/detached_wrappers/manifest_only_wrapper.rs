@@ -32,8 +32,11 @@ fn direct_trace_export_pack_and_bundle_allow_manifest_only_exact_sibling_wrapper
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
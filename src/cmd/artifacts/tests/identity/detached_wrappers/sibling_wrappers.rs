@@ -43,8 +43,14 @@ fn direct_trace_list_ignores_standalone_sibling_traces() {
                 tests: None,
                 memory: None,
                 findings: Vec::new(),
+                retries: Vec::new(),
             },
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
         }
         .write_json(path)
         .expect("trace");
@@ -63,8 +69,11 @@ fn direct_trace_list_ignores_standalone_sibling_traces() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let list =
@@ -114,8 +123,14 @@ fn direct_trace_export_and_pack_reject_unchecked_sibling_artifacts() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     }
     .write_json(&trace_path)
     .expect("trace");
@@ -134,8 +149,11 @@ fn direct_trace_export_and_pack_reject_unchecked_sibling_artifacts() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -188,8 +206,11 @@ fn direct_trace_export_and_pack_allow_valid_sibling_metadata() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -241,8 +262,11 @@ fn direct_trace_ignores_coherent_foreign_sibling_wrapper() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -42,8 +42,11 @@ fn load_summary_prefers_explicit_trace_over_sibling_bundle() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let summary = load_summary(&cfg, &explicit_trace.to_string_lossy())
@@ -100,8 +103,11 @@ fn load_summary_uses_manifest_declared_external_trace_when_report_missing() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let summary = load_summary(&cfg, run_id).expect("load summary");
@@ -69,8 +69,11 @@ fn artifacts_diff_marks_same_size_content_change_as_changed() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let diff = artifacts_diff(&cfg, "left", "right").expect("artifacts diff");
@@ -116,8 +119,11 @@ fn run_id_uses_report_declared_external_trace_path() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let trace_path = resolve_trace_path(&cfg, "r1").expect("resolve trace path");
@@ -31,8 +31,11 @@ fn pack_dir_prunes_stale_preexisting_files() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let out_dir = root.join("out");
@@ -74,8 +77,11 @@ fn export_dir_prunes_stale_preexisting_files() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let out_dir = root.join("out");
@@ -127,8 +133,11 @@ fn pack_and_export_reject_invalid_manifest_bytes() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -173,8 +182,11 @@ fn pack_and_export_reject_invalid_report_bytes() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -242,8 +254,11 @@ fn pack_and_export_reject_manifest_report_identity_mismatch() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out_pack = root.join("pack.zip");
     let out_export = root.join("export.zip");
@@ -294,8 +309,11 @@ fn zip_output_rejects_symlinked_parent_components() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let real_out_dir = root.join("real-out");
@@ -352,8 +370,11 @@ fn bundle_includes_replay_ci_and_env_reports() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     export_gate_bundle(&cfg, &trace.display().to_string(), &out).expect("bundle");
@@ -405,8 +426,11 @@ fn bundle_rejects_stale_report_without_manifest() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let out = root.join("bundle.zip");
 
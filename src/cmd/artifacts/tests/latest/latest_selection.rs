@@ -124,8 +124,11 @@ fn latest_alias_accepts_newer_manifest_only_wrapper() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let runs_dir = cfg.runs_dir();
     let older_dir = runs_dir.join("older");
@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 use crate::{
     Config, FlakeBudget, FozzyError, FozzyResult, ReplayOptions, ReportCommand, Reporter,
-    TraceFile, TracePath, profile_command, replay_trace, report_command, verify_trace_file,
+    TraceFile, TracePath, TraceSigningKey, profile_command, replay_trace, report_command,
+    verify_trace_file_with_key,
 };
 use crate::{ProfileCaptureLevel, ProfileCommand};
 
@@ -14,6 +15,10 @@ pub struct CiOptions {
     pub flake_budget_pct: Option<FlakeBudget>,
     pub perf_baseline: Option<String>,
     pub max_p99_delta_pct: Option<f64>,
+    /// Fail unless the trace carries a valid signature from `signing_key`.
+    pub require_signed: bool,
+    /// Key file used to validate or pin the trace signature; required by `require_signed`.
+    pub signing_key: Option<PathBuf>,
     pub strict: bool,
 }
 
@@ -54,11 +59,21 @@ pub fn ci_evaluate(config: &Config, opt: &CiOptions) -> FozzyResult<CiReport> {
             "--max-p99-delta-pct requires --perf-baseline".to_string(),
         ));
     }
+    if opt.require_signed && opt.signing_key.is_none() {
+        return Err(FozzyError::InvalidArgument(
+            "--require-signed requires --signing-key to name the trusted key".to_string(),
+        ));
+    }
     let mut checks = Vec::new();
     let trace_selector = opt.trace.to_string_lossy().to_string();
     let (trace_path, trace, trusted_bundle_mode) = resolve_ci_trace_input(config, &trace_selector)?;
 
-    let verify = verify_trace_file(&trace_path)?;
+    let signing_key = opt
+        .signing_key
+        .as_deref()
+        .map(TraceSigningKey::load)
+        .transpose()?;
+    let verify = verify_trace_file_with_key(&trace_path, signing_key.as_ref())?;
     let strict_integrity_ok =
         verify.checksum_present && verify.checksum_valid && verify.warnings.is_empty();
     checks.push(CiCheck {
@@ -75,6 +90,12 @@ pub fn ci_evaluate(config: &Config, opt: &CiOptions) -> FozzyResult<CiReport> {
             }
         )),
     });
+    if opt.require_signed || verify.signature.is_some() {
+        checks.push(trace_signature_check(
+            verify.signature.as_ref(),
+            opt.require_signed,
+        ));
+    }
 
     let replay = replay_trace(
         config,
@@ -85,6 +106,7 @@ pub fn ci_evaluate(config: &Config, opt: &CiOptions) -> FozzyResult<CiReport> {
             dump_events: false,
             profile_capture: ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )?;
     let expected = if trace.summary.status == crate::ExitStatus::Pass {
@@ -229,6 +251,43 @@ pub fn ci_evaluate(config: &Config, opt: &CiOptions) -> FozzyResult<CiReport> {
     })
 }
 
+/// A signature must be valid; `require_signed` also needs it to come from the trusted key.
+fn trace_signature_check(
+    status: Option<&crate::TraceSignatureStatus>,
+    require_signed: bool,
+) -> CiCheck {
+    let Some(status) = status else {
+        return CiCheck {
+            name: "trace_signature".to_string(),
+            ok: !require_signed,
+            detail: Some("unsigned".to_string()),
+        };
+    };
+    let ok = match status.valid {
+        Some(valid) => valid && (!require_signed || status.trusted),
+        None => !require_signed,
+    };
+    // The key id is the signing key's fingerprint, so it names the signer.
+    let mut detail = format!(
+        "scheme={} signer={} valid={} trusted={} engine={}",
+        status.scheme.as_str(),
+        status.key_id,
+        status
+            .valid
+            .map_or_else(|| "unknown".to_string(), |v| v.to_string()),
+        status.trusted,
+        status.provenance.engine.version
+    );
+    if let Some(reason) = status.reason.as_deref() {
+        detail.push_str(&format!(" reason={reason}"));
+    }
+    CiCheck {
+        name: "trace_signature".to_string(),
+        ok,
+        detail: Some(detail),
+    }
+}
+
 fn resolve_ci_trace_input(
     config: &Config,
     selector: &str,
@@ -300,6 +359,7 @@ mod tests {
                 tests: None,
                 memory: None,
                 findings: Vec::new(),
+                retries: Vec::new(),
             },
         );
         trace_file.write_json(&trace).expect("write trace");
@@ -317,8 +377,11 @@ mod tests {
             mem_artifacts: false,
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
             mem_fragmentation_seed: None,
             mem_pressure_wave: None,
+            trace_encoding: Default::default(),
         };
 
         let out = ci_command(
@@ -329,6 +392,8 @@ mod tests {
                 flake_budget_pct: None,
                 perf_baseline: None,
                 max_p99_delta_pct: None,
+                require_signed: false,
+                signing_key: None,
                 strict: false,
             },
         )
@@ -384,8 +449,11 @@ mod tests {
             mem_artifacts: false,
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
             mem_fragmentation_seed: None,
             mem_pressure_wave: None,
+            trace_encoding: Default::default(),
         };
 
         let err = ci_command(
@@ -396,6 +464,8 @@ mod tests {
                 flake_budget_pct: Some("5".parse().expect("budget")),
                 perf_baseline: None,
                 max_p99_delta_pct: None,
+                require_signed: false,
+                signing_key: None,
                 strict: false,
             },
         )
@@ -452,6 +522,7 @@ mod tests {
                     message: "detected 1 leaked allocation(s), leaked_bytes=256".to_string(),
                     location: None,
                 }],
+                retries: Vec::new(),
             },
         );
         trace_file.memory = Some(crate::MemoryTrace {
@@ -486,8 +557,11 @@ mod tests {
             mem_artifacts: false,
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
             mem_fragmentation_seed: None,
             mem_pressure_wave: None,
+            trace_encoding: Default::default(),
         };
 
         let report = ci_evaluate(
@@ -498,6 +572,8 @@ mod tests {
                 flake_budget_pct: None,
                 perf_baseline: None,
                 max_p99_delta_pct: None,
+                require_signed: false,
+                signing_key: None,
                 strict: true,
             },
         )
@@ -5,6 +5,8 @@ use std::path::PathBuf;
 
 use crate::{Config, FozzyResult};
 
+#[path = "corpus/coverage.rs"]
+mod coverage;
 #[path = "corpus/ops.rs"]
 mod ops;
 #[path = "corpus/path.rs"]
@@ -12,6 +14,9 @@ mod path;
 #[path = "corpus/zip.rs"]
 mod zip;
 
+pub use coverage::{CorpusEntryStats, CorpusStatsReport};
+
+use coverage::{corpus_stats, distill_corpus, merge_corpora};
 use ops::{export_zip, minimize_corpus};
 use zip::import_zip;
 
@@ -28,6 +33,28 @@ pub enum CorpusCommand {
         dir: PathBuf,
         #[arg(long)]
         budget: Option<crate::FozzyDuration>,
+        /// Replay entries against this fuzz target and keep the smallest set that
+        /// preserves all observed coverage (instead of only removing duplicates).
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Combine corpora from several directories into one content-addressed corpus.
+    Merge {
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+        #[arg(long)]
+        out: PathBuf,
+        /// Distill the merged corpus against this fuzz target.
+        #[arg(long)]
+        target: Option<String>,
+        #[arg(long)]
+        budget: Option<crate::FozzyDuration>,
+    },
+    /// Show per-entry coverage contribution for a fuzz target.
+    Stats {
+        dir: PathBuf,
+        #[arg(long)]
+        target: String,
     },
     Export {
         dir: PathBuf,
@@ -41,7 +68,7 @@ pub enum CorpusCommand {
     },
 }
 
-pub fn corpus_command(_config: &Config, command: &CorpusCommand) -> FozzyResult<serde_json::Value> {
+pub fn corpus_command(config: &Config, command: &CorpusCommand) -> FozzyResult<serde_json::Value> {
     match command {
         CorpusCommand::List { dir } => {
             let mut files = Vec::new();
@@ -72,7 +99,28 @@ pub fn corpus_command(_config: &Config, command: &CorpusCommand) -> FozzyResult<
             Ok(serde_json::json!({"added": out_path.to_string_lossy().to_string()}))
         }
 
-        CorpusCommand::Minimize { dir, budget } => minimize_corpus(dir, *budget),
+        CorpusCommand::Minimize {
+            dir,
+            budget,
+            target: None,
+        } => minimize_corpus(dir, *budget),
+
+        CorpusCommand::Minimize {
+            dir,
+            budget,
+            target: Some(target),
+        } => distill_corpus(config, dir, target, *budget),
+
+        CorpusCommand::Merge {
+            dirs,
+            out,
+            target,
+            budget,
+        } => merge_corpora(config, dirs, out, target.as_deref(), *budget),
+
+        CorpusCommand::Stats { dir, target } => {
+            Ok(serde_json::to_value(corpus_stats(config, dir, target)?)?)
+        }
 
         CorpusCommand::Export { dir, out } => {
             export_zip(dir, out)?;
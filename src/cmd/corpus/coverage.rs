@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::{Config, ExitStatus, FozzyError, FozzyResult, FuzzTarget, MemoryOptions};
+
+use super::ops::{corpus_files, replace_corpus_files};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusEntryStats {
+    pub path: String,
+    pub bytes: usize,
+    pub status: ExitStatus,
+    pub edges: usize,
+    #[serde(rename = "uniqueEdges")]
+    pub unique_edges: usize,
+    pub distilled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusStatsReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub dir: String,
+    pub target: String,
+    pub files: usize,
+    #[serde(rename = "uniqueInputs")]
+    pub unique_inputs: usize,
+    #[serde(rename = "totalEdges")]
+    pub total_edges: usize,
+    #[serde(rename = "distilledEntries")]
+    pub distilled_entries: usize,
+    pub entries: Vec<CorpusEntryStats>,
+}
+
+struct MeasuredEntry {
+    path: PathBuf,
+    hash: String,
+    bytes: Vec<u8>,
+    status: ExitStatus,
+    edges: BTreeSet<u64>,
+}
+
+/// `afl-cmin`-style distillation: replays every entry and keeps, for each observed
+/// edge, the smallest input that covers it.
+pub(super) fn distill_corpus(
+    config: &Config,
+    dir: &Path,
+    target: &str,
+    budget: Option<crate::FozzyDuration>,
+) -> FozzyResult<serde_json::Value> {
+    ensure_corpus_dir(dir, "corpus minimize")?;
+    let target: FuzzTarget = target.parse()?;
+    let check_budget = budget_check("corpus minimize", budget);
+    let files = corpus_files(dir, "corpus minimize", &check_budget)?;
+    if files.is_empty() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "corpus directory has no files to minimize: {}",
+            dir.display()
+        )));
+    }
+
+    let entries = measure_entries(config, &target, &files, &check_budget)?;
+    let selected = select_covering(&entries);
+    let keep = selected
+        .iter()
+        .map(|index| (entries[*index].hash.clone(), entries[*index].bytes.clone()))
+        .collect::<BTreeMap<_, _>>();
+    let total_edges = entries
+        .iter()
+        .flat_map(|entry| entry.edges.iter().copied())
+        .collect::<BTreeSet<_>>()
+        .len();
+    let bytes_before = files
+        .iter()
+        .map(|path| std::fs::metadata(path).map(|md| md.len()))
+        .sum::<std::io::Result<u64>>()?;
+    let bytes_after = keep.values().map(|bytes| bytes.len() as u64).sum::<u64>();
+
+    replace_corpus_files(dir, &files, &keep, &check_budget)?;
+
+    Ok(serde_json::json!({
+        "ok": true,
+        "mode": "coverage",
+        "dir": dir.to_string_lossy().to_string(),
+        "target": crate::target_string(&target),
+        "filesBefore": files.len(),
+        "filesAfter": keep.len(),
+        "edges": total_edges,
+        "bytesBefore": bytes_before,
+        "bytesAfter": bytes_after,
+        "bytesRemoved": bytes_before.saturating_sub(bytes_after)
+    }))
+}
+
+/// Combines corpora (for example from several CI machines) into `out` as
+/// content-addressed entries, optionally distilling the result.
+pub(super) fn merge_corpora(
+    config: &Config,
+    dirs: &[PathBuf],
+    out: &Path,
+    target: Option<&str>,
+    budget: Option<crate::FozzyDuration>,
+) -> FozzyResult<serde_json::Value> {
+    for dir in dirs {
+        ensure_corpus_dir(dir, "corpus merge")?;
+    }
+    std::fs::create_dir_all(out)?;
+    let check_budget = budget_check("corpus merge", budget);
+
+    let mut known = BTreeSet::<String>::new();
+    for path in corpus_files(out, "corpus merge", &check_budget)? {
+        known.insert(blake3::hash(&std::fs::read(path)?).to_hex().to_string());
+    }
+
+    let mut scanned = 0usize;
+    let mut added = 0usize;
+    let mut duplicates = 0usize;
+    for dir in dirs {
+        for path in corpus_files(dir, "corpus merge", &check_budget)? {
+            check_budget("read")?;
+            scanned += 1;
+            let bytes = std::fs::read(&path)?;
+            let hash = blake3::hash(&bytes).to_hex().to_string();
+            if !known.insert(hash.clone()) {
+                duplicates += 1;
+                continue;
+            }
+            std::fs::write(out.join(format!("input-{hash}.bin")), bytes)?;
+            added += 1;
+        }
+    }
+
+    let distill = match target {
+        Some(target) if !known.is_empty() => Some(distill_corpus(config, out, target, budget)?),
+        _ => None,
+    };
+
+    Ok(serde_json::json!({
+        "ok": true,
+        "out": out.to_string_lossy().to_string(),
+        "sources": dirs
+            .iter()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .collect::<Vec<_>>(),
+        "filesScanned": scanned,
+        "added": added,
+        "duplicatesSkipped": duplicates,
+        "entries": known.len(),
+        "distill": distill
+    }))
+}
+
+pub(super) fn corpus_stats(
+    config: &Config,
+    dir: &Path,
+    target: &str,
+) -> FozzyResult<CorpusStatsReport> {
+    ensure_corpus_dir(dir, "corpus stats")?;
+    let target: FuzzTarget = target.parse()?;
+    let check_budget = budget_check("corpus stats", None);
+    let files = corpus_files(dir, "corpus stats", &check_budget)?;
+    let entries = measure_entries(config, &target, &files, &check_budget)?;
+    let selected = select_covering(&entries);
+
+    let mut edge_owners = BTreeMap::<u64, usize>::new();
+    for entry in &entries {
+        for edge in &entry.edges {
+            *edge_owners.entry(*edge).or_default() += 1;
+        }
+    }
+
+    let mut stats = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| CorpusEntryStats {
+            path: entry.path.to_string_lossy().to_string(),
+            bytes: entry.bytes.len(),
+            status: entry.status,
+            edges: entry.edges.len(),
+            unique_edges: entry
+                .edges
+                .iter()
+                .filter(|edge| edge_owners.get(edge) == Some(&1))
+                .count(),
+            distilled: selected.contains(&index),
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| {
+        b.unique_edges
+            .cmp(&a.unique_edges)
+            .then(b.edges.cmp(&a.edges))
+            .then(a.path.cmp(&b.path))
+    });
+
+    Ok(CorpusStatsReport {
+        schema_version: "fozzy.corpus_stats.v1".to_string(),
+        dir: dir.to_string_lossy().to_string(),
+        target: crate::target_string(&target),
+        files: files.len(),
+        unique_inputs: entries.len(),
+        total_edges: edge_owners.len(),
+        distilled_entries: selected.len(),
+        entries: stats,
+    })
+}
+
+/// Executes each distinct input once. The per-input identity edge is dropped so
+/// only behavior-derived edges count toward coverage.
+fn measure_entries(
+    config: &Config,
+    target: &FuzzTarget,
+    files: &[PathBuf],
+    check_budget: &dyn Fn(&str) -> FozzyResult<()>,
+) -> FozzyResult<Vec<MeasuredEntry>> {
+    let memory = MemoryOptions {
+        track: false,
+        artifacts: false,
+        ..MemoryOptions::default()
+    };
+    let mut seen = BTreeSet::<String>::new();
+    let mut entries = Vec::new();
+    for path in files {
+        check_budget("execute")?;
+        let bytes = std::fs::read(path)?;
+        let hash = blake3::hash(&bytes).to_hex().to_string();
+        if !seen.insert(hash.clone()) {
+            continue;
+        }
+        let exec = crate::execute_target(config, target, &bytes, &memory)?;
+        let mut edges = exec.coverage;
+        edges.remove(&crate::input_identity_edge(&bytes));
+        entries.push(MeasuredEntry {
+            path: path.clone(),
+            hash,
+            bytes,
+            status: exec.status,
+            edges,
+        });
+    }
+    Ok(entries)
+}
+
+fn select_covering(entries: &[MeasuredEntry]) -> BTreeSet<usize> {
+    let mut best = BTreeMap::<u64, usize>::new();
+    for (index, entry) in entries.iter().enumerate() {
+        for edge in &entry.edges {
+            best.entry(*edge)
+                .and_modify(|current| {
+                    let incumbent = &entries[*current];
+                    if (entry.bytes.len(), &entry.hash) < (incumbent.bytes.len(), &incumbent.hash) {
+                        *current = index;
+                    }
+                })
+                .or_insert(index);
+        }
+    }
+    best.into_values().collect()
+}
+
+fn ensure_corpus_dir(dir: &Path, action: &str) -> FozzyResult<()> {
+    if !dir.exists() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "corpus directory not found: {}",
+            dir.display()
+        )));
+    }
+    if !dir.is_dir() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "{action} source is not a directory: {}",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+fn budget_check(
+    action: &'static str,
+    budget: Option<crate::FozzyDuration>,
+) -> impl Fn(&str) -> FozzyResult<()> {
+    let started = Instant::now();
+    let limit = budget.map(|d| d.0);
+    move |phase: &str| {
+        if let Some(limit) = limit
+            && started.elapsed() > limit
+        {
+            return Err(FozzyError::InvalidArgument(format!(
+                "{action} exceeded budget during {phase}: limit={}ms",
+                limit.as_millis()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use walkdir::WalkDir;
 use crate::{FozzyError, FozzyResult};
 
 use super::path::validate_output_file_path_secure;
+
 pub(super) fn minimize_corpus(
     dir: &Path,
     budget: Option<crate::FozzyDuration>,
@@ -38,27 +39,7 @@ pub(super) fn minimize_corpus(
         Ok(())
     };
 
-    let mut files = Vec::<PathBuf>::new();
-    for entry in WalkDir::new(dir).min_depth(1).max_depth(1) {
-        check_budget("scan")?;
-        let entry = entry.map_err(|e| {
-            let msg = e.to_string();
-            FozzyError::Io(
-                e.into_io_error()
-                    .unwrap_or_else(|| std::io::Error::other(msg)),
-            )
-        })?;
-        if entry.file_type().is_symlink() {
-            return Err(FozzyError::InvalidArgument(format!(
-                "corpus minimize refuses symlinked input: {}",
-                entry.path().display()
-            )));
-        }
-        if entry.file_type().is_file() {
-            files.push(entry.path().to_path_buf());
-        }
-    }
-    files.sort();
+    let files = corpus_files(dir, "corpus minimize", &check_budget)?;
 
     if files.is_empty() {
         return Err(FozzyError::InvalidArgument(format!(
@@ -85,6 +66,59 @@ pub(super) fn minimize_corpus(
         }
     }
 
+    replace_corpus_files(dir, &files, &unique_by_hash, &check_budget)?;
+
+    Ok(serde_json::json!({
+        "ok": true,
+        "dir": dir.to_string_lossy().to_string(),
+        "filesBefore": files.len(),
+        "filesAfter": unique_by_hash.len(),
+        "duplicatesRemoved": duplicate_files,
+        "bytesBefore": original_bytes,
+        "bytesAfter": original_bytes.saturating_sub(duplicate_bytes),
+        "bytesRemoved": duplicate_bytes
+    }))
+}
+
+/// Top-level corpus files in stable order; nested directories such as `crashes/`
+/// are left alone.
+pub(super) fn corpus_files(
+    dir: &Path,
+    action: &str,
+    check_budget: &dyn Fn(&str) -> FozzyResult<()>,
+) -> FozzyResult<Vec<PathBuf>> {
+    let mut files = Vec::<PathBuf>::new();
+    for entry in WalkDir::new(dir).min_depth(1).max_depth(1) {
+        check_budget("scan")?;
+        let entry = entry.map_err(|e| {
+            let msg = e.to_string();
+            FozzyError::Io(
+                e.into_io_error()
+                    .unwrap_or_else(|| std::io::Error::other(msg)),
+            )
+        })?;
+        if entry.file_type().is_symlink() {
+            return Err(FozzyError::InvalidArgument(format!(
+                "{action} refuses symlinked input: {}",
+                entry.path().display()
+            )));
+        }
+        if entry.file_type().is_file() {
+            files.push(entry.path().to_path_buf());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Swaps `files` for content-addressed `input-<blake3>.bin` copies of `keep`, staging
+/// the new set next to `dir` so a failure leaves the original corpus intact.
+pub(super) fn replace_corpus_files(
+    dir: &Path,
+    files: &[PathBuf],
+    keep: &BTreeMap<String, Vec<u8>>,
+    check_budget: &dyn Fn(&str) -> FozzyResult<()>,
+) -> FozzyResult<()> {
     let parent = dir.parent().unwrap_or_else(|| Path::new("."));
     let staging = parent.join(format!(
         ".corpus-minimize-{}.{}",
@@ -94,7 +128,7 @@ pub(super) fn minimize_corpus(
     std::fs::create_dir_all(&staging)?;
 
     let write_result = (|| -> FozzyResult<()> {
-        for (hash, bytes) in &unique_by_hash {
+        for (hash, bytes) in keep {
             check_budget("write")?;
             let name = format!("input-{hash}.bin");
             std::fs::write(staging.join(name), bytes)?;
@@ -108,7 +142,7 @@ pub(super) fn minimize_corpus(
     }
 
     let swap_result = (|| -> FozzyResult<()> {
-        for path in &files {
+        for path in files {
             check_budget("swap")?;
             std::fs::remove_file(path)?;
         }
@@ -135,16 +169,7 @@ pub(super) fn minimize_corpus(
         return Err(err);
     }
 
-    Ok(serde_json::json!({
-        "ok": true,
-        "dir": dir.to_string_lossy().to_string(),
-        "filesBefore": files.len(),
-        "filesAfter": unique_by_hash.len(),
-        "duplicatesRemoved": duplicate_files,
-        "bytesBefore": original_bytes,
-        "bytesAfter": original_bytes.saturating_sub(duplicate_bytes),
-        "bytesRemoved": duplicate_bytes
-    }))
+    Ok(())
 }
 
 pub(super) fn export_zip(dir: &Path, out_zip: &Path) -> FozzyResult<()> {
@@ -533,3 +533,107 @@ fn export_failure_does_not_clobber_existing_output_file() {
     std::fs::set_permissions(&unreadable, std::fs::Permissions::from_mode(0o600))
         .expect("restore chmod");
 }
+
+fn coverage_test_config(root: &std::path::Path) -> Config {
+    Config {
+        base_dir: root.join(".fozzy"),
+        reporter: crate::Reporter::Json,
+        proc_backend: crate::ProcBackend::Scripted,
+        fs_backend: crate::FsBackend::Virtual,
+        http_backend: crate::HttpBackend::Scripted,
+        mem_track: false,
+        mem_limit_mb: None,
+        mem_fail_after: None,
+        fail_on_leak: false,
+        leak_budget: None,
+        mem_artifacts: false,
+        profile_heap_alloc_budget: None,
+        profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
+        mem_fragmentation_seed: None,
+        mem_pressure_wave: None,
+        trace_encoding: Default::default(),
+    }
+}
+
+/// Scenario whose outcome depends only on the `ok` binding, so inputs fall into
+/// exactly two behaviors.
+fn write_two_behavior_scenario(root: &std::path::Path) -> String {
+    let path = root.join("flag.fozzy.json");
+    std::fs::write(
+        &path,
+        r#"{
+  "version": 1,
+  "name": "flag",
+  "generators": { "ok": { "type": "bool" } },
+  "steps": [ { "type": "assert_ok", "value": "{{ok}}" } ]
+}"#,
+    )
+    .expect("write scenario");
+    format!("scenario:{}", path.display())
+}
+
+#[test]
+fn coverage_minimize_keeps_smallest_input_per_behavior() {
+    let root = std::env::temp_dir().join(format!("fozzy-corpus-distill-{}", uuid::Uuid::new_v4()));
+    let corpus = root.join("corpus");
+    std::fs::create_dir_all(corpus.join("crashes")).expect("corpus");
+    let target = write_two_behavior_scenario(&root);
+    std::fs::write(corpus.join("a.bin"), br#"{"ok":true}"#).expect("pass small");
+    std::fs::write(corpus.join("b.bin"), br#"{ "ok" : true }"#).expect("pass large");
+    std::fs::write(corpus.join("c.bin"), br#"{"ok":false}"#).expect("fail");
+    std::fs::write(corpus.join("crashes").join("crash-x.bin"), b"x").expect("crash");
+
+    let out =
+        distill_corpus(&coverage_test_config(&root), &corpus, &target, None).expect("distill");
+    assert_eq!(out.get("filesBefore").and_then(|v| v.as_u64()), Some(3));
+    assert_eq!(out.get("filesAfter").and_then(|v| v.as_u64()), Some(2));
+
+    let mut names = std::fs::read_dir(&corpus)
+        .expect("read dir")
+        .map(|e| e.expect("entry").file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    let mut expected = vec![
+        "crashes".to_string(),
+        format!("input-{}.bin", blake3::hash(br#"{"ok":true}"#).to_hex()),
+        format!("input-{}.bin", blake3::hash(br#"{"ok":false}"#).to_hex()),
+    ];
+    expected.sort();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn merge_deduplicates_across_corpora_and_stats_report_contribution() {
+    let root = std::env::temp_dir().join(format!("fozzy-corpus-merge-{}", uuid::Uuid::new_v4()));
+    let a = root.join("a");
+    let b = root.join("b");
+    let merged = root.join("merged");
+    std::fs::create_dir_all(&a).expect("a");
+    std::fs::create_dir_all(&b).expect("b");
+    let target = write_two_behavior_scenario(&root);
+    std::fs::write(a.join("one.bin"), br#"{"ok":true}"#).expect("a1");
+    std::fs::write(b.join("one.bin"), br#"{"ok":true}"#).expect("b1");
+    std::fs::write(b.join("two.bin"), br#"{"ok":false}"#).expect("b2");
+
+    let config = coverage_test_config(&root);
+    let out = merge_corpora(&config, &[a, b], &merged, None, None).expect("merge");
+    assert_eq!(out.get("filesScanned").and_then(|v| v.as_u64()), Some(3));
+    assert_eq!(out.get("added").and_then(|v| v.as_u64()), Some(2));
+    assert_eq!(
+        out.get("duplicatesSkipped").and_then(|v| v.as_u64()),
+        Some(1)
+    );
+
+    let stats = corpus_stats(&config, &merged, &target).expect("stats");
+    assert_eq!(stats.unique_inputs, 2);
+    assert_eq!(stats.distilled_entries, 2);
+    assert!(
+        stats
+            .entries
+            .iter()
+            .all(|entry| entry.unique_edges > 0 && entry.distilled),
+        "pass and fail inputs each contribute their own edges: {stats:?}"
+    );
+}
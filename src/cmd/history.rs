@@ -0,0 +1,370 @@
+//! Run history queries (`fozzy history ...`) over the append-only history index.
+
+use clap::{Args, Subcommand, ValueEnum};
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::{Config, FozzyError, FozzyResult, HistoryRecord, load_run_history};
+
+#[derive(Debug, Subcommand)]
+pub enum HistoryCommand {
+    /// Pass rate per scenario over its last N recorded runs.
+    PassRate {
+        #[command(flatten)]
+        scope: HistoryScope,
+    },
+    /// Per-run values of a duration or profile metric per scenario, oldest first.
+    Trend {
+        #[command(flatten)]
+        scope: HistoryScope,
+        /// Metric to trend.
+        #[arg(long, default_value = "duration")]
+        metric: HistoryMetric,
+    },
+    /// The run where each currently failing scenario started failing.
+    FirstFailure {
+        /// Only scenarios whose path matches this glob.
+        #[arg(long)]
+        scenario: Option<String>,
+    },
+    /// Flaky retries and pass/fail flips per scenario over its last N recorded runs.
+    FlakeRate {
+        #[command(flatten)]
+        scope: HistoryScope,
+    },
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct HistoryScope {
+    /// Only scenarios whose path matches this glob.
+    #[arg(long)]
+    pub scenario: Option<String>,
+    /// Number of most recent runs of each scenario to consider.
+    #[arg(long, default_value_t = 20)]
+    pub last: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryMetric {
+    Duration,
+    P50,
+    P95,
+    P99,
+    Cpu,
+    Alloc,
+}
+
+impl HistoryMetric {
+    fn value(self, record: &HistoryRecord) -> Option<u64> {
+        let profile = record.scenario.profile;
+        match self {
+            Self::Duration => Some(record.scenario.duration_ms),
+            Self::P50 => profile.map(|p| p.p50_latency_ms),
+            Self::P95 => profile.map(|p| p.p95_latency_ms),
+            Self::P99 => profile.map(|p| p.p99_latency_ms),
+            Self::Cpu => profile.map(|p| p.cpu_time_ms),
+            Self::Alloc => profile.map(|p| p.alloc_bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryReport<T> {
+    pub schema_version: String,
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last: Option<usize>,
+    /// Unparsable history lines that were left out of the query.
+    #[serde(default)]
+    pub skipped_records: usize,
+    pub scenarios: Vec<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPassRate {
+    pub path: String,
+    pub runs: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub pass_rate_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryTrend {
+    pub path: String,
+    pub metric: HistoryMetric,
+    pub points: Vec<HistoryPoint>,
+    /// Change from the first to the last point, in percent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPoint {
+    pub run_id: String,
+    pub finished_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    pub value: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryFirstFailure {
+    pub path: String,
+    /// Consecutive failing runs up to and including the latest one.
+    pub failing_runs: usize,
+    pub first_failure: HistoryRunRef,
+    /// The last passing run before the failures, if the scenario ever passed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_pass: Option<HistoryRunRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRunRef {
+    pub run_id: String,
+    pub finished_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryFlakeRate {
+    pub path: String,
+    pub runs: usize,
+    /// Runs whose failure passed on a same-seed retry.
+    pub flaky: usize,
+    /// Outcome changes between consecutive runs.
+    pub flips: usize,
+    pub flake_rate_pct: f64,
+}
+
+pub fn history_command(
+    config: &Config,
+    command: &HistoryCommand,
+) -> FozzyResult<serde_json::Value> {
+    let history = load_run_history(&config.history_path())?;
+    let records = &history.records;
+    let skipped = history.skipped;
+    let value = match command {
+        HistoryCommand::PassRate { scope } => {
+            serde_json::to_value(history_pass_rate(records, scope)?.skipping(skipped))?
+        }
+        HistoryCommand::Trend { scope, metric } => {
+            serde_json::to_value(history_trend(records, scope, *metric)?.skipping(skipped))?
+        }
+        HistoryCommand::FirstFailure { scenario } => serde_json::to_value(
+            history_first_failure(records, scenario.as_deref())?.skipping(skipped),
+        )?,
+        HistoryCommand::FlakeRate { scope } => {
+            serde_json::to_value(history_flake_rate(records, scope)?.skipping(skipped))?
+        }
+    };
+    Ok(value)
+}
+
+impl<T> HistoryReport<T> {
+    fn skipping(mut self, skipped: usize) -> Self {
+        self.skipped_records = skipped;
+        self
+    }
+}
+
+pub fn history_pass_rate(
+    records: &[HistoryRecord],
+    scope: &HistoryScope,
+) -> FozzyResult<HistoryReport<HistoryPassRate>> {
+    let scenarios = recent_by_scenario(records, scope)?
+        .into_iter()
+        .map(|(path, runs)| {
+            let passed = runs.iter().filter(|record| record.passed()).count();
+            HistoryPassRate {
+                path,
+                runs: runs.len(),
+                passed,
+                failed: runs.len() - passed,
+                pass_rate_pct: pct(passed, runs.len()),
+            }
+        })
+        .collect();
+    Ok(report("pass_rate", Some(scope.last), scenarios))
+}
+
+pub fn history_trend(
+    records: &[HistoryRecord],
+    scope: &HistoryScope,
+    metric: HistoryMetric,
+) -> FozzyResult<HistoryReport<HistoryTrend>> {
+    let scenarios = recent_by_scenario(records, scope)?
+        .into_iter()
+        .map(|(path, runs)| {
+            let points: Vec<HistoryPoint> = runs
+                .iter()
+                .filter_map(|record| {
+                    Some(HistoryPoint {
+                        run_id: record.run_id.clone(),
+                        finished_at: record.finished_at.clone(),
+                        git_commit: record.git_commit.clone(),
+                        value: metric.value(record)?,
+                    })
+                })
+                .collect();
+            let delta_pct = match (points.first(), points.last()) {
+                (Some(first), Some(last)) if points.len() > 1 && first.value > 0 => {
+                    Some((last.value as f64 - first.value as f64) * 100.0 / first.value as f64)
+                }
+                _ => None,
+            };
+            HistoryTrend {
+                path,
+                metric,
+                points,
+                delta_pct,
+            }
+        })
+        .collect();
+    Ok(report("trend", Some(scope.last), scenarios))
+}
+
+/// Scenarios whose latest run failed, with the first run of their current failing
+/// streak. Looks at the whole history, not just the last N runs.
+pub fn history_first_failure(
+    records: &[HistoryRecord],
+    scenario: Option<&str>,
+) -> FozzyResult<HistoryReport<HistoryFirstFailure>> {
+    let scope = HistoryScope {
+        scenario: scenario.map(str::to_string),
+        last: usize::MAX,
+    };
+    let scenarios = recent_by_scenario(records, &scope)?
+        .into_iter()
+        .filter_map(|(path, runs)| {
+            let failing_runs = runs
+                .iter()
+                .rev()
+                .take_while(|record| !record.passed())
+                .count();
+            if failing_runs == 0 {
+                return None;
+            }
+            let first = runs.len() - failing_runs;
+            Some(HistoryFirstFailure {
+                path,
+                failing_runs,
+                first_failure: run_ref(runs[first]),
+                last_pass: first.checked_sub(1).map(|pass| run_ref(runs[pass])),
+            })
+        })
+        .collect();
+    Ok(report("first_failure", None, scenarios))
+}
+
+pub fn history_flake_rate(
+    records: &[HistoryRecord],
+    scope: &HistoryScope,
+) -> FozzyResult<HistoryReport<HistoryFlakeRate>> {
+    let scenarios = recent_by_scenario(records, scope)?
+        .into_iter()
+        .map(|(path, runs)| {
+            let flaky = runs
+                .iter()
+                .filter(|record| {
+                    record.scenario.classification == Some(crate::FailureClassification::Flaky)
+                })
+                .count();
+            let flips = runs
+                .windows(2)
+                .filter(|pair| pair[0].passed() != pair[1].passed())
+                .count();
+            HistoryFlakeRate {
+                path,
+                runs: runs.len(),
+                flaky,
+                flips,
+                flake_rate_pct: pct(flaky, runs.len()),
+            }
+        })
+        .collect();
+    Ok(report("flake_rate", Some(scope.last), scenarios))
+}
+
+/// The last `scope.last` records of each in-scope scenario, oldest first.
+fn recent_by_scenario<'a>(
+    records: &'a [HistoryRecord],
+    scope: &HistoryScope,
+) -> FozzyResult<BTreeMap<String, Vec<&'a HistoryRecord>>> {
+    if scope.last == 0 {
+        return Err(FozzyError::InvalidArgument(
+            "--last must be at least 1".to_string(),
+        ));
+    }
+    let matcher = scope
+        .scenario
+        .as_deref()
+        .map(scenario_matcher)
+        .transpose()?;
+    let mut by_path: BTreeMap<String, Vec<&HistoryRecord>> = BTreeMap::new();
+    for record in records {
+        let path = record.scenario.path.trim_start_matches("./");
+        if matcher
+            .as_ref()
+            .is_none_or(|matcher| matcher.is_match(path))
+        {
+            by_path.entry(path.to_string()).or_default().push(record);
+        }
+    }
+    for runs in by_path.values_mut() {
+        let skip = runs.len().saturating_sub(scope.last);
+        runs.drain(..skip);
+    }
+    Ok(by_path)
+}
+
+fn scenario_matcher(pattern: &str) -> FozzyResult<GlobMatcher> {
+    Glob::new(pattern.trim_start_matches("./"))
+        .map(|glob| glob.compile_matcher())
+        .map_err(|err| {
+            FozzyError::InvalidArgument(format!("invalid --scenario glob {pattern:?}: {err}"))
+        })
+}
+
+fn run_ref(record: &HistoryRecord) -> HistoryRunRef {
+    HistoryRunRef {
+        run_id: record.run_id.clone(),
+        finished_at: record.finished_at.clone(),
+        git_commit: record.git_commit.clone(),
+        seed: record.scenario.seed,
+    }
+}
+
+fn pct(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+fn report<T>(query: &str, last: Option<usize>, scenarios: Vec<T>) -> HistoryReport<T> {
+    HistoryReport {
+        schema_version: "fozzy.history.v1".to_string(),
+        query: query.to_string(),
+        last,
+        skipped_records: 0,
+        scenarios,
+    }
+}
+
+#[cfg(test)]
+#[path = "history/tests.rs"]
+mod tests;
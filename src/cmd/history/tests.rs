@@ -0,0 +1,175 @@
+use super::*;
+
+use crate::{
+    ExitStatus, FailureClassification, HistoryProfile, HistoryScenario, RunMode, RunSummary,
+};
+
+fn record(run: usize, path: &str, status: ExitStatus, duration_ms: u64) -> HistoryRecord {
+    HistoryRecord {
+        run_id: format!("run-{run}"),
+        mode: RunMode::Test,
+        finished_at: format!("2026-01-01T00:00:{run:02}Z"),
+        git_commit: Some(format!("commit{run}")),
+        scenario: HistoryScenario {
+            path: path.to_string(),
+            status,
+            seed: run as u64,
+            duration_ms,
+            duration_ns: duration_ms * 1_000_000,
+            classification: None,
+            quarantined: false,
+            profile: Some(HistoryProfile {
+                cpu_time_ms: 1,
+                alloc_bytes: 0,
+                p50_latency_ms: duration_ms / 2,
+                p95_latency_ms: duration_ms,
+                p99_latency_ms: duration_ms * 2,
+                max_latency_ms: duration_ms * 2,
+            }),
+        },
+    }
+}
+
+fn scope(last: usize) -> HistoryScope {
+    HistoryScope {
+        scenario: None,
+        last,
+    }
+}
+
+#[test]
+fn pass_rate_and_flake_rate_use_the_last_n_runs_of_each_scenario() {
+    let mut flaky = record(3, "tests/a.fozzy.json", ExitStatus::Fail, 10);
+    flaky.scenario.classification = Some(FailureClassification::Flaky);
+    let records = vec![
+        record(1, "tests/a.fozzy.json", ExitStatus::Fail, 10),
+        record(1, "./tests/b.fozzy.json", ExitStatus::Pass, 10),
+        record(2, "tests/a.fozzy.json", ExitStatus::Pass, 10),
+        flaky,
+        record(4, "tests/a.fozzy.json", ExitStatus::Fail, 10),
+    ];
+
+    let report = history_pass_rate(&records, &scope(3)).expect("pass rate");
+    assert_eq!(report.scenarios.len(), 2);
+    let a = &report.scenarios[0];
+    assert_eq!(a.path, "tests/a.fozzy.json");
+    assert_eq!((a.runs, a.passed, a.failed), (3, 2, 1));
+    assert_eq!(report.scenarios[1].path, "tests/b.fozzy.json");
+
+    let report = history_flake_rate(&records, &scope(3)).expect("flake rate");
+    let a = &report.scenarios[0];
+    assert_eq!((a.runs, a.flaky, a.flips), (3, 1, 1));
+
+    let only_b = HistoryScope {
+        scenario: Some("tests/b.*".to_string()),
+        last: 20,
+    };
+    let report = history_pass_rate(&records, &only_b).expect("pass rate");
+    assert_eq!(report.scenarios.len(), 1);
+    assert_eq!(report.scenarios[0].pass_rate_pct, 100.0);
+
+    assert!(history_pass_rate(&records, &scope(0)).is_err());
+}
+
+#[test]
+fn trend_reports_points_and_delta_for_duration_and_profile_metrics() {
+    let records = vec![
+        record(1, "tests/a.fozzy.json", ExitStatus::Pass, 10),
+        record(2, "tests/a.fozzy.json", ExitStatus::Pass, 12),
+        record(3, "tests/a.fozzy.json", ExitStatus::Pass, 15),
+    ];
+
+    let report = history_trend(&records, &scope(2), HistoryMetric::Duration).expect("trend");
+    let trend = &report.scenarios[0];
+    let values: Vec<u64> = trend.points.iter().map(|point| point.value).collect();
+    assert_eq!(values, vec![12, 15]);
+    assert_eq!(trend.delta_pct, Some(25.0));
+
+    let report = history_trend(&records, &scope(20), HistoryMetric::P99).expect("trend");
+    let values: Vec<u64> = report.scenarios[0]
+        .points
+        .iter()
+        .map(|point| point.value)
+        .collect();
+    assert_eq!(values, vec![20, 24, 30]);
+    assert_eq!(
+        report.scenarios[0].points[0].git_commit.as_deref(),
+        Some("commit1")
+    );
+}
+
+#[test]
+fn first_failure_finds_the_start_of_the_current_failing_streak() {
+    let records = vec![
+        record(1, "tests/a.fozzy.json", ExitStatus::Fail, 10),
+        record(2, "tests/a.fozzy.json", ExitStatus::Pass, 10),
+        record(3, "tests/a.fozzy.json", ExitStatus::Fail, 10),
+        record(4, "tests/a.fozzy.json", ExitStatus::Crash, 10),
+        record(4, "tests/b.fozzy.json", ExitStatus::Pass, 10),
+        record(5, "tests/c.fozzy.json", ExitStatus::Fail, 10),
+    ];
+
+    let report = history_first_failure(&records, None).expect("first failure");
+    assert_eq!(report.scenarios.len(), 2, "passing scenarios are omitted");
+    let a = &report.scenarios[0];
+    assert_eq!(a.failing_runs, 2);
+    assert_eq!(a.first_failure.run_id, "run-3");
+    assert_eq!(a.first_failure.git_commit.as_deref(), Some("commit3"));
+    assert_eq!(
+        a.last_pass.as_ref().map(|pass| pass.run_id.as_str()),
+        Some("run-2")
+    );
+    let c = &report.scenarios[1];
+    assert_eq!(c.first_failure.run_id, "run-5");
+    assert!(c.last_pass.is_none());
+}
+
+#[test]
+fn appended_history_round_trips_and_skips_bad_lines() {
+    let dir = std::env::temp_dir().join(format!("fozzy-history-{}", uuid::Uuid::new_v4()));
+    let config = Config {
+        base_dir: dir.join(".fozzy"),
+        ..Config::default()
+    };
+    assert!(
+        load_run_history(&config.history_path())
+            .expect("missing index")
+            .records
+            .is_empty()
+    );
+
+    let summary = RunSummary {
+        mode: RunMode::Test,
+        identity: crate::RunIdentity {
+            run_id: "r1".to_string(),
+            seed: 1,
+            trace_path: None,
+            report_path: None,
+            artifacts_dir: None,
+        },
+        finished_at: "2026-01-01T00:00:01Z".to_string(),
+        ..RunSummary::fixture()
+    };
+    let scenario = record(1, "tests/a.fozzy.json", ExitStatus::Pass, 3).scenario;
+    crate::append_run_history(&config, &summary, vec![scenario.clone()]).expect("append");
+    crate::append_run_history(&config, &summary, vec![scenario]).expect("append");
+
+    let records = load_run_history(&config.history_path())
+        .expect("load")
+        .records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].run_id, "r1");
+    assert_eq!(records[1].scenario.path, "tests/a.fozzy.json");
+
+    let mut text = std::fs::read_to_string(config.history_path()).expect("read");
+    text.push_str("{not json\n");
+    std::fs::write(config.history_path(), text).expect("write");
+    let history = load_run_history(&config.history_path()).expect("bad line is skipped");
+    assert_eq!(history.records.len(), 2);
+    assert_eq!(history.skipped, 1);
+
+    let out =
+        history_command(&config, &HistoryCommand::PassRate { scope: scope(20) }).expect("query");
+    assert_eq!(out["skippedRecords"], 1);
+    assert_eq!(out["scenarios"][0]["runs"], 2);
+}
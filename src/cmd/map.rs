@@ -13,12 +13,13 @@ mod scenario;
 #[path = "map/types.rs"]
 mod types;
 
-pub use dispatch::{map_command, map_suites};
+pub use dispatch::{map_command, map_suites, map_suites_selection};
 pub use types::*;
 
 #[allow(unused_imports)]
 pub(crate) use attribution::{
-    AttributionHints, covered_suites_for_hotspot, suite_allows_attribution_match, tokenize,
+    AttributionHints, covered_suites_for_hotspot, scenarios_attributed_to_source,
+    suite_allows_attribution_match, tokenize,
 };
 #[allow(unused_imports)]
 pub(crate) use policy::{
@@ -29,7 +30,7 @@ pub(crate) use policy::{
 #[allow(unused_imports)]
 pub(crate) use repo::{
     accumulate_signals_line, component_for_path, count_hits, discover_scan_roots, hotspot_hints,
-    is_candidate_file, scan_repo, score_signals, should_skip_path,
+    is_candidate_file, scan_repo, score_signals, should_skip_path, source_path_hints,
 };
 #[allow(unused_imports)]
 pub(crate) use scenario::{
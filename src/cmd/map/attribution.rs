@@ -1,7 +1,9 @@
 use std::collections::BTreeSet;
+use std::path::Path;
 
 use super::{
-    SUITE_RUN_REPLAY_CI, SUITE_TEST_DET, ScenarioCoverageIndex, ScenarioFact, SuiteCoverageEvidence,
+    SUITE_RUN_REPLAY_CI, SUITE_TEST_DET, ScenarioCoverageIndex, ScenarioFact,
+    SuiteCoverageEvidence, component_for_path, source_path_hints,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -99,7 +101,28 @@ pub(crate) fn suite_allows_attribution_match(
     if suite == SUITE_TEST_DET || suite == SUITE_RUN_REPLAY_CI {
         return true;
     }
+    hints_match_scenario(hints, scenario_tokens)
+}
+
+/// Paths of the scenarios attributed to a changed source file by shared tokens, as
+/// `map suites` would attribute them to a hotspot at that path.
+pub(crate) fn scenarios_attributed_to_source(
+    source: &Path,
+    scenarios: &[ScenarioFact],
+) -> Vec<String> {
+    let rel = source.strip_prefix(".").unwrap_or(source);
+    let hints = AttributionHints::from_hotspot_hints(&source_path_hints(
+        &component_for_path(rel),
+        &rel.to_string_lossy(),
+    ));
+    scenarios
+        .iter()
+        .filter(|scenario| hints_match_scenario(&hints, &scenario.tokens))
+        .map(|scenario| scenario.path.clone())
+        .collect()
+}
 
+fn hints_match_scenario(hints: &AttributionHints, scenario_tokens: &BTreeSet<String>) -> bool {
     let shared = hints
         .tokens
         .intersection(scenario_tokens)
@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::{Config, FozzyResult};
+use crate::{Config, FozzyResult, ScenarioSelectionReport};
 
 use super::{
     MapCommand, MapHotspotsReport, MapServicesReport, MapSuitesOptions, MapSuitesReport,
@@ -57,7 +57,13 @@ pub fn map_command(config: &Config, command: &MapCommand) -> FozzyResult<serde_j
             only_required,
             only_uncovered,
             max_matched_scenarios,
+            filter,
+            list,
         } => {
+            if *list {
+                let report = map_suites_selection(config, scenario_root, filter.as_deref())?;
+                return Ok(serde_json::to_value(report)?);
+            }
             let report = map_suites_with_cache(
                 &MapSuitesOptions {
                     root: root.clone(),
@@ -71,8 +77,10 @@ pub fn map_command(config: &Config, command: &MapCommand) -> FozzyResult<serde_j
                     only_required: *only_required,
                     only_uncovered: *only_uncovered,
                     max_matched_scenarios: *max_matched_scenarios,
+                    filter: filter.clone(),
                 },
                 Some(config.base_dir.join("cache")),
+                &config.runs_dir(),
             )?;
             Ok(serde_json::to_value(report)?)
         }
@@ -80,14 +88,32 @@ pub fn map_command(config: &Config, command: &MapCommand) -> FozzyResult<serde_j
 }
 
 pub fn map_suites(opt: &MapSuitesOptions) -> FozzyResult<MapSuitesReport> {
-    map_suites_with_cache(opt, None)
+    map_suites_with_cache(opt, None, &Config::default().runs_dir())
+}
+
+/// `fozzy map suites --list`: the scenarios under `scenario_root` and the filter's verdict.
+pub fn map_suites_selection(
+    config: &Config,
+    scenario_root: &Path,
+    filter: Option<&str>,
+) -> FozzyResult<ScenarioSelectionReport> {
+    crate::select_scenarios(
+        &discover_scenarios(scenario_root)?,
+        filter,
+        &config.runs_dir(),
+    )
 }
 
 fn map_suites_with_cache(
     opt: &MapSuitesOptions,
     cache_dir: Option<PathBuf>,
+    runs_dir: &Path,
 ) -> FozzyResult<MapSuitesReport> {
-    let scenario_files = discover_scenarios(&opt.scenario_root)?;
+    let mut scenario_files = discover_scenarios(&opt.scenario_root)?;
+    if opt.filter.is_some() {
+        scenario_files = crate::select_scenarios(&scenario_files, opt.filter.as_deref(), runs_dir)?
+            .selected_paths();
+    }
     let facts = scan_repo(&opt.root)?;
     let scenario_build = build_scenario_facts(&scenario_files, cache_dir.as_deref());
     let scenario_facts = scenario_build.facts;
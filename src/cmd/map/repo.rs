@@ -105,13 +105,15 @@ pub(crate) fn scan_repo(root: &Path) -> FozzyResult<RepoFacts> {
 }
 
 pub(crate) fn hotspot_hints(hotspot: &MapHotspot) -> Vec<String> {
+    source_path_hints(&hotspot.component, &hotspot.path)
+}
+
+/// Attribution hints for a source file: its component, path and file stem.
+pub(crate) fn source_path_hints(component: &str, path: &str) -> Vec<String> {
     let mut out = BTreeSet::<String>::new();
-    out.insert(hotspot.component.to_ascii_lowercase());
-    out.insert(hotspot.path.to_ascii_lowercase());
-    if let Some(stem) = Path::new(&hotspot.path)
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-    {
+    out.insert(component.to_ascii_lowercase());
+    out.insert(path.to_ascii_lowercase());
+    if let Some(stem) = Path::new(path).file_stem().and_then(|stem| stem.to_str()) {
         out.insert(stem.to_ascii_lowercase().replace('.', "-"));
         out.insert(stem.to_ascii_lowercase().replace('.', "_"));
     }
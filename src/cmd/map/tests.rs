@@ -40,6 +40,7 @@ fn map_suites_reports_uncovered_hotspots() {
         only_required: false,
         only_uncovered: false,
         max_matched_scenarios: 25,
+        filter: None,
     })
     .expect("map suites");
     assert!(report.required_hotspot_count > 0);
@@ -261,6 +262,7 @@ fn map_suites_credits_natural_fetch_host_scenario() {
         only_required: false,
         only_uncovered: false,
         max_matched_scenarios: 25,
+        filter: None,
     })
     .expect("map suites");
     let suite = report.suites.first().expect("suite");
@@ -302,6 +304,7 @@ fn map_suites_reports_unreadable_scenarios() {
         only_required: false,
         only_uncovered: false,
         max_matched_scenarios: 25,
+        filter: None,
     })
     .expect("map suites");
 
@@ -462,6 +465,7 @@ fn map_suites_credits_short_natural_hotspot_names() {
         only_required: false,
         only_uncovered: false,
         max_matched_scenarios: 25,
+        filter: None,
     })
     .expect("map suites");
 
@@ -551,6 +555,7 @@ fn map_suites_applies_filters_before_pagination() {
         only_required: false,
         only_uncovered: false,
         max_matched_scenarios: 25,
+        filter: None,
     };
 
     let full = map_suites(&base).expect("full report");
@@ -618,6 +623,7 @@ fn map_suites_all_returns_full_filtered_set_without_truncation() {
         only_required: true,
         only_uncovered: true,
         max_matched_scenarios: 25,
+        filter: None,
     })
     .expect("all report");
 
@@ -59,6 +59,12 @@ pub enum MapCommand {
         only_uncovered: bool,
         #[arg(long, default_value_t = 25)]
         max_matched_scenarios: usize,
+        /// Only credit coverage from scenarios this filter expression selects (see `fozzy test --filter`).
+        #[arg(long)]
+        filter: Option<String>,
+        /// Print the scenarios `--filter` selects, and why, instead of suite recommendations.
+        #[arg(long)]
+        list: bool,
     },
 }
 
@@ -227,6 +233,8 @@ pub struct MapSuitesOptions {
     pub only_required: bool,
     pub only_uncovered: bool,
     pub max_matched_scenarios: usize,
+    /// Scenario filter expression; only selected scenarios count as coverage.
+    pub filter: Option<String>,
 }
 
 #[derive(Debug, Clone)]
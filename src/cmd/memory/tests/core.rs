@@ -81,8 +81,14 @@ fn memory_diff_from_trace_inputs() {
                 tests: None,
                 memory: None,
                 findings: Vec::new(),
+                retries: Vec::new(),
             },
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
         };
         trace.write_json(path).expect("write trace");
     };
@@ -50,8 +50,14 @@ fn direct_trace_uses_summary_memory_when_embedded_memory_block_is_absent() {
                 in_use_bytes: 0,
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
 
@@ -128,8 +134,14 @@ fn direct_trace_uses_declared_artifacts_dir_for_memory_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -65,8 +65,14 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     explicit
         .write_json(&explicit_trace)
@@ -89,6 +95,7 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_graph() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let sibling = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -120,7 +127,12 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_graph() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: sibling_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     sibling
         .write_json(&sibling_trace)
@@ -216,8 +228,14 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_leaks() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     explicit
         .write_json(&explicit_trace)
@@ -240,6 +258,7 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_leaks() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let sibling = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -271,7 +290,12 @@ fn direct_trace_ignores_coherent_foreign_sibling_memory_leaks() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: sibling_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     sibling
         .write_json(&sibling_trace)
@@ -69,8 +69,14 @@ fn direct_trace_uses_exact_coherent_sibling_memory_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -181,8 +187,14 @@ fn direct_trace_rejects_partial_trusted_memory_graph_without_embedded_leaks() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -277,8 +289,14 @@ fn direct_trace_rejects_mismatched_trusted_memory_graph_sidecar() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -70,8 +70,14 @@ fn direct_trace_uses_exact_coherent_sibling_memory_leaks() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -151,8 +157,14 @@ fn direct_trace_rejects_partial_trusted_memory_leaks_without_embedded_graph() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -233,8 +245,14 @@ fn direct_trace_rejects_mismatched_trusted_memory_leaks_sidecar() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
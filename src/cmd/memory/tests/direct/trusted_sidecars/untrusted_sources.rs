@@ -71,8 +71,14 @@ fn direct_trace_ignores_forged_declared_memory_artifacts_dir() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -161,8 +167,14 @@ fn direct_trace_ignores_unchecked_sibling_memory_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -246,8 +258,14 @@ fn direct_trace_ignores_unchecked_sibling_memory_leaks() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
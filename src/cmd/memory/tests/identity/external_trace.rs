@@ -59,8 +59,14 @@ fn run_id_uses_report_declared_external_trace_path_for_memory_bundle() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -188,8 +194,14 @@ fn run_id_with_only_memory_graph_sidecar_still_uses_trace_leaks() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -264,6 +276,7 @@ fn run_id_with_only_memory_graph_sidecar_and_no_trace_is_rejected() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         })
         .expect("report json"),
     )
@@ -291,6 +304,7 @@ fn run_id_with_only_memory_graph_sidecar_and_no_trace_is_rejected() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
         &run_dir,
     )
@@ -392,8 +406,14 @@ fn run_id_uses_manifest_declared_external_trace_path_for_memory_bundle_without_r
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
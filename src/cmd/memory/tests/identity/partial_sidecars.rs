@@ -82,8 +82,14 @@ fn run_id_with_only_memory_leaks_sidecar_still_uses_trace_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -168,6 +174,7 @@ fn run_id_with_only_memory_leaks_sidecar_and_no_trace_is_rejected() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         })
         .expect("report json"),
     )
@@ -195,6 +202,7 @@ fn run_id_with_only_memory_leaks_sidecar_and_no_trace_is_rejected() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
         &run_dir,
     )
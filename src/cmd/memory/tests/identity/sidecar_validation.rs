@@ -57,8 +57,14 @@ fn memory_run_id_rejects_mismatched_memory_graph_sidecar() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -149,8 +155,14 @@ fn memory_run_id_rejects_mismatched_memory_leaks_sidecar() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
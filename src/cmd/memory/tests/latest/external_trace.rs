@@ -62,8 +62,14 @@ fn latest_alias_uses_report_declared_external_trace_path_for_memory_bundle() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -149,6 +155,7 @@ fn latest_alias_uses_manifest_declared_external_trace_path_for_memory_bundle_wit
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         })
         .expect("older report json"),
     )
@@ -226,8 +233,14 @@ fn latest_alias_uses_manifest_declared_external_trace_path_for_memory_bundle_wit
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
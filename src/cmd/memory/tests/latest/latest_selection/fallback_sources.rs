@@ -35,6 +35,7 @@ fn latest_memory_alias_skips_newer_trace_without_memory_data() {
             ..MemorySummary::default()
         }),
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let older_trace = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -68,7 +69,12 @@ fn latest_memory_alias_skips_newer_trace_without_memory_data() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: older_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     older_trace
         .write_json(&older_trace_path)
@@ -100,6 +106,7 @@ fn latest_memory_alias_skips_newer_trace_without_memory_data() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let newer_trace = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -118,7 +125,12 @@ fn latest_memory_alias_skips_newer_trace_without_memory_data() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: newer_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     newer_trace
         .write_json(&newer_trace_path)
@@ -175,6 +187,7 @@ fn latest_memory_alias_skips_newer_timeline_only_wrapper() {
             ..MemorySummary::default()
         }),
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let older_trace = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -208,7 +221,12 @@ fn latest_memory_alias_skips_newer_timeline_only_wrapper() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: older_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     older_trace
         .write_json(&older_trace_path)
@@ -240,6 +258,7 @@ fn latest_memory_alias_skips_newer_timeline_only_wrapper() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let newer_trace = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -258,7 +277,12 @@ fn latest_memory_alias_skips_newer_timeline_only_wrapper() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: newer_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     newer_trace
         .write_json(&newer_trace_path)
@@ -33,6 +33,7 @@ fn latest_memory_alias_skips_newer_stale_report_only_run() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let healthy_trace = crate::TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -66,7 +67,12 @@ fn latest_memory_alias_skips_newer_stale_report_only_run() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: healthy_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     healthy_trace
         .write_json(&external_trace)
@@ -139,6 +145,7 @@ fn latest_memory_alias_skips_newer_stale_report_only_run() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         })
         .expect("stale report json"),
     )
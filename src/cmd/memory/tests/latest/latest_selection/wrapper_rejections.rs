@@ -64,8 +64,14 @@ fn memory_run_id_rejects_trace_only_wrapper_without_report_manifest() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
 
@@ -37,6 +37,7 @@ fn memory_artifacts_reject_stale_report_without_manifest() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         })
         .expect("report json"),
     )
@@ -138,8 +139,14 @@ fn direct_trace_uses_manifest_only_declared_artifacts_dir_for_memory_graph() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -274,8 +281,14 @@ fn memory_run_id_rejects_incoherent_manifest_only_wrapper() {
                 ..MemorySummary::default()
             }),
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     let manifest = crate::RunManifest {
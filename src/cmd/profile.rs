@@ -35,6 +35,7 @@ use support::{
 };
 pub use types::*;
 
+pub(crate) use build::ProfileSource;
 pub use build::heap_budget_findings_from_trace;
 
 use crate::{
@@ -249,6 +250,15 @@ fn trace_digest(path: &Path) -> FozzyResult<String> {
     Ok(blake3::hash(&std::fs::read(path)?).to_hex().to_string())
 }
 
+/// Profile metrics for the events of `source` without writing any profiler artifacts.
+pub(crate) fn profile_metrics_from_events(source: ProfileSource<'_>) -> ProfileMetrics {
+    let timeline = build_profile_timeline(source);
+    let cpu = build_cpu_profile(source, &timeline);
+    let heap = build_heap_profile(source, &timeline);
+    let latency = build_latency_profile(source, &timeline);
+    build_profile_metrics(source, &timeline, &cpu, &heap, &latency)
+}
+
 pub fn write_profile_artifacts_from_trace_with_source(
     trace: &TraceFile,
     source_trace_path: Option<&Path>,
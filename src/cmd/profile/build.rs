@@ -1,14 +1,44 @@
 use super::support::detect_cpu_collector_capability;
 use super::*;
 
-pub(super) fn build_profile_timeline(trace: &TraceFile) -> Vec<ProfileEvent> {
-    let run_id = trace.summary.identity.run_id.clone();
-    let seed = trace.summary.identity.seed;
+/// The parts of a trace the profile builders read. Borrowed, so a finished scenario
+/// run can be profiled without first assembling a `TraceFile`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProfileSource<'a> {
+    pub(crate) run_id: &'a str,
+    pub(crate) seed: u64,
+    pub(crate) duration_ms: u64,
+    pub(crate) memory_in_use_bytes: u64,
+    pub(crate) events: &'a [crate::TraceEvent],
+}
+
+impl<'a> From<&'a TraceFile> for ProfileSource<'a> {
+    fn from(trace: &'a TraceFile) -> Self {
+        Self {
+            run_id: &trace.summary.identity.run_id,
+            seed: trace.summary.identity.seed,
+            duration_ms: trace.summary.duration_ms,
+            memory_in_use_bytes: trace
+                .memory
+                .as_ref()
+                .map(|m| m.summary.in_use_bytes)
+                .unwrap_or(0),
+            events: &trace.events,
+        }
+    }
+}
+
+pub(super) fn build_profile_timeline<'a>(
+    source: impl Into<ProfileSource<'a>>,
+) -> Vec<ProfileEvent> {
+    let source = source.into();
+    let run_id = source.run_id.to_string();
+    let seed = source.seed;
     let mut out = Vec::new();
     let mut open_spans = Vec::<String>::new();
-    for (idx, event) in trace.events.iter().enumerate() {
+    for (idx, event) in source.events.iter().enumerate() {
         let kind = map_event_kind(&event.name);
-        let t_next = trace.events.get(idx + 1).map(|n| n.time_ms);
+        let t_next = source.events.get(idx + 1).map(|n| n.time_ms);
         let duration = t_next.and_then(|n| n.checked_sub(event.time_ms));
         let mut tags = BTreeMap::new();
         tags.insert("name".to_string(), event.name.clone());
@@ -123,7 +153,11 @@ fn map_event_kind(name: &str) -> ProfileEventKind {
     }
 }
 
-pub(super) fn build_cpu_profile(trace: &TraceFile, timeline: &[ProfileEvent]) -> CpuProfile {
+pub(super) fn build_cpu_profile<'a>(
+    source: impl Into<ProfileSource<'a>>,
+    timeline: &[ProfileEvent],
+) -> CpuProfile {
+    let source = source.into();
     let capability = detect_cpu_collector_capability();
     let mut stacks = HashMap::<String, u64>::new();
     let samples = build_cpu_samples(timeline, capability.sample_period_ms);
@@ -140,7 +174,7 @@ pub(super) fn build_cpu_profile(trace: &TraceFile, timeline: &[ProfileEvent]) ->
 
     CpuProfile {
         schema_version: "fozzy.profile_cpu.v3".to_string(),
-        run_id: trace.summary.identity.run_id.clone(),
+        run_id: source.run_id.to_string(),
         collector: CpuCollectorInfo {
             domain: "host_time".to_string(),
             primary_collector: capability.primary_collector,
@@ -198,7 +232,11 @@ fn build_cpu_samples(timeline: &[ProfileEvent], sample_period_ms: u64) -> Vec<Cp
     samples
 }
 
-pub(super) fn build_heap_profile(trace: &TraceFile, timeline: &[ProfileEvent]) -> HeapProfile {
+pub(super) fn build_heap_profile<'a>(
+    source: impl Into<ProfileSource<'a>>,
+    timeline: &[ProfileEvent],
+) -> HeapProfile {
+    let source = source.into();
     #[derive(Clone)]
     struct LiveAlloc {
         bytes: u64,
@@ -324,17 +362,11 @@ pub(super) fn build_heap_profile(trace: &TraceFile, timeline: &[ProfileEvent]) -
         callsite.alloc_rate_per_sec = (callsite.alloc_bytes as f64) / span_s;
     }
 
-    let trace_memory_in_use = trace
-        .memory
-        .as_ref()
-        .map(|m| m.summary.in_use_bytes)
-        .unwrap_or(0);
-
     HeapProfile {
         schema_version: "fozzy.profile_heap.v2".to_string(),
-        run_id: trace.summary.identity.run_id.clone(),
+        run_id: source.run_id.to_string(),
         total_alloc_bytes,
-        in_use_bytes: in_use_bytes.max(trace_memory_in_use),
+        in_use_bytes: in_use_bytes.max(source.memory_in_use_bytes),
         alloc_rate_per_sec,
         hotspots: hotspot_list,
         lifetime_histogram,
@@ -342,10 +374,11 @@ pub(super) fn build_heap_profile(trace: &TraceFile, timeline: &[ProfileEvent]) -
     }
 }
 
-pub(super) fn build_latency_profile(
-    trace: &TraceFile,
+pub(super) fn build_latency_profile<'a>(
+    source: impl Into<ProfileSource<'a>>,
     timeline: &[ProfileEvent],
 ) -> LatencyProfile {
+    let source = source.into();
     #[derive(Debug, Clone)]
     struct SpanRec {
         span_id: String,
@@ -511,7 +544,7 @@ pub(super) fn build_latency_profile(
 
     LatencyProfile {
         schema_version: "fozzy.profile_latency.v1".to_string(),
-        run_id: trace.summary.identity.run_id.clone(),
+        run_id: source.run_id.to_string(),
         distribution,
         dependency_graph,
         critical_path,
@@ -616,15 +649,16 @@ pub(super) fn build_symbols_map(
     }
 }
 
-pub(super) fn build_profile_metrics(
-    trace: &TraceFile,
+pub(super) fn build_profile_metrics<'a>(
+    source: impl Into<ProfileSource<'a>>,
     timeline: &[ProfileEvent],
     cpu: &CpuProfile,
     heap: &HeapProfile,
     latency: &LatencyProfile,
 ) -> ProfileMetrics {
+    let source = source.into();
     let virtual_time_ms = timeline.last().map(|e| e.t_virtual).unwrap_or(0);
-    let host_time_ms = trace.summary.duration_ms;
+    let host_time_ms = source.duration_ms;
     let cpu_time_ms = cpu
         .folded_stacks
         .iter()
@@ -639,7 +673,7 @@ pub(super) fn build_profile_metrics(
         .count() as u64;
     ProfileMetrics {
         schema_version: "fozzy.profile_metrics.v2".to_string(),
-        run_id: trace.summary.identity.run_id.clone(),
+        run_id: source.run_id.to_string(),
         time_domains: TimeDomains {
             virtual_time: "deterministic, replay-critical".to_string(),
             host_monotonic_time: "non-deterministic, statistical comparison only".to_string(),
@@ -480,6 +480,9 @@ pub(super) fn dispatch_profile_command(
                     budget: budget.map(|b| b.0),
                     aggressive: false,
                     minimize: *minimize,
+                    preserve: crate::ShrinkPreserve::Status,
+                    finding_match: None,
+                    jobs: None,
                 },
                 &move |candidate_trace: &TraceFile| {
                     let candidate_value = metric_value(metric_for_predicate, candidate_trace)?;
@@ -503,7 +506,7 @@ pub(super) fn dispatch_profile_command(
                 Some(shrink_report_path.to_string_lossy().to_string());
             shrunk_trace.summary.identity.artifacts_dir =
                 Some(shrink_artifacts_dir.to_string_lossy().to_string());
-            shrunk_trace.write_json(Path::new(&shrunk.out_trace_path))?;
+            shrunk_trace.write(Path::new(&shrunk.out_trace_path), config.trace_encoding)?;
             std::fs::create_dir_all(&shrink_artifacts_dir)?;
             std::fs::write(
                 &shrink_report_path,
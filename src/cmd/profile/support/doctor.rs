@@ -249,6 +249,9 @@ pub(in crate::profile) fn profile_doctor(
                         budget: Some(std::time::Duration::from_secs(2)),
                         aggressive: false,
                         minimize: ShrinkMinimize::All,
+                        preserve: crate::ShrinkPreserve::Status,
+                        finding_match: None,
+                        jobs: None,
                     },
                 ) {
                     Ok(s) => {
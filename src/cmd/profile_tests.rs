@@ -73,8 +73,14 @@ fn sample_trace() -> TraceFile {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     }
 }
 
@@ -138,8 +144,14 @@ fn sample_trace_without_heap() -> TraceFile {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     }
 }
 
@@ -5,17 +5,17 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 use crate::TraceFile;
-use crate::{
-    Config, FlakeBudget, FozzyError, FozzyResult, Reporter, RunSummary, render_html,
-    render_junit_xml,
-};
+use crate::{Config, FlakeBudget, FozzyError, FozzyResult, Reporter, RunSummary};
 
 #[path = "report/flaky.rs"]
 mod flaky;
+#[path = "report/merge.rs"]
+mod merge;
 #[path = "report/query.rs"]
 mod query;
 
 use flaky::flaky_command;
+use merge::merge_command;
 use query::{list_query_paths, query_value};
 
 #[derive(Debug, Subcommand)]
@@ -43,13 +43,28 @@ pub enum ReportCommand {
     },
     /// Compare multiple runs/traces for flaky outcome patterns.
     Flaky {
-        /// Two or more run selectors or direct trace paths.
+        /// Two or more run selectors or direct trace paths, or one test run recorded with `--retries`.
         #[arg(value_name = "RUNS")]
         runs: Vec<String>,
         /// Maximum allowed flake rate percentage.
         #[arg(long)]
         flake_budget: Option<FlakeBudget>,
     },
+    /// Merge the reports of sharded `fozzy test --shard i/n` runs into one summary.
+    Merge {
+        /// Run selectors, run directories, or their `report.json` files.
+        #[arg(value_name = "RUNS", required = true)]
+        runs: Vec<String>,
+        /// Output format for the merged report.
+        #[arg(long, default_value = "json")]
+        format: Reporter,
+        /// Also write the merged report (JSON summary, JUnit XML, HTML, or text) to this path.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+        /// Merge runs that are not one complete `1/n..n/n` shard set.
+        #[arg(long)]
+        allow_partial: bool,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +87,23 @@ pub struct FlakyReport {
     pub is_flaky: bool,
     #[serde(rename = "flakeRatePct")]
     pub flake_rate_pct: f64,
+    /// Per-scenario retry evidence recorded by `fozzy test --retries`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenarios: Vec<FlakyScenario>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyScenario {
+    #[serde(rename = "runId")]
+    pub run_id: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification: Option<crate::FailureClassification>,
+    pub quarantined: bool,
+    #[serde(rename = "passedSeeds")]
+    pub passed_seeds: Vec<u64>,
+    #[serde(rename = "failedSeeds")]
+    pub failed_seeds: Vec<u64>,
 }
 
 pub fn report_command(config: &Config, command: &ReportCommand) -> FozzyResult<serde_json::Value> {
@@ -85,13 +117,9 @@ pub fn report_command(config: &Config, command: &ReportCommand) -> FozzyResult<s
                     format: *format,
                     content: summary.pretty(),
                 })?),
-                Reporter::Junit => Ok(serde_json::to_value(ReportEnvelope {
-                    format: *format,
-                    content: render_junit_xml(&summary),
-                })?),
-                Reporter::Html => Ok(serde_json::to_value(ReportEnvelope {
+                _ => Ok(serde_json::to_value(ReportEnvelope {
                     format: *format,
-                    content: render_html(&summary),
+                    content: crate::render_reporter(&summary, *format).unwrap_or_default(),
                 })?),
             }
         }
@@ -116,6 +144,12 @@ pub fn report_command(config: &Config, command: &ReportCommand) -> FozzyResult<s
             query_value(&value, expr)
         }
         ReportCommand::Flaky { runs, flake_budget } => flaky_command(config, runs, *flake_budget),
+        ReportCommand::Merge {
+            runs,
+            format,
+            out,
+            allow_partial,
+        } => merge_command(config, runs, *format, out.as_deref(), *allow_partial),
     }
 }
 
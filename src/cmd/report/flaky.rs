@@ -5,7 +5,7 @@ pub(super) fn flaky_command(
     runs: &[String],
     flake_budget: Option<FlakeBudget>,
 ) -> FozzyResult<serde_json::Value> {
-    if runs.len() < 2 {
+    if runs.is_empty() {
         return Err(FozzyError::Report(
             "flaky analysis requires at least two runs/traces".to_string(),
         ));
@@ -15,9 +15,26 @@ pub(super) fn flaky_command(
     let mut finding_sets = std::collections::BTreeSet::<Vec<String>>::new();
     let mut signatures = std::collections::BTreeMap::<String, usize>::new();
     let mut seen_run_ids = std::collections::BTreeSet::<String>::new();
+    let mut scenarios = Vec::<FlakyScenario>::new();
 
     for run in runs {
         let summary = load_summary(config, run)?;
+        if runs.len() == 1 && summary.retries.is_empty() {
+            return Err(FozzyError::Report(
+                "flaky analysis requires at least two runs/traces, or one test run recorded with --retries".to_string(),
+            ));
+        }
+        for retry in &summary.retries {
+            let (passed_seeds, failed_seeds) = retry.seeds_by_outcome();
+            scenarios.push(FlakyScenario {
+                run_id: summary.identity.run_id.clone(),
+                path: retry.path.clone(),
+                classification: retry.classification,
+                quarantined: retry.quarantined,
+                passed_seeds,
+                failed_seeds,
+            });
+        }
         if !seen_run_ids.insert(summary.identity.run_id.clone()) {
             return Err(FozzyError::Report(format!(
                 "duplicate run reference detected for runId={} (duplicates are not allowed in flaky analysis)",
@@ -43,12 +60,25 @@ pub(super) fn flaky_command(
         *signatures.entry(sig).or_insert(0) += 1;
     }
 
-    let is_flaky = status_counts.len() > 1 || finding_sets.len() > 1;
-    let dominant = signatures.values().copied().max().unwrap_or(0) as f64;
-    let total = runs.len() as f64;
-    let flake_rate_pct = if total == 0.0 {
-        0.0
+    let flaky_scenarios = scenarios
+        .iter()
+        .filter(|scenario| scenario.classification == Some(crate::FailureClassification::Flaky))
+        .count();
+    let is_flaky = status_counts.len() > 1 || finding_sets.len() > 1 || flaky_scenarios > 0;
+    let flake_rate_pct = if runs.len() == 1 {
+        // One run: the share of retried scenarios whose same-seed retry passed.
+        let retried = scenarios
+            .iter()
+            .filter(|scenario| scenario.classification.is_some())
+            .count();
+        if retried == 0 {
+            0.0
+        } else {
+            (flaky_scenarios as f64 / retried as f64) * 100.0
+        }
     } else {
+        let dominant = signatures.values().copied().max().unwrap_or(0) as f64;
+        let total = runs.len() as f64;
         ((total - dominant) / total) * 100.0
     };
     if let Some(budget) = flake_budget
@@ -67,6 +97,7 @@ pub(super) fn flaky_command(
         finding_title_sets: finding_sets.into_iter().collect(),
         is_flaky,
         flake_rate_pct,
+        scenarios,
     };
     Ok(serde_json::to_value(out)?)
 }
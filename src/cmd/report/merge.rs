@@ -0,0 +1,211 @@
+use super::*;
+
+/// Combines the reports of `fozzy test --shard i/n` runs into one test summary.
+///
+/// Runs may be given as run ids/selectors, run directories, or the `report.json` of
+/// a run. Each run's shard is read from the `timings.json` beside its report, and the
+/// runs must form one complete `1/n..n/n` set unless `allow_partial` is set. Test
+/// counts, durations and memory totals are summed, findings are collapsed, and the
+/// status is the first non-pass shard status.
+pub(super) fn merge_command(
+    config: &Config,
+    runs: &[String],
+    format: Reporter,
+    out: Option<&std::path::Path>,
+    allow_partial: bool,
+) -> FozzyResult<serde_json::Value> {
+    if runs.is_empty() {
+        return Err(FozzyError::Report(
+            "report merge requires at least one run".to_string(),
+        ));
+    }
+    let mut summaries = Vec::new();
+    let mut shards = Vec::new();
+    let mut seen_run_ids = std::collections::BTreeSet::<String>::new();
+    for run in runs {
+        let summary = load_shard_summary(config, run)?;
+        if summary.mode != crate::RunMode::Test || summary.tests.is_none() {
+            return Err(FozzyError::Report(format!(
+                "report merge only combines `fozzy test` runs; {run:?} is a {:?} run",
+                summary.mode
+            )));
+        }
+        if !seen_run_ids.insert(summary.identity.run_id.clone()) {
+            return Err(FozzyError::Report(format!(
+                "duplicate run reference detected for runId={} (each shard must be merged once)",
+                summary.identity.run_id
+            )));
+        }
+        shards.push((run.as_str(), recorded_shard(config, run, &summary)));
+        summaries.push(summary);
+    }
+    if !allow_partial {
+        check_shard_set(&shards)?;
+    }
+
+    let merged = merge_summaries(&summaries);
+    let content = match format {
+        Reporter::Json => serde_json::to_string_pretty(&merged)?,
+        Reporter::Pretty => merged.pretty(),
+        _ => crate::render_reporter(&merged, format).unwrap_or_default(),
+    };
+    if let Some(out) = out {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, &content)?;
+    }
+    match format {
+        Reporter::Json => Ok(report_doc(&merged)),
+        _ => Ok(serde_json::to_value(ReportEnvelope { format, content })?),
+    }
+}
+
+/// An explicit `report.json` is read directly, so shard artifacts downloaded into
+/// another directory still merge; anything else is resolved like `report show`.
+fn load_shard_summary(config: &Config, run: &str) -> FozzyResult<RunSummary> {
+    let Some(report) = explicit_report(run) else {
+        return load_summary(config, run);
+    };
+    let bytes = std::fs::read(report)
+        .map_err(|err| FozzyError::Report(format!("failed to read {}: {err}", report.display())))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|err| FozzyError::Report(format!("invalid report {}: {err}", report.display())))
+}
+
+fn explicit_report(run: &str) -> Option<&std::path::Path> {
+    let report = std::path::Path::new(run);
+    (report.file_name().and_then(|name| name.to_str()) == Some("report.json") && report.is_file())
+        .then_some(report)
+}
+
+/// The `--shard` a test run was started with, from the `timings.json` written next to
+/// its report. `None` when the run was not sharded or its timings are missing or
+/// belong to another run.
+fn recorded_shard(config: &Config, run: &str, summary: &RunSummary) -> Option<crate::TestShard> {
+    let artifacts_dir = match explicit_report(run) {
+        Some(report) => report.parent()?.to_path_buf(),
+        None => crate::resolve_artifacts_dir(config, run).ok()?,
+    };
+    let bytes = std::fs::read(artifacts_dir.join(crate::TEST_TIMINGS_FILE)).ok()?;
+    let timings: crate::TestTimingsReport = serde_json::from_slice(&bytes).ok()?;
+    if timings.run_id != summary.identity.run_id {
+        return None;
+    }
+    timings.shard
+}
+
+/// Rejects inputs that are not exactly the shards `1/n..n/n` of one split: a run
+/// without shard metadata, mixed `n`, the same `i` twice, or a missing `i`.
+pub(super) fn check_shard_set(shards: &[(&str, Option<crate::TestShard>)]) -> FozzyResult<()> {
+    const HINT: &str = "pass --allow-partial to merge anyway";
+    let mut count = None;
+    let mut owners = std::collections::BTreeMap::<usize, &str>::new();
+    for (run, shard) in shards {
+        let Some(shard) = shard else {
+            return Err(FozzyError::Report(format!(
+                "{run:?} has no recorded --shard (timings.json); {HINT}"
+            )));
+        };
+        match count {
+            None => count = Some((shard.count, *run)),
+            Some((n, first)) if n != shard.count => {
+                return Err(FozzyError::Report(format!(
+                    "mixed shard counts: {first:?} is one of {n} shards but {run:?} is {shard}; {HINT}"
+                )));
+            }
+            Some(_) => {}
+        }
+        if let Some(other) = owners.insert(shard.index, run) {
+            return Err(FozzyError::Report(format!(
+                "shard {shard} appears twice ({other:?} and {run:?}); {HINT}"
+            )));
+        }
+    }
+    let Some((n, _)) = count else {
+        return Ok(());
+    };
+    let missing: Vec<String> = (1..=n)
+        .filter(|index| !owners.contains_key(index))
+        .map(|index| format!("{index}/{n}"))
+        .collect();
+    if !missing.is_empty() {
+        return Err(FozzyError::Report(format!(
+            "incomplete shard set: missing {}; {HINT}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn merge_summaries(summaries: &[RunSummary]) -> RunSummary {
+    let mut tests = crate::TestCounts {
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        flaky: 0,
+        quarantined: 0,
+    };
+    let mut memory: Option<crate::MemorySummary> = None;
+    let mut findings = Vec::new();
+    let mut retries = Vec::new();
+    let mut duration_ms = 0u64;
+    let mut duration_ns = 0u64;
+    for summary in summaries {
+        if let Some(counts) = &summary.tests {
+            tests.passed += counts.passed;
+            tests.failed += counts.failed;
+            tests.skipped += counts.skipped;
+            tests.flaky += counts.flaky;
+            tests.quarantined += counts.quarantined;
+        }
+        retries.extend(summary.retries.iter().cloned());
+        if let Some(shard) = &summary.memory {
+            let total = memory.get_or_insert_with(crate::MemorySummary::default);
+            total.alloc_count = total.alloc_count.saturating_add(shard.alloc_count);
+            total.free_count = total.free_count.saturating_add(shard.free_count);
+            total.failed_alloc_count = total
+                .failed_alloc_count
+                .saturating_add(shard.failed_alloc_count);
+            total.in_use_bytes = total.in_use_bytes.saturating_add(shard.in_use_bytes);
+            total.peak_bytes = total.peak_bytes.max(shard.peak_bytes);
+            total.leaked_bytes = total.leaked_bytes.saturating_add(shard.leaked_bytes);
+            total.leaked_allocs = total.leaked_allocs.saturating_add(shard.leaked_allocs);
+        }
+        findings.extend(summary.findings.iter().cloned());
+        duration_ms = duration_ms.saturating_add(summary.duration_ms);
+        duration_ns = duration_ns.saturating_add(summary.duration_ns);
+    }
+    let status = summaries
+        .iter()
+        .map(|summary| summary.status)
+        .find(|status| *status != crate::ExitStatus::Pass)
+        .unwrap_or(crate::ExitStatus::Pass);
+    RunSummary {
+        status,
+        mode: crate::RunMode::Test,
+        identity: crate::RunIdentity {
+            run_id: uuid::Uuid::new_v4().to_string(),
+            seed: summaries.first().map(|s| s.identity.seed).unwrap_or(0),
+            trace_path: None,
+            report_path: None,
+            artifacts_dir: None,
+        },
+        started_at: summaries
+            .iter()
+            .map(|s| s.started_at.clone())
+            .min()
+            .unwrap_or_default(),
+        finished_at: summaries
+            .iter()
+            .map(|s| s.finished_at.clone())
+            .max()
+            .unwrap_or_default(),
+        duration_ms,
+        duration_ns,
+        tests: Some(tests),
+        memory,
+        findings: crate::collapse_findings(findings),
+        retries,
+    }
+}
@@ -31,6 +31,7 @@ fn write_summary(base: &std::path::Path, run_id: &str, status: ExitStatus) -> St
                 location: None,
             }]
         },
+        retries: Vec::new(),
     };
     std::fs::write(
         dir.join("report.json"),
@@ -146,8 +147,11 @@ fn flaky_budget_enforced() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let out = flaky_command(
@@ -189,14 +193,41 @@ fn flaky_rejects_duplicate_run_references() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = flaky_command(&cfg, &[a.clone(), a, b], None).expect_err("must reject duplicates");
     assert!(err.to_string().contains("duplicate run reference"));
 }
 
+#[test]
+fn merge_requires_one_complete_shard_set() {
+    let shard = |index, count| Some(crate::TestShard { index, count });
+    merge::check_shard_set(&[("a", shard(2, 2)), ("b", shard(1, 2))]).expect("complete set");
+
+    let cases = [
+        (vec![("a", shard(1, 3)), ("b", shard(3, 3))], "missing 2/3"),
+        (
+            vec![("a", shard(1, 2)), ("b", shard(2, 3))],
+            "mixed shard counts",
+        ),
+        (
+            vec![("a", shard(1, 2)), ("b", shard(1, 2))],
+            "appears twice",
+        ),
+        (vec![("a", shard(1, 1)), ("b", None)], "no recorded --shard"),
+    ];
+    for (shards, expected) in cases {
+        let err = merge::check_shard_set(&shards).expect_err(expected);
+        assert!(err.to_string().contains(expected), "{err}");
+        assert!(err.to_string().contains("--allow-partial"), "{err}");
+    }
+}
+
 #[test]
 fn load_summary_uses_manifest_declared_external_trace_when_report_missing() {
     let root = std::env::temp_dir().join(format!("fozzy-report-trace-{}", Uuid::new_v4()));
@@ -220,6 +251,7 @@ fn load_summary_uses_manifest_declared_external_trace_when_report_missing() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let trace = TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -238,7 +270,12 @@ fn load_summary_uses_manifest_declared_external_trace_when_report_missing() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&external_trace).expect("write trace");
     std::fs::write(
@@ -276,8 +313,11 @@ fn load_summary_uses_manifest_declared_external_trace_when_report_missing() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let loaded = load_summary(&cfg, "r1").expect("load summary");
@@ -322,6 +362,7 @@ fn load_summary_prefers_explicit_trace_over_sibling_report() {
             message: "from trace".to_string(),
             location: None,
         }],
+        retries: Vec::new(),
     };
     let report_summary = RunSummary {
         status: ExitStatus::Pass,
@@ -345,6 +386,7 @@ fn load_summary_prefers_explicit_trace_over_sibling_report() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     let trace = TraceFile {
         format: crate::TRACE_FORMAT.to_string(),
@@ -363,7 +405,12 @@ fn load_summary_prefers_explicit_trace_over_sibling_report() {
         decisions: Vec::new(),
         events: Vec::new(),
         summary: trace_summary.clone(),
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     std::fs::write(
@@ -386,8 +433,11 @@ fn load_summary_prefers_explicit_trace_over_sibling_report() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let loaded = load_summary(&cfg, &trace_path.to_string_lossy()).expect("load summary");
@@ -420,6 +470,7 @@ fn load_summary_rejects_stale_report_without_manifest() {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     };
     std::fs::write(
         run_dir.join("report.json"),
@@ -441,8 +492,11 @@ fn load_summary_rejects_stale_report_without_manifest() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = load_summary(&cfg, "r1").expect_err("must reject stale report");
@@ -492,8 +546,14 @@ fn load_summary_rejects_trace_only_run_wrapper_without_report_manifest() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
 
@@ -511,8 +571,11 @@ fn load_summary_rejects_trace_only_run_wrapper_without_report_manifest() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = load_summary(&cfg, "r1").expect_err("must reject trace-only wrapper");
@@ -566,8 +629,14 @@ fn load_summary_rejects_incoherent_manifest_only_run_wrapper() {
             tests: None,
             memory: None,
             findings: Vec::new(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     let manifest = crate::RunManifest {
@@ -614,8 +683,11 @@ fn load_summary_rejects_incoherent_manifest_only_run_wrapper() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
 
     let err = load_summary(&cfg, "r1").expect_err("must reject incoherent manifest-only wrapper");
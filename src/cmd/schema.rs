@@ -635,6 +635,34 @@ pub fn schema_doc() -> SchemaDoc {
                     ]
                 }),
             },
+            FileVariant {
+                name: "property",
+                required_top_level_keys: vec!["version", "name", "generators", "steps"],
+                minimal_example: serde_json::json!({
+                    "version": 1,
+                    "name": "property-example",
+                    "generators": {
+                        "n": { "type": "int", "min": 0, "max": 100 },
+                        "label": { "type": "string", "regex": "[a-z]{1,8}" }
+                    },
+                    "steps": [
+                        { "type": "trace_event", "name": "input-{{label}}" },
+                        { "type": "assert_ne_int", "a": "{{n}}", "b": -1 }
+                    ]
+                }),
+            },
+            FileVariant {
+                name: "grammar",
+                required_top_level_keys: vec!["version", "name", "grammar", "steps"],
+                minimal_example: serde_json::json!({
+                    "version": 1,
+                    "name": "grammar-example",
+                    "grammar": { "file": "query.ebnf", "bind": "query" },
+                    "steps": [
+                        { "type": "trace_event", "name": "query", "fields": { "text": "{{query}}" } }
+                    ]
+                }),
+            },
             FileVariant {
                 name: "distributed",
                 required_top_level_keys: vec!["version", "name", "distributed"],
@@ -0,0 +1,424 @@
+//! Trace comparison (`fozzy trace diff`): aligns the decision and event streams of
+//! two traces and reports the first divergent decision.
+
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+
+use crate::{Decision, FozzyResult, TraceEvent, TraceFile};
+
+/// Above this many cells the middle of a stream is compared positionally instead
+/// of by longest-common-subsequence alignment.
+const MAX_ALIGN_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiffReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub left: String,
+    pub right: String,
+    pub identical: bool,
+    #[serde(rename = "leftStatus")]
+    pub left_status: crate::ExitStatus,
+    #[serde(rename = "rightStatus")]
+    pub right_status: crate::ExitStatus,
+    #[serde(rename = "firstDivergence", skip_serializing_if = "Option::is_none")]
+    pub first_divergence: Option<DecisionDivergence>,
+    #[serde(
+        rename = "firstEventDivergence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub first_event_divergence: Option<EventDivergence>,
+    pub decisions: StreamDiffSummary,
+    pub events: StreamDiffSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionDivergence {
+    /// Position in the left decision stream (right stream for pure insertions).
+    pub index: usize,
+    /// Scenario step active at the divergence, from the preceding `step` decision.
+    #[serde(rename = "stepIndex", skip_serializing_if = "Option::is_none")]
+    pub step_index: Option<usize>,
+    #[serde(rename = "stepName", skip_serializing_if = "Option::is_none")]
+    pub step_name: Option<String>,
+    #[serde(rename = "leftKind", skip_serializing_if = "Option::is_none")]
+    pub left_kind: Option<String>,
+    #[serde(rename = "rightKind", skip_serializing_if = "Option::is_none")]
+    pub right_kind: Option<String>,
+    #[serde(rename = "leftObserved", skip_serializing_if = "Option::is_none")]
+    pub left_observed: Option<String>,
+    #[serde(rename = "rightObserved", skip_serializing_if = "Option::is_none")]
+    pub right_observed: Option<String>,
+    #[serde(
+        rename = "differingFields",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub differing_fields: Vec<String>,
+    #[serde(rename = "leftDecision", skip_serializing_if = "Option::is_none")]
+    pub left_decision: Option<serde_json::Value>,
+    #[serde(rename = "rightDecision", skip_serializing_if = "Option::is_none")]
+    pub right_decision: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDivergence {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<TraceEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<TraceEvent>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamDiffSummary {
+    #[serde(rename = "leftCount")]
+    pub left_count: usize,
+    #[serde(rename = "rightCount")]
+    pub right_count: usize,
+    /// Entries before the first divergence.
+    #[serde(rename = "commonPrefix")]
+    pub common_prefix: usize,
+    /// Aligned entries that match on both sides (including the common prefix).
+    pub matched: usize,
+    /// Aligned positions whose entries differ.
+    pub changed: usize,
+    #[serde(rename = "leftOnly")]
+    pub left_only: usize,
+    #[serde(rename = "rightOnly")]
+    pub right_only: usize,
+}
+
+pub fn diff_traces(left: &Path, right: &Path) -> FozzyResult<TraceDiffReport> {
+    let left_path = crate::normalize_trace_path(left);
+    let right_path = crate::normalize_trace_path(right);
+    let l = TraceFile::read_json(&left_path)?;
+    let r = TraceFile::read_json(&right_path)?;
+    Ok(diff_trace_files(
+        &l,
+        &r,
+        left_path.display().to_string(),
+        right_path.display().to_string(),
+    ))
+}
+
+pub fn diff_trace_files(
+    left: &TraceFile,
+    right: &TraceFile,
+    left_label: String,
+    right_label: String,
+) -> TraceDiffReport {
+    let decision_ops = align(&left.decisions, &right.decisions, |a, b| a == b);
+    let event_ops = align(&left.events, &right.events, event_eq);
+    let decisions = summarize(&decision_ops, left.decisions.len(), right.decisions.len());
+    let events = summarize(&event_ops, left.events.len(), right.events.len());
+
+    let first_divergence = first_change(&decision_ops)
+        .map(|op| decision_divergence(&left.decisions, &right.decisions, op));
+    let first_event_divergence = first_change(&event_ops).map(|op| {
+        let (l, r) = op_sides(op);
+        EventDivergence {
+            index: l.or(r).unwrap_or_default(),
+            left: l.and_then(|i| left.events.get(i).cloned()),
+            right: r.and_then(|i| right.events.get(i).cloned()),
+        }
+    });
+
+    TraceDiffReport {
+        schema_version: "fozzy.trace_diff.v1".to_string(),
+        left: left_label,
+        right: right_label,
+        identical: first_divergence.is_none()
+            && first_event_divergence.is_none()
+            && left.summary.status == right.summary.status,
+        left_status: left.summary.status,
+        right_status: right.summary.status,
+        first_divergence,
+        first_event_divergence,
+        decisions,
+        events,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlignOp {
+    Same(usize, usize),
+    Changed(usize, usize),
+    LeftOnly(usize),
+    RightOnly(usize),
+}
+
+/// Aligns two streams: shared prefix and suffix are matched directly, and the middle
+/// is aligned by LCS (or positionally when it is too large), with adjacent
+/// deletions and insertions folded into changes.
+fn align<T>(left: &[T], right: &[T], eq: impl Fn(&T, &T) -> bool) -> Vec<AlignOp> {
+    let prefix = left.iter().zip(right).take_while(|(a, b)| eq(a, b)).count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(a, b)| eq(a, b))
+        .count();
+    let l_mid = prefix..left.len() - suffix;
+    let r_mid = prefix..right.len() - suffix;
+
+    let mut ops: Vec<AlignOp> = (0..prefix).map(|i| AlignOp::Same(i, i)).collect();
+    let n = l_mid.len();
+    let m = r_mid.len();
+    if n.saturating_mul(m) <= MAX_ALIGN_CELLS {
+        // lcs[i][j] = LCS length of left[l_mid.start + i..] and right[r_mid.start + j..].
+        let width = m + 1;
+        let mut lcs = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * width + j] = if eq(&left[l_mid.start + i], &right[r_mid.start + j]) {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        let mut raw = Vec::new();
+        while i < n || j < m {
+            if i < n && j < m && eq(&left[l_mid.start + i], &right[r_mid.start + j]) {
+                raw.push(AlignOp::Same(l_mid.start + i, r_mid.start + j));
+                i += 1;
+                j += 1;
+            } else if j == m || (i < n && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                raw.push(AlignOp::LeftOnly(l_mid.start + i));
+                i += 1;
+            } else {
+                raw.push(AlignOp::RightOnly(r_mid.start + j));
+                j += 1;
+            }
+        }
+        ops.extend(fold_changes(raw));
+    } else {
+        for k in 0..n.max(m) {
+            ops.push(match (k < n, k < m) {
+                (true, true) => {
+                    let (a, b) = (l_mid.start + k, r_mid.start + k);
+                    if eq(&left[a], &right[b]) {
+                        AlignOp::Same(a, b)
+                    } else {
+                        AlignOp::Changed(a, b)
+                    }
+                }
+                (true, false) => AlignOp::LeftOnly(l_mid.start + k),
+                _ => AlignOp::RightOnly(r_mid.start + k),
+            });
+        }
+    }
+    ops.extend((0..suffix).map(|k| AlignOp::Same(l_mid.end + k, r_mid.end + k)));
+    ops
+}
+
+/// Pairs runs of left-only entries with the right-only entries that follow them.
+fn fold_changes(raw: Vec<AlignOp>) -> Vec<AlignOp> {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut k = 0;
+    while k < raw.len() {
+        let lefts: Vec<usize> = raw[k..]
+            .iter()
+            .map_while(|op| match op {
+                AlignOp::LeftOnly(i) => Some(*i),
+                _ => None,
+            })
+            .collect();
+        let rights: Vec<usize> = raw[k + lefts.len()..]
+            .iter()
+            .map_while(|op| match op {
+                AlignOp::RightOnly(j) => Some(*j),
+                _ => None,
+            })
+            .collect();
+        if lefts.is_empty() && rights.is_empty() {
+            out.push(raw[k]);
+            k += 1;
+            continue;
+        }
+        let paired = lefts.len().min(rights.len());
+        out.extend(
+            lefts
+                .iter()
+                .zip(&rights)
+                .map(|(i, j)| AlignOp::Changed(*i, *j)),
+        );
+        out.extend(lefts[paired..].iter().map(|i| AlignOp::LeftOnly(*i)));
+        out.extend(rights[paired..].iter().map(|j| AlignOp::RightOnly(*j)));
+        k += lefts.len() + rights.len();
+    }
+    out
+}
+
+fn summarize(ops: &[AlignOp], left_count: usize, right_count: usize) -> StreamDiffSummary {
+    let mut out = StreamDiffSummary {
+        left_count,
+        right_count,
+        common_prefix: ops
+            .iter()
+            .take_while(|op| matches!(op, AlignOp::Same(..)))
+            .count(),
+        ..StreamDiffSummary::default()
+    };
+    for op in ops {
+        match op {
+            AlignOp::Same(..) => out.matched += 1,
+            AlignOp::Changed(..) => out.changed += 1,
+            AlignOp::LeftOnly(_) => out.left_only += 1,
+            AlignOp::RightOnly(_) => out.right_only += 1,
+        }
+    }
+    out
+}
+
+fn first_change(ops: &[AlignOp]) -> Option<AlignOp> {
+    ops.iter()
+        .copied()
+        .find(|op| !matches!(op, AlignOp::Same(..)))
+}
+
+fn op_sides(op: AlignOp) -> (Option<usize>, Option<usize>) {
+    match op {
+        AlignOp::Same(i, j) | AlignOp::Changed(i, j) => (Some(i), Some(j)),
+        AlignOp::LeftOnly(i) => (Some(i), None),
+        AlignOp::RightOnly(j) => (None, Some(j)),
+    }
+}
+
+fn event_eq(a: &TraceEvent, b: &TraceEvent) -> bool {
+    a.name == b.name && a.fields == b.fields
+}
+
+fn decision_divergence(left: &[Decision], right: &[Decision], op: AlignOp) -> DecisionDivergence {
+    let (l, r) = op_sides(op);
+    let l_decision = l.and_then(|i| left.get(i));
+    let r_decision = r.and_then(|j| right.get(j));
+    let (step_index, step_name) = match (l, r) {
+        (Some(i), _) => active_step(&left[..i]),
+        (None, Some(j)) => active_step(&right[..j]),
+        (None, None) => (None, None),
+    };
+    let l_value = l_decision.and_then(|d| serde_json::to_value(d).ok());
+    let r_value = r_decision.and_then(|d| serde_json::to_value(d).ok());
+    let differing_fields = match (&l_value, &r_value) {
+        (Some(serde_json::Value::Object(a)), Some(serde_json::Value::Object(b))) => {
+            let mut keys: Vec<String> = a
+                .keys()
+                .chain(b.keys())
+                .filter(|k| a.get(*k) != b.get(*k))
+                .cloned()
+                .collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        }
+        _ => Vec::new(),
+    };
+    DecisionDivergence {
+        index: l.or(r).unwrap_or_default(),
+        step_index,
+        step_name,
+        left_kind: kind_of(&l_value),
+        right_kind: kind_of(&r_value),
+        left_observed: l_decision.map(observed),
+        right_observed: r_decision.map(observed),
+        differing_fields,
+        left_decision: l_value,
+        right_decision: r_value,
+    }
+}
+
+fn active_step(before: &[Decision]) -> (Option<usize>, Option<String>) {
+    before
+        .iter()
+        .rev()
+        .find_map(|d| match d {
+            Decision::Step { index, name } => Some((Some(*index), Some(name.clone()))),
+            _ => None,
+        })
+        .unwrap_or((None, None))
+}
+
+fn kind_of(value: &Option<serde_json::Value>) -> Option<String> {
+    value
+        .as_ref()
+        .and_then(|v| v.get("kind"))
+        .and_then(|k| k.as_str())
+        .map(str::to_string)
+}
+
+/// One-line description of what a decision observed.
+fn observed(decision: &Decision) -> String {
+    match decision {
+        Decision::RandU64 { value } => format!("rand_u64={value}"),
+        Decision::RandBytes { hex } => format!("rand_bytes={hex}"),
+        Decision::TimeSleepMs { ms } => format!("sleep {ms}ms"),
+        Decision::TimeAdvanceMs { ms } => format!("advance {ms}ms"),
+        Decision::FsWrite { path, data_hex, .. } => format!("write {path} ({data_hex})"),
+        Decision::FsReadAssert { path, data_hex, .. } => format!("read {path} ({data_hex})"),
+        Decision::FsSnapshot { name, entries, .. } => {
+            format!("snapshot {name} ({} entries)", entries.len())
+        }
+        Decision::FsRestore { name, .. } => format!("restore {name}"),
+        Decision::HttpRequest {
+            method,
+            path,
+            status_code,
+            body,
+            ..
+        } => format!("{method} {path} -> {status_code} body={}", clip(body)),
+        Decision::HttpRequestTimeout { method, path, .. } => {
+            format!("{method} {path} -> timeout")
+        }
+        Decision::ProcSpawn {
+            cmd,
+            exit_code,
+            stdout,
+            stderr,
+            ..
+        } => format!(
+            "{cmd} exit={exit_code} stdout={} stderr={}",
+            clip(stdout),
+            clip(stderr)
+        ),
+        Decision::ProcSpawnTimeout { cmd, stdout, .. } => {
+            format!("{cmd} timeout stdout={}", clip(stdout))
+        }
+        Decision::SchedulerPick { task_id, label } => format!("picked task {task_id} ({label})"),
+        Decision::NetDeliverPick { message_id } => format!("delivered message {message_id}"),
+        Decision::NetDrop {
+            message_id,
+            dropped,
+        } => format!("message {message_id} dropped={dropped}"),
+        Decision::MemoryAlloc {
+            bytes,
+            alloc_id,
+            failed_reason,
+            ..
+        } => match failed_reason {
+            Some(reason) => format!("alloc {bytes}B failed ({reason})"),
+            None => format!("alloc {bytes}B id={}", alloc_id.unwrap_or_default()),
+        },
+        Decision::MemoryFree { alloc_id, existed } => {
+            format!("free id={alloc_id} existed={existed}")
+        }
+        Decision::Step { index, name } => format!("step {index} ({name})"),
+        Decision::ExploreDeliver { msg_id } => format!("delivered message {msg_id}"),
+    }
+}
+
+fn clip(text: &str) -> String {
+    const MAX: usize = 120;
+    let mut out: String = text.chars().take(MAX).collect();
+    if text.chars().count() > MAX {
+        out.push('…');
+    }
+    format!("{out:?}")
+}
+
+#[cfg(test)]
+#[path = "trace_diff/tests.rs"]
+mod tests;
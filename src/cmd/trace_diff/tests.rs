@@ -0,0 +1,112 @@
+use super::*;
+use crate::{ExitStatus, RunIdentity, RunMode, RunSummary};
+
+fn trace(decisions: Vec<Decision>, events: Vec<TraceEvent>, status: ExitStatus) -> TraceFile {
+    TraceFile::new(
+        RunMode::Run,
+        None,
+        None,
+        decisions,
+        events,
+        RunSummary {
+            status,
+            identity: RunIdentity {
+                run_id: "r".to_string(),
+                seed: 1,
+                trace_path: None,
+                report_path: None,
+                artifacts_dir: None,
+            },
+            ..RunSummary::fixture()
+        },
+    )
+}
+
+fn step(index: usize) -> Decision {
+    Decision::Step {
+        index,
+        name: format!("s{index}"),
+    }
+}
+
+fn proc(stdout: &str) -> Decision {
+    Decision::ProcSpawn {
+        cmd: "echo".to_string(),
+        args: Vec::new(),
+        exit_code: 0,
+        stdout: stdout.to_string(),
+        stderr: String::new(),
+        peak_rss_bytes: 0,
+        rss_sample_count: 0,
+        duration_ms: 0,
+    }
+}
+
+fn event(name: &str) -> TraceEvent {
+    TraceEvent {
+        time_ms: 0,
+        name: name.to_string(),
+        fields: serde_json::Map::new(),
+    }
+}
+
+#[test]
+fn identical_traces_have_no_divergence() {
+    let t = trace(vec![step(0), proc("a")], vec![event("x")], ExitStatus::Pass);
+    let report = diff_trace_files(&t, &t, "a".into(), "b".into());
+    assert!(report.identical);
+    assert!(report.first_divergence.is_none());
+    assert_eq!(report.decisions.common_prefix, 2);
+}
+
+#[test]
+fn first_divergent_decision_reports_step_and_observations() {
+    let left = trace(
+        vec![step(0), proc("a"), step(1), proc("ok"), step(2)],
+        vec![event("x"), event("y")],
+        ExitStatus::Pass,
+    );
+    let right = trace(
+        vec![step(0), proc("a"), step(1), proc("boom"), step(2)],
+        vec![event("x"), event("z")],
+        ExitStatus::Fail,
+    );
+    let report = diff_trace_files(&left, &right, "a".into(), "b".into());
+    assert!(!report.identical);
+    let first = report.first_divergence.expect("divergence");
+    assert_eq!(first.index, 3);
+    assert_eq!(first.step_index, Some(1));
+    assert_eq!(first.left_kind.as_deref(), Some("proc_spawn"));
+    assert_eq!(first.differing_fields, vec!["stdout".to_string()]);
+    assert!(first.right_observed.expect("observed").contains("boom"));
+    assert_eq!(report.decisions.changed, 1);
+    assert_eq!(report.decisions.matched, 4);
+    assert_eq!(report.first_event_divergence.expect("event").index, 1);
+}
+
+#[test]
+fn inserted_decisions_are_aligned_instead_of_cascading() {
+    let left = trace(
+        vec![step(0), step(1), step(2), step(3)],
+        Vec::new(),
+        ExitStatus::Pass,
+    );
+    let right = trace(
+        vec![
+            step(0),
+            Decision::NetDeliverPick { message_id: 7 },
+            step(1),
+            step(2),
+            step(3),
+        ],
+        Vec::new(),
+        ExitStatus::Pass,
+    );
+    let report = diff_trace_files(&left, &right, "a".into(), "b".into());
+    let first = report.first_divergence.expect("divergence");
+    assert!(first.left_kind.is_none());
+    assert_eq!(first.right_kind.as_deref(), Some("net_deliver_pick"));
+    assert_eq!(report.decisions.right_only, 1);
+    assert_eq!(report.decisions.matched, 4);
+    assert_eq!(report.decisions.changed, 0);
+}
@@ -0,0 +1,630 @@
+//! Trace export (`fozzy trace export`): renders a trace as Chrome trace events or a
+//! Perfetto protobuf trace on virtual time.
+//!
+//! Steps, capability calls and explore deliveries become duration slices; network
+//! messages become flows between per-node tracks. Virtual time has millisecond
+//! resolution and many events share a tick, so events within the same millisecond
+//! are spread one microsecond apart in trace order to keep them visible and ordered.
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Config, FozzyError, FozzyResult, TraceEvent, TraceFile};
+
+const PROCESS_PID: u64 = 1;
+const ROOT_TRACK_UUID: u64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceExportFormat {
+    /// Chrome trace event JSON (chrome://tracing, Perfetto UI).
+    Chrome,
+    /// Perfetto protobuf trace.
+    Perfetto,
+}
+
+impl clap::ValueEnum for TraceExportFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Chrome, Self::Perfetto]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            Self::Chrome => clap::builder::PossibleValue::new("chrome"),
+            Self::Perfetto => clap::builder::PossibleValue::new("perfetto"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceExportReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub trace: String,
+    pub format: TraceExportFormat,
+    pub out: String,
+    pub tracks: usize,
+    pub slices: usize,
+    pub flows: usize,
+    pub instants: usize,
+}
+
+/// Exports the trace named by `selector` (a trace path, run id or alias) to `out`.
+pub fn export_trace(
+    config: &Config,
+    selector: &str,
+    format: TraceExportFormat,
+    out: &Path,
+) -> FozzyResult<TraceExportReport> {
+    let trace_path = resolve_export_trace(config, selector)?;
+    let trace = TraceFile::read_json(&trace_path)?;
+    let timeline = ExportTimeline::from_trace(&trace);
+    let bytes = match format {
+        TraceExportFormat::Chrome => serde_json::to_vec(&timeline.to_chrome_json(&trace))?,
+        TraceExportFormat::Perfetto => timeline.to_perfetto_proto(&trace),
+    };
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(out, bytes)?;
+    Ok(TraceExportReport {
+        schema_version: "fozzy.trace_export.v1".to_string(),
+        trace: trace_path.display().to_string(),
+        format,
+        out: out.display().to_string(),
+        tracks: timeline.tracks.len(),
+        slices: timeline.slices.len(),
+        flows: timeline.flows.len(),
+        instants: timeline.instants.len(),
+    })
+}
+
+fn resolve_export_trace(config: &Config, selector: &str) -> FozzyResult<PathBuf> {
+    let input = PathBuf::from(crate::normalize_run_or_trace_selector(selector));
+    if input.is_file() && crate::is_trace_path(&input) {
+        return Ok(input);
+    }
+    let artifacts_dir = crate::resolve_artifacts_dir(config, selector)?;
+    crate::resolve_trace_path_from_artifacts_dir(&artifacts_dir)?.ok_or_else(|| {
+        FozzyError::InvalidArgument(format!(
+            "no trace found for {selector:?} (looked in {})",
+            artifacts_dir.display()
+        ))
+    })
+}
+
+/// Format-neutral view of a trace: named tracks carrying slices, instants and flows.
+#[derive(Debug, Default)]
+pub(crate) struct ExportTimeline {
+    pub(crate) tracks: Vec<String>,
+    pub(crate) slices: Vec<ExportSlice>,
+    pub(crate) instants: Vec<ExportInstant>,
+    pub(crate) flows: Vec<ExportFlow>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExportSlice {
+    pub(crate) track: usize,
+    pub(crate) name: String,
+    pub(crate) category: &'static str,
+    pub(crate) start_us: u64,
+    pub(crate) end_us: u64,
+    pub(crate) args: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExportInstant {
+    pub(crate) track: usize,
+    pub(crate) name: String,
+    pub(crate) category: &'static str,
+    pub(crate) ts_us: u64,
+    pub(crate) args: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A causal arrow from one slice to another, e.g. a message send to its delivery.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExportFlow {
+    pub(crate) id: u64,
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+}
+
+struct OpenSpan {
+    task: String,
+    /// Resolved at `span_end` from the task name unless an event re-homes the span.
+    track: Option<usize>,
+    name: String,
+    category: &'static str,
+    start_us: u64,
+    args: serde_json::Map<String, serde_json::Value>,
+    flow_from: Option<usize>,
+}
+
+#[derive(Default)]
+struct TimelineBuilder {
+    timeline: ExportTimeline,
+    track_index: BTreeMap<String, usize>,
+    track_end_us: Vec<u64>,
+    last_slice: BTreeMap<usize, usize>,
+    open_spans: BTreeMap<String, OpenSpan>,
+    pending_details: BTreeMap<&'static str, serde_json::Map<String, serde_json::Value>>,
+    sent_messages: BTreeMap<u64, usize>,
+    clock_ms: u64,
+    clock_seq: u64,
+}
+
+impl ExportTimeline {
+    pub(crate) fn from_trace(trace: &TraceFile) -> Self {
+        let mut builder = TimelineBuilder::default();
+        for event in &trace.events {
+            builder.push(event);
+        }
+        builder.finish()
+    }
+
+    pub(crate) fn to_chrome_json(&self, trace: &TraceFile) -> serde_json::Value {
+        let mut events = Vec::new();
+        events.push(serde_json::json!({
+            "ph": "M", "pid": PROCESS_PID, "tid": 0, "name": "process_name",
+            "args": { "name": process_name(trace) },
+        }));
+        for (idx, name) in self.tracks.iter().enumerate() {
+            let tid = idx + 1;
+            events.push(serde_json::json!({
+                "ph": "M", "pid": PROCESS_PID, "tid": tid, "name": "thread_name",
+                "args": { "name": name },
+            }));
+            events.push(serde_json::json!({
+                "ph": "M", "pid": PROCESS_PID, "tid": tid, "name": "thread_sort_index",
+                "args": { "sort_index": tid },
+            }));
+        }
+        for slice in &self.slices {
+            events.push(serde_json::json!({
+                "ph": "X", "pid": PROCESS_PID, "tid": slice.track + 1,
+                "name": slice.name, "cat": slice.category,
+                "ts": slice.start_us, "dur": slice.end_us - slice.start_us,
+                "args": slice.args,
+            }));
+        }
+        for instant in &self.instants {
+            events.push(serde_json::json!({
+                "ph": "i", "s": "t", "pid": PROCESS_PID, "tid": instant.track + 1,
+                "name": instant.name, "cat": instant.category,
+                "ts": instant.ts_us, "args": instant.args,
+            }));
+        }
+        for flow in &self.flows {
+            let from = &self.slices[flow.from];
+            let to = &self.slices[flow.to];
+            events.push(serde_json::json!({
+                "ph": "s", "pid": PROCESS_PID, "tid": from.track + 1, "id": flow.id,
+                "name": "message", "cat": "flow", "ts": from.start_us,
+            }));
+            events.push(serde_json::json!({
+                "ph": "f", "bp": "e", "pid": PROCESS_PID, "tid": to.track + 1, "id": flow.id,
+                "name": "message", "cat": "flow", "ts": to.start_us,
+            }));
+        }
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+            "otherData": {
+                "producer": "fozzy",
+                "mode": trace.mode,
+                "scenario": trace.scenario_path,
+                "status": trace.summary.status,
+                "clock": "virtual",
+            },
+        })
+    }
+
+    /// Encodes the timeline as a `perfetto.protos.Trace` message.
+    pub(crate) fn to_perfetto_proto(&self, trace: &TraceFile) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut first = true;
+        let mut packet = |out: &mut Vec<u8>, timestamp_ns: Option<u64>, field: u32, body: &[u8]| {
+            let mut msg = Vec::new();
+            if let Some(ts) = timestamp_ns {
+                proto::varint_field(&mut msg, 8, ts);
+            }
+            proto::varint_field(&mut msg, 10, 1);
+            if first {
+                proto::varint_field(&mut msg, 13, 1);
+                first = false;
+            }
+            proto::bytes_field(&mut msg, field, body);
+            proto::bytes_field(out, 1, &msg);
+        };
+
+        let mut root = Vec::new();
+        proto::varint_field(&mut root, 1, ROOT_TRACK_UUID);
+        let mut process = Vec::new();
+        proto::varint_field(&mut process, 1, PROCESS_PID);
+        proto::bytes_field(&mut process, 6, process_name(trace).as_bytes());
+        proto::bytes_field(&mut root, 3, &process);
+        packet(&mut out, None, 60, &root);
+        for (idx, name) in self.tracks.iter().enumerate() {
+            let mut track = Vec::new();
+            proto::varint_field(&mut track, 1, track_uuid(idx));
+            proto::bytes_field(&mut track, 2, name.as_bytes());
+            proto::varint_field(&mut track, 5, ROOT_TRACK_UUID);
+            packet(&mut out, None, 60, &track);
+        }
+
+        let mut flows_out: BTreeMap<usize, Vec<u64>> = BTreeMap::new();
+        let mut flows_in: BTreeMap<usize, Vec<u64>> = BTreeMap::new();
+        for flow in &self.flows {
+            flows_out.entry(flow.from).or_default().push(flow.id);
+            flows_in.entry(flow.to).or_default().push(flow.id);
+        }
+
+        // (ts, order, body): ends sort before instants before begins at the same tick.
+        let mut track_events: Vec<(u64, u8, Vec<u8>)> = Vec::new();
+        for (idx, slice) in self.slices.iter().enumerate() {
+            let mut begin = Vec::new();
+            proto::varint_field(&mut begin, 9, 1);
+            proto::varint_field(&mut begin, 11, track_uuid(slice.track));
+            proto::bytes_field(&mut begin, 22, slice.category.as_bytes());
+            proto::bytes_field(&mut begin, 23, slice.name.as_bytes());
+            encode_annotations(&mut begin, &slice.args);
+            for id in flows_out.get(&idx).into_iter().flatten() {
+                proto::fixed64_field(&mut begin, 47, *id);
+            }
+            for id in flows_in.get(&idx).into_iter().flatten() {
+                proto::fixed64_field(&mut begin, 48, *id);
+            }
+            track_events.push((slice.start_us, 2, begin));
+
+            let mut end = Vec::new();
+            proto::varint_field(&mut end, 9, 2);
+            proto::varint_field(&mut end, 11, track_uuid(slice.track));
+            track_events.push((slice.end_us, 0, end));
+        }
+        for instant in &self.instants {
+            let mut body = Vec::new();
+            proto::varint_field(&mut body, 9, 3);
+            proto::varint_field(&mut body, 11, track_uuid(instant.track));
+            proto::bytes_field(&mut body, 22, instant.category.as_bytes());
+            proto::bytes_field(&mut body, 23, instant.name.as_bytes());
+            encode_annotations(&mut body, &instant.args);
+            track_events.push((instant.ts_us, 1, body));
+        }
+        track_events.sort_by_key(|(ts, order, _)| (*ts, *order));
+        for (ts_us, _, body) in track_events {
+            packet(&mut out, Some(ts_us.saturating_mul(1000)), 11, &body);
+        }
+        out
+    }
+}
+
+impl TimelineBuilder {
+    fn track(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.track_index.get(name) {
+            return *idx;
+        }
+        let idx = self.timeline.tracks.len();
+        self.timeline.tracks.push(name.to_string());
+        self.track_index.insert(name.to_string(), idx);
+        self.track_end_us.push(0);
+        idx
+    }
+
+    fn node_track(&mut self, node: &str) -> usize {
+        self.track(&format!("node {node}"))
+    }
+
+    /// Microsecond timestamp for the next event at virtual time `time_ms`.
+    fn tick(&mut self, time_ms: u64) -> u64 {
+        let time_ms = time_ms.max(self.clock_ms);
+        if time_ms == self.clock_ms {
+            self.clock_seq = (self.clock_seq + 1).min(999);
+        } else {
+            self.clock_ms = time_ms;
+            self.clock_seq = 0;
+        }
+        self.clock_ms.saturating_mul(1000) + self.clock_seq
+    }
+
+    /// Adds a slice, clamped so slices on one track never overlap.
+    fn slice(
+        &mut self,
+        track: usize,
+        name: String,
+        category: &'static str,
+        start_us: u64,
+        end_us: u64,
+        args: serde_json::Map<String, serde_json::Value>,
+    ) -> usize {
+        let start_us = start_us.max(self.track_end_us[track]);
+        let end_us = end_us.max(start_us + 1);
+        self.track_end_us[track] = end_us;
+        let idx = self.timeline.slices.len();
+        self.timeline.slices.push(ExportSlice {
+            track,
+            name,
+            category,
+            start_us,
+            end_us,
+            args,
+        });
+        self.last_slice.insert(track, idx);
+        idx
+    }
+
+    fn instant(&mut self, track: usize, event: &TraceEvent, category: &'static str, ts_us: u64) {
+        self.timeline.instants.push(ExportInstant {
+            track,
+            name: event.name.clone(),
+            category,
+            ts_us,
+            args: event.fields.clone(),
+        });
+    }
+
+    fn flow(&mut self, from: usize, to: usize) {
+        let id = self.timeline.flows.len() as u64 + 1;
+        self.timeline.flows.push(ExportFlow { id, from, to });
+    }
+
+    fn push(&mut self, event: &TraceEvent) {
+        let ts = self.tick(event.time_ms);
+        let fields = &event.fields;
+        match event.name.as_str() {
+            // Scheduler picks are implied by the span that follows.
+            "sched_pick" => {}
+            "span_start" => {
+                let Some(span) = str_field(fields, "span") else {
+                    let track = self.track("events");
+                    return self.instant(track, event, "event", ts);
+                };
+                let task = str_field(fields, "task").unwrap_or("span");
+                let (track, name, category) = match task {
+                    "step" => (
+                        Some(self.track("steps")),
+                        str_field(fields, "step_kind").unwrap_or(span).to_string(),
+                        "step",
+                    ),
+                    _ => (None, span.to_string(), "span"),
+                };
+                let mut args = fields.clone();
+                args.remove("span");
+                args.remove("task");
+                args.remove("step_kind");
+                self.open_spans.insert(
+                    span.to_string(),
+                    OpenSpan {
+                        task: task.to_string(),
+                        track,
+                        name,
+                        category,
+                        start_us: ts,
+                        args,
+                        flow_from: None,
+                    },
+                );
+            }
+            "deliver" => {
+                // Explore delivery: re-home the open span onto the receiving node.
+                let span = format!("deliver-{}", u64_field(fields, "id"));
+                let from = str_field(fields, "from").map(|node| self.node_track(node));
+                let to = str_field(fields, "to").map(|node| self.node_track(node));
+                let flow_from = from.and_then(|track| self.last_slice.get(&track).copied());
+                if let Some(open) = self.open_spans.get_mut(&span) {
+                    if to.is_some() {
+                        open.track = to;
+                    }
+                    open.name = str_field(fields, "kind").unwrap_or("deliver").to_string();
+                    open.category = "deliver";
+                    open.args.extend(fields.clone());
+                    open.flow_from = flow_from;
+                } else if let Some(to) = to {
+                    let slice =
+                        self.slice(to, "deliver".to_string(), "deliver", ts, ts, fields.clone());
+                    if let Some(from) = flow_from {
+                        self.flow(from, slice);
+                    }
+                }
+            }
+            "span_end" => {
+                let Some(open) =
+                    str_field(fields, "span").and_then(|span| self.open_spans.remove(span))
+                else {
+                    return;
+                };
+                let duration_us = u64_field(fields, "duration_ms").saturating_mul(1000);
+                let end_us = ts.max(open.start_us.saturating_add(duration_us));
+                let mut args = open.args;
+                if let Some(status) = fields.get("status") {
+                    args.insert("status".to_string(), status.clone());
+                }
+                let track = match open.track {
+                    Some(track) => track,
+                    None => self.track(&open.task),
+                };
+                let slice =
+                    self.slice(track, open.name, open.category, open.start_us, end_us, args);
+                if let Some(from) = open.flow_from {
+                    self.flow(from, slice);
+                }
+            }
+            "proc_spawn" => {
+                self.pending_details.insert("proc", fields.clone());
+            }
+            "http_request" => {
+                self.pending_details.insert("http", fields.clone());
+            }
+            // Network traffic is drawn as node tracks and flows instead.
+            "capability_net" => {}
+            name if name.starts_with("capability_") => {
+                let kind = &name["capability_".len()..];
+                let track = self.track(kind);
+                let mut args = fields.clone();
+                if let Some(detail) = self.pending_details.remove(kind) {
+                    for (key, value) in detail {
+                        args.entry(key).or_insert(value);
+                    }
+                }
+                let op = str_field(fields, "op").unwrap_or(kind);
+                let name = match str_field(&args, "cmd").or_else(|| str_field(&args, "path")) {
+                    Some(target) => format!("{op} {target}"),
+                    None => op.to_string(),
+                };
+                let duration_us = u64_field(fields, "duration_ms").saturating_mul(1000);
+                let start_us = ts.saturating_sub(duration_us);
+                self.slice(track, name, "capability", start_us, ts, args);
+            }
+            "net_send" => {
+                let Some(from) = str_field(fields, "from") else {
+                    return;
+                };
+                let track = self.node_track(from);
+                let slice = self.slice(track, "send".to_string(), "net", ts, ts, fields.clone());
+                if let Some(id) = fields.get("id").and_then(|v| v.as_u64()) {
+                    self.sent_messages.insert(id, slice);
+                }
+            }
+            "net_deliver" => {
+                let Some(to) = str_field(fields, "to") else {
+                    return;
+                };
+                let track = self.node_track(to);
+                let slice = self.slice(track, "recv".to_string(), "net", ts, ts, fields.clone());
+                if let Some(from) = fields
+                    .get("id")
+                    .and_then(|v| v.as_u64())
+                    .and_then(|id| self.sent_messages.remove(&id))
+                {
+                    self.flow(from, slice);
+                }
+            }
+            "net_drop" => {
+                let track = match str_field(fields, "from") {
+                    Some(from) => self.node_track(from),
+                    None => self.track("network"),
+                };
+                self.instant(track, event, "net", ts);
+            }
+            "client_put" => {
+                if let Some(node) = str_field(fields, "node") {
+                    let track = self.node_track(node);
+                    self.slice(track, event.name.clone(), "client", ts, ts, fields.clone());
+                }
+            }
+            "partition" | "heal" => {
+                let track = self.track("network");
+                self.instant(track, event, "fault", ts);
+            }
+            "crash" | "restart" => {
+                let track = match str_field(fields, "node") {
+                    Some(node) => self.node_track(node),
+                    None => self.track("network"),
+                };
+                self.instant(track, event, "fault", ts);
+            }
+            _ => {
+                let track = self.track("events");
+                self.instant(track, event, "event", ts);
+            }
+        }
+    }
+
+    fn finish(mut self) -> ExportTimeline {
+        // Spans still open when the trace ended (a failing step) run to the last tick.
+        let end_us = self.clock_ms.saturating_mul(1000) + self.clock_seq + 1;
+        for (_, open) in std::mem::take(&mut self.open_spans) {
+            let mut args = open.args;
+            args.insert("status".to_string(), serde_json::json!("unfinished"));
+            let track = match open.track {
+                Some(track) => track,
+                None => self.track(&open.task),
+            };
+            self.slice(track, open.name, open.category, open.start_us, end_us, args);
+        }
+        self.timeline
+    }
+}
+
+fn process_name(trace: &TraceFile) -> String {
+    match &trace.scenario_path {
+        Some(path) => format!("fozzy {} {path}", mode_name(trace)),
+        None => format!("fozzy {}", mode_name(trace)),
+    }
+}
+
+fn mode_name(trace: &TraceFile) -> String {
+    serde_json::to_value(trace.mode)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| "run".to_string())
+}
+
+fn track_uuid(track: usize) -> u64 {
+    ROOT_TRACK_UUID + 1 + track as u64
+}
+
+fn str_field<'a>(
+    fields: &'a serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Option<&'a str> {
+    fields.get(key).and_then(|v| v.as_str())
+}
+
+fn u64_field(fields: &serde_json::Map<String, serde_json::Value>, key: &str) -> u64 {
+    fields.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+fn encode_annotations(out: &mut Vec<u8>, args: &serde_json::Map<String, serde_json::Value>) {
+    for (name, value) in args {
+        let mut annotation = Vec::new();
+        proto::bytes_field(&mut annotation, 10, name.as_bytes());
+        match value {
+            serde_json::Value::Bool(b) => proto::varint_field(&mut annotation, 2, u64::from(*b)),
+            serde_json::Value::Number(n) => {
+                if let Some(u) = n.as_u64() {
+                    proto::varint_field(&mut annotation, 3, u);
+                } else if let Some(i) = n.as_i64() {
+                    proto::varint_field(&mut annotation, 4, i as u64);
+                } else {
+                    proto::fixed64_field(&mut annotation, 5, n.as_f64().unwrap_or(0.0).to_bits());
+                }
+            }
+            serde_json::Value::String(s) => proto::bytes_field(&mut annotation, 6, s.as_bytes()),
+            other => proto::bytes_field(&mut annotation, 6, other.to_string().as_bytes()),
+        }
+        proto::bytes_field(out, 4, &annotation);
+    }
+}
+
+/// Minimal protobuf wire encoding for the handful of Perfetto messages we emit.
+pub(crate) mod proto {
+    pub(crate) fn varint(out: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    pub(crate) fn varint_field(out: &mut Vec<u8>, field: u32, value: u64) {
+        varint(out, u64::from(field) << 3);
+        varint(out, value);
+    }
+
+    pub(crate) fn fixed64_field(out: &mut Vec<u8>, field: u32, value: u64) {
+        varint(out, (u64::from(field) << 3) | 1);
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn bytes_field(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+        varint(out, (u64::from(field) << 3) | 2);
+        varint(out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+    }
+}
+
+#[cfg(test)]
+#[path = "trace_export/tests.rs"]
+mod tests;
@@ -0,0 +1,314 @@
+use super::*;
+use crate::{RunIdentity, RunMode, RunSummary};
+
+fn trace(mode: RunMode, events: Vec<TraceEvent>) -> TraceFile {
+    TraceFile::new(
+        mode,
+        None,
+        None,
+        Vec::new(),
+        events,
+        RunSummary {
+            mode,
+            identity: RunIdentity {
+                run_id: "r".to_string(),
+                seed: 1,
+                trace_path: None,
+                report_path: None,
+                artifacts_dir: None,
+            },
+            ..RunSummary::fixture()
+        },
+    )
+}
+
+fn event(time_ms: u64, name: &str, fields: serde_json::Value) -> TraceEvent {
+    TraceEvent {
+        time_ms,
+        name: name.to_string(),
+        fields: fields.as_object().cloned().unwrap_or_default(),
+    }
+}
+
+fn step_events(index: usize, kind: &str, inner: Vec<TraceEvent>) -> Vec<TraceEvent> {
+    let span = format!("step-{index}");
+    let mut events = vec![event(
+        0,
+        "span_start",
+        serde_json::json!({"span": span, "task": "step", "step_index": index, "step_kind": kind}),
+    )];
+    events.extend(inner);
+    events.push(event(
+        0,
+        "span_end",
+        serde_json::json!({"span": span, "status": "ok", "duration_ms": 0}),
+    ));
+    events
+}
+
+fn scenario_trace() -> TraceFile {
+    let mut events = step_events(
+        0,
+        "proc_spawn",
+        vec![
+            event(
+                0,
+                "proc_spawn",
+                serde_json::json!({"cmd": "git", "exit_code": 0}),
+            ),
+            event(
+                0,
+                "capability_proc",
+                serde_json::json!({"op": "spawn", "cmd": "git", "duration_ms": 0}),
+            ),
+        ],
+    );
+    events.extend(step_events(
+        1,
+        "net_send",
+        vec![event(
+            0,
+            "net_send",
+            serde_json::json!({"id": 1, "from": "n1", "to": "n2", "payload_size": 5}),
+        )],
+    ));
+    events.extend(step_events(
+        2,
+        "net_deliver_one",
+        vec![event(
+            3,
+            "net_deliver",
+            serde_json::json!({"id": 1, "from": "n1", "to": "n2", "payload_size": 5}),
+        )],
+    ));
+    trace(RunMode::Run, events)
+}
+
+fn track_named(timeline: &ExportTimeline, name: &str) -> usize {
+    timeline
+        .tracks
+        .iter()
+        .position(|track| track == name)
+        .unwrap_or_else(|| panic!("missing track {name}: {:?}", timeline.tracks))
+}
+
+#[test]
+fn steps_capabilities_and_messages_map_to_slices_and_flows() {
+    let timeline = ExportTimeline::from_trace(&scenario_trace());
+    let steps = track_named(&timeline, "steps");
+    let step_names: Vec<&str> = timeline
+        .slices
+        .iter()
+        .filter(|slice| slice.track == steps)
+        .map(|slice| slice.name.as_str())
+        .collect();
+    assert_eq!(
+        step_names,
+        vec!["proc_spawn", "net_send", "net_deliver_one"]
+    );
+
+    let proc = track_named(&timeline, "proc");
+    let spawn = timeline
+        .slices
+        .iter()
+        .find(|slice| slice.track == proc)
+        .expect("proc slice");
+    assert_eq!(spawn.name, "spawn git");
+    assert_eq!(spawn.args.get("exit_code"), Some(&serde_json::json!(0)));
+
+    // Same-millisecond events stay ordered; the delivery lands on virtual time 3ms.
+    let send = &timeline.slices[timeline.flows[0].from];
+    let recv = &timeline.slices[timeline.flows[0].to];
+    assert_eq!(timeline.flows.len(), 1);
+    assert_eq!(send.track, track_named(&timeline, "node n1"));
+    assert_eq!(recv.track, track_named(&timeline, "node n2"));
+    assert_eq!(recv.start_us, 3000);
+    assert!(send.start_us < recv.start_us);
+    for slice in &timeline.slices {
+        assert!(slice.end_us > slice.start_us, "{slice:?}");
+    }
+}
+
+#[test]
+fn explore_deliveries_land_on_receiving_node_with_flow_from_sender() {
+    let events = vec![
+        event(
+            0,
+            "client_put",
+            serde_json::json!({"node": "n0", "key": "x"}),
+        ),
+        event(0, "partition", serde_json::json!({"a": "n0", "b": "n1"})),
+        event(
+            1,
+            "sched_pick",
+            serde_json::json!({"task_id": 1, "queue_len": 1}),
+        ),
+        event(
+            1,
+            "span_start",
+            serde_json::json!({"span": "deliver-1", "task": "deliver"}),
+        ),
+        event(
+            1,
+            "deliver",
+            serde_json::json!({"id": 1, "from": "n0", "to": "n1", "kind": "kv_repl", "key": "x"}),
+        ),
+        event(
+            1,
+            "span_end",
+            serde_json::json!({"span": "deliver-1", "status": "ok", "duration_ms": 1}),
+        ),
+        event(2, "crash", serde_json::json!({"node": "n1"})),
+    ];
+    let timeline = ExportTimeline::from_trace(&trace(RunMode::Explore, events));
+    assert!(!timeline.tracks.iter().any(|track| track == "deliver"));
+    let n0 = track_named(&timeline, "node n0");
+    let n1 = track_named(&timeline, "node n1");
+    let delivery = timeline
+        .slices
+        .iter()
+        .position(|slice| slice.track == n1)
+        .expect("delivery slice");
+    assert_eq!(timeline.slices[delivery].name, "kv_repl");
+    assert_eq!(
+        timeline.slices[delivery].end_us - timeline.slices[delivery].start_us,
+        1000
+    );
+    assert_eq!(timeline.flows.len(), 1);
+    assert_eq!(timeline.slices[timeline.flows[0].from].track, n0);
+    assert_eq!(timeline.flows[0].to, delivery);
+
+    let instants: Vec<(&str, usize)> = timeline
+        .instants
+        .iter()
+        .map(|instant| (instant.name.as_str(), instant.track))
+        .collect();
+    assert_eq!(
+        instants,
+        vec![
+            ("partition", track_named(&timeline, "network")),
+            ("crash", n1)
+        ]
+    );
+}
+
+#[test]
+fn chrome_export_emits_named_threads_slices_and_flow_pairs() {
+    let trace = scenario_trace();
+    let doc = ExportTimeline::from_trace(&trace).to_chrome_json(&trace);
+    let events = doc["traceEvents"].as_array().expect("traceEvents");
+    let phases = |ph: &str| events.iter().filter(|e| e["ph"] == ph).count();
+    assert_eq!(phases("X"), 6);
+    assert_eq!(phases("s"), 1);
+    assert_eq!(phases("f"), 1);
+    assert!(
+        events
+            .iter()
+            .any(|e| e["name"] == "thread_name" && e["args"]["name"] == "node n2")
+    );
+}
+
+/// Reads `(field, wire type, payload)` triples from one protobuf message.
+fn decode(mut bytes: &[u8]) -> Vec<(u64, u8, Vec<u8>)> {
+    fn varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = bytes.split_first().expect("truncated varint");
+            *bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        value
+    }
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = varint(&mut bytes);
+        let wire = (key & 7) as u8;
+        let payload = match wire {
+            0 => varint(&mut bytes).to_le_bytes().to_vec(),
+            1 => {
+                let (head, rest) = bytes.split_at(8);
+                bytes = rest;
+                head.to_vec()
+            }
+            2 => {
+                let len = varint(&mut bytes) as usize;
+                let (head, rest) = bytes.split_at(len);
+                bytes = rest;
+                head.to_vec()
+            }
+            other => panic!("unexpected wire type {other}"),
+        };
+        fields.push((key >> 3, wire, payload));
+    }
+    fields
+}
+
+fn varint_value(payload: &[u8]) -> u64 {
+    u64::from_le_bytes(payload.try_into().expect("varint payload"))
+}
+
+#[test]
+fn perfetto_export_is_a_well_formed_packet_stream() {
+    let trace = scenario_trace();
+    let timeline = ExportTimeline::from_trace(&trace);
+    let bytes = timeline.to_perfetto_proto(&trace);
+    let packets = decode(&bytes);
+    assert!(
+        packets
+            .iter()
+            .all(|(field, wire, _)| *field == 1 && *wire == 2)
+    );
+
+    let mut descriptors = 0;
+    let mut last_ts = 0;
+    let mut types = Vec::new();
+    let mut flow_ids = Vec::new();
+    let mut terminating = Vec::new();
+    for (_, _, packet) in &packets {
+        let fields = decode(packet);
+        assert!(
+            fields
+                .iter()
+                .any(|(f, _, v)| *f == 10 && varint_value(v) == 1)
+        );
+        if fields.iter().any(|(f, _, _)| *f == 60) {
+            descriptors += 1;
+            continue;
+        }
+        let ts = fields
+            .iter()
+            .find(|(f, _, _)| *f == 8)
+            .map(|(_, _, v)| varint_value(v))
+            .expect("timestamp");
+        assert!(ts >= last_ts, "packets must be time ordered");
+        last_ts = ts;
+        let (_, _, body) = fields
+            .iter()
+            .find(|(f, _, _)| *f == 11)
+            .expect("track_event");
+        for (field, wire, value) in decode(body) {
+            match field {
+                9 => types.push(varint_value(&value)),
+                47 => {
+                    assert_eq!(wire, 1);
+                    flow_ids.push(value);
+                }
+                48 => {
+                    assert_eq!(wire, 1);
+                    terminating.push(value);
+                }
+                _ => {}
+            }
+        }
+    }
+    assert_eq!(descriptors, 1 + timeline.tracks.len());
+    let begins = types.iter().filter(|t| **t == 1).count();
+    let ends = types.iter().filter(|t| **t == 2).count();
+    assert_eq!(begins, timeline.slices.len());
+    assert_eq!(ends, begins);
+    assert_eq!(flow_ids.len(), 1);
+    assert_eq!(flow_ids, terminating);
+}
@@ -68,20 +68,95 @@ pub fn usage_doc() -> UsageDoc {
                 when: "Reproduce a failure exactly from a recorded trace, to debug without drift.".to_string(),
                 how: "fozzy replay .fozzy/runs/<runId>/trace.fozzy --dump-events --json. Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
             },
+            UsageItem {
+                command: "fozzy replay --debug".to_string(),
+                when: "Step through a recorded scenario, move back in time, and inspect kv/fs/net/memory/clock state at a breakpoint.".to_string(),
+                how: "fozzy replay trace.fozzy --debug (interactive) or fozzy replay trace.fozzy --debug-script cmds.txt --json for scripted, non-TTY sessions.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy replay --from-step".to_string(),
+                when: "Jump to late steps of a long trace without re-executing everything before them.".to_string(),
+                how: "fozzy replay trace.fozzy --from-step 9000 --json (or --from-time 30s); resumes from the nearest recorded snapshot, set by `[snapshots] every_steps` in fozzy.toml.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy replay --override".to_string(),
+                when: "Ask what would have happened if a recorded response, exit code, drop, or random value had been different.".to_string(),
+                how: "fozzy replay trace.fozzy --override 'http_request#2:status_code=503' --continue live --record whatif.fozzy --json; the new trace links back to its parent under `counterfactual`.".to_string(),
+            },
             UsageItem {
                 command: "fozzy trace verify".to_string(),
                 when: "Validate trace integrity/version before replaying or handing artifacts to CI/other teams.".to_string(),
                 how: "fozzy trace verify .fozzy/runs/<runId>/trace.fozzy --json. Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
             },
+            UsageItem {
+                command: "fozzy test --record suite.fozzya".to_string(),
+                when: "A CI test run recorded many scenarios and developers need all of the traces in one file.".to_string(),
+                how: "fozzy test --det --record suite.fozzya (add --record-collision append to extend it), then fozzy replay 'suite.fozzya#<scenario>' or fozzy trace verify suite.fozzya --json.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy test --shard".to_string(),
+                when: "Split one scenario suite across CI machines without maintaining per-machine globs.".to_string(),
+                how: "fozzy test --det --seed 7 --shard 2/4 --json on each machine (add --shard-strategy timing to balance by the durations in a shared .fozzy/runs cache), then fozzy report merge <run1> <run2> <run3> <run4> --format junit --out junit.xml.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy test --watch".to_string(),
+                when: "Iterating on scenarios, fixtures, or the code they exercise and you want fast re-runs of only what changed.".to_string(),
+                how: "fozzy test --det --watch --watch-src 'src/**/*.rs' (each change re-runs the affected scenarios and prints one summary line; --json prints fozzy.watch_cycle.v1 lines).".to_string(),
+            },
+            UsageItem {
+                command: "fozzy test --filter".to_string(),
+                when: "You want to run a slice of the suite by tag, owner, tier, kind, name, or last result.".to_string(),
+                how: "Add \"tags\", \"owner\", \"tier\" to scenario files, then fozzy test --filter 'tag:smoke and not tag:slow' (add --list to see what is selected and why; the same expressions work in full --scenario-filter, gate --filter, and map suites --filter).".to_string(),
+            },
+            UsageItem {
+                command: "fozzy test --retries".to_string(),
+                when: "A CI suite has intermittent failures and you need to tell flaky scenarios from real regressions without extra runs.".to_string(),
+                how: "fozzy test --det --retries 2 --quarantine quarantine.txt --json (same-seed passes are classified flaky, listed scenarios report without failing the run), then fozzy report flaky <runId> for the passing and failing seeds.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy test --reporter".to_string(),
+                when: "CI tooling needs results as TAP, SARIF code-scanning findings, GitHub Actions annotations, or a live event stream.".to_string(),
+                how: "fozzy test --det --reporter sarif (also tap, github, or jsonl) writes report.sarif, report.tap, or github-annotations.txt under .fozzy/runs/<runId>; --reporter jsonl also streams fozzy.reporter_event.v1 run, scenario, and step events to stdout during test, fuzz, and explore.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy history".to_string(),
+                when: "You need to know when a scenario started failing or how its duration or p99 latency has moved over recent runs.".to_string(),
+                how: "fozzy history first-failure --scenario 'tests/checkout*' --json (run id, commit, and seed of the first failing run), or fozzy history trend --metric p99 --last 20; pass-rate and flake-rate use the same --scenario/--last scope.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy trace diff".to_string(),
+                when: "A replay drifted or two seeds behaved differently and you need the first point where they split.".to_string(),
+                how: "fozzy trace diff a.fozzy b.fozzy --json (firstDivergence shows the step, decision kind, and what each side observed).".to_string(),
+            },
+            UsageItem {
+                command: "fozzy trace export".to_string(),
+                when: "You want to see a run's steps, capability calls, and message flows on a timeline.".to_string(),
+                how: "fozzy trace export trace.fozzy --format perfetto --out run.pftrace --json, then open the file in ui.perfetto.dev (use --format chrome for chrome://tracing).".to_string(),
+            },
+            UsageItem {
+                command: "fozzy trace redact".to_string(),
+                when: "A trace captured auth headers, tokens, or secret stdout and needs to be attached to a ticket.".to_string(),
+                how: "Configure [redact] in fozzy.toml, then fozzy trace redact trace.fozzy --out clean.fozzy --json (set on_record = true to redact at record time).".to_string(),
+            },
+            UsageItem {
+                command: "fozzy trace upgrade".to_string(),
+                when: "Archived traces were recorded on an older schema and replay warns they are stale.".to_string(),
+                how: "fozzy trace upgrade old.fozzy --out new.fozzy --json; audit an archive with fozzy trace upgrade traces/ --check --json.".to_string(),
+            },
+            UsageItem {
+                command: "fozzy trace sign".to_string(),
+                when: "A release or audit gate must prove who produced a trace and that it was not edited.".to_string(),
+                how: "fozzy trace keygen --out release.key, then fozzy trace sign trace.fozzy --key release.key; gate with fozzy ci trace.fozzy --require-signed --signing-key release.key.pub.".to_string(),
+            },
             UsageItem {
                 command: "fozzy shrink".to_string(),
                 when: "Minimize a failing run to the smallest scenario/trace that still triggers the bug.".to_string(),
-                how: "fozzy shrink trace.fozzy --minimize all --budget 30s --json (then replay the .min.fozzy output). Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
+                how: "fozzy shrink trace.fozzy --minimize all --budget 30s --json (then replay the .min.fozzy output). Step scenarios drop whole steps first, then shrink values inside the remaining steps (bodies, durations, counts, headers, args); --minimize faults drops fault-injection steps and --minimize schedule moves network deliveries toward FIFO. Add --preserve finding (or --finding-match <regex>) to keep the original finding instead of any failure; each accepted reduction is listed in <out>.lineage.json. Add --jobs <n> to evaluate candidates in parallel; the output is the same for any value. Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
             },
             UsageItem {
                 command: "fozzy fuzz".to_string(),
                 when: "Find new bugs automatically by mutating inputs and exploring states; use for robustness/security testing.".to_string(),
-                how: "fozzy fuzz scenario:tests/example.fozzy.json --mode coverage --time 30s --record /tmp/fuzz.fozzy. Scenario-backed fuzzing is the supported product surface. Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
+                how: "fozzy fuzz scenario:tests/example.fozzy.json --mode coverage --time 30s --record /tmp/fuzz.fozzy; then `fozzy fuzz triage .fozzy/corpus/crashes --target scenario:tests/example.fozzy.json --json` to bucket crashes into unique minimized reproducers. For HTTP APIs, `fozzy fuzz openapi:api.json --http-backend host --runs 500` fuzzes every documented operation and `fozzy fuzz openapi-scenarios api.json` emits starter scenarios. Scenario-backed fuzzing is the supported product surface. Strictest setting: strict mode is on by default; add `--unsafe` only to opt out.".to_string(),
             },
             UsageItem {
                 command: "fozzy explore".to_string(),
@@ -91,7 +166,7 @@ pub fn usage_doc() -> UsageDoc {
             UsageItem {
                 command: "fozzy corpus".to_string(),
                 when: "Manage fuzz corpora: seed inputs, export/import to share failing cases across machines/CI.".to_string(),
-                how: "fozzy corpus add <dir> <file>; fozzy corpus list <dir>; fozzy corpus minimize <dir>; fozzy corpus export <dir> --out corpus.zip. `minimize` canonicalizes entries to content-addressed `input-<blake3>.bin` names and removes duplicate payloads.".to_string(),
+                how: "fozzy corpus add <dir> <file>; fozzy corpus list <dir>; fozzy corpus minimize <dir> [--target scenario:tests/example.fozzy.json]; fozzy corpus merge ci-a ci-b --out merged --target <target>; fozzy corpus stats <dir> --target <target>; fozzy corpus export <dir> --out corpus.zip. `minimize` canonicalizes entries to content-addressed `input-<blake3>.bin` names and removes duplicate payloads; with `--target` it keeps only the smallest entries that preserve coverage.".to_string(),
             },
             UsageItem {
                 command: "fozzy artifacts".to_string(),
@@ -101,7 +176,7 @@ pub fn usage_doc() -> UsageDoc {
             UsageItem {
                 command: "fozzy report".to_string(),
                 when: "Render a run summary in a specific format for CI (JUnit) or humans (HTML/pretty).".to_string(),
-                how: "fozzy report show <runId|trace> --format junit; fozzy report query <runId> --path '.findings[].title'; fozzy report flaky <run1> <run2> --flake-budget 5. Aliases (`latest`, `last-pass`, `last-fail`) are supported, but CI should prefer explicit run ids or trace paths when race-sensitive.".to_string(),
+                how: "fozzy report show <runId|trace> --format junit; fozzy report query <runId> --path '.findings[].title'; fozzy report flaky <run1> <run2> --flake-budget 5; fozzy report merge <shard runs...> --format junit --out junit.xml. Aliases (`latest`, `last-pass`, `last-fail`) are supported, but CI should prefer explicit run ids or trace paths when race-sensitive.".to_string(),
             },
             UsageItem {
                 command: "fozzy memory".to_string(),
@@ -22,6 +22,8 @@ mod clock;
 mod config;
 #[path = "cmd/corpus.rs"]
 mod corpus;
+#[path = "runtime/counterfactual.rs"]
+mod counterfactual;
 #[path = "model/decisions.rs"]
 mod decisions;
 #[path = "runtime/doctor.rs"]
@@ -42,6 +44,8 @@ mod finalize;
 mod fsutil;
 #[path = "modes/fuzz.rs"]
 mod fuzz;
+#[path = "cmd/history.rs"]
+mod history;
 #[path = "runtime/host.rs"]
 mod host;
 #[path = "runtime/init_scaffold.rs"]
@@ -58,24 +62,46 @@ mod memorycap;
 mod profile;
 #[path = "cmd/report.rs"]
 mod report;
+#[path = "model/reporters.rs"]
+mod reporters;
 #[path = "model/reporting.rs"]
 mod reporting;
 #[path = "runtime/run_flow.rs"]
 mod run_flow;
+#[path = "runtime/run_history.rs"]
+mod run_history;
 #[path = "model/scenario.rs"]
 mod scenario;
+#[path = "model/scenario_filter.rs"]
+mod scenario_filter;
+#[path = "runtime/schedule_shrink.rs"]
+mod schedule_shrink;
 #[path = "runtime/scheduler.rs"]
 mod scheduler;
 #[path = "cmd/schema.rs"]
 mod schema;
+#[path = "runtime/shrink_target.rs"]
+mod shrink_target;
+#[path = "runtime/test_retry.rs"]
+mod test_retry;
 #[path = "runtime/test_runner.rs"]
 mod test_runner;
+#[path = "runtime/test_shard.rs"]
+mod test_shard;
 #[path = "runtime/timeline.rs"]
 mod timeline;
+#[path = "cmd/trace_diff.rs"]
+mod trace_diff;
+#[path = "cmd/trace_export.rs"]
+mod trace_export;
 #[path = "runtime/tracefile.rs"]
 mod tracefile;
 #[path = "cmd/usage.rs"]
 mod usage;
+#[path = "runtime/value_shrink.rs"]
+mod value_shrink;
+#[path = "runtime/watch.rs"]
+mod watch;
 
 pub(crate) use artifact_bundle::*;
 pub(crate) use artifact_cache::*;
@@ -88,6 +114,7 @@ pub use ci::*;
 pub use clock::*;
 pub use config::*;
 pub use corpus::*;
+pub use counterfactual::*;
 pub use decisions::*;
 pub use doctor::*;
 pub use duration::*;
@@ -97,6 +124,7 @@ pub use error::*;
 pub use explore::*;
 pub use fsutil::*;
 pub use fuzz::*;
+pub use history::*;
 pub use init_scaffold::*;
 pub use map::*;
 pub use mem::*;
@@ -104,12 +132,23 @@ pub use memory::*;
 pub use memorycap::*;
 pub use profile::*;
 pub use report::*;
+pub use reporters::*;
 pub use reporting::*;
 pub use run_flow::*;
+pub use run_history::*;
 pub use scenario::*;
+pub use scenario_filter::*;
+pub(crate) use schedule_shrink::*;
 pub use scheduler::*;
 pub use schema::*;
+pub use shrink_target::*;
+pub use test_retry::*;
 pub use test_runner::*;
+pub use test_shard::*;
 pub use timeline::*;
+pub use trace_diff::*;
+pub use trace_export::*;
 pub use tracefile::*;
 pub use usage::*;
+pub(crate) use value_shrink::*;
+pub use watch::*;
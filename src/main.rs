@@ -18,7 +18,7 @@ include!("cli_args.rs");
 
 pub(crate) use cli_runtime::{
     enforce_strict_run, enforce_strict_summary, exit_code_for_status, resolve_memory_options,
-    strict_enabled,
+    strict_enabled, watch_and_rerun,
 };
 pub(crate) use fozzy::*;
 
@@ -47,10 +47,13 @@ fn main() -> ExitCode {
         );
     }
 
-    let config = match Config::load_optional_checked(&cli.config) {
+    let mut config = match Config::load_optional_checked(&cli.config) {
         Ok(cfg) => cfg,
         Err(err) => return print_error_and_exit(&logger, anyhow::anyhow!("{err}")),
     };
+    if let Some(encoding) = cli.trace_encoding.or_else(fozzy::TraceEncoding::from_env) {
+        config.trace_encoding = encoding;
+    }
 
     match cli_dispatch::run_command(&cli, &config, &logger) {
         Ok(code) => code,
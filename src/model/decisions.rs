@@ -142,6 +142,24 @@ impl<'a> DecisionCursor<'a> {
     pub fn remaining(&self) -> usize {
         self.decisions.len().saturating_sub(self.index)
     }
+
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    pub fn peek(&self) -> Option<&'a Decision> {
+        self.decisions.get(self.index)
+    }
+
+    pub fn seek(&mut self, index: usize) {
+        self.index = index.min(self.decisions.len());
+    }
+
+    /// Decisions between `from` and the current position.
+    pub fn consumed_since(&self, from: usize) -> &'a [Decision] {
+        let end = self.index.min(self.decisions.len());
+        &self.decisions[from.min(end)..end]
+    }
 }
 
 impl<'a> Iterator for DecisionCursor<'a> {
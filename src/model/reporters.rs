@@ -0,0 +1,492 @@
+//! TAP, SARIF, GitHub Actions and JSON Lines reporters.
+//!
+//! Each renderer is a pure function of the run summary, so the artifact it writes can be
+//! checked against `report.json` later. The JSON Lines reporter also streams run,
+//! scenario and step events to stdout while `test`, `fuzz` and `explore` run.
+
+use serde::{Deserialize, Serialize};
+
+use std::io::Write as _;
+use std::time::Duration;
+
+use crate::{
+    ExitStatus, Finding, FindingKind, FozzyResult, Reporter, RunMode, RunSummary, TestCounts,
+    TraceEvent,
+};
+
+pub const REPORTER_EVENT_SCHEMA: &str = "fozzy.reporter_event.v1";
+
+/// Artifact file written by `write_reporter_artifacts` for each reporter, if any.
+pub fn reporter_artifact_name(reporter: Reporter) -> Option<&'static str> {
+    match reporter {
+        Reporter::Pretty | Reporter::Json => None,
+        Reporter::Junit => Some("junit.xml"),
+        Reporter::Html => Some("report.html"),
+        Reporter::Tap => Some("report.tap"),
+        Reporter::Sarif => Some("report.sarif"),
+        Reporter::Jsonl => Some("report.jsonl"),
+        Reporter::Github => Some("github-annotations.txt"),
+    }
+}
+
+/// Renders `summary` in `reporter`'s artifact format; `None` for pretty and json.
+pub fn render_reporter(summary: &RunSummary, reporter: Reporter) -> Option<String> {
+    match reporter {
+        Reporter::Pretty | Reporter::Json => None,
+        Reporter::Junit => Some(crate::render_junit_xml(summary)),
+        Reporter::Html => Some(crate::render_html(summary)),
+        Reporter::Tap => Some(render_tap(summary)),
+        Reporter::Sarif => Some(render_sarif(summary)),
+        Reporter::Jsonl => Some(render_jsonl_events(summary)),
+        Reporter::Github => Some(render_github_annotations(summary)),
+    }
+}
+
+/// TAP 14: one test point per finding (or one for the whole run when there are none),
+/// with the finding's kind, message and location as a YAML diagnostic block.
+pub fn render_tap(summary: &RunSummary) -> String {
+    let ok = if summary.status == ExitStatus::Pass {
+        "ok"
+    } else {
+        "not ok"
+    };
+    let mut out = String::from("TAP version 14\n");
+    out.push_str(&format!("1..{}\n", summary.findings.len().max(1)));
+    if summary.findings.is_empty() {
+        out.push_str(&format!(
+            "{ok} 1 - {}\n",
+            tap_description(&summary.identity.run_id)
+        ));
+        if summary.status != ExitStatus::Pass {
+            out.push_str("  ---\n");
+            out.push_str(&format!("  status: {}\n", status_name(summary.status)));
+            out.push_str("  ...\n");
+        }
+    }
+    for (i, finding) in summary.findings.iter().enumerate() {
+        out.push_str(&format!(
+            "{ok} {} - {}\n",
+            i + 1,
+            tap_description(&finding.title)
+        ));
+        out.push_str("  ---\n");
+        out.push_str(&format!("  kind: {}\n", kind_name(&finding.kind)));
+        out.push_str(&format!("  message: {}\n", yaml_string(&finding.message)));
+        if let Some(location) = &finding.location
+            && let Some(file) = &location.file
+        {
+            out.push_str("  at:\n");
+            out.push_str(&format!("    file: {}\n", yaml_string(file)));
+            if let Some(line) = location.line {
+                out.push_str(&format!("    line: {line}\n"));
+            }
+            if let Some(col) = location.col {
+                out.push_str(&format!("    column: {col}\n"));
+            }
+        }
+        out.push_str("  ...\n");
+    }
+    if let Some(tests) = &summary.tests {
+        out.push_str(&format!(
+            "# tests passed={} failed={} skipped={} flaky={} quarantined={}\n",
+            tests.passed, tests.failed, tests.skipped, tests.flaky, tests.quarantined
+        ));
+    }
+    out
+}
+
+/// SARIF 2.1.0 with one rule per finding title and one result per finding. Findings are
+/// `error`s in a failed run and `warning`s in a passing one.
+pub fn render_sarif(summary: &RunSummary) -> String {
+    let mut rules: Vec<&str> = summary.findings.iter().map(|f| f.title.as_str()).collect();
+    rules.sort_unstable();
+    rules.dedup();
+    let level = finding_level(summary);
+    let results: Vec<serde_json::Value> = summary
+        .findings
+        .iter()
+        .map(|finding| {
+            let mut result = serde_json::json!({
+                "ruleId": finding.title,
+                "ruleIndex": rules.binary_search(&finding.title.as_str()).unwrap_or_default(),
+                "level": level,
+                "message": { "text": finding.message },
+                "properties": { "kind": kind_name(&finding.kind) },
+            });
+            if let Some(location) = &finding.location
+                && let Some(file) = &location.file
+            {
+                let mut region = serde_json::Map::new();
+                if let Some(line) = location.line {
+                    region.insert("startLine".to_string(), line.into());
+                }
+                if let Some(col) = location.col {
+                    region.insert("startColumn".to_string(), col.into());
+                }
+                let mut physical = serde_json::json!({ "artifactLocation": { "uri": file } });
+                if !region.is_empty() {
+                    physical["region"] = serde_json::Value::Object(region);
+                }
+                result["locations"] = serde_json::json!([{ "physicalLocation": physical }]);
+            }
+            result
+        })
+        .collect();
+    let rules: Vec<serde_json::Value> = rules
+        .iter()
+        .map(|title| {
+            serde_json::json!({
+                "id": title,
+                "shortDescription": { "text": title },
+            })
+        })
+        .collect();
+    let doc = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": "fozzy", "rules": rules } },
+            "automationDetails": {
+                "id": format!("fozzy/{}/{}", mode_name(summary.mode), summary.identity.run_id)
+            },
+            "invocations": [{ "executionSuccessful": summary.status == ExitStatus::Pass }],
+            "properties": {
+                "runId": summary.identity.run_id,
+                "seed": summary.identity.seed,
+                "status": status_name(summary.status),
+            },
+            "results": results,
+        }],
+    });
+    let mut out = serde_json::to_string_pretty(&doc).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+/// GitHub Actions workflow commands: an `::error` (or `::warning` when the run passed)
+/// per finding, with `file`/`line`/`col` when the finding has a location.
+pub fn render_github_annotations(summary: &RunSummary) -> String {
+    let level = finding_level(summary);
+    let mut out = String::new();
+    for finding in &summary.findings {
+        let mut props = Vec::new();
+        if let Some(location) = &finding.location
+            && let Some(file) = &location.file
+        {
+            props.push(format!("file={}", github_property(file)));
+            if let Some(line) = location.line {
+                props.push(format!("line={line}"));
+            }
+            if let Some(col) = location.col {
+                props.push(format!("col={col}"));
+            }
+        }
+        props.push(format!("title={}", github_property(&finding.title)));
+        out.push_str(&format!(
+            "::{level} {}::{}\n",
+            props.join(","),
+            github_data(&finding.message)
+        ));
+    }
+    if summary.findings.is_empty() && summary.status != ExitStatus::Pass {
+        out.push_str(&format!(
+            "::error title={}::{}\n",
+            github_property(&format!("fozzy {}", mode_name(summary.mode))),
+            github_data(&format!(
+                "run {} finished with status {}",
+                summary.identity.run_id,
+                status_name(summary.status)
+            ))
+        ));
+    }
+    out
+}
+
+/// The run-level events of `summary` (`run_started`, one `finding` per finding,
+/// `run_finished`). The live `--reporter jsonl` stream starts and ends with these same
+/// lines, with scenario and step events in between.
+pub fn render_jsonl_events(summary: &RunSummary) -> String {
+    let mut out = event_line(
+        &summary.identity.run_id,
+        &ReporterEvent::run_started(summary),
+    );
+    for line in run_finished_lines(summary) {
+        out.push_str(&line);
+    }
+    out
+}
+
+/// One line of the `--reporter jsonl` stream (`fozzy.reporter_event.v1`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "event",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum ReporterEvent {
+    RunStarted {
+        mode: RunMode,
+        seed: u64,
+        started_at: String,
+    },
+    ScenarioStarted {
+        path: String,
+        /// Fuzz input number; absent for test and explore scenarios.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        iteration: Option<u64>,
+        seed: u64,
+    },
+    Step {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        iteration: Option<u64>,
+        index: usize,
+        time_ms: u64,
+        name: String,
+        #[serde(default)]
+        fields: serde_json::Map<String, serde_json::Value>,
+    },
+    ScenarioFinished {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        iteration: Option<u64>,
+        seed: u64,
+        status: ExitStatus,
+        duration_ms: u64,
+        /// Finding titles.
+        #[serde(default)]
+        findings: Vec<String>,
+    },
+    Finding {
+        #[serde(flatten)]
+        finding: Finding,
+    },
+    RunFinished {
+        status: ExitStatus,
+        finished_at: String,
+        duration_ms: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tests: Option<TestCounts>,
+        findings: usize,
+    },
+}
+
+impl ReporterEvent {
+    fn run_started(summary: &RunSummary) -> Self {
+        Self::RunStarted {
+            mode: summary.mode,
+            seed: summary.identity.seed,
+            started_at: summary.started_at.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventLine<'a> {
+    schema_version: &'static str,
+    run_id: &'a str,
+    #[serde(flatten)]
+    event: &'a ReporterEvent,
+}
+
+fn event_line(run_id: &str, event: &ReporterEvent) -> String {
+    let mut line = serde_json::to_string(&EventLine {
+        schema_version: REPORTER_EVENT_SCHEMA,
+        run_id,
+        event,
+    })
+    .unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+fn run_finished_lines(summary: &RunSummary) -> Vec<String> {
+    let run_id = &summary.identity.run_id;
+    let mut lines: Vec<String> = summary
+        .findings
+        .iter()
+        .map(|finding| {
+            event_line(
+                run_id,
+                &ReporterEvent::Finding {
+                    finding: finding.clone(),
+                },
+            )
+        })
+        .collect();
+    lines.push(event_line(
+        run_id,
+        &ReporterEvent::RunFinished {
+            status: summary.status,
+            finished_at: summary.finished_at.clone(),
+            duration_ms: summary.duration_ms,
+            tests: summary.tests.clone(),
+            findings: summary.findings.len(),
+        },
+    ));
+    lines
+}
+
+/// Live `--reporter jsonl` output. Disabled (every call is a no-op) for other reporters.
+#[derive(Debug, Default)]
+pub(crate) struct ReporterEventStream {
+    run_id: String,
+    enabled: bool,
+}
+
+impl ReporterEventStream {
+    pub(crate) fn for_reporter(reporter: Reporter, run_id: &str) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            enabled: matches!(reporter, Reporter::Jsonl),
+        }
+    }
+
+    pub(crate) fn run_started(
+        &mut self,
+        mode: RunMode,
+        seed: u64,
+        started_at: &str,
+    ) -> FozzyResult<()> {
+        self.emit(&ReporterEvent::RunStarted {
+            mode,
+            seed,
+            started_at: started_at.to_string(),
+        })
+    }
+
+    pub(crate) fn scenario_started(
+        &mut self,
+        path: &str,
+        iteration: Option<u64>,
+        seed: u64,
+    ) -> FozzyResult<()> {
+        self.emit(&ReporterEvent::ScenarioStarted {
+            path: path.to_string(),
+            iteration,
+            seed,
+        })
+    }
+
+    /// Emits the scenario's trace events as `step` events, then `scenario_finished`.
+    pub(crate) fn scenario_finished(
+        &mut self,
+        scenario: ScenarioEvents<'_>,
+        status: ExitStatus,
+        duration: Duration,
+        findings: &[Finding],
+    ) -> FozzyResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        for (index, event) in scenario.events.iter().enumerate() {
+            self.emit(&ReporterEvent::Step {
+                path: scenario.path.to_string(),
+                iteration: scenario.iteration,
+                index,
+                time_ms: event.time_ms,
+                name: event.name.clone(),
+                fields: event.fields.clone(),
+            })?;
+        }
+        self.emit(&ReporterEvent::ScenarioFinished {
+            path: scenario.path.to_string(),
+            iteration: scenario.iteration,
+            seed: scenario.seed,
+            status,
+            duration_ms: crate::duration_fields(duration).0,
+            findings: findings.iter().map(|f| f.title.clone()).collect(),
+        })
+    }
+
+    /// Emits the summary's `finding` events and the closing `run_finished` event.
+    pub(crate) fn run_finished(&mut self, summary: &RunSummary) -> FozzyResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        for line in run_finished_lines(summary) {
+            self.write(&line)?;
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, event: &ReporterEvent) -> FozzyResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let line = event_line(&self.run_id, event);
+        self.write(&line)
+    }
+
+    fn write(&mut self, line: &str) -> FozzyResult<()> {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(line.as_bytes())?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// The scenario a batch of `step` events belongs to.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScenarioEvents<'a> {
+    pub(crate) path: &'a str,
+    pub(crate) iteration: Option<u64>,
+    pub(crate) seed: u64,
+    pub(crate) events: &'a [TraceEvent],
+}
+
+fn finding_level(summary: &RunSummary) -> &'static str {
+    if summary.status == ExitStatus::Pass {
+        "warning"
+    } else {
+        "error"
+    }
+}
+
+fn status_name(status: ExitStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn mode_name(mode: RunMode) -> String {
+    serde_json::to_value(mode)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn kind_name(kind: &FindingKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// TAP descriptions end at a `#` (directive) or newline.
+fn tap_description(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('#', "\\#")
+        .replace(['\n', '\r'], " ")
+}
+
+/// A double-quoted YAML scalar; JSON string syntax is valid YAML.
+fn yaml_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+fn github_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn github_property(text: &str) -> String {
+    github_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+#[path = "reporters/tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use super::*;
+
+use crate::{FindingLocation, RunIdentity};
+
+fn summary(status: ExitStatus, findings: Vec<Finding>) -> RunSummary {
+    RunSummary {
+        status,
+        mode: RunMode::Test,
+        identity: RunIdentity {
+            run_id: "r1".to_string(),
+            seed: 7,
+            trace_path: None,
+            report_path: None,
+            artifacts_dir: None,
+        },
+        finished_at: "2026-01-01T00:00:01Z".to_string(),
+        duration_ms: 1000,
+        duration_ns: 1_000_000_000,
+        findings,
+        ..RunSummary::fixture()
+    }
+}
+
+fn finding(title: &str, message: &str, file: Option<&str>) -> Finding {
+    Finding {
+        kind: FindingKind::Assertion,
+        title: title.to_string(),
+        message: message.to_string(),
+        location: file.map(|file| FindingLocation {
+            file: Some(file.to_string()),
+            line: Some(12),
+            col: Some(3),
+            details: None,
+        }),
+    }
+}
+
+#[test]
+fn tap_has_a_plan_and_one_point_per_finding_with_location() {
+    let tap = render_tap(&summary(
+        ExitStatus::Fail,
+        vec![
+            finding("assert_eq", "left != right", Some("tests/a.fozzy.json")),
+            finding("issue #4", "multi\nline", None),
+        ],
+    ));
+    let lines: Vec<&str> = tap.lines().collect();
+    assert_eq!(lines[0], "TAP version 14");
+    assert_eq!(lines[1], "1..2");
+    assert_eq!(lines[2], "not ok 1 - assert_eq");
+    assert!(tap.contains("    file: \"tests/a.fozzy.json\"\n    line: 12\n    column: 3\n"));
+    assert!(tap.contains("not ok 2 - issue \\#4\n"));
+    assert!(tap.contains("  message: \"multi\\nline\"\n"));
+
+    let tap = render_tap(&summary(ExitStatus::Pass, Vec::new()));
+    assert_eq!(tap, "TAP version 14\n1..1\nok 1 - r1\n");
+}
+
+#[test]
+fn sarif_lists_rules_and_located_results() {
+    let sarif = render_sarif(&summary(
+        ExitStatus::Fail,
+        vec![
+            finding("b_rule", "second", None),
+            finding("a_rule", "first", Some("src/lib.rs")),
+        ],
+    ));
+    let doc: serde_json::Value = serde_json::from_str(&sarif).expect("sarif json");
+    assert_eq!(doc["version"], "2.1.0");
+    let run = &doc["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "a_rule");
+    assert_eq!(run["automationDetails"]["id"], "fozzy/test/r1");
+    let results = run["results"].as_array().expect("results");
+    assert_eq!(results[0]["ruleIndex"], 1);
+    assert_eq!(results[0]["level"], "error");
+    assert!(results[0].get("locations").is_none());
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+    assert_eq!(location["region"]["startLine"], 12);
+
+    let sarif = render_sarif(&summary(
+        ExitStatus::Pass,
+        vec![finding("a_rule", "note", None)],
+    ));
+    assert!(sarif.contains("\"level\": \"warning\""));
+}
+
+#[test]
+fn github_annotations_escape_properties_and_data() {
+    let text = render_github_annotations(&summary(
+        ExitStatus::Fail,
+        vec![finding("a:b,c", "50%\ndone", Some("tests/a.fozzy.json"))],
+    ));
+    assert_eq!(
+        text,
+        "::error file=tests/a.fozzy.json,line=12,col=3,title=a%3Ab%2Cc::50%25%0Adone\n"
+    );
+
+    let text = render_github_annotations(&summary(ExitStatus::Timeout, Vec::new()));
+    assert_eq!(
+        text,
+        "::error title=fozzy test::run r1 finished with status timeout\n"
+    );
+    assert!(render_github_annotations(&summary(ExitStatus::Pass, Vec::new())).is_empty());
+}
+
+#[test]
+fn jsonl_events_bracket_findings_between_run_started_and_run_finished() {
+    let text = render_jsonl_events(&summary(
+        ExitStatus::Fail,
+        vec![finding("assert_eq", "boom", None)],
+    ));
+    let events: Vec<serde_json::Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("event json"))
+        .collect();
+    let names: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(names, vec!["run_started", "finding", "run_finished"]);
+    assert!(events.iter().all(|event| {
+        event["schemaVersion"] == REPORTER_EVENT_SCHEMA && event["runId"] == "r1"
+    }));
+    assert_eq!(events[0]["startedAt"], "2026-01-01T00:00:00Z");
+    assert_eq!(events[1]["title"], "assert_eq");
+    assert_eq!(events[2]["status"], "fail");
+    assert_eq!(events[2]["findings"], 1);
+}
@@ -13,6 +13,10 @@ pub enum Reporter {
     Json,
     Junit,
     Html,
+    Tap,
+    Sarif,
+    Jsonl,
+    Github,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -47,7 +51,16 @@ impl FromStr for FlakeBudget {
 
 impl clap::ValueEnum for Reporter {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::Pretty, Self::Json, Self::Junit, Self::Html]
+        &[
+            Self::Pretty,
+            Self::Json,
+            Self::Junit,
+            Self::Html,
+            Self::Tap,
+            Self::Sarif,
+            Self::Jsonl,
+            Self::Github,
+        ]
     }
 
     fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
@@ -56,6 +69,10 @@ impl clap::ValueEnum for Reporter {
             Self::Json => clap::builder::PossibleValue::new("json"),
             Self::Junit => clap::builder::PossibleValue::new("junit"),
             Self::Html => clap::builder::PossibleValue::new("html"),
+            Self::Tap => clap::builder::PossibleValue::new("tap"),
+            Self::Sarif => clap::builder::PossibleValue::new("sarif"),
+            Self::Jsonl => clap::builder::PossibleValue::new("jsonl"),
+            Self::Github => clap::builder::PossibleValue::new("github"),
         })
     }
 }
@@ -195,6 +212,9 @@ pub struct RunSummary {
     pub memory: Option<crate::MemorySummary>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub findings: Vec<Finding>,
+    /// Failing `fozzy test` scenarios with their retry attempts and quarantine state.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retries: Vec<ScenarioRetry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -202,6 +222,78 @@ pub struct TestCounts {
     pub passed: u64,
     pub failed: u64,
     pub skipped: u64,
+    /// Scenarios that failed, then passed when retried with the same seed (counted in `passed`).
+    #[serde(default)]
+    pub flaky: u64,
+    /// Failing scenarios on the quarantine list (not counted in `failed`).
+    #[serde(default)]
+    pub quarantined: u64,
+}
+
+/// A scenario that failed in a test run, and the evidence from retrying it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioRetry {
+    pub path: String,
+    /// Seed of the original, failing attempt.
+    pub seed: u64,
+    pub status: ExitStatus,
+    /// Absent when the scenario was not retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<FailureClassification>,
+    #[serde(default)]
+    pub quarantined: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<RetryAttempt>,
+}
+
+impl ScenarioRetry {
+    /// Seeds that passed and failed across the original run and every retry.
+    pub fn seeds_by_outcome(&self) -> (Vec<u64>, Vec<u64>) {
+        let mut passed = Vec::new();
+        let mut failed = vec![self.seed];
+        for attempt in &self.attempts {
+            if attempt.status == ExitStatus::Pass {
+                passed.push(attempt.seed);
+            } else {
+                failed.push(attempt.seed);
+            }
+        }
+        passed.sort_unstable();
+        passed.dedup();
+        failed.sort_unstable();
+        failed.dedup();
+        (passed, failed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryAttempt {
+    pub seed: u64,
+    /// Whether this attempt reused the original seed rather than a fresh one.
+    pub same_seed: bool,
+    pub status: ExitStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClassification {
+    /// Every retry with the original seed failed again.
+    Deterministic,
+    /// A retry with the original seed passed.
+    Flaky,
+}
+
+impl FailureClassification {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Deterministic => "deterministic",
+            Self::Flaky => "flaky",
+        }
+    }
 }
 
 impl RunSummary {
@@ -226,6 +318,17 @@ impl RunSummary {
                 tests.passed, tests.failed, tests.skipped
             ));
         }
+        for retry in &self.retries {
+            let (passed, failed) = retry.seeds_by_outcome();
+            out.push_str(&format!(
+                "retry: {} classification={} quarantined={} passedSeeds={passed:?} failedSeeds={failed:?}\n",
+                retry.path,
+                retry
+                    .classification
+                    .map_or("none", FailureClassification::as_str),
+                retry.quarantined
+            ));
+        }
         if let Some(mem) = &self.memory {
             out.push_str(&format!(
                 "memory: allocs={} frees={} failed_allocs={} in_use={} peak={} leaked_bytes={} leaked_allocs={}\n",
@@ -527,6 +630,33 @@ fn html_escape(s: &str) -> String {
     xml_escape(s)
 }
 
+#[cfg(test)]
+impl RunSummary {
+    /// Passing run summary for test fixtures; set what a test cares about with
+    /// `RunSummary { .., ..RunSummary::fixture() }`.
+    pub(crate) fn fixture() -> Self {
+        Self {
+            status: ExitStatus::Pass,
+            mode: RunMode::Run,
+            identity: RunIdentity {
+                run_id: "run".to_string(),
+                seed: 1,
+                trace_path: None,
+                report_path: None,
+                artifacts_dir: None,
+            },
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            finished_at: "2026-01-01T00:00:00Z".to_string(),
+            duration_ms: 0,
+            duration_ns: 0,
+            tests: None,
+            memory: None,
+            findings: Vec::new(),
+            retries: Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,616 @@
+//! Scenario metadata and the `--filter` expression language.
+//!
+//! Scenario files may declare `tags`, `owner`, `tier`, `timeout` and `retries` next to
+//! `version` and `name`. A filter combines terms with `and`, `or`, `not` and parentheses (`not`
+//! binds tightest, then `and`):
+//!
+//! - `tag:<t>`, `owner:<o>`, `tier:<t>`, `name:<n>` match exactly;
+//!   `kind:steps|suites|distributed` matches the file variant.
+//! - `path:<s>` and a bare word match a substring of the path. Input made only of
+//!   plain words (no keys, `~`, quotes, parentheses or operators) keeps the
+//!   pre-expression `--filter` behavior: the whole string, spaces included, is one
+//!   path substring.
+//! - `<key>~<regex>` matches `tag`, `owner`, `tier`, `name` or `path` by regex.
+//! - `last:<status>` matches the scenario's status in the most recent test run that ran
+//!   it (`timings.json`): `pass`, `fail`, `timeout`, `crash`, `error`, `failed` (any
+//!   non-pass) or `none` (no recorded run).
+//!
+//! Values with spaces may be double-quoted: `name~"checkout (eu|us)"`.
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{ExitStatus, FozzyError, FozzyResult};
+
+/// Optional selection metadata at the top level of a scenario file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioMeta {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// Default per-scenario timeout (e.g. `"5s"`); `--timeout` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Retries for a failing run of this scenario; `--retries` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+}
+
+impl ScenarioMeta {
+    pub fn load(path: &Path) -> FozzyResult<Self> {
+        let bytes = std::fs::read(path)?;
+        let doc: serde_json::Value = serde_json::from_slice(&bytes).map_err(|err| {
+            FozzyError::Scenario(format!(
+                "failed to parse scenario {}: {err}",
+                path.display()
+            ))
+        })?;
+        Self::from_doc(&doc, path)
+    }
+
+    fn from_doc(doc: &serde_json::Value, path: &Path) -> FozzyResult<Self> {
+        let meta: Self = serde_json::from_value(doc.clone()).map_err(|err| {
+            FozzyError::Scenario(format!(
+                "invalid scenario metadata in {}: {err}",
+                path.display()
+            ))
+        })?;
+        meta.timeout_duration().map_err(|err| {
+            FozzyError::Scenario(format!(
+                "invalid scenario timeout in {}: {err}",
+                path.display()
+            ))
+        })?;
+        Ok(meta)
+    }
+
+    pub fn timeout_duration(&self) -> FozzyResult<Option<Duration>> {
+        self.timeout
+            .as_deref()
+            .map(crate::parse_duration)
+            .transpose()
+    }
+}
+
+/// A scenario as seen by a filter, and whether and why it was selected.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioSelectionEntry {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// `steps`, `suites` or `distributed`; absent when the file does not parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(flatten)]
+    pub meta: ScenarioMeta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_status: Option<ExitStatus>,
+    pub selected: bool,
+    /// The filter with each term marked `[yes]` or `[no]` for this scenario.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// `--list` output: every candidate scenario and the filter's verdict on it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioSelectionReport {
+    pub schema_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    pub total: usize,
+    pub selected: usize,
+    pub scenarios: Vec<ScenarioSelectionEntry>,
+    /// Problems reading run history for `last:` terms; the CLI prints them to stderr.
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+impl ScenarioSelectionReport {
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        self.scenarios
+            .iter()
+            .filter(|entry| entry.selected)
+            .map(|entry| PathBuf::from(&entry.path))
+            .collect()
+    }
+}
+
+/// Applies `filter` to `paths`, reading scenario metadata and, for `last:` terms, the
+/// statuses recorded under `runs_dir`. A scenario that does not parse is still listed
+/// (and can match path terms) so the command that runs it reports the parse error.
+pub fn select_scenarios(
+    paths: &[PathBuf],
+    filter: Option<&str>,
+    runs_dir: &Path,
+) -> FozzyResult<ScenarioSelectionReport> {
+    let filter = filter.map(ScenarioFilter::from_str).transpose()?;
+    let mut warnings = Vec::new();
+    let history: BTreeMap<String, ExitStatus> = match &filter {
+        Some(filter) if filter.uses_history() => {
+            let recorded = crate::recorded_scenario_timings(runs_dir);
+            warnings = recorded.warnings;
+            recorded
+                .timings
+                .into_iter()
+                .map(|(path, timing)| (path, timing.status))
+                .collect()
+        }
+        _ => BTreeMap::new(),
+    };
+    let mut scenarios = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut entry = scenario_entry(path)?;
+        entry.last_status = history.get(&entry.path).copied();
+        if let Some(filter) = &filter {
+            entry.selected = filter.matches(&entry);
+            entry.reason = Some(filter.explain(&entry));
+        }
+        scenarios.push(entry);
+    }
+    Ok(ScenarioSelectionReport {
+        schema_version: "fozzy.scenario_selection.v1".to_string(),
+        filter: filter.map(|filter| filter.source),
+        total: scenarios.len(),
+        selected: scenarios.iter().filter(|entry| entry.selected).count(),
+        scenarios,
+        warnings,
+    })
+}
+
+fn scenario_entry(path: &Path) -> FozzyResult<ScenarioSelectionEntry> {
+    let mut entry = ScenarioSelectionEntry {
+        path: path.display().to_string(),
+        name: None,
+        kind: None,
+        meta: ScenarioMeta::default(),
+        last_status: None,
+        selected: true,
+        reason: None,
+    };
+    let Some(doc) = std::fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .filter(serde_json::Value::is_object)
+    else {
+        return Ok(entry);
+    };
+    entry.name = doc
+        .get("name")
+        .and_then(|name| name.as_str())
+        .map(str::to_string);
+    entry.kind = ["distributed", "suites", "steps"]
+        .into_iter()
+        .find(|kind| doc.get(*kind).is_some())
+        .map(str::to_string);
+    entry.meta = ScenarioMeta::from_doc(&doc, path)?;
+    Ok(entry)
+}
+
+/// A parsed `--filter` expression.
+#[derive(Debug, Clone)]
+pub struct ScenarioFilter {
+    source: String,
+    /// `None` for an empty filter, which selects everything.
+    expr: Option<FilterExpr>,
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Term(FilterTerm),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+#[derive(Debug, Clone)]
+struct FilterTerm {
+    text: String,
+    key: FilterKey,
+    matcher: TermMatcher,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterKey {
+    Tag,
+    Owner,
+    Tier,
+    Name,
+    Path,
+    Kind,
+    Last,
+}
+
+#[derive(Debug, Clone)]
+enum TermMatcher {
+    Exact(String),
+    Substring(String),
+    Regex(regex::Regex),
+}
+
+impl FromStr for ScenarioFilter {
+    type Err = FozzyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(source)?;
+        let expr = if tokens.is_empty() {
+            None
+        } else if is_legacy_substring(source, &tokens) {
+            Some(FilterExpr::Term(FilterTerm {
+                text: source.to_string(),
+                key: FilterKey::Path,
+                matcher: TermMatcher::Substring(source.to_string()),
+            }))
+        } else {
+            let mut parser = Parser {
+                source,
+                tokens: &tokens,
+                pos: 0,
+            };
+            let expr = parser.or()?;
+            if let Some(token) = tokens.get(parser.pos) {
+                return Err(invalid_filter(
+                    source,
+                    &format!("unexpected {token}; join terms with `and` or `or`"),
+                ));
+            }
+            Some(expr)
+        };
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+}
+
+impl ScenarioFilter {
+    pub fn matches(&self, scenario: &ScenarioSelectionEntry) -> bool {
+        self.expr.as_ref().is_none_or(|expr| expr.matches(scenario))
+    }
+
+    pub fn explain(&self, scenario: &ScenarioSelectionEntry) -> String {
+        match &self.expr {
+            Some(expr) => expr.explain(scenario, 0),
+            None => "empty filter selects every scenario".to_string(),
+        }
+    }
+
+    fn uses_history(&self) -> bool {
+        self.expr.as_ref().is_some_and(FilterExpr::uses_history)
+    }
+}
+
+impl FilterExpr {
+    fn matches(&self, scenario: &ScenarioSelectionEntry) -> bool {
+        match self {
+            Self::Term(term) => term.matches(scenario),
+            Self::Not(inner) => !inner.matches(scenario),
+            Self::And(left, right) => left.matches(scenario) && right.matches(scenario),
+            Self::Or(left, right) => left.matches(scenario) || right.matches(scenario),
+        }
+    }
+
+    /// Renders the expression with each term's verdict; `precedence` is that of the
+    /// enclosing operator (0 = none, 1 = `or`, 2 = `and`, 3 = `not`).
+    fn explain(&self, scenario: &ScenarioSelectionEntry, precedence: u8) -> String {
+        let (own, text) = match self {
+            Self::Term(term) => {
+                let verdict = if term.matches(scenario) { "yes" } else { "no" };
+                return format!("{}[{verdict}]", term.text);
+            }
+            Self::Not(inner) => (3, format!("not {}", inner.explain(scenario, 3))),
+            Self::And(left, right) => (
+                2,
+                format!(
+                    "{} and {}",
+                    left.explain(scenario, 2),
+                    right.explain(scenario, 2)
+                ),
+            ),
+            Self::Or(left, right) => (
+                1,
+                format!(
+                    "{} or {}",
+                    left.explain(scenario, 1),
+                    right.explain(scenario, 1)
+                ),
+            ),
+        };
+        if own < precedence {
+            format!("({text})")
+        } else {
+            text
+        }
+    }
+
+    fn uses_history(&self) -> bool {
+        match self {
+            Self::Term(term) => term.key == FilterKey::Last,
+            Self::Not(inner) => inner.uses_history(),
+            Self::And(left, right) | Self::Or(left, right) => {
+                left.uses_history() || right.uses_history()
+            }
+        }
+    }
+}
+
+impl FilterTerm {
+    fn parse(text: &str, source: &str) -> FozzyResult<Self> {
+        let split = text.find([':', '~']);
+        let keyed = split.and_then(|at| {
+            let key = match &text[..at] {
+                "tag" => FilterKey::Tag,
+                "owner" => FilterKey::Owner,
+                "tier" => FilterKey::Tier,
+                "name" => FilterKey::Name,
+                "path" => FilterKey::Path,
+                "kind" => FilterKey::Kind,
+                "last" => FilterKey::Last,
+                _ => return None,
+            };
+            Some((key, &text[at..at + 1], &text[at + 1..]))
+        });
+        let Some((key, op, value)) = keyed else {
+            if let Some(at) = split
+                && text[..at].chars().all(|c| c.is_ascii_alphabetic())
+                && at > 0
+            {
+                return Err(invalid_filter(
+                    source,
+                    &format!(
+                        "unknown key {:?} (expected tag, owner, tier, name, path, kind or last)",
+                        &text[..at]
+                    ),
+                ));
+            }
+            return Ok(Self {
+                text: text.to_string(),
+                key: FilterKey::Path,
+                matcher: TermMatcher::Substring(text.to_string()),
+            });
+        };
+        if value.is_empty() {
+            return Err(invalid_filter(source, &format!("{text:?} has no value")));
+        }
+        let matcher = match (key, op) {
+            (FilterKey::Kind | FilterKey::Last, "~") => {
+                return Err(invalid_filter(
+                    source,
+                    &format!("{text:?}: `kind` and `last` take `:` values, not regexes"),
+                ));
+            }
+            (_, "~") => TermMatcher::Regex(regex::Regex::new(value).map_err(|err| {
+                invalid_filter(source, &format!("invalid regex in {text:?}: {err}"))
+            })?),
+            (FilterKey::Kind, _) if !matches!(value, "steps" | "suites" | "distributed") => {
+                return Err(invalid_filter(
+                    source,
+                    &format!("unknown kind {value:?} (expected steps, suites or distributed)"),
+                ));
+            }
+            (FilterKey::Last, _)
+                if !matches!(
+                    value,
+                    "pass" | "fail" | "timeout" | "crash" | "error" | "failed" | "none"
+                ) =>
+            {
+                return Err(invalid_filter(
+                    source,
+                    &format!(
+                        "unknown status {value:?} (expected pass, fail, timeout, crash, error, failed or none)"
+                    ),
+                ));
+            }
+            (FilterKey::Path, _) => TermMatcher::Substring(value.to_string()),
+            _ => TermMatcher::Exact(value.to_string()),
+        };
+        Ok(Self {
+            text: text.to_string(),
+            key,
+            matcher,
+        })
+    }
+
+    fn matches(&self, scenario: &ScenarioSelectionEntry) -> bool {
+        let values: Vec<&str> = match self.key {
+            FilterKey::Tag => scenario.meta.tags.iter().map(String::as_str).collect(),
+            FilterKey::Owner => scenario.meta.owner.as_deref().into_iter().collect(),
+            FilterKey::Tier => scenario.meta.tier.as_deref().into_iter().collect(),
+            FilterKey::Name => scenario.name.as_deref().into_iter().collect(),
+            FilterKey::Path => vec![scenario.path.as_str()],
+            FilterKey::Kind => scenario.kind.as_deref().into_iter().collect(),
+            FilterKey::Last => {
+                let TermMatcher::Exact(wanted) = &self.matcher else {
+                    return false;
+                };
+                return last_status_matches(wanted, scenario.last_status);
+            }
+        };
+        values.into_iter().any(|value| match &self.matcher {
+            TermMatcher::Exact(wanted) => value == wanted,
+            TermMatcher::Substring(wanted) => value.contains(wanted.as_str()),
+            TermMatcher::Regex(regex) => regex.is_match(value),
+        })
+    }
+}
+
+fn last_status_matches(wanted: &str, status: Option<ExitStatus>) -> bool {
+    match (wanted, status) {
+        ("none", None) => true,
+        ("failed", Some(status)) => status != ExitStatus::Pass,
+        (_, Some(ExitStatus::Pass)) => wanted == "pass",
+        (_, Some(ExitStatus::Fail)) => wanted == "fail",
+        (_, Some(ExitStatus::Timeout)) => wanted == "timeout",
+        (_, Some(ExitStatus::Crash)) => wanted == "crash",
+        (_, Some(ExitStatus::Error)) => wanted == "error",
+        (_, None) => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => f.write_str("`(`"),
+            Self::Close => f.write_str("`)`"),
+            Self::And => f.write_str("`and`"),
+            Self::Or => f.write_str("`or`"),
+            Self::Not => f.write_str("`not`"),
+            Self::Term(text) => write!(f, "term {text:?}"),
+        }
+    }
+}
+
+/// Plain words without keys, regexes, quotes, parentheses or operators are the
+/// pre-expression `--filter`: one substring of the path, spaces included.
+fn is_legacy_substring(source: &str, tokens: &[Token]) -> bool {
+    !source.contains([':', '~', '"']) && tokens.iter().all(|token| matches!(token, Token::Term(_)))
+}
+
+/// Splits on whitespace and parentheses. Inside a term, quotes group text and
+/// parentheses only end the term when unbalanced, so `name~(a|b)` stays one term.
+fn tokenize(source: &str) -> FozzyResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            continue;
+        }
+        let mut text = String::new();
+        let mut quoted = false;
+        let mut depth = 0usize;
+        while let Some(&c) = chars.peek() {
+            match c {
+                '"' => {
+                    chars.next();
+                    quoted = true;
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '"' {
+                            closed = true;
+                            break;
+                        }
+                        text.push(c);
+                    }
+                    if !closed {
+                        return Err(invalid_filter(source, "unterminated quote"));
+                    }
+                }
+                c if c.is_whitespace() => break,
+                ')' if depth == 0 => break,
+                '(' => {
+                    depth += 1;
+                    text.push(c);
+                    chars.next();
+                }
+                ')' => {
+                    depth -= 1;
+                    text.push(c);
+                    chars.next();
+                }
+                c => {
+                    text.push(c);
+                    chars.next();
+                }
+            }
+        }
+        tokens.push(match text.as_str() {
+            "and" if !quoted => Token::And,
+            "or" if !quoted => Token::Or,
+            "not" if !quoted => Token::Not,
+            _ => Token::Term(text),
+        });
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> FozzyResult<FilterExpr> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> FozzyResult<FilterExpr> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> FozzyResult<FilterExpr> {
+        if self.eat(&Token::Not) {
+            return Ok(FilterExpr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Open) {
+            let expr = self.or()?;
+            if !self.eat(&Token::Close) {
+                return Err(invalid_filter(self.source, "missing `)`"));
+            }
+            return Ok(expr);
+        }
+        match self.tokens.get(self.pos) {
+            Some(Token::Term(text)) => {
+                self.pos += 1;
+                Ok(FilterExpr::Term(FilterTerm::parse(text, self.source)?))
+            }
+            Some(token) => Err(invalid_filter(
+                self.source,
+                &format!("expected a term, found {token}"),
+            )),
+            None => Err(invalid_filter(
+                self.source,
+                "expression ends where a term was expected",
+            )),
+        }
+    }
+}
+
+fn invalid_filter(source: &str, detail: &str) -> FozzyError {
+    FozzyError::InvalidArgument(format!("invalid filter {source:?}: {detail}"))
+}
+
+#[cfg(test)]
+#[path = "scenario_filter/tests.rs"]
+mod tests;
@@ -0,0 +1,185 @@
+use super::*;
+
+fn entry(path: &str, name: &str, kind: &str, meta: ScenarioMeta) -> ScenarioSelectionEntry {
+    ScenarioSelectionEntry {
+        path: path.to_string(),
+        name: Some(name.to_string()),
+        kind: Some(kind.to_string()),
+        meta,
+        last_status: None,
+        selected: true,
+        reason: None,
+    }
+}
+
+fn tagged(tags: &[&str], owner: Option<&str>) -> ScenarioMeta {
+    ScenarioMeta {
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        owner: owner.map(str::to_string),
+        tier: None,
+        timeout: None,
+        retries: None,
+    }
+}
+
+fn filter(source: &str) -> ScenarioFilter {
+    source.parse().expect("filter parses")
+}
+
+#[test]
+fn terms_match_metadata_kind_name_and_path() {
+    let smoke = entry(
+        "tests/checkout.fozzy.json",
+        "checkout eu",
+        "steps",
+        tagged(&["smoke"], Some("payments")),
+    );
+    let slow = entry(
+        "tests/dist/raft.fozzy.json",
+        "raft",
+        "distributed",
+        tagged(&["smoke", "slow"], None),
+    );
+
+    let expr = filter("tag:smoke and not tag:slow");
+    assert!(expr.matches(&smoke));
+    assert!(!expr.matches(&slow));
+    assert!(filter("owner:payments").matches(&smoke));
+    assert!(!filter("owner:payments").matches(&slow));
+    assert!(filter("kind:distributed").matches(&slow));
+    assert!(filter(r#"name~"checkout (eu|us)""#).matches(&smoke));
+    assert!(!filter("name~^raft$").matches(&smoke));
+    assert!(filter("dist").matches(&slow));
+    assert!(filter("path:checkout").matches(&smoke));
+    assert!(!filter("tag:smo").matches(&smoke));
+    assert!(filter("").matches(&slow));
+}
+
+#[test]
+fn plain_word_filters_keep_whole_string_path_substring_matching() {
+    let spaced = entry(
+        "tests/login flow.fozzy.json",
+        "login",
+        "steps",
+        tagged(&[], None),
+    );
+    let other = entry(
+        "tests/flow login.fozzy.json",
+        "login",
+        "steps",
+        tagged(&[], None),
+    );
+    let legacy = filter("login flow");
+    assert!(legacy.matches(&spaced));
+    assert!(!legacy.matches(&other));
+    assert_eq!(legacy.explain(&spaced), "login flow[yes]");
+    // Any operator turns the input back into an expression of separate terms.
+    assert!(filter("login and flow").matches(&other));
+    assert!(filter("path:login and path:flow").matches(&other));
+}
+
+#[test]
+fn not_binds_tighter_than_and_which_binds_tighter_than_or() {
+    let both = entry("a", "a", "steps", tagged(&["x", "y"], None));
+    let only_z = entry("b", "b", "steps", tagged(&["z"], None));
+
+    assert!(filter("tag:z or tag:x and not tag:y").matches(&only_z));
+    assert!(!filter("tag:z or tag:x and not tag:y").matches(&both));
+    assert!(!filter("(tag:z or tag:x) and not tag:y").matches(&both));
+    assert!(filter("not (tag:x and tag:z)").matches(&both));
+}
+
+#[test]
+fn last_status_terms_use_recorded_history() {
+    let mut scenario = entry("a", "a", "steps", ScenarioMeta::default());
+    assert!(filter("last:none").matches(&scenario));
+    assert!(!filter("last:failed").matches(&scenario));
+
+    scenario.last_status = Some(ExitStatus::Timeout);
+    assert!(filter("last:failed").matches(&scenario));
+    assert!(filter("last:timeout").matches(&scenario));
+    assert!(!filter("last:pass or last:none").matches(&scenario));
+
+    scenario.last_status = Some(ExitStatus::Pass);
+    assert!(!filter("last:failed").matches(&scenario));
+}
+
+#[test]
+fn explain_marks_each_term_and_keeps_grouping() {
+    let scenario = entry("a", "a", "steps", tagged(&["smoke"], None));
+    assert_eq!(
+        filter("tag:smoke and not (tag:slow or owner:payments)").explain(&scenario),
+        "tag:smoke[yes] and not (tag:slow[no] or owner:payments[no])"
+    );
+    assert_eq!(
+        filter("(tag:smoke or tag:slow) and kind:steps").explain(&scenario),
+        "(tag:smoke[yes] or tag:slow[no]) and kind:steps[yes]"
+    );
+}
+
+#[test]
+fn malformed_filters_are_invalid_arguments() {
+    for source in [
+        "tag:smoke and",
+        "(tag:smoke",
+        "tag:smoke)",
+        "tag:smoke tag:slow",
+        "colour:red",
+        "kind:lambda",
+        "last:flaky",
+        "name~(",
+        "tag:",
+    ] {
+        let err = source
+            .parse::<ScenarioFilter>()
+            .expect_err("filter should be rejected");
+        assert!(
+            matches!(err, FozzyError::InvalidArgument(_)),
+            "{source}: {err}"
+        );
+    }
+}
+
+#[test]
+fn selection_reads_metadata_and_rejects_bad_timeouts() {
+    let dir = std::env::temp_dir().join(format!("fozzy-filter-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let tagged = dir.join("a.fozzy.json");
+    std::fs::write(
+        &tagged,
+        r#"{"version":1,"name":"a","tags":["smoke"],"owner":"payments","tier":"1","timeout":"5s","steps":[]}"#,
+    )
+    .expect("write scenario");
+    let broken = dir.join("b.fozzy.json");
+    std::fs::write(&broken, b"not json").expect("write scenario");
+
+    let report = select_scenarios(
+        &[tagged.clone(), broken.clone()],
+        Some("tag:smoke"),
+        &dir.join("runs"),
+    )
+    .expect("select");
+    assert_eq!((report.total, report.selected), (2, 1));
+    assert_eq!(report.selected_paths(), vec![tagged.clone()]);
+    assert_eq!(report.scenarios[0].kind.as_deref(), Some("steps"));
+    assert_eq!(report.scenarios[0].meta.tier.as_deref(), Some("1"));
+    assert_eq!(
+        ScenarioMeta::load(&tagged)
+            .expect("meta")
+            .timeout_duration()
+            .expect("timeout"),
+        Some(Duration::from_secs(5))
+    );
+    assert!(report.scenarios[1].kind.is_none());
+
+    std::fs::write(
+        &tagged,
+        r#"{"version":1,"name":"a","timeout":"soon","steps":[]}"#,
+    )
+    .expect("write scenario");
+    let err = select_scenarios(&[tagged], None, &dir.join("runs")).expect_err("bad timeout");
+    assert!(
+        err.to_string().contains("invalid scenario timeout"),
+        "{err}"
+    );
+}
@@ -36,9 +36,24 @@ pub fn explore(
     let mut scenario = load_explore_scenario(&scenario_path, opt.nodes)?;
     apply_faults_preset(&mut scenario, opt.faults.as_deref())?;
     apply_checker_override(&mut scenario, opt.checker.as_deref())?;
+    let mut stream = crate::ReporterEventStream::for_reporter(opt.reporter, &run_id);
+    let scenario_label = scenario_path.as_path().display().to_string();
+    stream.run_started(RunMode::Explore, seed, &started_at)?;
+    stream.scenario_started(&scenario_label, None, seed)?;
     let (status, findings, events, delivered, decisions) =
         run_explore_inner(&scenario, seed, opt.schedule, opt.steps, opt.time)?;
     let _ = delivered;
+    stream.scenario_finished(
+        crate::ScenarioEvents {
+            path: &scenario_label,
+            iteration: None,
+            seed,
+            events: &events,
+        },
+        status,
+        started.elapsed(),
+        &findings,
+    )?;
     let memory_report: Option<MemoryRunReport> = None;
 
     let finished_at = wall_time_iso_utc();
@@ -62,6 +77,7 @@ pub fn explore(
         tests: None,
         memory: memory_report.as_ref().map(|m| m.summary.clone()),
         findings: findings.clone(),
+        retries: Vec::new(),
     };
     let mut profile_trace = TraceFile::new_explore(
         ExploreTrace {
@@ -81,18 +97,7 @@ pub fn explore(
         summary.findings = crate::collapse_findings(summary.findings.clone());
     }
 
-    if matches!(opt.reporter, crate::Reporter::Junit) {
-        std::fs::write(
-            artifacts_dir.join("junit.xml"),
-            crate::render_junit_xml(&summary),
-        )?;
-    }
-    if matches!(opt.reporter, crate::Reporter::Html) {
-        std::fs::write(
-            artifacts_dir.join("report.html"),
-            crate::render_html(&summary),
-        )?;
-    }
+    write_reporter_artifacts(&summary, &artifacts_dir, opt.reporter)?;
 
     let should_record = opt.record_trace_to.is_some() || status != ExitStatus::Pass;
     if should_record {
@@ -113,7 +118,10 @@ pub fn explore(
             summary.clone(),
         );
         trace.memory = memory_report.as_ref().map(|m| m.to_trace());
-        crate::write_trace_to_target(&trace, &out)?;
+        if let Some(redactor) = crate::Redactor::for_record(config)? {
+            redactor.redact_trace(&mut trace)?;
+        }
+        crate::write_trace_to_target(&trace, &out, config.trace_encoding)?;
     }
     let emit_heavy = should_emit_heavy_artifacts(status, should_record)
         || should_emit_full_profile(opt.profile_capture);
@@ -148,8 +156,12 @@ pub fn explore(
         &artifacts_dir,
         profile_metadata.as_ref(),
     )?;
+    stream.run_finished(&summary)?;
 
-    Ok(crate::RunResult { summary })
+    Ok(crate::RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
 }
 
 pub fn replay_explore_trace(
@@ -210,6 +222,7 @@ pub fn replay_explore_trace(
         tests: None,
         memory: trace.memory.as_ref().map(|m| m.summary.clone()),
         findings,
+        retries: Vec::new(),
     };
     let mut profile_trace = TraceFile::new_explore(
         explore.clone(),
@@ -257,16 +270,19 @@ pub fn replay_explore_trace(
         profile_metadata.as_ref(),
     )?;
 
-    Ok(crate::RunResult { summary })
+    Ok(crate::RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
 }
 
 pub fn shrink_explore_trace(
-    _config: &Config,
+    config: &Config,
     trace_path: crate::TracePath,
     opt: &crate::ShrinkOptions,
 ) -> FozzyResult<crate::ShrinkResult> {
     let trace = TraceFile::read_json(trace_path.as_path())?;
-    let target_status = trace.summary.status;
+    let target = crate::ShrinkTarget::from_trace(&trace, opt)?;
     let Some(explore) = trace.explore.as_ref() else {
         return Err(FozzyError::Trace("not an explore trace".to_string()));
     };
@@ -290,22 +306,30 @@ pub fn shrink_explore_trace(
             let mut improved = false;
             let mut i = 0usize;
             while i < candidate.len() && Instant::now() < deadline {
-                let mut trial = candidate.clone();
-                let end = (i + chunk).min(trial.len());
-                trial.drain(i..end);
-                if trial.is_empty() {
-                    i += chunk;
-                    continue;
-                }
-
-                let (status, _findings, _events, _delivered, _decisions) =
-                    run_explore_replay_inner(&explore.scenario, seed, explore.schedule, &trial)?;
-                if crate::shrink_status_matches(target_status, status) {
-                    candidate = trial;
-                    improved = true;
-                    continue;
-                }
-                i += chunk;
+                let starts: Vec<usize> = (i..candidate.len()).step_by(chunk).collect();
+                let accepted = target.first_accepted(&starts, Some(deadline), |&start| {
+                    let mut trial = candidate.clone();
+                    let end = (start + chunk).min(trial.len());
+                    trial.drain(start..end);
+                    if trial.is_empty() {
+                        return Ok(None);
+                    }
+                    let (status, findings, _events, _delivered, _decisions) =
+                        run_explore_replay_inner(
+                            &explore.scenario,
+                            seed,
+                            explore.schedule,
+                            &trial,
+                        )?;
+                    Ok(target.matches(status, &findings).then_some(trial))
+                })?;
+                let Some((pos, trial)) = accepted else {
+                    break;
+                };
+                target.record("schedule", "decisions", candidate.len(), trial.len());
+                i = starts[pos];
+                candidate = trial;
+                improved = true;
             }
 
             if !improved {
@@ -326,37 +350,43 @@ pub fn shrink_explore_trace(
             let mut improved = false;
             let mut i = 0usize;
             while i < steps.len() && Instant::now() < deadline {
-                let end = (i + chunk).min(steps.len());
-                if !steps[i..end].iter().all(shrinkable_setup_step) {
-                    i += chunk;
-                    continue;
-                }
-                let mut trial = steps.clone();
-                trial.drain(i..end);
-                if trial.is_empty() {
-                    i += chunk;
-                    continue;
-                }
-                let trial_scenario = ScenarioV1Explore {
-                    version: shrunk_scenario.version,
-                    name: shrunk_scenario.name.clone(),
-                    nodes: shrunk_scenario.nodes.clone(),
-                    steps: trial.clone(),
-                    invariants: shrunk_scenario.invariants.clone(),
+                let starts: Vec<usize> = (i..steps.len())
+                    .step_by(chunk)
+                    .filter(|&start| {
+                        steps[start..(start + chunk).min(steps.len())]
+                            .iter()
+                            .all(shrinkable_setup_step)
+                    })
+                    .collect();
+                let accepted = target.first_accepted(&starts, Some(deadline), |&start| {
+                    let mut trial = steps.clone();
+                    trial.drain(start..(start + chunk).min(steps.len()));
+                    if trial.is_empty() {
+                        return Ok(None);
+                    }
+                    let trial_scenario = ScenarioV1Explore {
+                        version: shrunk_scenario.version,
+                        name: shrunk_scenario.name.clone(),
+                        nodes: shrunk_scenario.nodes.clone(),
+                        steps: trial.clone(),
+                        invariants: shrunk_scenario.invariants.clone(),
+                    };
+                    let (status, findings, _events, _delivered, _decisions) = run_explore_inner(
+                        &trial_scenario,
+                        seed,
+                        explore.schedule,
+                        None,
+                        Some(shrink_trial_duration()),
+                    )?;
+                    Ok(target.matches(status, &findings).then_some(trial))
+                })?;
+                let Some((pos, trial)) = accepted else {
+                    break;
                 };
-                let (status, _findings, _events, _delivered, _decisions) = run_explore_inner(
-                    &trial_scenario,
-                    seed,
-                    explore.schedule,
-                    None,
-                    Some(shrink_trial_duration()),
-                )?;
-                if crate::shrink_status_matches(target_status, status) {
-                    steps = trial;
-                    improved = true;
-                    continue;
-                }
-                i += chunk;
+                target.record("steps", "steps", steps.len(), trial.len());
+                i = starts[pos];
+                steps = trial;
+                improved = true;
             }
             if !improved {
                 if chunk == 1 {
@@ -385,7 +415,7 @@ pub fn shrink_explore_trace(
             None,
             Some(shrink_trial_duration()),
         )?;
-        if crate::shrink_status_matches(target_status, trial.0) {
+        if target.matches(trial.0, &trial.1) {
             trial
         } else {
             run_explore_replay_inner(&explore.scenario, seed, explore.schedule, &best_decisions)?
@@ -413,6 +443,7 @@ pub fn shrink_explore_trace(
         tests: None,
         memory: trace.memory.as_ref().map(|m| m.summary.clone()),
         findings,
+        retries: Vec::new(),
     };
 
     let out_explore = if opt.minimize == crate::ShrinkMinimize::All {
@@ -426,15 +457,22 @@ pub fn shrink_explore_trace(
     };
 
     let trace_out = TraceFile::new_explore(out_explore, out_decisions, events, summary.clone());
-    trace_out.write_json(&out_path).map_err(|err| {
-        FozzyError::Trace(format!(
-            "failed to write shrunk explore trace to {}: {err}",
-            out_path.display()
-        ))
-    })?;
+    trace_out
+        .write(&out_path, config.trace_encoding)
+        .map_err(|err| {
+            FozzyError::Trace(format!(
+                "failed to write shrunk explore trace to {}: {err}",
+                out_path.display()
+            ))
+        })?;
 
+    let lineage = target.write_lineage(trace_path.as_path(), &out_path)?;
     Ok(crate::ShrinkResult {
         out_trace_path: out_path.to_string_lossy().to_string(),
-        result: crate::RunResult { summary },
+        result: crate::RunResult {
+            summary,
+            warnings: Vec::new(),
+        },
+        lineage,
     })
 }
@@ -3,28 +3,52 @@
 //! This is intentionally self-contained so fuzz targets can evolve without
 //! entangling the core scenario runner.
 
+#[path = "fuzz/command.rs"]
+mod command;
 #[path = "fuzz/corpus.rs"]
 mod corpus;
 #[path = "fuzz/exec.rs"]
 mod exec;
+#[path = "fuzz/grammar.rs"]
+mod grammar;
+#[path = "fuzz/openapi.rs"]
+mod openapi;
+#[path = "fuzz/property.rs"]
+mod property;
 #[path = "fuzz/report.rs"]
 mod report;
 #[path = "fuzz/run.rs"]
 mod run;
+#[path = "fuzz/triage.rs"]
+mod triage;
 #[path = "fuzz/types.rs"]
 mod types;
 #[path = "fuzz/util.rs"]
 mod util;
 
+pub use command::{FuzzCommand, fuzz_command};
+pub use property::GeneratorSpec;
 pub use run::{fuzz, replay_fuzz_trace, shrink_fuzz_trace};
+pub use triage::{
+    CrashSignature, FuzzTriageBucket, FuzzTriageOptions, FuzzTriageReport, triage_crashes,
+};
 pub use types::{FuzzCoverageStats, FuzzMode, FuzzOptions, FuzzTarget, FuzzTrace};
 
 pub(crate) use corpus::{
-    crash_trace_output_path, load_corpus, persist_corpus_input, persist_crash_input,
-    persist_crash_min_input,
+    crash_trace_output_path, load_corpus, load_input_trees, persist_corpus_input,
+    persist_crash_input, persist_crash_min_input, persist_input_tree,
+};
+pub(crate) use exec::{
+    execute_target, fuzz_exec_memory, fuzz_trace_memory_options, input_identity_edge, target_string,
+};
+pub(crate) use grammar::{DerivationNode, load_grammar_scenario, shrink_grammar_input};
+pub(crate) use openapi::{execute_openapi_target, write_openapi_scenarios};
+pub(crate) use property::{
+    bindings_json, encode_bindings, load_property_scenario, sample_regex, shrink_property_input,
+    substitute,
 };
-pub(crate) use exec::{execute_target, fuzz_exec_memory, fuzz_trace_memory_options, target_string};
 pub(crate) use report::{heap_budget_policy, should_emit_heavy_artifacts};
+pub(crate) use triage::normalize_crash_message;
 pub(crate) use util::{
     gen_seed, hex_decode, minimize_input, mutate_bytes, rng_from_seed, seed_from_input, stable_edge,
 };
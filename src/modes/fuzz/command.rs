@@ -0,0 +1,72 @@
+use clap::Subcommand;
+use std::path::PathBuf;
+
+use crate::{Config, FozzyResult};
+
+use super::{FuzzTarget, FuzzTriageOptions, triage_crashes, write_openapi_scenarios};
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum FuzzCommand {
+    /// Replay persisted crash inputs, bucket them by signature, and minimize one reproducer per bucket.
+    Triage {
+        /// Crash directory (usually `<corpus>/crashes`).
+        crash_dir: PathBuf,
+        /// Fuzz target the crashes were found against.
+        #[arg(long)]
+        target: String,
+        /// Maximum input size accepted while minimizing reproducers.
+        #[arg(long, default_value_t = 4096)]
+        max_input: usize,
+        /// Output directory for `triage.json` and per-bucket reproducers (default: `<crash-dir>/triage`).
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Skip `minimize_input` and keep the smallest original input per bucket.
+        #[arg(long)]
+        no_minimize: bool,
+    },
+    /// Emit one starter `http_request` scenario per operation of an OpenAPI 3 spec.
+    OpenapiScenarios {
+        /// OpenAPI 3 document (JSON).
+        spec: PathBuf,
+        /// Output directory for `<operation>.fozzy.json` files.
+        #[arg(long, default_value = "tests/openapi")]
+        out: PathBuf,
+        /// Base URL for requests (default: the spec's `servers[0].url`).
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Overwrite scenarios that already exist.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+pub fn fuzz_command(config: &Config, command: &FuzzCommand) -> FozzyResult<serde_json::Value> {
+    match command {
+        FuzzCommand::Triage {
+            crash_dir,
+            target,
+            max_input,
+            out,
+            no_minimize,
+        } => {
+            let target: FuzzTarget = target.parse()?;
+            let report = triage_crashes(
+                config,
+                &target,
+                &FuzzTriageOptions {
+                    crash_dir: crash_dir.clone(),
+                    out_dir: out.clone(),
+                    max_input_bytes: *max_input,
+                    minimize: !*no_minimize,
+                },
+            )?;
+            Ok(serde_json::to_value(report)?)
+        }
+        FuzzCommand::OpenapiScenarios {
+            spec,
+            out,
+            base_url,
+            force,
+        } => write_openapi_scenarios(spec, out, base_url.as_deref(), *force),
+    }
+}
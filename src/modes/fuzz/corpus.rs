@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use crate::FozzyResult;
 
+use super::DerivationNode;
+
 pub(crate) fn load_corpus(dir: &Path) -> FozzyResult<Vec<Vec<u8>>> {
     let mut out = Vec::new();
     if !dir.exists() {
@@ -30,6 +32,44 @@ pub(crate) fn persist_corpus_input(dir: &Path, bytes: &[u8]) -> FozzyResult<Path
     Ok(out)
 }
 
+/// Stores the derivation tree of a grammar input as `trees/<blake3>.json`, keyed
+/// by the same hash as the input bytes.
+pub(crate) fn persist_input_tree(
+    dir: &Path,
+    bytes: &[u8],
+    tree: &DerivationNode,
+) -> FozzyResult<PathBuf> {
+    let trees = dir.join("trees");
+    std::fs::create_dir_all(&trees)?;
+    let out = trees.join(format!("{}.json", blake3::hash(bytes).to_hex()));
+    if !out.exists() {
+        std::fs::write(&out, serde_json::to_vec(tree)?)?;
+    }
+    Ok(out)
+}
+
+/// Loads stored derivation trees; unreadable entries are skipped.
+pub(crate) fn load_input_trees(dir: &Path) -> FozzyResult<Vec<DerivationNode>> {
+    let trees = dir.join("trees");
+    let mut out = Vec::new();
+    if !trees.is_dir() {
+        return Ok(out);
+    }
+    let mut paths = std::fs::read_dir(&trees)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        if let Ok(tree) = serde_json::from_slice(&std::fs::read(path)?) {
+            out.push(tree);
+        }
+    }
+    Ok(out)
+}
+
 pub(crate) fn persist_crash_input(dir: &Path, bytes: &[u8]) -> FozzyResult<PathBuf> {
     let name = format!("crash-{}.bin", blake3::hash(bytes).to_hex());
     let out = dir.join("crashes").join(name);
@@ -7,7 +7,10 @@ use crate::{
     ScenarioPath, TraceEvent, TraceFile, wall_time_iso_utc,
 };
 
-use super::{FuzzTarget, seed_from_input, stable_edge};
+use super::{
+    FuzzTarget, bindings_json, execute_openapi_target, load_grammar_scenario,
+    load_property_scenario, seed_from_input, stable_edge,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct FuzzExec {
@@ -34,6 +37,7 @@ pub(crate) fn execute_target(
         FuzzTarget::Scenario { path } => {
             execute_scenario_target(config, path, input, scenario_memory)
         }
+        FuzzTarget::OpenApi { spec } => execute_openapi_target(config, spec, input),
     }?;
     exec.started_at = started_at;
     exec.finished_at = wall_time_iso_utc();
@@ -46,9 +50,16 @@ pub(crate) fn execute_target(
 pub(crate) fn target_string(target: &FuzzTarget) -> String {
     match target {
         FuzzTarget::Scenario { path } => format!("scenario:{}", path.display()),
+        FuzzTarget::OpenApi { spec } => format!("openapi:{}", spec.display()),
     }
 }
 
+/// Edge unique to each distinct input. It keeps every new input interesting to the
+/// fuzz loop, but carries no behavior, so corpus distillation ignores it.
+pub(crate) fn input_identity_edge(input: &[u8]) -> u64 {
+    stable_edge(&format!("scenario_seed:{}", seed_from_input(input)))
+}
+
 pub(crate) fn fuzz_exec_memory(
     exec_memory: Option<&crate::MemoryTrace>,
     harness_memory: Option<&crate::MemoryRunReport>,
@@ -85,7 +96,22 @@ fn execute_scenario_target(
 ) -> FozzyResult<FuzzExec> {
     let seed = seed_from_input(input);
     let scenario_path = ScenarioPath::new(path.to_path_buf());
-    let parsed = crate::Scenario::load_file(&scenario_path)?;
+    let mut bindings_event = None;
+    let parsed = match load_property_scenario(path)? {
+        Some(property) => {
+            let bindings = property.bindings_from_input(input);
+            bindings_event = Some(TraceEvent {
+                time_ms: 0,
+                name: "property_bindings".to_string(),
+                fields: bindings_json(&bindings),
+            });
+            property.instantiate(&bindings)?
+        }
+        None => match load_grammar_scenario(path)? {
+            Some(grammar) => grammar.instantiate(input)?,
+            None => crate::Scenario::load_file(&scenario_path)?,
+        },
+    };
     let parsed = match parsed {
         ScenarioFile::Steps(steps) => ScenarioTarget::Steps(steps),
         ScenarioFile::Distributed(distributed) => {
@@ -93,7 +119,7 @@ fn execute_scenario_target(
         }
         ScenarioFile::Suites(_) => ScenarioTarget::Suites,
     };
-    let exec = match parsed {
+    let mut exec = match parsed {
         ScenarioTarget::Steps(scenario) => {
             let run =
                 crate::run_embedded_steps_for_fuzz(&scenario, path, seed, scenario_memory.clone())?;
@@ -134,10 +160,17 @@ fn execute_scenario_target(
         }
     };
 
+    if let Some(event) = bindings_event {
+        exec.events.insert(0, event);
+    }
+
     let mut coverage = BTreeSet::new();
     coverage.insert(stable_edge(&format!("scenario_path:{}", path.display())));
     coverage.insert(stable_edge(&format!("scenario_status:{:?}", exec.status)));
-    coverage.insert(stable_edge(&format!("scenario_seed:{seed}")));
+    coverage.insert(input_identity_edge(input));
+    for event in &exec.events {
+        coverage.insert(stable_edge(&format!("scenario_event:{}", event.name)));
+    }
     for finding in &exec.findings {
         coverage.insert(stable_edge(&format!(
             "scenario_finding:{}:{}",
@@ -0,0 +1,909 @@
+//! Grammar-based input generation from EBNF or JSON grammar files.
+//!
+//! A scenario opts in with a top-level `grammar` block naming the grammar file
+//! and the scenario variable the generated sentence is bound to. The fuzz loop
+//! mutates derivation trees rather than bytes, and shrinking replaces subtrees.
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::RngCore as _;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::{Config, FozzyError, FozzyResult, MemoryOptions, ScenarioFile};
+
+use super::{FuzzTarget, execute_target, substitute};
+
+const DEFAULT_MAX_DEPTH: usize = 10;
+/// Upper bound on candidate executions for one shrink.
+const MAX_SHRINK_ATTEMPTS: usize = 2_000;
+/// Inputs longer than this are not parsed back into trees (byte shrinking applies).
+const MAX_PARSE_CHARS: usize = 1_024;
+const MAX_PARSE_WORK: usize = 200_000;
+
+#[derive(Debug, Clone, Deserialize)]
+struct GrammarBlock {
+    file: PathBuf,
+    #[serde(default)]
+    start: Option<String>,
+    #[serde(default = "default_bind")]
+    bind: String,
+    #[serde(default = "default_max_depth")]
+    max_depth: usize,
+}
+
+fn default_bind() -> String {
+    "input".to_string()
+}
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Symbol {
+    Text(String),
+    Range(char, char),
+    Rule(String),
+}
+
+/// Rules in definition order, each with its alternatives.
+type RuleList = Vec<(String, Vec<Vec<Symbol>>)>;
+
+/// Derivation tree node. Serialized into `<corpus>/trees/` next to the sentence bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DerivationNode {
+    Rule {
+        rule: String,
+        alt: usize,
+        children: Vec<DerivationNode>,
+    },
+    Text {
+        text: String,
+    },
+}
+
+impl DerivationNode {
+    pub(crate) fn text(&self) -> String {
+        let mut out = String::new();
+        self.write_text(&mut out);
+        out
+    }
+
+    fn write_text(&self, out: &mut String) {
+        match self {
+            Self::Text { text } => out.push_str(text),
+            Self::Rule { children, .. } => {
+                for child in children {
+                    child.write_text(out);
+                }
+            }
+        }
+    }
+
+    fn text_len(&self) -> usize {
+        match self {
+            Self::Text { text } => text.len(),
+            Self::Rule { children, .. } => children.iter().map(Self::text_len).sum(),
+        }
+    }
+
+    fn rule_name(&self) -> Option<&str> {
+        match self {
+            Self::Rule { rule, .. } => Some(rule),
+            Self::Text { .. } => None,
+        }
+    }
+
+    fn at(&self, path: &[usize]) -> &DerivationNode {
+        match (path.split_first(), self) {
+            (Some((index, rest)), Self::Rule { children, .. }) => children[*index].at(rest),
+            _ => self,
+        }
+    }
+
+    fn replaced(&self, path: &[usize], replacement: &DerivationNode) -> DerivationNode {
+        let mut out = self.clone();
+        let mut cursor = &mut out;
+        for index in path {
+            let Self::Rule { children, .. } = cursor else {
+                return out;
+            };
+            cursor = &mut children[*index];
+        }
+        *cursor = replacement.clone();
+        out
+    }
+
+    /// Pre-order `(path, depth)` of every rule node.
+    fn rule_paths(&self) -> Vec<(Vec<usize>, usize)> {
+        fn walk(node: &DerivationNode, path: &mut Vec<usize>, out: &mut Vec<(Vec<usize>, usize)>) {
+            if let DerivationNode::Rule { children, .. } = node {
+                out.push((path.clone(), path.len()));
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    walk(child, path, out);
+                    path.pop();
+                }
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Grammar {
+    rules: BTreeMap<String, Vec<Vec<Symbol>>>,
+    start: String,
+    max_depth: usize,
+    /// Smallest derivation size per rule and the alternative that achieves it.
+    minimal: BTreeMap<String, (u64, usize)>,
+}
+
+impl Grammar {
+    fn new(ordered: RuleList, start: Option<String>, max_depth: usize) -> FozzyResult<Self> {
+        let Some(first) = ordered.first().map(|(name, _)| name.clone()) else {
+            return Err(FozzyError::Scenario("grammar defines no rules".to_string()));
+        };
+        let start = start.unwrap_or(first);
+        let mut rules = BTreeMap::<String, Vec<Vec<Symbol>>>::new();
+        for (name, alts) in ordered {
+            rules.entry(name).or_default().extend(alts);
+        }
+        if !rules.contains_key(&start) {
+            return Err(FozzyError::Scenario(format!(
+                "grammar start rule {start:?} is not defined"
+            )));
+        }
+        for (name, alts) in &rules {
+            for symbol in alts.iter().flatten() {
+                if let Symbol::Rule(reference) = symbol
+                    && !rules.contains_key(reference)
+                {
+                    return Err(FozzyError::Scenario(format!(
+                        "grammar rule {name:?} references undefined rule {reference:?}"
+                    )));
+                }
+            }
+        }
+
+        let mut minimal = BTreeMap::<String, (u64, usize)>::new();
+        loop {
+            let mut changed = false;
+            for (name, alts) in &rules {
+                for (index, alt) in alts.iter().enumerate() {
+                    let cost = alt.iter().try_fold(1u64, |acc, symbol| {
+                        let cost = match symbol {
+                            Symbol::Text(text) => Some(text.chars().count() as u64),
+                            Symbol::Range(..) => Some(1),
+                            Symbol::Rule(reference) => minimal.get(reference).map(|(c, _)| *c),
+                        }?;
+                        Some(acc.saturating_add(cost))
+                    });
+                    if let Some(cost) = cost
+                        && minimal.get(name).is_none_or(|(best, _)| cost < *best)
+                    {
+                        minimal.insert(name.clone(), (cost, index));
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        if let Some(name) = rules.keys().find(|name| !minimal.contains_key(*name)) {
+            return Err(FozzyError::Scenario(format!(
+                "grammar rule {name:?} never derives a finite string"
+            )));
+        }
+
+        Ok(Self {
+            rules,
+            start,
+            max_depth,
+            minimal,
+        })
+    }
+
+    pub(crate) fn generate(&self, rng: &mut ChaCha20Rng) -> DerivationNode {
+        self.derive(&self.start, 0, Some(rng))
+    }
+
+    /// Random derivation below `max_depth`; the smallest alternative beyond it.
+    /// Without an RNG this builds the minimal derivation of `rule`.
+    fn derive(
+        &self,
+        rule: &str,
+        depth: usize,
+        mut rng: Option<&mut ChaCha20Rng>,
+    ) -> DerivationNode {
+        let alts = &self.rules[rule];
+        let alt = match rng.as_deref_mut() {
+            Some(rng) if depth < self.max_depth => (rng.next_u64() % alts.len() as u64) as usize,
+            _ => self.minimal[rule].1,
+        };
+        let children = alts[alt]
+            .iter()
+            .map(|symbol| match symbol {
+                Symbol::Text(text) => DerivationNode::Text { text: text.clone() },
+                Symbol::Range(lo, hi) => {
+                    let span = u64::from(*hi) - u64::from(*lo) + 1;
+                    let offset = rng.as_deref_mut().map_or(0, |rng| rng.next_u64() % span);
+                    let ch = char::from_u32(u32::from(*lo) + offset as u32).unwrap_or(*lo);
+                    DerivationNode::Text {
+                        text: ch.to_string(),
+                    }
+                }
+                Symbol::Rule(reference) => self.derive(reference, depth + 1, rng.as_deref_mut()),
+            })
+            .collect();
+        DerivationNode::Rule {
+            rule: rule.to_string(),
+            alt,
+            children,
+        }
+    }
+
+    /// Regenerates, splices (from `donors`), or hoists one random subtree.
+    pub(crate) fn mutate(
+        &self,
+        tree: &DerivationNode,
+        rng: &mut ChaCha20Rng,
+        donors: &[DerivationNode],
+    ) -> DerivationNode {
+        let paths = tree.rule_paths();
+        if paths.is_empty() {
+            return self.generate(rng);
+        }
+        let (path, depth) = &paths[(rng.next_u64() % paths.len() as u64) as usize];
+        let node = tree.at(path);
+        let rule = node.rule_name().unwrap_or(&self.start);
+        let strategy = rng.next_u64() % 10;
+        let replacement = if strategy < 2 {
+            let candidates = donors
+                .iter()
+                .flat_map(|donor| {
+                    donor
+                        .rule_paths()
+                        .into_iter()
+                        .map(move |(p, _)| donor.at(&p))
+                        .filter(|candidate| candidate.rule_name() == Some(rule))
+                })
+                .collect::<Vec<_>>();
+            (!candidates.is_empty())
+                .then(|| candidates[(rng.next_u64() % candidates.len() as u64) as usize].clone())
+        } else if strategy < 3 {
+            let nested = same_rule_descendants(node, rule);
+            (!nested.is_empty())
+                .then(|| nested[(rng.next_u64() % nested.len() as u64) as usize].clone())
+        } else {
+            None
+        };
+        let replacement = replacement.unwrap_or_else(|| self.derive(rule, *depth, Some(rng)));
+        tree.replaced(path, &replacement)
+    }
+
+    /// True when `tree` is a derivation of this grammar's start rule.
+    pub(crate) fn accepts(&self, tree: &DerivationNode) -> bool {
+        tree.rule_name() == Some(self.start.as_str()) && self.check(tree)
+    }
+
+    fn check(&self, node: &DerivationNode) -> bool {
+        let DerivationNode::Rule {
+            rule,
+            alt,
+            children,
+        } = node
+        else {
+            return false;
+        };
+        let Some(symbols) = self.rules.get(rule).and_then(|alts| alts.get(*alt)) else {
+            return false;
+        };
+        symbols.len() == children.len()
+            && symbols
+                .iter()
+                .zip(children)
+                .all(|(symbol, child)| match (symbol, child) {
+                    (Symbol::Text(expected), DerivationNode::Text { text }) => expected == text,
+                    (Symbol::Range(lo, hi), DerivationNode::Text { text }) => {
+                        let mut chars = text.chars();
+                        matches!((chars.next(), chars.next()), (Some(c), None) if (*lo..=*hi).contains(&c))
+                    }
+                    (Symbol::Rule(reference), DerivationNode::Rule { rule, .. }) => {
+                        reference == rule && self.check(child)
+                    }
+                    _ => false,
+                })
+    }
+
+    /// Recovers a derivation for `input` so inputs without a stored tree can still
+    /// shrink structurally. Gives up on long inputs or runaway ambiguity.
+    pub(crate) fn parse(&self, input: &str) -> Option<DerivationNode> {
+        let chars = input.chars().collect::<Vec<_>>();
+        if chars.len() > MAX_PARSE_CHARS {
+            return None;
+        }
+        let mut parser = Parser {
+            grammar: self,
+            input: &chars,
+            memo: HashMap::new(),
+            active: HashSet::new(),
+            work: 0,
+        };
+        let results = parser.parse_rule(&self.start, 0);
+        if parser.work > MAX_PARSE_WORK {
+            return None;
+        }
+        results
+            .into_iter()
+            .find(|(end, _)| *end == chars.len())
+            .map(|(_, node)| node)
+    }
+}
+
+fn same_rule_descendants<'a>(node: &'a DerivationNode, rule: &str) -> Vec<&'a DerivationNode> {
+    node.rule_paths()
+        .into_iter()
+        .skip(1)
+        .map(|(path, _)| node.at(&path))
+        .filter(|candidate| candidate.rule_name() == Some(rule))
+        .collect()
+}
+
+type Parses = Vec<(usize, DerivationNode)>;
+
+/// Memoized all-ends recursive descent. Left recursion is cut (treated as no
+/// match), which can miss some parses but never loops.
+struct Parser<'a> {
+    grammar: &'a Grammar,
+    input: &'a [char],
+    memo: HashMap<(&'a str, usize), Parses>,
+    active: HashSet<(&'a str, usize)>,
+    work: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_rule(&mut self, rule: &'a str, pos: usize) -> Parses {
+        if let Some(hit) = self.memo.get(&(rule, pos)) {
+            return hit.clone();
+        }
+        if self.work > MAX_PARSE_WORK || !self.active.insert((rule, pos)) {
+            return Vec::new();
+        }
+        let grammar = self.grammar;
+        let mut out = Parses::new();
+        for (alt, symbols) in grammar.rules[rule].iter().enumerate() {
+            for (end, children) in self.parse_seq(symbols, pos) {
+                if out.iter().all(|(seen, _)| *seen != end) {
+                    out.push((
+                        end,
+                        DerivationNode::Rule {
+                            rule: rule.to_string(),
+                            alt,
+                            children,
+                        },
+                    ));
+                }
+            }
+        }
+        self.active.remove(&(rule, pos));
+        self.memo.insert((rule, pos), out.clone());
+        out
+    }
+
+    fn parse_seq(
+        &mut self,
+        symbols: &'a [Symbol],
+        pos: usize,
+    ) -> Vec<(usize, Vec<DerivationNode>)> {
+        self.work += 1;
+        let Some((first, rest)) = symbols.split_first() else {
+            return vec![(pos, Vec::new())];
+        };
+        if self.work > MAX_PARSE_WORK {
+            return Vec::new();
+        }
+        let mut out = Vec::<(usize, Vec<DerivationNode>)>::new();
+        for (mid, node) in self.parse_symbol(first, pos) {
+            for (end, tail) in self.parse_seq(rest, mid) {
+                if out.iter().any(|(seen, _)| *seen == end) {
+                    continue;
+                }
+                let mut children = Vec::with_capacity(tail.len() + 1);
+                children.push(node.clone());
+                children.extend(tail);
+                out.push((end, children));
+            }
+        }
+        out
+    }
+
+    fn parse_symbol(&mut self, symbol: &'a Symbol, pos: usize) -> Parses {
+        match symbol {
+            Symbol::Text(text) => {
+                let expected = text.chars().collect::<Vec<_>>();
+                let end = pos + expected.len();
+                if self.input.get(pos..end) == Some(expected.as_slice()) {
+                    vec![(end, DerivationNode::Text { text: text.clone() })]
+                } else {
+                    Vec::new()
+                }
+            }
+            Symbol::Range(lo, hi) => match self.input.get(pos) {
+                Some(ch) if (*lo..=*hi).contains(ch) => vec![(
+                    pos + 1,
+                    DerivationNode::Text {
+                        text: ch.to_string(),
+                    },
+                )],
+                _ => Vec::new(),
+            },
+            Symbol::Rule(rule) => self.parse_rule(rule, pos),
+        }
+    }
+}
+
+/// A scenario with a `grammar` block; the remaining document is a template
+/// instantiated with each generated sentence.
+#[derive(Debug, Clone)]
+pub(crate) struct GrammarScenario {
+    pub(crate) grammar: Grammar,
+    bind: String,
+    template: serde_json::Value,
+}
+
+/// Returns `None` when the scenario declares no grammar.
+pub(crate) fn load_grammar_scenario(path: &Path) -> FozzyResult<Option<GrammarScenario>> {
+    let bytes = std::fs::read(path)?;
+    let mut doc: serde_json::Value = serde_json::from_slice(&bytes).map_err(|err| {
+        FozzyError::Scenario(format!(
+            "failed to parse scenario {}: {err}",
+            path.display()
+        ))
+    })?;
+    let Some(raw) = doc.as_object_mut().and_then(|obj| obj.remove("grammar")) else {
+        return Ok(None);
+    };
+    let block: GrammarBlock = serde_json::from_value(raw).map_err(|err| {
+        FozzyError::Scenario(format!(
+            "invalid grammar block in scenario {}: {err}",
+            path.display()
+        ))
+    })?;
+    let grammar_path = path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&block.file);
+    let source = std::fs::read_to_string(&grammar_path).map_err(|err| {
+        FozzyError::Scenario(format!(
+            "failed to read grammar {}: {err}",
+            grammar_path.display()
+        ))
+    })?;
+    let grammar = parse_grammar_source(&grammar_path, &source, block.start, block.max_depth)?;
+    Ok(Some(GrammarScenario {
+        grammar,
+        bind: block.bind,
+        template: doc,
+    }))
+}
+
+impl GrammarScenario {
+    pub(crate) fn instantiate(&self, input: &[u8]) -> FozzyResult<ScenarioFile> {
+        let bindings = BTreeMap::from([(
+            self.bind.clone(),
+            serde_json::Value::String(String::from_utf8_lossy(input).to_string()),
+        )]);
+        let mut doc = self.template.clone();
+        substitute(&mut doc, &bindings);
+        serde_json::from_value(doc).map_err(|err| {
+            FozzyError::Scenario(format!(
+                "grammar scenario does not parse after binding input: {err}"
+            ))
+        })
+    }
+}
+
+/// Greedy subtree shrinking: replace a node with the minimal derivation of its
+/// rule or hoist a nested node of the same rule, keeping any shorter failing
+/// sentence. Returns `None` when no derivation is available for `input`.
+pub(crate) fn shrink_grammar_input(
+    config: &Config,
+    target: &FuzzTarget,
+    scenario: &GrammarScenario,
+    input: &[u8],
+    tree: Option<&DerivationNode>,
+    shrink: &crate::ShrinkTarget,
+    scenario_memory: &MemoryOptions,
+) -> FozzyResult<Option<(Vec<u8>, DerivationNode)>> {
+    let grammar = &scenario.grammar;
+    let tree = match tree {
+        Some(tree) => Some(tree.clone()),
+        None => std::str::from_utf8(input)
+            .ok()
+            .and_then(|text| grammar.parse(text)),
+    };
+    let Some(mut best) = tree else {
+        return Ok(None);
+    };
+    let mut best_len = best.text_len();
+    let mut attempts = 0usize;
+    while attempts < MAX_SHRINK_ATTEMPTS {
+        let mut candidates = Vec::new();
+        for (path, _) in best.rule_paths() {
+            let node = best.at(&path);
+            let Some(rule) = node.rule_name() else {
+                continue;
+            };
+            let node_len = node.text_len();
+            let mut replacements = vec![grammar.derive(rule, 0, None)];
+            replacements.extend(same_rule_descendants(node, rule).into_iter().cloned());
+            for replacement in replacements {
+                let candidate_len = best_len - node_len + replacement.text_len();
+                if candidate_len < best_len {
+                    candidates.push((path.clone(), replacement, candidate_len));
+                }
+            }
+        }
+        candidates.truncate(MAX_SHRINK_ATTEMPTS - attempts);
+        let accepted = shrink.first_accepted(&candidates, None, |(path, replacement, _)| {
+            let candidate = best.replaced(path, replacement);
+            let exec =
+                execute_target(config, target, candidate.text().as_bytes(), scenario_memory)?;
+            Ok(shrink
+                .matches(exec.status, &exec.findings)
+                .then_some(candidate))
+        })?;
+        let Some((pos, candidate)) = accepted else {
+            break;
+        };
+        attempts += pos + 1;
+        let candidate_len = candidates[pos].2;
+        shrink.record("grammar", "bytes", best_len, candidate_len);
+        best = candidate;
+        best_len = candidate_len;
+    }
+    Ok(Some((best.text().into_bytes(), best)))
+}
+
+fn parse_grammar_source(
+    path: &Path,
+    source: &str,
+    start: Option<String>,
+    max_depth: usize,
+) -> FozzyResult<Grammar> {
+    let rules = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+        parse_json_grammar(source)
+    } else {
+        EbnfParser::new(source).and_then(EbnfParser::parse)
+    }
+    .map_err(|err| FozzyError::Scenario(format!("invalid grammar {}: {err}", path.display())))?;
+    let start = start.map(|name| name.trim_matches(['<', '>']).to_string());
+    Grammar::new(rules, start, max_depth)
+}
+
+/// JSON grammars map `<rule>` to expansion strings, where `<name>` references a
+/// rule and everything else is literal text:
+/// `{"<start>": ["<digit>", "<digit><start>"], "<digit>": ["0", "1"]}`.
+fn parse_json_grammar(source: &str) -> Result<RuleList, String> {
+    let doc: BTreeMap<String, Vec<String>> =
+        serde_json::from_str(source).map_err(|err| err.to_string())?;
+    let names = doc
+        .keys()
+        .map(|key| key.trim_matches(['<', '>']).to_string())
+        .collect::<HashSet<_>>();
+    let mut rules = Vec::new();
+    // `<start>` first so it is the default start rule.
+    let mut keys = doc.keys().collect::<Vec<_>>();
+    keys.sort_by_key(|key| *key != "<start>");
+    for key in keys {
+        let alts = doc[key]
+            .iter()
+            .map(|expansion| {
+                let mut symbols = Vec::new();
+                let mut rest = expansion.as_str();
+                while !rest.is_empty() {
+                    let reference = rest.find('<').and_then(|open| {
+                        let close = open + rest[open..].find('>')?;
+                        let name = &rest[open + 1..close];
+                        names.contains(name).then_some((open, close, name))
+                    });
+                    match reference {
+                        Some((open, close, name)) => {
+                            if open > 0 {
+                                symbols.push(Symbol::Text(rest[..open].to_string()));
+                            }
+                            symbols.push(Symbol::Rule(name.to_string()));
+                            rest = &rest[close + 1..];
+                        }
+                        None => {
+                            symbols.push(Symbol::Text(rest.to_string()));
+                            rest = "";
+                        }
+                    }
+                }
+                symbols
+            })
+            .collect();
+        rules.push((key.trim_matches(['<', '>']).to_string(), alts));
+    }
+    Ok(rules)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(String),
+    Define,
+    Alt,
+    End,
+    DotDot,
+    Open(char),
+    Close(char),
+    Postfix(char),
+}
+
+/// EBNF subset: `rule = a "lit" | ( group ) [ optional ] { repeat } "a".."z" ;`
+/// with `::=` accepted for `=`, `<rule>` names, postfix `? * +`, and `(* *)` or `#`
+/// comments. Rule terminators (`;` or `.`) are optional between rules.
+struct EbnfParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    rules: RuleList,
+    fresh: usize,
+}
+
+impl EbnfParser {
+    fn new(source: &str) -> Result<Self, String> {
+        let chars = source.chars().collect::<Vec<_>>();
+        let mut tokens = Vec::new();
+        let mut i = 0usize;
+        while i < chars.len() {
+            let ch = chars[i];
+            match ch {
+                c if c.is_whitespace() || c == ',' => i += 1,
+                '#' => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                '(' if chars.get(i + 1) == Some(&'*') => {
+                    i += 2;
+                    while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == ')') {
+                        i += 1;
+                    }
+                    i += 2;
+                }
+                '"' | '\'' => {
+                    let quote = ch;
+                    let mut text = String::new();
+                    i += 1;
+                    loop {
+                        match chars.get(i) {
+                            None => return Err("unterminated string literal".to_string()),
+                            Some(c) if *c == quote => break,
+                            Some('\\') => {
+                                i += 1;
+                                text.push(match chars.get(i) {
+                                    Some('n') => '\n',
+                                    Some('t') => '\t',
+                                    Some('r') => '\r',
+                                    Some(other) => *other,
+                                    None => return Err("unterminated escape".to_string()),
+                                });
+                            }
+                            Some(c) => text.push(*c),
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                    tokens.push(Token::Literal(text));
+                }
+                ':' if chars.get(i + 1) == Some(&':') && chars.get(i + 2) == Some(&'=') => {
+                    tokens.push(Token::Define);
+                    i += 3;
+                }
+                '=' => {
+                    tokens.push(Token::Define);
+                    i += 1;
+                }
+                '|' => {
+                    tokens.push(Token::Alt);
+                    i += 1;
+                }
+                '.' if chars.get(i + 1) == Some(&'.') => {
+                    tokens.push(Token::DotDot);
+                    i += 2;
+                    if chars.get(i) == Some(&'.') {
+                        i += 1;
+                    }
+                }
+                ';' | '.' => {
+                    tokens.push(Token::End);
+                    i += 1;
+                }
+                '(' | '[' | '{' => {
+                    tokens.push(Token::Open(ch));
+                    i += 1;
+                }
+                ')' | ']' | '}' => {
+                    tokens.push(Token::Close(ch));
+                    i += 1;
+                }
+                '?' | '*' | '+' => {
+                    tokens.push(Token::Postfix(ch));
+                    i += 1;
+                }
+                '<' => {
+                    let close = chars[i..]
+                        .iter()
+                        .position(|c| *c == '>')
+                        .ok_or_else(|| "unterminated <rule> name".to_string())?;
+                    tokens.push(Token::Ident(chars[i + 1..i + close].iter().collect()));
+                    i += close + 1;
+                }
+                c if c.is_alphanumeric() || c == '_' => {
+                    let start = i;
+                    while i < chars.len()
+                        && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+                    {
+                        i += 1;
+                    }
+                    tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                }
+                other => return Err(format!("unexpected character {other:?}")),
+            }
+        }
+        Ok(Self {
+            tokens,
+            pos: 0,
+            rules: Vec::new(),
+            fresh: 0,
+        })
+    }
+
+    fn parse(mut self) -> Result<RuleList, String> {
+        while self.pos < self.tokens.len() {
+            let Some(Token::Ident(name)) = self.tokens.get(self.pos).cloned() else {
+                return Err(format!("expected rule name at token {}", self.pos));
+            };
+            if self.tokens.get(self.pos + 1) != Some(&Token::Define) {
+                return Err(format!("expected `=` after rule name {name:?}"));
+            }
+            self.pos += 2;
+            let alts = self.parse_alts(&name)?;
+            if self.tokens.get(self.pos) == Some(&Token::End) {
+                self.pos += 1;
+            }
+            // Rule bodies are inserted ahead of helper rules created while parsing them.
+            let index = self.rules.len() - self.fresh_since(&name);
+            self.rules.insert(index, (name, alts));
+        }
+        Ok(self.rules)
+    }
+
+    fn fresh_since(&self, name: &str) -> usize {
+        self.rules
+            .iter()
+            .rev()
+            .take_while(|(helper, _)| helper.starts_with(&format!("{name}#")))
+            .count()
+    }
+
+    fn at_rule_boundary(&self) -> bool {
+        match self.tokens.get(self.pos) {
+            None | Some(Token::End) | Some(Token::Alt) | Some(Token::Close(_)) => true,
+            Some(Token::Ident(_)) => self.tokens.get(self.pos + 1) == Some(&Token::Define),
+            _ => false,
+        }
+    }
+
+    fn parse_alts(&mut self, owner: &str) -> Result<Vec<Vec<Symbol>>, String> {
+        let mut alts = vec![self.parse_seq(owner)?];
+        while self.tokens.get(self.pos) == Some(&Token::Alt) {
+            self.pos += 1;
+            alts.push(self.parse_seq(owner)?);
+        }
+        Ok(alts)
+    }
+
+    fn parse_seq(&mut self, owner: &str) -> Result<Vec<Symbol>, String> {
+        let mut symbols = Vec::new();
+        while !self.at_rule_boundary() {
+            let mut symbol = self.parse_term(owner)?;
+            while let Some(Token::Postfix(op)) = self.tokens.get(self.pos).cloned() {
+                self.pos += 1;
+                let item = vec![symbol];
+                symbol = match op {
+                    '?' => self.helper(owner, vec![item, Vec::new()]),
+                    '*' => self.repetition(owner, item, false),
+                    _ => self.repetition(owner, item, true),
+                };
+            }
+            symbols.push(symbol);
+        }
+        Ok(symbols)
+    }
+
+    fn parse_term(&mut self, owner: &str) -> Result<Symbol, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of grammar".to_string())?;
+        self.pos += 1;
+        match token {
+            Token::Ident(name) => Ok(Symbol::Rule(name)),
+            Token::Literal(text) => {
+                if self.tokens.get(self.pos) == Some(&Token::DotDot) {
+                    let Some(Token::Literal(hi)) = self.tokens.get(self.pos + 1).cloned() else {
+                        return Err("expected literal after `..`".to_string());
+                    };
+                    self.pos += 2;
+                    let (Some(lo), Some(hi)) = (single_char(&text), single_char(&hi)) else {
+                        return Err("character ranges need single-character bounds".to_string());
+                    };
+                    if lo > hi {
+                        return Err(format!("empty character range {lo:?}..{hi:?}"));
+                    }
+                    return Ok(Symbol::Range(lo, hi));
+                }
+                Ok(Symbol::Text(text))
+            }
+            Token::Open(open) => {
+                let alts = self.parse_alts(owner)?;
+                let close = match open {
+                    '(' => ')',
+                    '[' => ']',
+                    _ => '}',
+                };
+                if self.tokens.get(self.pos) != Some(&Token::Close(close)) {
+                    return Err(format!("expected `{close}`"));
+                }
+                self.pos += 1;
+                Ok(match open {
+                    '(' => self.helper(owner, alts),
+                    '[' => {
+                        let inner = self.helper(owner, alts);
+                        self.helper(owner, vec![vec![inner], Vec::new()])
+                    }
+                    _ => {
+                        let inner = self.helper(owner, alts);
+                        self.repetition(owner, vec![inner], false)
+                    }
+                })
+            }
+            other => Err(format!("unexpected token {other:?}")),
+        }
+    }
+
+    fn helper(&mut self, owner: &str, alts: Vec<Vec<Symbol>>) -> Symbol {
+        self.fresh += 1;
+        let name = format!("{owner}#{}", self.fresh);
+        self.rules.push((name.clone(), alts));
+        Symbol::Rule(name)
+    }
+
+    fn repetition(&mut self, owner: &str, item: Vec<Symbol>, at_least_one: bool) -> Symbol {
+        self.fresh += 1;
+        let name = format!("{owner}#{}", self.fresh);
+        let mut recurse = item.clone();
+        recurse.push(Symbol::Rule(name.clone()));
+        let base = if at_least_one { item } else { Vec::new() };
+        self.rules.push((name.clone(), vec![base, recurse]));
+        Symbol::Rule(name)
+    }
+}
+
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
+    }
+}
@@ -0,0 +1,1170 @@
+//! OpenAPI 3 driven HTTP fuzzing for `openapi:<spec.json>` targets.
+//!
+//! Each input seeds one request against the live service named by the spec's
+//! `servers[0].url` (`--http-backend host`): a valid request, one at schema
+//! boundaries, or one that violates the declared schema. Responses are checked
+//! against the documented status codes and response schemas.
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::RngCore as _;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::TraceEvent;
+use crate::host::{HostHttpDispatch, dispatch_host_http};
+use crate::{Config, ExitStatus, Finding, FindingKind, FozzyError, FozzyResult, HttpBackend};
+
+use super::exec::FuzzExec;
+use super::{input_identity_edge, rng_from_seed, sample_regex, seed_from_input, stable_edge};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Nesting limit for `$ref` chains and generated/validated values.
+const MAX_SCHEMA_DEPTH: usize = 8;
+/// Response schema violations listed per finding.
+const MAX_REPORTED_VIOLATIONS: usize = 5;
+const METHODS: &[&str] = &["get", "put", "post", "delete", "patch", "head", "options"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestVariant {
+    Valid,
+    Boundary,
+    Invalid,
+}
+
+impl RequestVariant {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Boundary => "boundary",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    name: String,
+    location: String,
+    required: bool,
+    schema: Value,
+}
+
+#[derive(Debug, Clone)]
+struct RequestBody {
+    required: bool,
+    schema: Value,
+}
+
+#[derive(Debug, Clone)]
+struct Operation {
+    id: String,
+    method: String,
+    path: String,
+    parameters: Vec<Parameter>,
+    body: Option<RequestBody>,
+    /// Status key (`200`, `4XX`, `DEFAULT`) to the JSON response schema, if any.
+    responses: BTreeMap<String, Option<Value>>,
+}
+
+#[derive(Debug, Clone)]
+struct OpenApiSpec {
+    doc: Value,
+    base_url: Option<String>,
+    operations: Vec<Operation>,
+}
+
+#[derive(Debug, Clone)]
+struct PlannedRequest {
+    variant: RequestVariant,
+    url: String,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    /// What an invalid request breaks, for finding messages.
+    violation: Option<String>,
+}
+
+pub(crate) fn execute_openapi_target(
+    config: &Config,
+    spec_path: &Path,
+    input: &[u8],
+) -> FozzyResult<FuzzExec> {
+    if config.http_backend != HttpBackend::Host {
+        return Err(FozzyError::InvalidArgument(
+            "fuzz target openapi: sends real requests and requires --http-backend host".to_string(),
+        ));
+    }
+    let spec = load_openapi_spec(spec_path)?;
+    let Some(base_url) = spec.base_url.as_deref() else {
+        return Err(FozzyError::InvalidArgument(format!(
+            "openapi spec {} needs an absolute servers[0].url to fuzz against",
+            spec_path.display()
+        )));
+    };
+
+    let mut rng = rng_from_seed(seed_from_input(input));
+    let operation = &spec.operations[(rng.next_u64() % spec.operations.len() as u64) as usize];
+    let variant = match rng.next_u64() % 4 {
+        0 | 1 => RequestVariant::Valid,
+        2 => RequestVariant::Boundary,
+        _ => RequestVariant::Invalid,
+    };
+    let request = spec.plan_request(operation, base_url, variant, &mut rng);
+
+    let mut request_fields = serde_json::Map::new();
+    request_fields.insert("operation".to_string(), Value::from(operation.id.clone()));
+    request_fields.insert("method".to_string(), Value::from(operation.method.clone()));
+    request_fields.insert("url".to_string(), Value::from(request.url.clone()));
+    request_fields.insert("variant".to_string(), Value::from(request.variant.as_str()));
+    if let Some(body) = &request.body {
+        request_fields.insert("body".to_string(), Value::from(body.clone()));
+    }
+    if let Some(violation) = &request.violation {
+        request_fields.insert("violation".to_string(), Value::from(violation.clone()));
+    }
+    let mut events = vec![TraceEvent {
+        time_ms: 0,
+        name: "openapi_request".to_string(),
+        fields: request_fields,
+    }];
+    let mut coverage = BTreeSet::from([
+        input_identity_edge(input),
+        stable_edge(&format!(
+            "openapi:{}:{}",
+            operation.id,
+            request.variant.as_str()
+        )),
+    ]);
+
+    let label = format!(
+        "{} {} ({} request{})",
+        operation.method,
+        operation.path,
+        request.variant.as_str(),
+        request
+            .violation
+            .as_deref()
+            .map(|violation| format!(": {violation}"))
+            .unwrap_or_default()
+    );
+    let (status, findings) = match dispatch_host_http(
+        &operation.method,
+        &request.url,
+        &request.headers,
+        request.body.as_deref(),
+        Some(REQUEST_TIMEOUT),
+    ) {
+        Err(message) => (
+            ExitStatus::Error,
+            vec![Finding {
+                kind: FindingKind::Checker,
+                title: "openapi_request_failed".to_string(),
+                message: format!("{label}: {message}"),
+                location: None,
+            }],
+        ),
+        Ok(HostHttpDispatch::TimedOut) => (
+            ExitStatus::Timeout,
+            vec![Finding {
+                kind: FindingKind::Hang,
+                title: "openapi_timeout".to_string(),
+                message: format!("{label} timed out after {}ms", REQUEST_TIMEOUT.as_millis()),
+                location: None,
+            }],
+        ),
+        Ok(HostHttpDispatch::Completed(response)) => {
+            let mut response_fields = serde_json::Map::new();
+            response_fields.insert("status".to_string(), Value::from(response.status));
+            response_fields.insert("bytes".to_string(), Value::from(response.body.len()));
+            events.push(TraceEvent {
+                time_ms: 0,
+                name: "openapi_response".to_string(),
+                fields: response_fields,
+            });
+            coverage.insert(stable_edge(&format!(
+                "openapi:{}:{}:{}",
+                operation.id,
+                request.variant.as_str(),
+                response.status
+            )));
+            let findings = spec.check_response(operation, &label, response.status, &response.body);
+            let status = if findings.is_empty() {
+                ExitStatus::Pass
+            } else {
+                ExitStatus::Fail
+            };
+            (status, findings)
+        }
+    };
+
+    Ok(FuzzExec {
+        status,
+        findings,
+        events,
+        coverage,
+        memory: None,
+        started_at: String::new(),
+        finished_at: String::new(),
+        duration_ms: 0,
+        duration_ns: 0,
+    })
+}
+
+/// Writes one starter `http_request` scenario per operation into `out_dir`.
+/// Existing files are kept unless `force` is set.
+pub(crate) fn write_openapi_scenarios(
+    spec_path: &Path,
+    out_dir: &Path,
+    base_url: Option<&str>,
+    force: bool,
+) -> FozzyResult<Value> {
+    let spec = load_openapi_spec(spec_path)?;
+    let base_url = base_url
+        .map(|url| url.trim_end_matches('/').to_string())
+        .or_else(|| spec.base_url.clone())
+        .ok_or_else(|| {
+            FozzyError::InvalidArgument(format!(
+                "openapi spec {} has no absolute servers[0].url; pass --base-url",
+                spec_path.display()
+            ))
+        })?;
+    std::fs::create_dir_all(out_dir)?;
+
+    let mut used = BTreeSet::<String>::new();
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    for operation in &spec.operations {
+        let base = slug(&operation.id);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !used.insert(name.clone()) {
+            name = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+        let path: PathBuf = out_dir.join(format!("{name}.fozzy.json"));
+        if path.exists() && !force {
+            skipped.push(path.to_string_lossy().to_string());
+            continue;
+        }
+
+        let mut rng = rng_from_seed(0);
+        let request = spec.plan_request(operation, &base_url, RequestVariant::Valid, &mut rng);
+        let mut step = serde_json::Map::new();
+        step.insert("type".to_string(), Value::from("http_request"));
+        step.insert("method".to_string(), Value::from(operation.method.clone()));
+        step.insert("path".to_string(), Value::from(request.url));
+        if !request.headers.is_empty() {
+            step.insert(
+                "headers".to_string(),
+                serde_json::to_value(&request.headers)?,
+            );
+        }
+        if let Some(body) = request.body {
+            step.insert("body".to_string(), Value::from(body));
+        }
+        if let Some(status) = operation
+            .responses
+            .keys()
+            .filter_map(|key| key.parse::<u16>().ok())
+            .find(|code| (200..300).contains(code))
+        {
+            step.insert("expect_status".to_string(), Value::from(status));
+        }
+        let scenario = serde_json::json!({
+            "version": 1,
+            "name": format!("openapi-{name}"),
+            "steps": [Value::Object(step)]
+        });
+        std::fs::write(&path, serde_json::to_vec_pretty(&scenario)?)?;
+        written.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(serde_json::json!({
+        "ok": true,
+        "spec": spec_path.to_string_lossy().to_string(),
+        "out": out_dir.to_string_lossy().to_string(),
+        "baseUrl": base_url,
+        "operations": spec.operations.len(),
+        "written": written,
+        "skipped": skipped
+    }))
+}
+
+fn load_openapi_spec(path: &Path) -> FozzyResult<OpenApiSpec> {
+    let bytes = std::fs::read(path).map_err(|err| {
+        FozzyError::InvalidArgument(format!(
+            "failed to read openapi spec {}: {err}",
+            path.display()
+        ))
+    })?;
+    let doc: Value = serde_json::from_slice(&bytes).map_err(|err| {
+        FozzyError::InvalidArgument(format!(
+            "failed to parse openapi spec {} (JSON expected): {err}",
+            path.display()
+        ))
+    })?;
+    if !doc
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("3."))
+    {
+        return Err(FozzyError::InvalidArgument(format!(
+            "openapi spec {} is not an OpenAPI 3 document",
+            path.display()
+        )));
+    }
+
+    let base_url = doc
+        .pointer("/servers/0")
+        .and_then(|server| {
+            let mut url = server.get("url")?.as_str()?.to_string();
+            if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+                for (name, variable) in variables {
+                    if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                        url = url.replace(&format!("{{{name}}}"), default);
+                    }
+                }
+            }
+            Some(url)
+        })
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .map(|url| url.trim_end_matches('/').to_string());
+
+    let mut operations = Vec::new();
+    for (route, item) in doc
+        .get("paths")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let item = resolve(&doc, item);
+        let shared = item.get("parameters").and_then(Value::as_array);
+        for method in METHODS {
+            let Some(op) = item.get(*method) else {
+                continue;
+            };
+            let mut parameters = Vec::<Parameter>::new();
+            for raw in shared
+                .into_iter()
+                .chain(op.get("parameters").and_then(Value::as_array))
+            {
+                for param in raw {
+                    let param = resolve(&doc, param);
+                    let (Some(name), Some(location)) = (
+                        param.get("name").and_then(Value::as_str),
+                        param.get("in").and_then(Value::as_str),
+                    ) else {
+                        continue;
+                    };
+                    if !matches!(location, "path" | "query" | "header") {
+                        continue;
+                    }
+                    parameters.retain(|p| !(p.name == name && p.location == location));
+                    parameters.push(Parameter {
+                        name: name.to_string(),
+                        location: location.to_string(),
+                        required: location == "path"
+                            || param.get("required").and_then(Value::as_bool) == Some(true),
+                        schema: param
+                            .get("schema")
+                            .cloned()
+                            .unwrap_or_else(|| serde_json::json!({ "type": "string" })),
+                    });
+                }
+            }
+            let body = op
+                .get("requestBody")
+                .map(|body| resolve(&doc, body))
+                .and_then(|body| {
+                    Some(RequestBody {
+                        required: body.get("required").and_then(Value::as_bool) == Some(true),
+                        schema: json_schema(body)?,
+                    })
+                });
+            let responses = op
+                .get("responses")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(code, response)| {
+                    (
+                        code.to_ascii_uppercase(),
+                        json_schema(resolve(&doc, response)),
+                    )
+                })
+                .collect();
+            operations.push(Operation {
+                id: op
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{} {route}", method.to_ascii_uppercase())),
+                method: method.to_ascii_uppercase(),
+                path: route.clone(),
+                parameters,
+                body,
+                responses,
+            });
+        }
+    }
+    if operations.is_empty() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "openapi spec {} declares no operations",
+            path.display()
+        )));
+    }
+    Ok(OpenApiSpec {
+        doc,
+        base_url,
+        operations,
+    })
+}
+
+/// Schema of the JSON media type (`application/json` or `*+json`) of a request
+/// body or response object.
+fn json_schema(object: &Value) -> Option<Value> {
+    object
+        .get("content")?
+        .as_object()?
+        .iter()
+        .find(|(media, _)| {
+            let media = media.split(';').next().unwrap_or_default().trim();
+            media == "application/json" || media.ends_with("+json")
+        })
+        .and_then(|(_, media)| media.get("schema").cloned())
+}
+
+fn resolve<'a>(doc: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let Some(pointer) = value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+        else {
+            break;
+        };
+        match doc.pointer(pointer) {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+    value
+}
+
+impl OpenApiSpec {
+    fn plan_request(
+        &self,
+        operation: &Operation,
+        base_url: &str,
+        variant: RequestVariant,
+        rng: &mut ChaCha20Rng,
+    ) -> PlannedRequest {
+        let targets = operation.parameters.len() + usize::from(operation.body.is_some());
+        let (variant, violate_index) = match variant {
+            RequestVariant::Invalid if targets > 0 => {
+                (variant, Some((rng.next_u64() % targets as u64) as usize))
+            }
+            RequestVariant::Invalid => (RequestVariant::Valid, None),
+            _ => (variant, None),
+        };
+        let boundary = variant == RequestVariant::Boundary;
+        let mut violation = None;
+
+        let mut path = operation.path.clone();
+        let mut query = Vec::<(String, String)>::new();
+        let mut headers = BTreeMap::<String, String>::new();
+        for (index, param) in operation.parameters.iter().enumerate() {
+            let value = if violate_index == Some(index) {
+                if param.required && param.location != "path" && one_in(rng, 3) {
+                    violation = Some(format!(
+                        "missing required {} parameter {:?}",
+                        param.location, param.name
+                    ));
+                    None
+                } else {
+                    violation = Some(format!(
+                        "{} parameter {:?} violates its schema",
+                        param.location, param.name
+                    ));
+                    Some(self.violate(&param.schema, rng, 0))
+                }
+            } else if param.required || boundary || one_in(rng, 2) {
+                Some(self.sample(&param.schema, boundary, rng, 0))
+            } else {
+                None
+            };
+            let Some(value) = value else {
+                continue;
+            };
+            let text = param_text(&value);
+            match param.location.as_str() {
+                "path" => {
+                    path = path.replace(&format!("{{{}}}", param.name), &percent_encode(&text))
+                }
+                "query" => query.push((param.name.clone(), text)),
+                _ => {
+                    headers.insert(
+                        param.name.to_ascii_lowercase(),
+                        text.replace(['\r', '\n'], " "),
+                    );
+                }
+            }
+        }
+
+        let body = operation.body.as_ref().and_then(|body| {
+            let value = if violate_index == Some(operation.parameters.len()) {
+                if body.required && one_in(rng, 4) {
+                    violation = Some("missing required request body".to_string());
+                    return None;
+                }
+                violation = Some("request body violates its schema".to_string());
+                self.violate(&body.schema, rng, 0)
+            } else if body.required || boundary || one_in(rng, 2) {
+                self.sample(&body.schema, boundary, rng, 0)
+            } else {
+                return None;
+            };
+            Some(value.to_string())
+        });
+        if body.is_some() {
+            headers.insert("content-type".to_string(), "application/json".to_string());
+        }
+        if !operation.responses.is_empty() {
+            headers.insert("accept".to_string(), "application/json".to_string());
+        }
+
+        let mut url = format!("{base_url}{path}");
+        if !query.is_empty() {
+            let encoded = query
+                .iter()
+                .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+                .collect::<Vec<_>>()
+                .join("&");
+            url = format!("{url}?{encoded}");
+        }
+        PlannedRequest {
+            variant,
+            url,
+            headers,
+            body,
+            violation,
+        }
+    }
+
+    fn check_response(
+        &self,
+        operation: &Operation,
+        label: &str,
+        status: u16,
+        body: &str,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if status >= 500 {
+            findings.push(Finding {
+                kind: FindingKind::TargetBehavior,
+                title: "openapi_server_error".to_string(),
+                message: format!("{label} returned {status}"),
+                location: None,
+            });
+        }
+        let declared = operation
+            .responses
+            .get(&status.to_string())
+            .or_else(|| operation.responses.get(&format!("{}XX", status / 100)))
+            .or_else(|| operation.responses.get("DEFAULT"));
+        match declared {
+            None => findings.push(Finding {
+                kind: FindingKind::Assertion,
+                title: "openapi_undocumented_status".to_string(),
+                message: format!(
+                    "{label} returned undocumented status {status}; documented: {}",
+                    operation
+                        .responses
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                location: None,
+            }),
+            Some(Some(schema)) if !body.trim().is_empty() => {
+                let violations = match serde_json::from_str::<Value>(body) {
+                    Ok(value) => {
+                        let mut violations = Vec::new();
+                        self.validate(schema, &value, "$", &mut violations, 0);
+                        violations
+                    }
+                    Err(err) => vec![format!("response body is not JSON: {err}")],
+                };
+                if !violations.is_empty() {
+                    findings.push(Finding {
+                        kind: FindingKind::Assertion,
+                        title: "openapi_response_schema".to_string(),
+                        message: format!(
+                            "{label} returned {status} with a body that does not match the documented schema: {}",
+                            violations
+                                .iter()
+                                .take(MAX_REPORTED_VIOLATIONS)
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join("; ")
+                        ),
+                        location: None,
+                    });
+                }
+            }
+            Some(_) => {}
+        }
+        findings
+    }
+
+    /// A value conforming to `schema`; with `boundary`, sizes and ranges sit at
+    /// their declared limits.
+    fn sample(&self, schema: &Value, boundary: bool, rng: &mut ChaCha20Rng, depth: usize) -> Value {
+        let schema = resolve(&self.doc, schema);
+        if depth > MAX_SCHEMA_DEPTH {
+            return Value::Null;
+        }
+        if let Some(value) = schema.get("const") {
+            return value.clone();
+        }
+        if let Some(options) = schema.get("enum").and_then(Value::as_array)
+            && !options.is_empty()
+        {
+            let index = if boundary {
+                if one_in(rng, 2) { 0 } else { options.len() - 1 }
+            } else {
+                pick(rng, options.len())
+            };
+            return options[index].clone();
+        }
+        if !boundary
+            && one_in(rng, 4)
+            && let Some(example) = schema.get("example")
+        {
+            return example.clone();
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(options) = schema.get(key).and_then(Value::as_array)
+                && !options.is_empty()
+            {
+                let option = &options[pick(rng, options.len())];
+                return self.sample(option, boundary, rng, depth + 1);
+            }
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = serde_json::Map::new();
+            for part in parts {
+                match self.sample(part, boundary, rng, depth + 1) {
+                    Value::Object(fields) => merged.extend(fields),
+                    other => return other,
+                }
+            }
+            return Value::Object(merged);
+        }
+
+        match schema_type(schema) {
+            "integer" => {
+                let (lo, hi) = int_bounds(schema);
+                if boundary {
+                    let (floor, ceil) =
+                        if schema.get("format").and_then(Value::as_str) == Some("int32") {
+                            (i64::from(i32::MIN), i64::from(i32::MAX))
+                        } else {
+                            (i64::MIN, i64::MAX)
+                        };
+                    let candidates = [lo.unwrap_or(floor), hi.unwrap_or(ceil)];
+                    return Value::from(candidates[pick(rng, candidates.len())]);
+                }
+                let lo =
+                    lo.unwrap_or_else(|| hi.map_or(-1000, |hi| hi.saturating_sub(2000)).min(-1000));
+                let hi = hi.unwrap_or_else(|| lo.saturating_add(2000)).max(lo);
+                let span = (i128::from(hi) - i128::from(lo) + 1) as u128;
+                let offset = (u128::from(rng.next_u64()) % span) as i128;
+                Value::from((i128::from(lo) + offset) as i64)
+            }
+            "number" => {
+                let lo = schema.get("minimum").and_then(Value::as_f64);
+                let hi = schema.get("maximum").and_then(Value::as_f64);
+                let value = if boundary {
+                    if one_in(rng, 2) {
+                        lo.unwrap_or(-1e12)
+                    } else {
+                        hi.unwrap_or(1e12)
+                    }
+                } else {
+                    let lo = lo.unwrap_or(-1000.0);
+                    let hi = hi.unwrap_or(lo + 2000.0).max(lo);
+                    lo + (rng.next_u64() % 10_000) as f64 / 10_000.0 * (hi - lo)
+                };
+                serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number)
+            }
+            "boolean" => Value::Bool(one_in(rng, 2)),
+            "string" => Value::String(sample_string(schema, boundary, rng)),
+            "array" => {
+                let min = usize_field(schema, "minItems").unwrap_or(0);
+                let max = usize_field(schema, "maxItems");
+                let len = if boundary {
+                    if one_in(rng, 2) {
+                        min
+                    } else {
+                        max.unwrap_or(min + 8).min(min + 32)
+                    }
+                } else {
+                    min + pick(
+                        rng,
+                        max.unwrap_or(min + 3).min(min + 3).saturating_sub(min) + 1,
+                    )
+                };
+                let items = schema.get("items").cloned().unwrap_or(Value::Null);
+                Value::Array(
+                    (0..len)
+                        .map(|_| self.sample(&items, boundary, rng, depth + 1))
+                        .collect(),
+                )
+            }
+            "object" => {
+                let required = required_fields(schema);
+                let all_optional = one_in(rng, 2);
+                let mut fields = serde_json::Map::new();
+                for (name, property) in schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                {
+                    let include = required.contains(name.as_str())
+                        || if boundary {
+                            all_optional
+                        } else {
+                            one_in(rng, 2)
+                        };
+                    if include {
+                        fields.insert(
+                            name.clone(),
+                            self.sample(property, boundary, rng, depth + 1),
+                        );
+                    }
+                }
+                Value::Object(fields)
+            }
+            _ => Value::Null,
+        }
+    }
+
+    /// A value that breaks `schema`: wrong type, out of range, wrong length, or an
+    /// object missing a required property.
+    fn violate(&self, schema: &Value, rng: &mut ChaCha20Rng, depth: usize) -> Value {
+        let schema = resolve(&self.doc, schema);
+        let mut candidates = Vec::<Value>::new();
+        let kind = schema_type(schema);
+        candidates.push(match kind {
+            "integer" | "number" => Value::from("not-a-number"),
+            "boolean" => Value::from("maybe"),
+            "array" => serde_json::json!({ "unexpected": true }),
+            "object" => serde_json::json!(["unexpected"]),
+            _ => serde_json::json!({ "unexpected": [1, 2, 3] }),
+        });
+        if schema.get("enum").is_some() {
+            candidates.push(Value::from("__fozzy_invalid__"));
+        }
+        match kind {
+            "integer" => {
+                let (lo, hi) = int_bounds(schema);
+                if let Some(lo) = lo.and_then(|lo| lo.checked_sub(1)) {
+                    candidates.push(Value::from(lo));
+                }
+                if let Some(hi) = hi.and_then(|hi| hi.checked_add(1)) {
+                    candidates.push(Value::from(hi));
+                }
+                candidates.push(serde_json::json!(1.5));
+            }
+            "number" => {
+                if let Some(lo) = schema.get("minimum").and_then(Value::as_f64) {
+                    candidates.push(serde_json::json!(lo - 1.0));
+                }
+                if let Some(hi) = schema.get("maximum").and_then(Value::as_f64) {
+                    candidates.push(serde_json::json!(hi + 1.0));
+                }
+            }
+            "string" => {
+                if let Some(max) = usize_field(schema, "maxLength") {
+                    candidates.push(Value::from("a".repeat(max.saturating_add(1).min(1 << 16))));
+                }
+                if let Some(min) = usize_field(schema, "minLength").filter(|min| *min > 0) {
+                    candidates.push(Value::from("a".repeat(min - 1)));
+                }
+                if schema.get("format").is_some() || schema.get("pattern").is_some() {
+                    candidates.push(Value::from("~~ not valid ~~"));
+                }
+            }
+            "array" => {
+                let items = schema.get("items").cloned().unwrap_or(Value::Null);
+                if let Some(max) = usize_field(schema, "maxItems").filter(|max| *max < 64) {
+                    candidates.push(Value::Array(
+                        (0..=max)
+                            .map(|_| self.sample(&items, false, rng, depth + 1))
+                            .collect(),
+                    ));
+                }
+                if usize_field(schema, "minItems").is_some_and(|min| min > 0) {
+                    candidates.push(Value::Array(Vec::new()));
+                }
+                if depth < MAX_SCHEMA_DEPTH && !items.is_null() {
+                    candidates.push(Value::Array(vec![self.violate(&items, rng, depth + 1)]));
+                }
+            }
+            "object" => {
+                let valid = self.sample(schema, false, rng, depth + 1);
+                let required = required_fields(schema).into_iter().collect::<Vec<_>>();
+                if let Value::Object(fields) = &valid {
+                    if !required.is_empty() {
+                        let mut missing = fields.clone();
+                        missing.remove(required[pick(rng, required.len())]);
+                        candidates.push(Value::Object(missing));
+                    }
+                    let properties = schema
+                        .get("properties")
+                        .and_then(Value::as_object)
+                        .map(|properties| properties.iter().collect::<Vec<_>>())
+                        .unwrap_or_default();
+                    if depth < MAX_SCHEMA_DEPTH && !properties.is_empty() {
+                        let (name, property) = properties[pick(rng, properties.len())];
+                        let mut broken = fields.clone();
+                        broken.insert(name.clone(), self.violate(property, rng, depth + 1));
+                        candidates.push(Value::Object(broken));
+                    }
+                }
+            }
+            _ => {}
+        }
+        candidates.swap_remove(pick(rng, candidates.len()))
+    }
+
+    fn validate(
+        &self,
+        schema: &Value,
+        value: &Value,
+        at: &str,
+        violations: &mut Vec<String>,
+        depth: usize,
+    ) {
+        let schema = resolve(&self.doc, schema);
+        if depth > MAX_SCHEMA_DEPTH || !schema.is_object() {
+            return;
+        }
+        if value.is_null()
+            && (schema.get("nullable").and_then(Value::as_bool) == Some(true)
+                || schema
+                    .get("type")
+                    .and_then(Value::as_array)
+                    .is_some_and(|types| types.iter().any(|t| t == "null")))
+        {
+            return;
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            for part in parts {
+                self.validate(part, value, at, violations, depth + 1);
+            }
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(options) = schema.get(key).and_then(Value::as_array) {
+                let matching = options
+                    .iter()
+                    .filter(|option| {
+                        let mut nested = Vec::new();
+                        self.validate(option, value, at, &mut nested, depth + 1);
+                        nested.is_empty()
+                    })
+                    .count();
+                let ok = if key == "oneOf" {
+                    matching == 1
+                } else {
+                    matching > 0
+                };
+                if !ok {
+                    violations.push(format!("{at}: matches {matching} of the {key} schemas"));
+                }
+            }
+        }
+        if let Some(options) = schema.get("enum").and_then(Value::as_array)
+            && !options.contains(value)
+        {
+            violations.push(format!("{at}: {value} is not one of the enum values"));
+        }
+        if let Some(expected) = schema.get("type") {
+            let types = match expected {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|name| type_matches(name, value)) {
+                violations.push(format!(
+                    "{at}: expected {}, got {}",
+                    types.join("|"),
+                    json_type(value)
+                ));
+                return;
+            }
+        }
+
+        match value {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                    && number < min
+                {
+                    violations.push(format!("{at}: {number} is below minimum {min}"));
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                    && number > max
+                {
+                    violations.push(format!("{at}: {number} is above maximum {max}"));
+                }
+            }
+            Value::String(text) => {
+                let len = text.chars().count();
+                if let Some(min) = usize_field(schema, "minLength")
+                    && len < min
+                {
+                    violations.push(format!("{at}: length {len} is below minLength {min}"));
+                }
+                if let Some(max) = usize_field(schema, "maxLength")
+                    && len > max
+                {
+                    violations.push(format!("{at}: length {len} is above maxLength {max}"));
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+                    && let Ok(matcher) = regex::Regex::new(pattern)
+                    && !matcher.is_match(text)
+                {
+                    violations.push(format!("{at}: {text:?} does not match pattern {pattern:?}"));
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = usize_field(schema, "minItems")
+                    && items.len() < min
+                {
+                    violations.push(format!(
+                        "{at}: {} items is below minItems {min}",
+                        items.len()
+                    ));
+                }
+                if let Some(max) = usize_field(schema, "maxItems")
+                    && items.len() > max
+                {
+                    violations.push(format!(
+                        "{at}: {} items is above maxItems {max}",
+                        items.len()
+                    ));
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.validate(
+                            item_schema,
+                            item,
+                            &format!("{at}[{index}]"),
+                            violations,
+                            depth + 1,
+                        );
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                for name in required_fields(schema) {
+                    if !fields.contains_key(name) {
+                        violations.push(format!("{at}: missing required property {name:?}"));
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (name, field) in fields {
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property) => self.validate(
+                            property,
+                            field,
+                            &format!("{at}.{name}"),
+                            violations,
+                            depth + 1,
+                        ),
+                        None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                            violations.push(format!("{at}: unexpected property {name:?}"));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sample_string(schema: &Value, boundary: bool, rng: &mut ChaCha20Rng) -> String {
+    let min = usize_field(schema, "minLength").unwrap_or(0);
+    let max = usize_field(schema, "maxLength");
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+        && let Some(text) = sample_regex(pattern, rng)
+    {
+        return text;
+    }
+    let n = rng.next_u64() % 1000;
+    match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") if !boundary => return format!("2024-01-{:02}T00:00:00Z", n % 28 + 1),
+        Some("date") if !boundary => return format!("2024-01-{:02}", n % 28 + 1),
+        Some("uuid") => {
+            let hex = format!("{:016x}{:016x}", rng.next_u64(), rng.next_u64());
+            return format!(
+                "{}-{}-4{}-a{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[13..16],
+                &hex[17..20],
+                &hex[20..]
+            );
+        }
+        Some("email") if !boundary => return format!("user{n}@example.com"),
+        Some("uri" | "url") if !boundary => return format!("https://example.com/{n}"),
+        Some("ipv4") => return format!("10.0.{}.{}", n / 256 % 256, n % 256),
+        _ => {}
+    }
+    let len = if boundary {
+        if one_in(rng, 2) {
+            min
+        } else {
+            max.unwrap_or(min + 256).min(min + 4096)
+        }
+    } else {
+        min + pick(
+            rng,
+            max.unwrap_or(min + 12).min(min + 12).saturating_sub(min) + 1,
+        )
+    };
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    (0..len)
+        .map(|_| char::from(ALPHABET[pick(rng, ALPHABET.len())]))
+        .collect()
+}
+
+fn schema_type(schema: &Value) -> &str {
+    match schema.get("type") {
+        Some(Value::String(name)) => name,
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|name| *name != "null")
+            .unwrap_or("null"),
+        _ if schema.get("properties").is_some() => "object",
+        _ if schema.get("items").is_some() => "array",
+        _ => "string",
+    }
+}
+
+/// Inclusive integer bounds, honoring both OpenAPI 3.0 boolean and 3.1 numeric
+/// `exclusiveMinimum`/`exclusiveMaximum`.
+fn int_bounds(schema: &Value) -> (Option<i64>, Option<i64>) {
+    let bound = |inclusive: &str, exclusive: &str, step: i64| match (
+        schema.get(inclusive).and_then(Value::as_f64),
+        schema.get(exclusive),
+    ) {
+        (_, Some(Value::Number(limit))) => limit.as_f64().map(|limit| limit as i64 + step),
+        (Some(limit), Some(Value::Bool(true))) => Some(limit as i64 + step),
+        (Some(limit), _) => Some(limit as i64),
+        (None, _) => None,
+    };
+    (
+        bound("minimum", "exclusiveMinimum", 1),
+        bound("maximum", "exclusiveMaximum", -1),
+    )
+}
+
+fn required_fields(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+fn usize_field(schema: &Value, key: &str) -> Option<usize> {
+    schema
+        .get(key)
+        .and_then(Value::as_u64)
+        .map(|value| value as usize)
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|v| v.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn param_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(param_text).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+fn slug(text: &str) -> String {
+    let mut out = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_alphanumeric() {
+            out.push(ch.to_ascii_lowercase());
+        } else if !out.ends_with('-') && !out.is_empty() {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() {
+        "operation".to_string()
+    } else {
+        out
+    }
+}
+
+fn one_in(rng: &mut ChaCha20Rng, n: u64) -> bool {
+    rng.next_u64().is_multiple_of(n)
+}
+
+fn pick(rng: &mut ChaCha20Rng, len: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (rng.next_u64() % len as u64) as usize
+    }
+}
@@ -0,0 +1,685 @@
+//! Typed input generators for property-mode fuzzing.
+//!
+//! A scenario opts in by declaring a top-level `generators` map. Each generator is
+//! bound to a variable that steps reference as `"{{name}}"`; the value is sampled
+//! from the fuzz RNG and shrunk structurally (value trees, not bytes) on failure.
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::RngCore as _;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::{Config, FozzyError, FozzyResult, MemoryOptions, ScenarioFile};
+
+use super::{FuzzTarget, execute_target, rng_from_seed, seed_from_input};
+
+/// Extra repetitions sampled for unbounded regex quantifiers (`*`, `+`, `{n,}`).
+const DEFAULT_MAX_REPEAT: u32 = 8;
+/// Upper bound on candidate executions for one shrink.
+const MAX_SHRINK_ATTEMPTS: usize = 2_000;
+/// Largest `max_len` a list generator may declare.
+const MAX_LIST_LEN: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeneratorSpec {
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Bool,
+    String {
+        regex: String,
+        #[serde(default = "default_max_repeat")]
+        max_repeat: u32,
+    },
+    Const {
+        value: serde_json::Value,
+    },
+    OneOf {
+        options: Vec<GeneratorSpec>,
+    },
+    List {
+        item: Box<GeneratorSpec>,
+        #[serde(default)]
+        min_len: usize,
+        max_len: usize,
+    },
+    Object {
+        fields: BTreeMap<String, GeneratorSpec>,
+    },
+}
+
+fn default_max_repeat() -> u32 {
+    DEFAULT_MAX_REPEAT
+}
+
+/// Generated value plus the choices needed to shrink it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum GenTree {
+    Int(i64),
+    Bool(bool),
+    Str(String),
+    Const(serde_json::Value),
+    OneOf { index: usize, value: Box<GenTree> },
+    List(Vec<GenTree>),
+    Object(BTreeMap<String, GenTree>),
+}
+
+impl GenTree {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Int(value) => serde_json::Value::from(*value),
+            Self::Bool(value) => serde_json::Value::Bool(*value),
+            Self::Str(value) => serde_json::Value::String(value.clone()),
+            Self::Const(value) => value.clone(),
+            Self::OneOf { value, .. } => value.to_json(),
+            Self::List(items) => items.iter().map(Self::to_json).collect(),
+            Self::Object(fields) => fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_json()))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        }
+    }
+}
+
+pub(crate) type Bindings = BTreeMap<String, GenTree>;
+
+#[derive(Debug, Clone)]
+enum Generator {
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Bool,
+    String {
+        hir: Hir,
+        matcher: regex::Regex,
+        max_repeat: u32,
+    },
+    Const(serde_json::Value),
+    OneOf(Vec<Generator>),
+    List {
+        item: Box<Generator>,
+        min_len: usize,
+        max_len: usize,
+    },
+    Object(BTreeMap<String, Generator>),
+}
+
+/// Source of generator choices: the fuzz RNG while sampling, or always-zero when
+/// building the simplest value of a generator.
+enum Pick<'a> {
+    Random(&'a mut ChaCha20Rng),
+    Minimal,
+}
+
+impl Pick<'_> {
+    fn below(&mut self, bound: u64) -> u64 {
+        match self {
+            Self::Random(rng) if bound > 0 => rng.next_u64() % bound,
+            _ => 0,
+        }
+    }
+}
+
+impl Generator {
+    fn compile(name: &str, spec: &GeneratorSpec) -> FozzyResult<Self> {
+        Ok(match spec {
+            GeneratorSpec::Int { min, max } => {
+                if min > max {
+                    return Err(FozzyError::Scenario(format!(
+                        "generator {name:?}: int min {min} exceeds max {max}"
+                    )));
+                }
+                Self::Int {
+                    min: *min,
+                    max: *max,
+                }
+            }
+            GeneratorSpec::Bool => Self::Bool,
+            GeneratorSpec::String { regex, max_repeat } => {
+                let hir = regex_syntax::ParserBuilder::new()
+                    .build()
+                    .parse(regex)
+                    .map_err(|err| {
+                        FozzyError::Scenario(format!(
+                            "generator {name:?}: invalid string regex {regex:?}: {err}"
+                        ))
+                    })?;
+                let matcher = regex::Regex::new(&format!("^(?:{regex})$")).map_err(|err| {
+                    FozzyError::Scenario(format!(
+                        "generator {name:?}: invalid string regex {regex:?}: {err}"
+                    ))
+                })?;
+                Self::String {
+                    hir,
+                    matcher,
+                    max_repeat: *max_repeat,
+                }
+            }
+            GeneratorSpec::Const { value } => Self::Const(value.clone()),
+            GeneratorSpec::OneOf { options } => {
+                if options.is_empty() {
+                    return Err(FozzyError::Scenario(format!(
+                        "generator {name:?}: one_of requires at least one option"
+                    )));
+                }
+                Self::OneOf(
+                    options
+                        .iter()
+                        .map(|option| Self::compile(name, option))
+                        .collect::<FozzyResult<_>>()?,
+                )
+            }
+            GeneratorSpec::List {
+                item,
+                min_len,
+                max_len,
+            } => {
+                if min_len > max_len {
+                    return Err(FozzyError::Scenario(format!(
+                        "generator {name:?}: list min_len {min_len} exceeds max_len {max_len}"
+                    )));
+                }
+                if *max_len > MAX_LIST_LEN {
+                    return Err(FozzyError::Scenario(format!(
+                        "generator {name:?}: list max_len {max_len} exceeds the limit of {MAX_LIST_LEN}"
+                    )));
+                }
+                Self::List {
+                    item: Box::new(Self::compile(name, item)?),
+                    min_len: *min_len,
+                    max_len: *max_len,
+                }
+            }
+            GeneratorSpec::Object { fields } => Self::Object(
+                fields
+                    .iter()
+                    .map(|(field, spec)| {
+                        Ok((
+                            field.clone(),
+                            Self::compile(&format!("{name}.{field}"), spec)?,
+                        ))
+                    })
+                    .collect::<FozzyResult<_>>()?,
+            ),
+        })
+    }
+
+    fn generate(&self, pick: &mut Pick<'_>) -> GenTree {
+        match self {
+            Self::Int { min, max } => {
+                let value = match pick {
+                    Pick::Minimal => int_shrink_target(*min, *max),
+                    Pick::Random(_) => {
+                        let span = (*max as i128 - *min as i128 + 1) as u128;
+                        let offset = if span > u64::MAX as u128 {
+                            pick.below(u64::MAX) as i128
+                        } else {
+                            pick.below(span as u64) as i128
+                        };
+                        (*min as i128 + offset) as i64
+                    }
+                };
+                GenTree::Int(value)
+            }
+            Self::Bool => GenTree::Bool(pick.below(2) == 1),
+            Self::String {
+                hir, max_repeat, ..
+            } => {
+                let mut out = String::new();
+                generate_regex(hir, *max_repeat, pick, &mut out);
+                GenTree::Str(out)
+            }
+            Self::Const(value) => GenTree::Const(value.clone()),
+            Self::OneOf(options) => {
+                let index = pick.below(options.len() as u64) as usize;
+                GenTree::OneOf {
+                    index,
+                    value: Box::new(options[index].generate(pick)),
+                }
+            }
+            Self::List {
+                item,
+                min_len,
+                max_len,
+            } => {
+                let len = min_len + pick.below((max_len - min_len + 1) as u64) as usize;
+                GenTree::List((0..len).map(|_| item.generate(pick)).collect())
+            }
+            Self::Object(fields) => GenTree::Object(
+                fields
+                    .iter()
+                    .map(|(name, field)| (name.clone(), field.generate(pick)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Rebuilds a tree from a previously encoded JSON value, or `None` when the
+    /// value could not have been produced by this generator.
+    fn decode(&self, value: &serde_json::Value) -> Option<GenTree> {
+        match self {
+            Self::Int { min, max } => value
+                .as_i64()
+                .filter(|v| (*min..=*max).contains(v))
+                .map(GenTree::Int),
+            Self::Bool => value.as_bool().map(GenTree::Bool),
+            Self::String { matcher, .. } => value
+                .as_str()
+                .filter(|s| matcher.is_match(s))
+                .map(|s| GenTree::Str(s.to_string())),
+            Self::Const(expected) => (expected == value).then(|| GenTree::Const(value.clone())),
+            Self::OneOf(options) => options.iter().enumerate().find_map(|(index, option)| {
+                option.decode(value).map(|tree| GenTree::OneOf {
+                    index,
+                    value: Box::new(tree),
+                })
+            }),
+            Self::List {
+                item,
+                min_len,
+                max_len,
+            } => {
+                let items = value.as_array()?;
+                if items.len() < *min_len || items.len() > *max_len {
+                    return None;
+                }
+                items
+                    .iter()
+                    .map(|entry| item.decode(entry))
+                    .collect::<Option<Vec<_>>>()
+                    .map(GenTree::List)
+            }
+            Self::Object(fields) => {
+                let object = value.as_object()?;
+                fields
+                    .iter()
+                    .map(|(name, field)| Some((name.clone(), field.decode(object.get(name)?)?)))
+                    .collect::<Option<BTreeMap<_, _>>>()
+                    .map(GenTree::Object)
+            }
+        }
+    }
+
+    /// Simpler variants of `tree`, most aggressive first.
+    fn shrink(&self, tree: &GenTree) -> Vec<GenTree> {
+        match (self, tree) {
+            (Self::Int { min, max }, GenTree::Int(value)) => {
+                shrink_int(*value, int_shrink_target(*min, *max))
+                    .into_iter()
+                    .map(GenTree::Int)
+                    .collect()
+            }
+            (Self::Bool, GenTree::Bool(true)) => vec![GenTree::Bool(false)],
+            (Self::String { matcher, .. }, GenTree::Str(value)) => shrink_string(value)
+                .into_iter()
+                .filter(|candidate| matcher.is_match(candidate))
+                .map(GenTree::Str)
+                .collect(),
+            (Self::OneOf(options), GenTree::OneOf { index, value }) => {
+                let mut out = (0..*index)
+                    .map(|earlier| GenTree::OneOf {
+                        index: earlier,
+                        value: Box::new(options[earlier].generate(&mut Pick::Minimal)),
+                    })
+                    .collect::<Vec<_>>();
+                out.extend(
+                    options[*index]
+                        .shrink(value)
+                        .into_iter()
+                        .map(|inner| GenTree::OneOf {
+                            index: *index,
+                            value: Box::new(inner),
+                        }),
+                );
+                out
+            }
+            (Self::List { item, min_len, .. }, GenTree::List(items)) => {
+                let mut out = Vec::new();
+                if items.len() > *min_len {
+                    out.push(GenTree::List(items[..*min_len].to_vec()));
+                    let half = items.len() / 2;
+                    if half > *min_len && half < items.len() {
+                        out.push(GenTree::List(items[..half].to_vec()));
+                        out.push(GenTree::List(items[items.len() - half..].to_vec()));
+                    }
+                    for index in 0..items.len() {
+                        let mut fewer = items.clone();
+                        fewer.remove(index);
+                        out.push(GenTree::List(fewer));
+                    }
+                }
+                for (index, entry) in items.iter().enumerate() {
+                    for candidate in item.shrink(entry) {
+                        let mut next = items.clone();
+                        next[index] = candidate;
+                        out.push(GenTree::List(next));
+                    }
+                }
+                out.dedup();
+                out
+            }
+            (Self::Object(fields), GenTree::Object(values)) => {
+                let mut out = Vec::new();
+                for (name, field) in fields {
+                    let Some(current) = values.get(name) else {
+                        continue;
+                    };
+                    for candidate in field.shrink(current) {
+                        let mut next = values.clone();
+                        next.insert(name.clone(), candidate);
+                        out.push(GenTree::Object(next));
+                    }
+                }
+                out
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A scenario with `generators` bindings; the remaining document is a template
+/// instantiated once per input.
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyScenario {
+    generators: BTreeMap<String, Generator>,
+    template: serde_json::Value,
+}
+
+/// Returns `None` when the scenario declares no generators.
+pub(crate) fn load_property_scenario(path: &Path) -> FozzyResult<Option<PropertyScenario>> {
+    let bytes = std::fs::read(path)?;
+    let mut doc: serde_json::Value = serde_json::from_slice(&bytes).map_err(|err| {
+        FozzyError::Scenario(format!(
+            "failed to parse scenario {}: {err}",
+            path.display()
+        ))
+    })?;
+    let Some(raw) = doc.as_object_mut().and_then(|obj| obj.remove("generators")) else {
+        return Ok(None);
+    };
+    let specs: BTreeMap<String, GeneratorSpec> = serde_json::from_value(raw).map_err(|err| {
+        FozzyError::Scenario(format!(
+            "invalid generators in scenario {}: {err}",
+            path.display()
+        ))
+    })?;
+    if specs.is_empty() {
+        return Ok(None);
+    }
+    let generators = specs
+        .iter()
+        .map(|(name, spec)| Ok((name.clone(), Generator::compile(name, spec)?)))
+        .collect::<FozzyResult<_>>()?;
+    Ok(Some(PropertyScenario {
+        generators,
+        template: doc,
+    }))
+}
+
+impl PropertyScenario {
+    pub(crate) fn generate(&self, rng: &mut ChaCha20Rng) -> Bindings {
+        let mut pick = Pick::Random(rng);
+        self.generators
+            .iter()
+            .map(|(name, generator)| (name.clone(), generator.generate(&mut pick)))
+            .collect()
+    }
+
+    /// Decodes bindings written by [`encode_bindings`]; any other input (for example
+    /// raw corpus bytes) seeds a fresh deterministic sample instead.
+    pub(crate) fn bindings_from_input(&self, input: &[u8]) -> Bindings {
+        serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(input)
+            .ok()
+            .and_then(|values| {
+                self.generators
+                    .iter()
+                    .map(|(name, generator)| {
+                        Some((name.clone(), generator.decode(values.get(name)?)?))
+                    })
+                    .collect::<Option<Bindings>>()
+            })
+            .unwrap_or_else(|| self.generate(&mut rng_from_seed(seed_from_input(input))))
+    }
+
+    pub(crate) fn instantiate(&self, bindings: &Bindings) -> FozzyResult<ScenarioFile> {
+        let values = bindings
+            .iter()
+            .map(|(name, tree)| (name.clone(), tree.to_json()))
+            .collect::<BTreeMap<_, _>>();
+        let mut doc = self.template.clone();
+        substitute(&mut doc, &values);
+        serde_json::from_value(doc).map_err(|err| {
+            FozzyError::Scenario(format!(
+                "property scenario does not parse after binding generators: {err}"
+            ))
+        })
+    }
+
+    fn shrink_candidates(&self, bindings: &Bindings) -> Vec<Bindings> {
+        let mut out = Vec::new();
+        for (name, generator) in &self.generators {
+            let Some(current) = bindings.get(name) else {
+                continue;
+            };
+            for candidate in generator.shrink(current) {
+                let mut next = bindings.clone();
+                next.insert(name.clone(), candidate);
+                out.push(next);
+            }
+        }
+        out
+    }
+}
+
+pub(crate) fn encode_bindings(bindings: &Bindings) -> Vec<u8> {
+    let values = bindings
+        .iter()
+        .map(|(name, tree)| (name.clone(), tree.to_json()))
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::to_vec(&values).unwrap_or_default()
+}
+
+pub(crate) fn bindings_json(bindings: &Bindings) -> serde_json::Map<String, serde_json::Value> {
+    bindings
+        .iter()
+        .map(|(name, tree)| (name.clone(), tree.to_json()))
+        .collect()
+}
+
+/// Greedy value-tree shrinking: accept the first simpler binding set that keeps
+/// the failure, then restart from it.
+pub(crate) fn shrink_property_input(
+    config: &Config,
+    target: &FuzzTarget,
+    property: &PropertyScenario,
+    input: &[u8],
+    shrink: &crate::ShrinkTarget,
+    scenario_memory: &MemoryOptions,
+) -> FozzyResult<Vec<u8>> {
+    let mut best = property.bindings_from_input(input);
+    let mut attempts = 0usize;
+    while attempts < MAX_SHRINK_ATTEMPTS {
+        let mut candidates = property.shrink_candidates(&best);
+        candidates.truncate(MAX_SHRINK_ATTEMPTS - attempts);
+        let accepted = shrink.first_accepted(&candidates, None, |candidate| {
+            let encoded = encode_bindings(candidate);
+            let exec = execute_target(config, target, &encoded, scenario_memory)?;
+            Ok(shrink
+                .matches(exec.status, &exec.findings)
+                .then_some(encoded))
+        })?;
+        let Some((pos, encoded)) = accepted else {
+            break;
+        };
+        attempts += pos + 1;
+        shrink.record(
+            "bindings",
+            "bytes",
+            encode_bindings(&best).len(),
+            encoded.len(),
+        );
+        best = candidates.swap_remove(pos);
+    }
+    Ok(encode_bindings(&best))
+}
+
+/// Replaces `"{{name}}"` placeholders in a scenario template with bound values.
+pub(crate) fn substitute(
+    value: &mut serde_json::Value,
+    bindings: &BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::String(text) => {
+            if let Some(name) = text
+                .strip_prefix("{{")
+                .and_then(|rest| rest.strip_suffix("}}"))
+                .map(str::trim)
+                && let Some(bound) = bindings.get(name)
+            {
+                *value = bound.clone();
+                return;
+            }
+            if text.contains("{{") {
+                for (name, bound) in bindings {
+                    let rendered = match bound {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    *text = text.replace(&format!("{{{{{name}}}}}"), &rendered);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute(item, bindings);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute(field, bindings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn int_shrink_target(min: i64, max: i64) -> i64 {
+    0i64.clamp(min, max)
+}
+
+fn shrink_int(value: i64, target: i64) -> Vec<i64> {
+    let mut out = Vec::new();
+    let mut delta = value as i128 - target as i128;
+    while delta != 0 {
+        out.push((value as i128 - delta) as i64);
+        delta /= 2;
+    }
+    out
+}
+
+fn shrink_string(value: &str) -> Vec<String> {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut out = Vec::new();
+    if chars.is_empty() {
+        return out;
+    }
+    out.push(String::new());
+    if chars.len() > 1 {
+        out.push(chars[..chars.len() / 2].iter().collect());
+        out.push(chars[chars.len() / 2..].iter().collect());
+    }
+    for index in 0..chars.len() {
+        let mut fewer = chars.clone();
+        fewer.remove(index);
+        out.push(fewer.into_iter().collect());
+    }
+    for (index, ch) in chars.iter().enumerate() {
+        for simpler in ['a', '0', ' '] {
+            if simpler < *ch {
+                let mut next = chars.clone();
+                next[index] = simpler;
+                out.push(next.into_iter().collect());
+                break;
+            }
+        }
+    }
+    out.dedup();
+    out
+}
+
+/// Samples a string matching `pattern`; `None` when the pattern does not parse.
+pub(crate) fn sample_regex(pattern: &str, rng: &mut ChaCha20Rng) -> Option<String> {
+    let hir = regex_syntax::ParserBuilder::new()
+        .build()
+        .parse(pattern)
+        .ok()?;
+    let mut out = String::new();
+    generate_regex(&hir, DEFAULT_MAX_REPEAT, &mut Pick::Random(rng), &mut out);
+    Some(out)
+}
+
+fn generate_regex(hir: &Hir, max_repeat: u32, pick: &mut Pick<'_>, out: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(Class::Unicode(class)) => {
+            let total = class
+                .ranges()
+                .iter()
+                .map(|range| u64::from(range.end()) - u64::from(range.start()) + 1)
+                .sum::<u64>();
+            let mut offset = pick.below(total);
+            for range in class.ranges() {
+                let width = u64::from(range.end()) - u64::from(range.start()) + 1;
+                if offset < width {
+                    if let Some(ch) = char::from_u32(u32::from(range.start()) + offset as u32) {
+                        out.push(ch);
+                    }
+                    return;
+                }
+                offset -= width;
+            }
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let total = class
+                .ranges()
+                .iter()
+                .map(|range| u64::from(range.end() - range.start()) + 1)
+                .sum::<u64>();
+            let mut offset = pick.below(total);
+            for range in class.ranges() {
+                let width = u64::from(range.end() - range.start()) + 1;
+                if offset < width {
+                    out.push(char::from(range.start() + offset as u8));
+                    return;
+                }
+                offset -= width;
+            }
+        }
+        HirKind::Repetition(repetition) => {
+            let max = repetition
+                .max
+                .unwrap_or_else(|| repetition.min.saturating_add(max_repeat));
+            let count = repetition.min + pick.below(u64::from(max - repetition.min) + 1) as u32;
+            for _ in 0..count {
+                generate_regex(&repetition.sub, max_repeat, pick, out);
+            }
+        }
+        HirKind::Capture(capture) => generate_regex(&capture.sub, max_repeat, pick, out),
+        HirKind::Concat(parts) => {
+            for part in parts {
+                generate_regex(part, max_repeat, pick, out);
+            }
+        }
+        HirKind::Alternation(options) => {
+            let index = pick.below(options.len() as u64) as usize;
+            generate_regex(&options[index], max_repeat, pick, out);
+        }
+    }
+}
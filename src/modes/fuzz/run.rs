@@ -14,10 +14,12 @@ use crate::{
 use crate::{FozzyError, FozzyResult};
 
 use super::{
-    FuzzCoverageStats, FuzzMode, FuzzOptions, FuzzTarget, crash_trace_output_path, execute_target,
-    fuzz_exec_memory, fuzz_trace_memory_options, gen_seed, heap_budget_policy, hex_decode,
-    load_corpus, minimize_input, mutate_bytes, persist_corpus_input, persist_crash_input,
-    persist_crash_min_input, rng_from_seed, should_emit_heavy_artifacts, target_string,
+    FuzzCoverageStats, FuzzMode, FuzzOptions, FuzzTarget, crash_trace_output_path, encode_bindings,
+    execute_target, fuzz_exec_memory, fuzz_trace_memory_options, gen_seed, heap_budget_policy,
+    hex_decode, load_corpus, load_grammar_scenario, load_input_trees, load_property_scenario,
+    minimize_input, mutate_bytes, persist_corpus_input, persist_crash_input,
+    persist_crash_min_input, persist_input_tree, rng_from_seed, should_emit_heavy_artifacts,
+    shrink_grammar_input, target_string,
 };
 
 type LastExec = (
@@ -71,6 +73,25 @@ pub fn fuzz(
     };
     let max_runs = opt.runs.unwrap_or(u64::MAX);
 
+    let property = match (opt.mode, target) {
+        (FuzzMode::Property, FuzzTarget::Scenario { path }) => load_property_scenario(path)?,
+        _ => None,
+    };
+    let grammar = match (&property, target) {
+        (None, FuzzTarget::Scenario { path }) => load_grammar_scenario(path)?,
+        _ => None,
+    };
+    let mut trees = Vec::new();
+    if let Some(grammar) = grammar.as_ref() {
+        trees = load_input_trees(&corpus_dir)?
+            .into_iter()
+            .filter(|tree| grammar.grammar.accepts(tree))
+            .collect();
+        if trees.is_empty() {
+            trees.push(grammar.grammar.generate(&mut rng));
+        }
+    }
+
     let mut corpus = load_corpus(&corpus_dir)?;
     if corpus.is_empty() {
         corpus.push(Vec::new());
@@ -91,6 +112,10 @@ pub fn fuzz(
         None
     };
 
+    let mut stream = crate::ReporterEventStream::for_reporter(opt.reporter, &run_id);
+    let target_label = target_string(target);
+    stream.run_started(RunMode::Fuzz, seed, &started_at)?;
+
     let mut executed = 0u64;
     while executed < max_runs {
         if let Some(deadline) = deadline
@@ -99,10 +124,29 @@ pub fn fuzz(
             break;
         }
 
-        let base = &corpus[(rng.next_u64() as usize) % corpus.len()];
-        let mut input = base.clone();
-        mutate_bytes(&mut input, &mut rng, opt.max_input_bytes);
+        let mut tree = None;
+        let input = match (property.as_ref(), grammar.as_ref()) {
+            (Some(property), _) => encode_bindings(&property.generate(&mut rng)),
+            (None, Some(grammar)) => {
+                let base = &trees[(rng.next_u64() as usize) % trees.len()];
+                let mut next = grammar.grammar.mutate(base, &mut rng, &trees);
+                if next.text().len() > opt.max_input_bytes {
+                    next = base.clone();
+                }
+                let input = next.text().into_bytes();
+                tree = Some(next);
+                input
+            }
+            (None, None) => {
+                let base = &corpus[(rng.next_u64() as usize) % corpus.len()];
+                let mut input = base.clone();
+                mutate_bytes(&mut input, &mut rng, opt.max_input_bytes);
+                input
+            }
+        };
 
+        stream.scenario_started(&target_label, Some(executed), seed)?;
+        let exec_started = Instant::now();
         let mut exec = execute_target(config, target, &input, &opt.memory)?;
         if let Some(mem) = memory_state.as_mut() {
             let outcome = mem.allocate(
@@ -125,6 +169,17 @@ pub fn fuzz(
                 let _ = mem.free(id, executed);
             }
         }
+        stream.scenario_finished(
+            crate::ScenarioEvents {
+                path: &target_label,
+                iteration: Some(executed),
+                seed,
+                events: &exec.events,
+            },
+            exec.status,
+            exec_started.elapsed(),
+            &exec.findings,
+        )?;
         last_exec = Some((
             input.clone(),
             exec.events.clone(),
@@ -149,6 +204,10 @@ pub fn fuzz(
             if matches!(opt.mode, FuzzMode::Coverage) {
                 corpus.push(input.clone());
                 persist_corpus_input(&corpus_dir, &input)?;
+                if let Some(tree) = tree.as_ref() {
+                    persist_input_tree(&corpus_dir, &input, tree)?;
+                    trees.push(tree.clone());
+                }
             }
         }
 
@@ -157,6 +216,9 @@ pub fn fuzz(
             findings.extend(exec.findings.clone());
 
             let _crash_path = persist_crash_input(&corpus_dir, &input)?;
+            if let Some(tree) = tree.as_ref() {
+                persist_input_tree(&corpus_dir, &input, tree)?;
+            }
             let report_path = artifacts_dir.join("report.json");
             let finished_at = wall_time_iso_utc();
             let (duration_ms, duration_ns) = crate::duration_fields(started.elapsed());
@@ -182,6 +244,7 @@ pub fn fuzz(
                 tests: None,
                 memory: crash_memory.as_ref().map(|memory| memory.summary.clone()),
                 findings: exec.findings.clone(),
+                retries: Vec::new(),
             };
             let mut budget_trace = TraceFile::new_fuzz(
                 target_string(target),
@@ -197,8 +260,6 @@ pub fn fuzz(
                 summary.findings = crate::collapse_findings(summary.findings.clone());
             }
 
-            write_reporter_artifacts(&summary, &artifacts_dir, opt.reporter)?;
-
             let requested_trace_out = crash_trace_output_path(
                 opt.record_trace_to.as_deref(),
                 &artifacts_dir,
@@ -214,7 +275,10 @@ pub fn fuzz(
                 summary.clone(),
             );
             trace.memory = crash_memory.as_ref().map(|memory| memory.to_trace());
-            crate::write_trace_to_target(&trace, &trace_out)?;
+            if let Some(redactor) = crate::Redactor::for_record(config)? {
+                redactor.redact_trace(&mut trace)?;
+            }
+            crate::write_trace_to_target(&trace, &trace_out, config.trace_encoding)?;
             crash_trace_path = Some(trace_out.clone());
             let emit_heavy = should_emit_heavy_artifacts(exec.status, true)
                 || matches!(opt.profile_capture, ProfileCaptureLevel::Full);
@@ -243,15 +307,45 @@ pub fn fuzz(
             )?;
 
             if opt.minimize || opt.shrink {
-                let minimized = minimize_input(
-                    config,
-                    target,
-                    &input,
-                    opt.max_input_bytes,
-                    exec.status,
-                    &opt.memory,
-                )?;
+                let shrink = crate::ShrinkTarget::status(exec.status);
+                let shrunk_tree = match (grammar.as_ref(), tree.as_ref()) {
+                    (Some(grammar), Some(tree)) => shrink_grammar_input(
+                        config,
+                        target,
+                        grammar,
+                        &input,
+                        Some(tree),
+                        &shrink,
+                        &opt.memory,
+                    )?,
+                    _ => None,
+                };
+                let minimized = match shrunk_tree {
+                    Some((minimized, min_tree)) => {
+                        persist_input_tree(&corpus_dir, &minimized, &min_tree)?;
+                        minimized
+                    }
+                    None => minimize_input(
+                        config,
+                        target,
+                        &input,
+                        opt.max_input_bytes,
+                        &shrink,
+                        &opt.memory,
+                    )?,
+                };
                 let _min_path = persist_crash_min_input(&corpus_dir, &minimized)?;
+                if property.is_some() {
+                    findings.push(Finding {
+                        kind: FindingKind::Assertion,
+                        title: "property_counterexample".to_string(),
+                        message: format!(
+                            "shrunk failing bindings: {}",
+                            String::from_utf8_lossy(&minimized)
+                        ),
+                        location: None,
+                    });
+                }
             }
 
             if opt.crash_only {
@@ -296,6 +390,7 @@ pub fn fuzz(
             .as_ref()
             .map(|memory| memory.summary.clone()),
         findings,
+        retries: Vec::new(),
     };
     let (profile_input, profile_events, profile_status, profile_findings, profile_memory) =
         last_exec
@@ -358,7 +453,10 @@ pub fn fuzz(
         let mut trace = TraceFile::new_fuzz(target_string(target), &input, events, trace_summary);
         trace.memory =
             exec_memory.or_else(|| effective_memory.as_ref().map(|memory| memory.to_trace()));
-        crate::write_trace_to_target(&trace, &written)?;
+        if let Some(redactor) = crate::Redactor::for_record(config)? {
+            redactor.redact_trace(&mut trace)?;
+        }
+        crate::write_trace_to_target(&trace, &written, config.trace_encoding)?;
     }
     profile_trace.summary = {
         let mut summary_for_profile = summary.clone();
@@ -388,14 +486,19 @@ pub fn fuzz(
             &artifacts_dir,
         )?);
     }
+    write_reporter_artifacts(&summary, &artifacts_dir, opt.reporter)?;
     write_summary_report(
         &summary,
         &report_path,
         &artifacts_dir,
         profile_metadata.as_ref(),
     )?;
+    stream.run_finished(&summary)?;
 
-    Ok(crate::RunResult { summary })
+    Ok(crate::RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
 }
 
 pub fn replay_fuzz_trace(
@@ -483,6 +586,7 @@ pub fn replay_fuzz_trace(
         tests: None,
         memory: exec.memory.as_ref().map(|memory| memory.summary.clone()),
         findings,
+        retries: Vec::new(),
     };
     let mut profile_trace = TraceFile::new_fuzz(
         fuzz.target.clone(),
@@ -524,7 +628,10 @@ pub fn replay_fuzz_trace(
         &artifacts_dir,
         profile_metadata.as_ref(),
     )?;
-    Ok(crate::RunResult { summary })
+    Ok(crate::RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
 }
 
 pub fn shrink_fuzz_trace(
@@ -533,7 +640,7 @@ pub fn shrink_fuzz_trace(
     opt: &crate::ShrinkOptions,
 ) -> FozzyResult<crate::ShrinkResult> {
     let trace = TraceFile::read_json(trace_path.as_path())?;
-    let target_status = trace.summary.status;
+    let shrink = crate::ShrinkTarget::from_trace(&trace, opt)?;
     let Some(fuzz) = trace.fuzz.as_ref() else {
         return Err(FozzyError::Trace("not a fuzz trace".to_string()));
     };
@@ -552,7 +659,7 @@ pub fn shrink_fuzz_trace(
         &target,
         &input,
         1024 * 1024,
-        target_status,
+        &shrink,
         &fuzz_trace_memory_options(&trace),
     )?;
     let exec = execute_target(
@@ -584,6 +691,7 @@ pub fn shrink_fuzz_trace(
         tests: None,
         memory: exec.memory.as_ref().map(|memory| memory.summary.clone()),
         findings: exec.findings.clone(),
+        retries: Vec::new(),
     };
 
     let mut trace_out = TraceFile::new_fuzz(
@@ -593,15 +701,22 @@ pub fn shrink_fuzz_trace(
         summary.clone(),
     );
     trace_out.memory = exec.memory.clone();
-    trace_out.write_json(&out_path).map_err(|err| {
-        FozzyError::Trace(format!(
-            "failed to write shrunk fuzz trace to {}: {err}",
-            out_path.display()
-        ))
-    })?;
-
+    trace_out
+        .write(&out_path, config.trace_encoding)
+        .map_err(|err| {
+            FozzyError::Trace(format!(
+                "failed to write shrunk fuzz trace to {}: {err}",
+                out_path.display()
+            ))
+        })?;
+
+    let lineage = shrink.write_lineage(trace_path.as_path(), &out_path)?;
     Ok(crate::ShrinkResult {
         out_trace_path: out_path.to_string_lossy().to_string(),
-        result: crate::RunResult { summary },
+        result: crate::RunResult {
+            summary,
+            warnings: Vec::new(),
+        },
+        lineage,
     })
 }
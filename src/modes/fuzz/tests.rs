@@ -1,5 +1,9 @@
+use super::property::encode_bindings;
+use super::triage::{crash_stack_hash, normalize_crash_message};
 use super::{
-    FuzzTarget, crash_trace_output_path, execute_target, replay_fuzz_trace, with_numeric_suffix,
+    FuzzTarget, FuzzTriageOptions, crash_trace_output_path, execute_target, load_grammar_scenario,
+    load_property_scenario, minimize_input, replay_fuzz_trace, rng_from_seed, triage_crashes,
+    with_numeric_suffix, write_openapi_scenarios,
 };
 use crate::{
     CURRENT_TRACE_VERSION, Config, MemoryOptions, ProfileCaptureLevel, Reporter, RunIdentity,
@@ -29,8 +33,11 @@ fn test_config(root: &Path) -> Config {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     }
 }
 
@@ -155,8 +162,14 @@ fn replay_fuzz_trace_uses_replayed_memory_summary() {
             tests: None,
             memory: exec.memory.as_ref().map(|memory| memory.summary.clone()),
             findings: exec.findings.clone(),
+            retries: Vec::new(),
         },
+        redaction: None,
+        migrations: Vec::new(),
+        counterfactual: None,
+        snapshots: Vec::new(),
         checksum: None,
+        signature: None,
     };
     trace.write_json(&trace_path).expect("write trace");
     let replayed = replay_fuzz_trace(
@@ -169,6 +182,7 @@ fn replay_fuzz_trace_uses_replayed_memory_summary() {
             dump_events: false,
             profile_capture: ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("replay fuzz trace");
@@ -181,3 +195,507 @@ fn replay_fuzz_trace_uses_replayed_memory_summary() {
         Some(256)
     );
 }
+
+#[test]
+fn triage_buckets_equivalent_crashes_and_writes_reproducer() {
+    let root = temp_workspace("triage-buckets");
+    let scenario = root.join("crash.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{
+  "version": 1,
+  "name": "always-fails",
+  "steps": [
+    { "type": "fail", "message": "boom" }
+  ]
+}"#,
+    )
+    .expect("write scenario");
+    let crash_dir = root.join("crashes");
+    std::fs::create_dir_all(&crash_dir).expect("crash dir");
+    std::fs::write(crash_dir.join("crash-a.bin"), b"alpha-input").expect("write crash");
+    std::fs::write(crash_dir.join("crash-b.bin"), b"bb").expect("write crash");
+    std::fs::write(crash_dir.join("crash-b.min.bin"), b"b").expect("write min crash");
+
+    let report = triage_crashes(
+        &test_config(&root),
+        &FuzzTarget::Scenario { path: scenario },
+        &FuzzTriageOptions {
+            crash_dir: crash_dir.clone(),
+            out_dir: None,
+            max_input_bytes: 4096,
+            minimize: true,
+        },
+    )
+    .expect("triage");
+
+    assert_eq!(report.scanned, 2, "min.bin reproducers are not rescanned");
+    assert_eq!(report.reproduced, 2);
+    assert_eq!(report.buckets.len(), 1);
+    let bucket = &report.buckets[0];
+    assert_eq!(bucket.count, 2);
+    assert_eq!(bucket.original_bytes, 2);
+    assert!(bucket.reproducer_bytes <= 2);
+    assert!(Path::new(&bucket.reproducer).exists());
+    assert!(crash_dir.join("triage").join("triage.json").exists());
+}
+
+#[test]
+fn crash_message_normalization_strips_run_specific_noise() {
+    assert_eq!(
+        normalize_crash_message("read of size 8 at 0x7ffd12ab  (repeated 3 times)"),
+        "read of size N at 0x?"
+    );
+    assert_eq!(
+        normalize_crash_message("index 12 out of bounds"),
+        normalize_crash_message("index 407 out of bounds")
+    );
+}
+
+#[test]
+fn crash_stack_hash_ignores_addresses_and_symbol_hashes() {
+    let asan_a = "ERROR: AddressSanitizer\n    #0 0x55d1c3 in parse_header /src/p.c:41:7\n    #1 0x55d1ff in main /src/m.c:9";
+    let asan_b = "ERROR: AddressSanitizer\n    #0 0x41aa00 in parse_header /src/p.c:41:7\n    #1 0x41ab10 in main /src/m.c:9";
+    let rust = "   0: parser::parse_header::h0123456789abcdef\n   1: main::h00ff";
+    let hash = |text: &str| crash_stack_hash(&[], std::iter::once(&text.to_string()));
+    assert!(hash(asan_a).is_some());
+    assert_eq!(hash(asan_a), hash(asan_b));
+    assert_ne!(hash(asan_a), hash(rust));
+    assert_eq!(hash("no frames here"), None);
+}
+
+fn write_property_scenario(root: &Path) -> PathBuf {
+    let path = root.join("property.fozzy.json");
+    std::fs::write(
+        &path,
+        r#"{
+  "version": 1,
+  "name": "property",
+  "generators": {
+    "n": { "type": "int", "min": -50, "max": 1000 },
+    "name": { "type": "string", "regex": "[a-z]{2,6}-[0-9]+" },
+    "flag": { "type": "one_of", "options": [ { "type": "const", "value": true }, { "type": "bool" } ] },
+    "items": { "type": "list", "item": { "type": "int", "min": 1, "max": 9 }, "max_len": 4 }
+  },
+  "steps": [
+    { "type": "trace_event", "name": "input-{{name}}", "fields": { "items": "{{items}}" } },
+    { "type": "assert_ok", "value": "{{flag}}", "msg": "flag for {{name}} n={{n}}" }
+  ]
+}"#,
+    )
+    .expect("write scenario");
+    path
+}
+
+#[test]
+fn property_generators_sample_deterministically_within_spec() {
+    let root = temp_workspace("property-sample");
+    let path = write_property_scenario(&root);
+    let property = load_property_scenario(&path)
+        .expect("load")
+        .expect("generators declared");
+    let matcher = regex::Regex::new("^[a-z]{2,6}-[0-9]+$").expect("regex");
+    for seed in 0..32 {
+        let first = encode_bindings(&property.generate(&mut rng_from_seed(seed)));
+        let second = encode_bindings(&property.generate(&mut rng_from_seed(seed)));
+        assert_eq!(first, second, "same seed must sample the same bindings");
+        let values: serde_json::Value = serde_json::from_slice(&first).expect("json bindings");
+        let n = values["n"].as_i64().expect("int");
+        assert!((-50..=1000).contains(&n));
+        assert!(matcher.is_match(values["name"].as_str().expect("string")));
+        assert!(values["items"].as_array().expect("list").len() <= 4);
+        assert_eq!(
+            encode_bindings(&property.bindings_from_input(&first)),
+            first,
+            "encoded bindings decode back to the same values"
+        );
+    }
+}
+
+#[test]
+fn property_list_generators_reject_unbounded_lengths() {
+    let root = temp_workspace("property-list-bound");
+    let path = root.join("huge.fozzy.json");
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{
+  "version": 1,
+  "name": "huge",
+  "generators": {{
+    "items": {{ "type": "list", "item": {{ "type": "bool" }}, "max_len": {} }}
+  }},
+  "steps": [ {{ "type": "trace_event", "name": "items" }} ]
+}}"#,
+            usize::MAX
+        ),
+    )
+    .expect("write scenario");
+    let err = load_property_scenario(&path).expect_err("max_len above the limit is rejected");
+    assert!(err.to_string().contains("exceeds the limit"), "{err}");
+}
+
+#[test]
+fn property_failures_shrink_on_generator_tree() {
+    let root = temp_workspace("property-shrink");
+    let path = write_property_scenario(&root);
+    let failing = br#"{"flag":false,"items":[7,3,9],"n":871,"name":"qwerty-98231"}"#;
+    let target = FuzzTarget::Scenario { path };
+    let config = test_config(&root);
+    let memory = MemoryOptions {
+        track: false,
+        artifacts: false,
+        ..MemoryOptions::default()
+    };
+    let exec = execute_target(&config, &target, failing, &memory).expect("exec");
+    assert_eq!(exec.status, crate::ExitStatus::Fail);
+    assert_eq!(exec.events[0].name, "property_bindings");
+
+    let shrunk = minimize_input(
+        &config,
+        &target,
+        failing,
+        4096,
+        &crate::ShrinkTarget::status(exec.status),
+        &memory,
+    )
+    .expect("shrink");
+    let values: serde_json::Value = serde_json::from_slice(&shrunk).expect("json bindings");
+    assert_eq!(
+        values,
+        serde_json::json!({ "flag": false, "items": [], "n": 0, "name": "aa-0" })
+    );
+}
+
+fn write_grammar_scenario(root: &Path) -> PathBuf {
+    std::fs::write(
+        root.join("arith.ebnf"),
+        r#"(* integer arithmetic *)
+expr   = term { ( "+" | "-" ) term } ;
+term   = factor { "*" factor } ;
+factor = number | "(" expr ")" ;
+number = digit+ ;
+digit  = "0".."9" ;
+"#,
+    )
+    .expect("write grammar");
+    let path = root.join("grammar.fozzy.json");
+    std::fs::write(
+        &path,
+        r#"{
+  "version": 1,
+  "name": "grammar",
+  "grammar": { "file": "arith.ebnf", "bind": "expr", "max_depth": 6 },
+  "steps": [
+    { "type": "trace_event", "name": "expr", "fields": { "text": "{{expr}}" } },
+    { "type": "assert_eq_str", "a": "{{expr}}", "b": "1", "msg": "evaluated {{expr}}" }
+  ]
+}"#,
+    )
+    .expect("write scenario");
+    path
+}
+
+#[test]
+fn grammar_generation_yields_parseable_derivations() {
+    let root = temp_workspace("grammar-generate");
+    let path = write_grammar_scenario(&root);
+    let scenario = load_grammar_scenario(&path)
+        .expect("load")
+        .expect("grammar declared");
+    let grammar = &scenario.grammar;
+    for seed in 0..32 {
+        let tree = grammar.generate(&mut rng_from_seed(seed));
+        assert_eq!(tree, grammar.generate(&mut rng_from_seed(seed)));
+        assert!(
+            grammar.accepts(&tree),
+            "generated tree must follow the grammar"
+        );
+        let text = tree.text();
+        let parsed = grammar.parse(&text).expect("generated sentence parses");
+        assert!(grammar.accepts(&parsed));
+        assert_eq!(parsed.text(), text);
+
+        let mutated = grammar.mutate(
+            &tree,
+            &mut rng_from_seed(seed + 100),
+            std::slice::from_ref(&tree),
+        );
+        assert!(
+            grammar.accepts(&mutated),
+            "mutations stay inside the grammar"
+        );
+    }
+    assert!(grammar.parse("(1+").is_none());
+}
+
+#[test]
+fn json_grammars_and_invalid_grammars_are_reported() {
+    let root = temp_workspace("grammar-json");
+    std::fs::write(
+        root.join("digits.json"),
+        r#"{ "<start>": ["<digit>", "<digit><start>"], "<digit>": ["0", "1", "2"] }"#,
+    )
+    .expect("write grammar");
+    std::fs::write(root.join("loop.ebnf"), r#"a = "x" a ;"#).expect("write grammar");
+    std::fs::write(root.join("undefined.ebnf"), r#"a = b ;"#).expect("write grammar");
+    let scenario = |file: &str| {
+        let path = root.join(format!("{file}.fozzy.json"));
+        std::fs::write(
+            &path,
+            format!(
+                r#"{{"version":1,"name":"g","grammar":{{"file":"{file}"}},"steps":[{{"type":"trace_event","name":"{{{{input}}}}"}}]}}"#
+            ),
+        )
+        .expect("write scenario");
+        load_grammar_scenario(&path)
+    };
+
+    let digits = scenario("digits.json").expect("load").expect("grammar");
+    let tree = digits.grammar.generate(&mut rng_from_seed(7));
+    assert!(tree.text().chars().all(|ch| matches!(ch, '0' | '1' | '2')));
+    assert!(digits.grammar.parse("2101").is_some());
+
+    let looping = scenario("loop.ebnf").expect_err("no finite derivation");
+    assert!(
+        looping
+            .to_string()
+            .contains("never derives a finite string")
+    );
+    let undefined = scenario("undefined.ebnf").expect_err("undefined rule");
+    assert!(undefined.to_string().contains("undefined rule"));
+}
+
+#[test]
+fn grammar_failures_shrink_by_subtree_replacement() {
+    let root = temp_workspace("grammar-shrink");
+    let path = write_grammar_scenario(&root);
+    let target = FuzzTarget::Scenario { path };
+    let config = test_config(&root);
+    let memory = MemoryOptions {
+        track: false,
+        artifacts: false,
+        ..MemoryOptions::default()
+    };
+    let failing = b"(12+3)*45-(6*7)";
+    let exec = execute_target(&config, &target, failing, &memory).expect("exec");
+    assert_eq!(exec.status, crate::ExitStatus::Fail);
+
+    let shrunk = minimize_input(
+        &config,
+        &target,
+        failing,
+        4096,
+        &crate::ShrinkTarget::status(exec.status),
+        &memory,
+    )
+    .expect("shrink");
+    assert_eq!(shrunk, b"0");
+}
+
+const PETSTORE_SPEC: &str = r##"{
+  "openapi": "3.0.3",
+  "info": { "title": "pets", "version": "1" },
+  "servers": [{ "url": "SERVER_URL" }],
+  "paths": {
+    "/pets": {
+      "get": {
+        "operationId": "listPets",
+        "parameters": [
+          { "name": "limit", "in": "query", "required": true,
+            "schema": { "type": "integer", "minimum": 1, "maximum": 100 } }
+        ],
+        "responses": {
+          "200": { "content": { "application/json": {
+            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } } } } },
+          "400": { "description": "bad limit" }
+        }
+      },
+      "post": {
+        "operationId": "createPet",
+        "requestBody": { "required": true, "content": { "application/json": {
+          "schema": { "$ref": "#/components/schemas/Pet" } } } },
+        "responses": {
+          "201": { "content": { "application/json": {
+            "schema": { "$ref": "#/components/schemas/Pet" } } } }
+        }
+      }
+    },
+    "/pets/{petId}": {
+      "delete": {
+        "operationId": "deletePet",
+        "parameters": [{ "name": "petId", "in": "path", "schema": { "type": "integer" } }],
+        "responses": { "204": { "description": "deleted" } }
+      }
+    }
+  },
+  "components": { "schemas": { "Pet": {
+    "type": "object", "required": ["id", "name"],
+    "properties": {
+      "id": { "type": "integer", "minimum": 1 },
+      "name": { "type": "string", "minLength": 1, "maxLength": 32 }
+    }
+  } } }
+}"##;
+
+/// Petstore with three bugs: out-of-range `limit` crashes with 500, create
+/// returns a string id, and delete answers with an undocumented 418.
+fn spawn_petstore_server() -> (String, std::sync::mpsc::Sender<()>) {
+    use std::io::{Read as _, Write as _};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind http listener");
+    listener.set_nonblocking(true).expect("nonblocking");
+    let addr = listener.local_addr().expect("local addr");
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        let started = std::time::Instant::now();
+        while stop_rx.try_recv().is_err() && started.elapsed() < std::time::Duration::from_secs(60)
+        {
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(2));
+                    continue;
+                }
+            };
+            stream.set_nonblocking(false).expect("blocking stream");
+            let mut raw = Vec::new();
+            let mut buf = [0u8; 4096];
+            let head_end = loop {
+                let n = stream.read(&mut buf).unwrap_or(0);
+                if n == 0 {
+                    break None;
+                }
+                raw.extend_from_slice(&buf[..n]);
+                if let Some(pos) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break Some(pos + 4);
+                }
+            };
+            let Some(head_end) = head_end else { continue };
+            let head = String::from_utf8_lossy(&raw[..head_end]).to_string();
+            let content_length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            while raw.len() < head_end + content_length {
+                let n = stream.read(&mut buf).unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                raw.extend_from_slice(&buf[..n]);
+            }
+            let target = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+            let (status, body) = if head.starts_with("GET") {
+                let limit = target
+                    .split_once("limit=")
+                    .and_then(|(_, rest)| rest.split('&').next()?.parse::<i64>().ok());
+                match limit {
+                    None => ("400 Bad Request", String::new()),
+                    Some(limit) if !(1..=100).contains(&limit) => {
+                        ("500 Internal Server Error", String::new())
+                    }
+                    Some(_) => ("200 OK", r#"[{"id":1,"name":"rex"}]"#.to_string()),
+                }
+            } else if head.starts_with("POST") {
+                ("201 Created", r#"{"id":"seven","name":"rex"}"#.to_string())
+            } else {
+                ("418 I'm a teapot", String::new())
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (format!("http://{addr}"), stop_tx)
+}
+
+#[test]
+fn openapi_target_flags_server_errors_undocumented_statuses_and_schema_drift() {
+    let root = temp_workspace("openapi-fuzz");
+    let (url, stop) = spawn_petstore_server();
+    let spec = root.join("petstore.json");
+    std::fs::write(&spec, PETSTORE_SPEC.replace("SERVER_URL", &url)).expect("write spec");
+    let target: FuzzTarget = format!("openapi:{}", spec.display())
+        .parse()
+        .expect("openapi target");
+    let memory = MemoryOptions {
+        track: false,
+        artifacts: false,
+        ..MemoryOptions::default()
+    };
+
+    let scripted = execute_target(&test_config(&root), &target, b"seed", &memory);
+    assert!(
+        scripted
+            .expect_err("openapi needs host http")
+            .to_string()
+            .contains("--http-backend host")
+    );
+
+    let mut config = test_config(&root);
+    config.http_backend = crate::HttpBackend::Host;
+    let mut titles = std::collections::BTreeSet::new();
+    let mut variants = std::collections::BTreeSet::new();
+    for seed in 0u64..80 {
+        let exec = execute_target(&config, &target, &seed.to_le_bytes(), &memory).expect("exec");
+        assert_eq!(exec.events[0].name, "openapi_request");
+        variants.insert(
+            exec.events[0].fields["variant"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+        titles.extend(exec.findings.iter().map(|finding| finding.title.clone()));
+    }
+    let _ = stop.send(());
+    assert_eq!(
+        variants.into_iter().collect::<Vec<_>>(),
+        vec!["boundary", "invalid", "valid"]
+    );
+    for expected in [
+        "openapi_server_error",
+        "openapi_undocumented_status",
+        "openapi_response_schema",
+    ] {
+        assert!(titles.contains(expected), "missing {expected}: {titles:?}");
+    }
+    assert!(!titles.contains("openapi_request_failed"), "{titles:?}");
+}
+
+#[test]
+fn openapi_scenarios_are_emitted_per_operation() {
+    let root = temp_workspace("openapi-scenarios");
+    let spec = root.join("petstore.json");
+    std::fs::write(&spec, PETSTORE_SPEC.replace("SERVER_URL", "/relative")).expect("write spec");
+    let out = root.join("scenarios");
+    let err = write_openapi_scenarios(&spec, &out, None, false).expect_err("needs base url");
+    assert!(err.to_string().contains("--base-url"));
+
+    let report = write_openapi_scenarios(&spec, &out, Some("http://127.0.0.1:9/"), false)
+        .expect("write scenarios");
+    assert_eq!(report["written"].as_array().map(Vec::len), Some(3));
+    let create = std::fs::read_to_string(out.join("createpet.fozzy.json")).expect("scenario");
+    let doc: serde_json::Value = serde_json::from_str(&create).expect("json");
+    let step = &doc["steps"][0];
+    assert_eq!(step["type"], "http_request");
+    assert_eq!(step["path"], "http://127.0.0.1:9/pets");
+    assert_eq!(step["expect_status"], 201);
+    let body: serde_json::Value =
+        serde_json::from_str(step["body"].as_str().expect("body")).expect("json body");
+    assert!(body["id"].as_i64().is_some_and(|id| id >= 1));
+    for name in ["createpet", "listpets", "deletepet"] {
+        crate::Scenario::load_file(&crate::ScenarioPath::new(
+            out.join(format!("{name}.fozzy.json")),
+        ))
+        .expect("emitted scenario loads");
+    }
+
+    let again =
+        write_openapi_scenarios(&spec, &out, Some("http://127.0.0.1:9"), false).expect("rerun");
+    assert_eq!(again["skipped"].as_array().map(Vec::len), Some(3));
+}
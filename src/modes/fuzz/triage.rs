@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Config, ExitStatus, FindingKind, FozzyError, FozzyResult, MemoryOptions, TraceEvent};
+
+use super::exec::FuzzExec;
+use super::{FuzzTarget, execute_target, minimize_input, target_string};
+
+/// Number of leading stack frames folded into a crash stack hash.
+const STACK_HASH_FRAMES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct FuzzTriageOptions {
+    pub crash_dir: PathBuf,
+    pub out_dir: Option<PathBuf>,
+    pub max_input_bytes: usize,
+    pub minimize: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashSignature {
+    pub status: ExitStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<FindingKind>,
+    pub title: String,
+    #[serde(rename = "normalizedMessage")]
+    pub normalized_message: String,
+    #[serde(rename = "stackHash", skip_serializing_if = "Option::is_none")]
+    pub stack_hash: Option<String>,
+}
+
+impl CrashSignature {
+    pub fn id(&self) -> String {
+        let material = format!(
+            "{:?}|{:?}|{}|{}|{}",
+            self.status,
+            self.kind,
+            self.title,
+            self.normalized_message,
+            self.stack_hash.as_deref().unwrap_or_default()
+        );
+        blake3::hash(material.as_bytes()).to_hex()[..16].to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzTriageBucket {
+    pub id: String,
+    pub signature: CrashSignature,
+    pub count: usize,
+    pub inputs: Vec<String>,
+    pub reproducer: String,
+    #[serde(rename = "reproducerBytes")]
+    pub reproducer_bytes: usize,
+    #[serde(rename = "originalBytes")]
+    pub original_bytes: usize,
+    pub minimized: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzTriageReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub target: String,
+    #[serde(rename = "crashDir")]
+    pub crash_dir: String,
+    #[serde(rename = "reportPath")]
+    pub report_path: String,
+    pub scanned: usize,
+    pub reproduced: usize,
+    #[serde(
+        rename = "notReproduced",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub not_reproduced: Vec<String>,
+    pub buckets: Vec<FuzzTriageBucket>,
+}
+
+pub fn triage_crashes(
+    config: &Config,
+    target: &FuzzTarget,
+    opt: &FuzzTriageOptions,
+) -> FozzyResult<FuzzTriageReport> {
+    if !opt.crash_dir.is_dir() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "fuzz triage crash directory not found: {}",
+            opt.crash_dir.display()
+        )));
+    }
+    let inputs = crash_inputs(&opt.crash_dir)?;
+    if inputs.is_empty() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "fuzz triage found no crash inputs (*.bin) in {}",
+            opt.crash_dir.display()
+        )));
+    }
+
+    let memory = MemoryOptions {
+        track: false,
+        artifacts: false,
+        ..MemoryOptions::default()
+    };
+    let mut grouped = BTreeMap::<String, (CrashSignature, Vec<(PathBuf, Vec<u8>)>)>::new();
+    let mut not_reproduced = Vec::new();
+    for path in &inputs {
+        let bytes = std::fs::read(path)?;
+        let exec = execute_target(config, target, &bytes, &memory)?;
+        if exec.status == ExitStatus::Pass {
+            not_reproduced.push(path.to_string_lossy().to_string());
+            continue;
+        }
+        let signature = crash_signature(&exec);
+        grouped
+            .entry(signature.id())
+            .or_insert_with(|| (signature, Vec::new()))
+            .1
+            .push((path.clone(), bytes));
+    }
+
+    let out_dir = opt
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| opt.crash_dir.join("triage"));
+    std::fs::create_dir_all(&out_dir)?;
+
+    let mut buckets = Vec::with_capacity(grouped.len());
+    for (id, (signature, mut members)) in grouped {
+        members.sort_by(|(a_path, a), (b_path, b)| a.len().cmp(&b.len()).then(a_path.cmp(b_path)));
+        let smallest = members[0].1.clone();
+        let mut reproducer = smallest.clone();
+        let mut minimized = false;
+        if opt.minimize {
+            let candidate = minimize_input(
+                config,
+                target,
+                &smallest,
+                opt.max_input_bytes,
+                &crate::ShrinkTarget::status(signature.status),
+                &memory,
+            )?;
+            // `minimize_input` only preserves the failure class; keep the reduction
+            // only when it still lands in the same bucket.
+            if candidate != smallest {
+                let exec = execute_target(config, target, &candidate, &memory)?;
+                if exec.status != ExitStatus::Pass && crash_signature(&exec) == signature {
+                    reproducer = candidate;
+                    minimized = true;
+                }
+            }
+        }
+
+        let reproducer_path = out_dir.join(format!("bucket-{id}.min.bin"));
+        std::fs::write(&reproducer_path, &reproducer)?;
+        buckets.push(FuzzTriageBucket {
+            id,
+            signature,
+            count: members.len(),
+            inputs: members
+                .iter()
+                .map(|(path, _)| path.to_string_lossy().to_string())
+                .collect(),
+            reproducer: reproducer_path.to_string_lossy().to_string(),
+            reproducer_bytes: reproducer.len(),
+            original_bytes: smallest.len(),
+            minimized,
+        });
+    }
+    buckets.sort_by(|a, b| b.count.cmp(&a.count).then(a.id.cmp(&b.id)));
+
+    let report_path = out_dir.join("triage.json");
+    let report = FuzzTriageReport {
+        schema_version: "fozzy.fuzz_triage.v1".to_string(),
+        target: target_string(target),
+        crash_dir: opt.crash_dir.to_string_lossy().to_string(),
+        report_path: report_path.to_string_lossy().to_string(),
+        scanned: inputs.len(),
+        reproduced: inputs.len() - not_reproduced.len(),
+        not_reproduced,
+        buckets,
+    };
+    std::fs::write(&report_path, serde_json::to_vec_pretty(&report)?)?;
+    Ok(report)
+}
+
+pub(crate) fn crash_signature(exec: &FuzzExec) -> CrashSignature {
+    let primary = exec.findings.first();
+    CrashSignature {
+        status: exec.status,
+        kind: primary.map(|finding| finding.kind.clone()),
+        title: primary
+            .map(|finding| finding.title.clone())
+            .unwrap_or_else(|| format!("{:?}", exec.status).to_ascii_lowercase()),
+        normalized_message: primary
+            .map(|finding| normalize_crash_message(&finding.message))
+            .unwrap_or_default(),
+        stack_hash: crash_stack_hash(&exec.events, exec.findings.iter().map(|f| &f.message)),
+    }
+}
+
+/// Strips run-specific noise (addresses, counters, repeat suffixes) so equivalent
+/// crashes share one signature.
+pub(crate) fn normalize_crash_message(message: &str) -> String {
+    let message = match message.rfind(" (repeated ") {
+        Some(idx) if message.ends_with(" times)") => &message[..idx],
+        _ => message,
+    };
+    let mut out = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '0' && matches!(chars.peek(), Some('x') | Some('X')) {
+            chars.next();
+            while chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                chars.next();
+            }
+            out.push_str("0x?");
+        } else if ch.is_ascii_digit() {
+            while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                chars.next();
+            }
+            out.push('N');
+        } else if ch.is_whitespace() {
+            while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                chars.next();
+            }
+            out.push(' ');
+        } else {
+            out.push(ch);
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Hashes the top frames of a sanitizer report or backtrace found in proc stderr
+/// events or finding messages.
+pub(crate) fn crash_stack_hash<'a>(
+    events: &'a [TraceEvent],
+    messages: impl Iterator<Item = &'a String>,
+) -> Option<String> {
+    let stderr = events
+        .iter()
+        .filter(|event| event.name == "proc_spawn")
+        .filter_map(|event| event.fields.get("stderr").and_then(|v| v.as_str()));
+    for text in stderr.chain(messages.map(String::as_str)) {
+        let frames = stack_frames(text);
+        if !frames.is_empty() {
+            let joined = frames
+                .iter()
+                .take(STACK_HASH_FRAMES)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Some(blake3::hash(joined.as_bytes()).to_hex()[..16].to_string());
+        }
+    }
+    None
+}
+
+fn stack_frames(text: &str) -> Vec<String> {
+    let mut frames = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        // Sanitizer frames: `#0 0x55d1c3 in parse_header /src/parser.c:41:7`
+        if let Some(rest) = line.strip_prefix('#')
+            && rest.starts_with(|c: char| c.is_ascii_digit())
+        {
+            if let Some((_, tail)) = rest.split_once(" in ") {
+                let function = tail.split_whitespace().next().unwrap_or_default();
+                frames.push(function.to_string());
+            }
+            continue;
+        }
+        // Rust backtrace frames: `3: parser::parse_header`
+        if let Some((index, tail)) = line.split_once(": ")
+            && !index.is_empty()
+            && index.chars().all(|c| c.is_ascii_digit())
+        {
+            let function = tail.split_whitespace().next().unwrap_or_default();
+            let function = function
+                .rsplit_once("::h")
+                .filter(|(_, hash)| hash.chars().all(|c| c.is_ascii_hexdigit()))
+                .map(|(head, _)| head)
+                .unwrap_or(function);
+            if !function.is_empty() {
+                frames.push(function.to_string());
+            }
+        }
+    }
+    frames
+}
+
+fn crash_inputs(dir: &Path) -> FozzyResult<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if !name.ends_with(".bin") || name.ends_with(".min.bin") {
+            continue;
+        }
+        out.push(path);
+    }
+    out.sort();
+    Ok(out)
+}
@@ -26,7 +26,13 @@ impl clap::ValueEnum for FuzzMode {
 
 #[derive(Debug, Clone)]
 pub enum FuzzTarget {
-    Scenario { path: PathBuf },
+    Scenario {
+        path: PathBuf,
+    },
+    /// OpenAPI 3 JSON document; requests go to its `servers[0].url`.
+    OpenApi {
+        spec: PathBuf,
+    },
 }
 
 impl std::str::FromStr for FuzzTarget {
@@ -43,6 +49,15 @@ impl std::str::FromStr for FuzzTarget {
             }
             return Ok(Self::Scenario { path });
         }
+        if let Some(rest) = s.strip_prefix("openapi:") {
+            let spec = PathBuf::from(rest.trim());
+            if spec.as_os_str().is_empty() {
+                return Err(FozzyError::InvalidArgument(
+                    "fuzz target openapi: requires a spec path".to_string(),
+                ));
+            }
+            return Ok(Self::OpenApi { spec });
+        }
         if s.ends_with(".fozzy.json") {
             return Ok(Self::Scenario {
                 path: PathBuf::from(s),
@@ -50,7 +65,7 @@ impl std::str::FromStr for FuzzTarget {
         }
 
         Err(FozzyError::InvalidArgument(format!(
-            "unsupported fuzz target {s:?} (expected scenario:<path.fozzy.json> or <path.fozzy.json>)"
+            "unsupported fuzz target {s:?} (expected scenario:<path.fozzy.json>, <path.fozzy.json>, or openapi:<spec.json>)"
         )))
     }
 }
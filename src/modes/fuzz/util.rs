@@ -1,9 +1,12 @@
 use rand_chacha::ChaCha20Rng;
 use rand_core::{RngCore as _, SeedableRng as _};
 
-use crate::{Config, ExitStatus, FozzyError, FozzyResult, MemoryOptions};
+use crate::{Config, FozzyError, FozzyResult, MemoryOptions};
 
-use super::{FuzzTarget, execute_target};
+use super::{
+    FuzzTarget, execute_target, load_grammar_scenario, load_property_scenario,
+    shrink_grammar_input, shrink_property_input,
+};
 
 pub(crate) fn mutate_bytes(buf: &mut Vec<u8>, rng: &mut ChaCha20Rng, max_len: usize) {
     let choice = (rng.next_u64() % 4) as u8;
@@ -20,33 +23,56 @@ pub(crate) fn minimize_input(
     target: &FuzzTarget,
     input: &[u8],
     max_len: usize,
-    target_status: ExitStatus,
+    shrink: &crate::ShrinkTarget,
     scenario_memory: &MemoryOptions,
 ) -> FozzyResult<Vec<u8>> {
+    let scenario_path = match target {
+        FuzzTarget::Scenario { path } => Some(path.as_path()),
+        FuzzTarget::OpenApi { .. } => None,
+    };
+    if let Some(path) = scenario_path
+        && let Some(property) = load_property_scenario(path)?
+    {
+        return shrink_property_input(config, target, &property, input, shrink, scenario_memory);
+    }
+    if let Some(path) = scenario_path
+        && let Some(grammar) = load_grammar_scenario(path)?
+        && let Some((minimized, _)) = shrink_grammar_input(
+            config,
+            target,
+            &grammar,
+            input,
+            None,
+            shrink,
+            scenario_memory,
+        )?
+    {
+        return Ok(minimized);
+    }
     let mut best = input.to_vec();
     let mut chunk = best.len().max(1).div_ceil(2);
     while chunk > 0 && best.len() > 1 {
         let mut improved = false;
         let mut index = 0usize;
         while index < best.len() {
-            let mut trial = best.clone();
-            let end = (index + chunk).min(trial.len());
-            trial.drain(index..end);
-            if trial.is_empty() {
-                index += chunk;
-                continue;
-            }
-            if trial.len() > max_len {
-                index += chunk;
-                continue;
-            }
-            let exec = execute_target(config, target, &trial, scenario_memory)?;
-            if crate::shrink_status_matches(target_status, exec.status) {
-                best = trial;
-                improved = true;
-                continue;
-            }
-            index += chunk;
+            let starts: Vec<usize> = (index..best.len()).step_by(chunk).collect();
+            let accepted = shrink.first_accepted(&starts, None, |&start| {
+                let mut trial = best.clone();
+                let end = (start + chunk).min(trial.len());
+                trial.drain(start..end);
+                if trial.is_empty() || trial.len() > max_len {
+                    return Ok(None);
+                }
+                let exec = execute_target(config, target, &trial, scenario_memory)?;
+                Ok(shrink.matches(exec.status, &exec.findings).then_some(trial))
+            })?;
+            let Some((pos, trial)) = accepted else {
+                break;
+            };
+            shrink.record("input", "bytes", best.len(), trial.len());
+            index = starts[pos];
+            best = trial;
+            improved = true;
         }
 
         if !improved {
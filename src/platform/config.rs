@@ -66,6 +66,18 @@ pub struct Config {
     /// Optional heap in-use budget in bytes (profile-derived).
     #[serde(default)]
     pub profile_heap_in_use_budget: Option<u64>,
+
+    /// Secret redaction rules for recorded traces (`[redact]`).
+    #[serde(default)]
+    pub redact: crate::RedactConfig,
+
+    /// Mid-run engine snapshots for recorded traces (`[snapshots]`).
+    #[serde(default)]
+    pub snapshots: crate::SnapshotConfig,
+
+    /// On-disk encoding of recorded traces (`json`, `binary`, `binary-deflate`).
+    #[serde(default)]
+    pub trace_encoding: crate::TraceEncoding,
 }
 
 fn default_base_dir() -> PathBuf {
@@ -114,6 +126,9 @@ impl Default for Config {
             mem_artifacts: default_mem_artifacts(),
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
+            trace_encoding: Default::default(),
         }
     }
 }
@@ -156,6 +171,10 @@ impl Config {
         self.base_dir.join("run-alias-index.json")
     }
 
+    pub fn history_path(&self) -> PathBuf {
+        self.base_dir.join("history.jsonl")
+    }
+
     pub fn corpora_dir(&self) -> PathBuf {
         self.base_dir.join("corpora")
     }
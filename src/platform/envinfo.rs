@@ -46,6 +46,18 @@ fn resolved_commit_hash() -> Option<String> {
     {
         return Some(commit.to_string());
     }
+    git_head_commit()
+}
+
+/// Short commit hash of the git checkout in the current directory, if any. Resolved
+/// once per process, so repeated run history appends (e.g. under `--watch`) do not
+/// each spawn git.
+pub(crate) fn git_head_commit() -> Option<String> {
+    static GIT_HEAD_COMMIT: OnceLock<Option<String>> = OnceLock::new();
+    GIT_HEAD_COMMIT.get_or_init(resolve_git_head_commit).clone()
+}
+
+fn resolve_git_head_commit() -> Option<String> {
     let out = Command::new("git")
         .args(["rev-parse", "--short=12", "HEAD"])
         .output()
@@ -158,6 +158,13 @@ fn has_glob_meta(pattern: &str) -> bool {
 }
 
 pub fn default_min_trace_path(input: &std::path::Path) -> PathBuf {
+    if let Some((archive, entry)) = crate::split_archive_selector(input) {
+        let stem = archive
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("archive");
+        return archive.with_file_name(format!("{stem}.{}.min.fozzy", entry.replace('/', "_")));
+    }
     let parent = input
         .parent()
         .map(|p| p.to_path_buf())
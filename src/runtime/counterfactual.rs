@@ -0,0 +1,437 @@
+//! Counterfactual ("what-if") replay: replay a scenario trace with selected
+//! recorded decisions overridden, then continue scripted or live.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::engine::{ReplayDecisions, ReplayOverrides, run_scenario_replay_inner};
+use crate::finalize::{
+    build_run_summary, build_single_scenario_trace, write_reporter_artifacts, write_summary_report,
+};
+use crate::{
+    Config, Decision, FozzyError, FozzyResult, FsBackend, HttpBackend, ProcBackend,
+    RecordCollisionPolicy, Reporter, RunMode, RunResult, TraceFile, TracePath,
+};
+
+/// Decision fields an override may change, per decision kind.
+const EDITABLE_FIELDS: &[(&str, &[&str])] = &[
+    ("http_request", &["status_code", "body", "headers.*"]),
+    ("http_request_timeout", &["status_code"]),
+    ("proc_spawn", &["exit_code", "stdout", "stderr"]),
+    ("proc_spawn_timeout", &["exit_code"]),
+    ("net_drop", &["dropped"]),
+    ("net_deliver_pick", &["message_id"]),
+    ("rand_u64", &["value"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub enum OverrideTarget {
+    /// The `n`th (1-based) recorded decision of `kind`.
+    Nth { kind: String, n: usize },
+    /// Absolute (0-based) position in the decision log.
+    Index { index: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum OverrideEdit {
+    /// Turn an HTTP request or process spawn into a timeout.
+    Timeout,
+    /// Invert a `net_drop` decision.
+    Flip,
+    Set {
+        field: String,
+        value: String,
+    },
+}
+
+/// One `--override` spec: `<kind>#<n>:<edit>` or `@<index>:<edit>`, where the
+/// edit is `timeout`, `flip` or `<field>=<value>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionOverride {
+    pub target: OverrideTarget,
+    pub edit: OverrideEdit,
+}
+
+impl FromStr for DecisionOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |why: &str| {
+            format!("invalid override {s:?}: {why} (expected <kind>#<n>:<edit> or @<index>:<edit>)")
+        };
+        let (selector, edit) = s.split_once(':').ok_or_else(|| invalid("missing ':'"))?;
+        let target = if let Some(index) = selector.strip_prefix('@') {
+            OverrideTarget::Index {
+                index: index
+                    .parse()
+                    .map_err(|_| invalid("decision index is not a number"))?,
+            }
+        } else {
+            let (kind, n) = selector
+                .split_once('#')
+                .ok_or_else(|| invalid("missing '#<n>'"))?;
+            let n: usize = n
+                .parse()
+                .map_err(|_| invalid("occurrence is not a number"))?;
+            if n == 0 {
+                return Err(invalid("occurrences are counted from 1"));
+            }
+            if !EDITABLE_FIELDS.iter().any(|(k, _)| *k == kind) {
+                return Err(invalid(&format!(
+                    "decision kind {kind:?} cannot be overridden"
+                )));
+            }
+            OverrideTarget::Nth {
+                kind: kind.to_string(),
+                n,
+            }
+        };
+        let edit = match edit {
+            "timeout" => OverrideEdit::Timeout,
+            "flip" => OverrideEdit::Flip,
+            _ => {
+                let (field, value) = edit
+                    .split_once('=')
+                    .ok_or_else(|| invalid("edit must be timeout, flip or <field>=<value>"))?;
+                OverrideEdit::Set {
+                    field: field.to_string(),
+                    value: value.to_string(),
+                }
+            }
+        };
+        Ok(Self { target, edit })
+    }
+}
+
+impl std::fmt::Display for DecisionOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            OverrideTarget::Nth { kind, n } => write!(f, "{kind}#{n}:")?,
+            OverrideTarget::Index { index } => write!(f, "@{index}:")?,
+        }
+        match &self.edit {
+            OverrideEdit::Timeout => write!(f, "timeout"),
+            OverrideEdit::Flip => write!(f, "flip"),
+            OverrideEdit::Set { field, value } => write!(f, "{field}={value}"),
+        }
+    }
+}
+
+/// How execution proceeds after the last overridden decision.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Continuation {
+    /// Keep consuming the remaining recorded decisions; drift is reported.
+    #[default]
+    Scripted,
+    /// Leave the trace and execute the rest of the scenario with the run backends.
+    Live,
+}
+
+impl clap::ValueEnum for Continuation {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Scripted, Self::Live]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            Self::Scripted => clap::builder::PossibleValue::new("scripted"),
+            Self::Live => clap::builder::PossibleValue::new("live"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedOverride {
+    pub index: usize,
+    pub spec: String,
+    pub recorded: Decision,
+    pub value: Decision,
+}
+
+/// Lineage of a trace produced by a counterfactual replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceCounterfactual {
+    pub parent: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_checksum: Option<String>,
+    pub continuation: Continuation,
+    pub overrides: Vec<AppliedOverride>,
+    /// Decision index where execution left the parent trace (live continuation).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_from: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhatIfOptions {
+    pub overrides: Vec<DecisionOverride>,
+    pub continuation: Continuation,
+    pub out_trace_path: Option<PathBuf>,
+    pub record_collision: RecordCollisionPolicy,
+    pub proc_backend: ProcBackend,
+    pub fs_backend: FsBackend,
+    pub http_backend: HttpBackend,
+    pub reporter: Reporter,
+}
+
+/// Applies overrides to a copy of `decisions`, in order.
+pub fn apply_decision_overrides(
+    decisions: &[Decision],
+    overrides: &[DecisionOverride],
+) -> FozzyResult<(Vec<Decision>, Vec<AppliedOverride>)> {
+    let mut out = decisions.to_vec();
+    let mut applied = Vec::with_capacity(overrides.len());
+    for spec in overrides {
+        let index = resolve_target(decisions, &spec.target).ok_or_else(|| {
+            FozzyError::InvalidArgument(format!("override {spec}: no such decision"))
+        })?;
+        let mut value = serde_json::to_value(&out[index])?;
+        edit_decision(&mut value, &spec.edit)
+            .map_err(|why| FozzyError::InvalidArgument(format!("override {spec}: {why}")))?;
+        let decision: Decision = serde_json::from_value(value).map_err(|e| {
+            FozzyError::InvalidArgument(format!("override {spec}: invalid decision: {e}"))
+        })?;
+        out[index] = decision.clone();
+        applied.push(AppliedOverride {
+            index,
+            spec: spec.to_string(),
+            recorded: decisions[index].clone(),
+            value: decision,
+        });
+    }
+    Ok((out, applied))
+}
+
+fn resolve_target(decisions: &[Decision], target: &OverrideTarget) -> Option<usize> {
+    match target {
+        OverrideTarget::Index { index } => (*index < decisions.len()).then_some(*index),
+        OverrideTarget::Nth { kind, n } => decisions
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| decision_kind(d).as_deref() == Some(kind.as_str()))
+            .nth(n - 1)
+            .map(|(i, _)| i),
+    }
+}
+
+fn decision_kind(decision: &Decision) -> Option<String> {
+    serde_json::to_value(decision)
+        .ok()?
+        .get("kind")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn edit_decision(value: &mut Value, edit: &OverrideEdit) -> Result<(), String> {
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| "decision is not an object".to_string())?;
+    let kind = obj
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let Some((_, fields)) = EDITABLE_FIELDS.iter().find(|(k, _)| *k == kind) else {
+        return Err(format!("{kind} decisions cannot be overridden"));
+    };
+    match edit {
+        OverrideEdit::Timeout => match kind.as_str() {
+            "http_request" => {
+                obj.retain(|k, _| matches!(k.as_str(), "method" | "path" | "duration_ms"));
+                obj.insert("kind".to_string(), Value::from("http_request_timeout"));
+            }
+            "proc_spawn" => {
+                obj.remove("exit_code");
+                obj.insert("kind".to_string(), Value::from("proc_spawn_timeout"));
+            }
+            _ => return Err(format!("timeout does not apply to {kind}")),
+        },
+        OverrideEdit::Flip => {
+            let Some(Value::Bool(dropped)) = obj.get("dropped").cloned() else {
+                return Err(format!("flip does not apply to {kind}"));
+            };
+            obj.insert("dropped".to_string(), Value::Bool(!dropped));
+        }
+        OverrideEdit::Set { field, value } => {
+            if let Some(name) = field.strip_prefix("headers.") {
+                if !fields.contains(&"headers.*") {
+                    return Err(format!("{kind} has no headers"));
+                }
+                let headers = obj
+                    .entry("headers")
+                    .or_insert_with(|| Value::Object(Default::default()));
+                if let Some(headers) = headers.as_object_mut() {
+                    headers.insert(name.to_string(), Value::from(value.as_str()));
+                }
+                return Ok(());
+            }
+            if !fields.contains(&field.as_str()) {
+                return Err(format!(
+                    "field {field:?} cannot be overridden on {kind} (allowed: {})",
+                    fields.join(", ")
+                ));
+            }
+            match kind.as_str() {
+                "http_request_timeout" => {
+                    obj.insert("kind".to_string(), Value::from("http_request"));
+                    obj.insert("body".to_string(), Value::from(""));
+                }
+                "proc_spawn_timeout" => {
+                    obj.insert("kind".to_string(), Value::from("proc_spawn"));
+                }
+                _ => {}
+            }
+            let parsed = match obj.get(field.as_str()) {
+                Some(Value::String(_)) => Value::from(value.as_str()),
+                _ => serde_json::from_str(value)
+                    .map_err(|_| format!("value {value:?} for {field} is not valid JSON"))?,
+            };
+            obj.insert(field.clone(), parsed);
+        }
+    }
+    Ok(())
+}
+
+/// Replay settings for a trace produced by a counterfactual replay.
+pub(crate) fn replay_overrides(trace: &TraceFile) -> Option<ReplayOverrides> {
+    let counterfactual = trace.counterfactual.as_ref()?;
+    let mut overridden: Vec<usize> = counterfactual.overrides.iter().map(|o| o.index).collect();
+    overridden.sort_unstable();
+    overridden.dedup();
+    Some(ReplayOverrides {
+        overridden,
+        live_after: None,
+    })
+}
+
+/// Replays a scenario trace with `opt.overrides` applied and records the outcome
+/// as a new trace linked to its parent.
+pub fn whatif_replay_trace(
+    config: &Config,
+    trace_path: TracePath,
+    opt: &WhatIfOptions,
+) -> FozzyResult<RunResult> {
+    if opt.overrides.is_empty() {
+        return Err(FozzyError::InvalidArgument(
+            "what-if replay needs at least one --override".to_string(),
+        ));
+    }
+    let parent = TraceFile::read_json(trace_path.as_path())?;
+    let scenario = parent.scenario.as_ref().ok_or_else(|| {
+        FozzyError::Trace(
+            "what-if replay needs a scenario trace (fuzz and explore traces are not supported)"
+                .to_string(),
+        )
+    })?;
+    let scenario_path = parent
+        .scenario_path
+        .clone()
+        .unwrap_or_else(|| trace_path.as_path().to_string_lossy().to_string());
+    let seed = parent.summary.identity.seed;
+
+    let (decisions, applied) = apply_decision_overrides(&parent.decisions, &opt.overrides)?;
+    let mut settings = replay_overrides(&parent).unwrap_or_default();
+    settings.overridden.extend(applied.iter().map(|o| o.index));
+    settings.overridden.sort_unstable();
+    settings.overridden.dedup();
+    let (proc_backend, fs_backend, http_backend) = match opt.continuation {
+        Continuation::Scripted => (
+            ProcBackend::Scripted,
+            FsBackend::Virtual,
+            HttpBackend::Scripted,
+        ),
+        Continuation::Live => {
+            settings.live_after = applied.iter().map(|o| o.index).max();
+            (opt.proc_backend, opt.fs_backend, opt.http_backend)
+        }
+    };
+
+    let mut run = run_scenario_replay_inner(
+        config,
+        RunMode::Replay,
+        scenario,
+        &scenario_path,
+        seed,
+        Some(ReplayDecisions::Recorded(&decisions)),
+        None,
+        false,
+        proc_backend,
+        fs_backend,
+        http_backend,
+        crate::replay_memory_options(&parent),
+        crate::replay_redactor(config, &parent)?,
+        Some(&settings),
+        None,
+    )?;
+
+    run.decisions.decisions = match run.live_from {
+        Some((recorded, produced)) => decisions[..recorded.min(decisions.len())]
+            .iter()
+            .cloned()
+            .chain(run.decisions.decisions[produced..].iter().cloned())
+            .collect(),
+        None => decisions[..run.replay_consumed.min(decisions.len())].to_vec(),
+    };
+    run.redaction = parent.redaction.clone();
+
+    let run_id = Uuid::new_v4().to_string();
+    let artifacts_dir = config.runs_dir().join(&run_id);
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let report_path = artifacts_dir.join("report.json");
+    let requested = opt
+        .out_trace_path
+        .clone()
+        .unwrap_or_else(|| artifacts_dir.join("trace.fozzy"));
+    let target = crate::resolve_record_target(&requested, opt.record_collision)?;
+    let mut child = build_single_scenario_trace(
+        &target,
+        &run,
+        &run_id,
+        seed,
+        RunMode::Run,
+        Some(report_path.to_string_lossy().to_string()),
+        Some(artifacts_dir.to_string_lossy().to_string()),
+    );
+    child.counterfactual = Some(TraceCounterfactual {
+        parent: trace_path.as_path().display().to_string(),
+        parent_checksum: parent.checksum.clone(),
+        continuation: opt.continuation,
+        overrides: applied,
+        live_from: run.live_from.map(|(recorded, _)| recorded),
+    });
+    crate::write_trace_to_target(&child, &target, config.trace_encoding)?;
+
+    let summary = build_run_summary(
+        run.status,
+        RunMode::Replay,
+        run_id,
+        seed,
+        Some(target.to_string_lossy().to_string()),
+        Some(report_path.to_string_lossy().to_string()),
+        Some(artifacts_dir.to_string_lossy().to_string()),
+        run.started_at.clone(),
+        run.finished_at.clone(),
+        run.duration_ms,
+        run.duration_ns,
+        None,
+        run.memory.as_ref().map(|m| m.summary.clone()),
+        run.findings.clone(),
+    );
+    write_reporter_artifacts(&summary, &artifacts_dir, opt.reporter)?;
+    write_summary_report(&summary, &report_path, &artifacts_dir, None)?;
+    Ok(RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+#[path = "counterfactual/tests.rs"]
+mod tests;
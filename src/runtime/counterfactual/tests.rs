@@ -0,0 +1,104 @@
+use super::*;
+
+fn decisions(raw: serde_json::Value) -> Vec<Decision> {
+    serde_json::from_value(raw).expect("decisions")
+}
+
+#[test]
+fn override_specs_parse_and_round_trip() {
+    for raw in [
+        "http_request#2:status_code=503",
+        "http_request#1:headers.retry-after=5",
+        "proc_spawn#1:timeout",
+        "net_drop#3:flip",
+        "@7:value=42",
+    ] {
+        let spec: DecisionOverride = raw.parse().expect(raw);
+        assert_eq!(spec.to_string(), raw);
+    }
+    let spec: DecisionOverride = "rand_u64#1:value=9".parse().expect("spec");
+    assert_eq!(
+        spec.target,
+        OverrideTarget::Nth {
+            kind: "rand_u64".to_string(),
+            n: 1
+        }
+    );
+
+    for bad in [
+        "rand_u64#1",
+        "rand_u64:value=1",
+        "rand_u64#0:value=1",
+        "time_sleep_ms#1:ms=5",
+        "@x:flip",
+        "net_drop#1:drop",
+    ] {
+        assert!(
+            bad.parse::<DecisionOverride>().is_err(),
+            "{bad} should fail"
+        );
+    }
+}
+
+#[test]
+fn overrides_edit_the_targeted_decision_only() {
+    let recorded = decisions(serde_json::json!([
+        {"kind": "rand_u64", "value": 1},
+        {"kind": "net_drop", "message_id": 4, "dropped": false},
+        {"kind": "rand_u64", "value": 2},
+        {"kind": "http_request", "method": "GET", "path": "/a", "status_code": 200, "body": "ok"},
+    ]));
+    let overrides: Vec<DecisionOverride> = [
+        "rand_u64#2:value=99",
+        "net_drop#1:flip",
+        "http_request#1:timeout",
+    ]
+    .iter()
+    .map(|raw| raw.parse().expect("spec"))
+    .collect();
+
+    let (edited, applied) = apply_decision_overrides(&recorded, &overrides).expect("apply");
+    assert_eq!(edited[0], recorded[0]);
+    assert_eq!(
+        edited[1],
+        decisions(serde_json::json!([
+            {"kind": "net_drop", "message_id": 4, "dropped": true}
+        ]))[0]
+    );
+    assert_eq!(edited[2], Decision::RandU64 { value: 99 });
+    assert_eq!(
+        edited[3],
+        Decision::HttpRequestTimeout {
+            method: "GET".to_string(),
+            path: "/a".to_string(),
+            duration_ms: 0,
+        }
+    );
+    assert_eq!(
+        applied.iter().map(|a| a.index).collect::<Vec<_>>(),
+        vec![2, 1, 3]
+    );
+    assert_eq!(applied[0].recorded, Decision::RandU64 { value: 2 });
+}
+
+#[test]
+fn overrides_reject_missing_targets_and_foreign_fields() {
+    let recorded = decisions(serde_json::json!([
+        {"kind": "rand_u64", "value": 1},
+        {"kind": "time_sleep_ms", "ms": 5},
+    ]));
+    for raw in [
+        "rand_u64#2:value=3",
+        "rand_u64#1:flip",
+        "rand_u64#1:body=x",
+        "rand_u64#1:value=not-a-number",
+        "@1:value=3",
+        "@9:value=3",
+    ] {
+        let spec: DecisionOverride = raw.parse().expect(raw);
+        assert!(
+            apply_decision_overrides(&recorded, &[spec]).is_err(),
+            "{raw} should be rejected"
+        );
+    }
+}
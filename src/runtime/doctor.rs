@@ -87,6 +87,7 @@ pub fn doctor(config: &Config, opt: &DoctorOptions) -> FozzyResult<DoctorReport>
                     FsBackend::Virtual,
                     HttpBackend::Scripted,
                     MemoryOptions::default(),
+                    None,
                 )?;
                 if i == 0
                     && let Some(finding) = run.findings.iter().find(|f| f.title == "proc_unmatched")
@@ -0,0 +1,627 @@
+//! Interactive replay debugger (`fozzy replay --debug`).
+//!
+//! Steps a scenario trace forward one scheduled step at a time. Stepping back
+//! restores the nearest `ExecCheckpoint` and re-executes forward from it.
+
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::{
+    Decision, DecisionCursor, ExitStatus, Finding, FindingKind, FozzyError, FozzyResult,
+    MemoryOptions, ScenarioV1Steps,
+};
+
+use super::drivers::replay_step;
+use super::exec::ExecCtx;
+use super::helpers::{ExecCheckpoint, ReplayCursor, ReplayDecisions};
+use super::types::{FsBackend, HttpBackend, ProcBackend};
+
+/// A checkpoint is kept every this many executed steps.
+const CHECKPOINT_EVERY: usize = 8;
+
+const HELP: &str = "commands: step [n] | back [n] | goto <pos> | continue | restart | \
+break step <idx> | break event <name> | break decision <kind> | break time <ms> | \
+breaks | delete <n> | clear | where | kv | fs | net | memory | clock | state | \
+events [n] | decisions [n] | next | help | quit";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "on", content = "value", rename_all = "snake_case")]
+pub enum Breakpoint {
+    /// Stop before the step with this scenario index runs.
+    Step(usize),
+    /// Stop after a step that emits an event with this name.
+    Event(String),
+    /// Stop after a step that consumes a decision of this kind.
+    Decision(String),
+    /// Stop once the virtual clock reaches this many milliseconds.
+    Time(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugCommand {
+    Step(usize),
+    Back(usize),
+    Goto(usize),
+    Continue,
+    Restart,
+    Break(Breakpoint),
+    Breaks,
+    Delete(usize),
+    Clear,
+    Where,
+    Kv,
+    Fs,
+    Net,
+    Memory,
+    Clock,
+    State,
+    Events(usize),
+    Decisions(usize),
+    Next,
+    Help,
+    Quit,
+}
+
+impl DebugCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut parts = line.split_whitespace();
+        let Some(head) = parts.next() else {
+            return Err("empty command".to_string());
+        };
+        let rest: Vec<&str> = parts.collect();
+        let count = |default: usize| -> Result<usize, String> {
+            match rest.first() {
+                None => Ok(default),
+                Some(raw) => raw
+                    .parse::<usize>()
+                    .map_err(|_| format!("expected a number, got {raw:?}")),
+            }
+        };
+        let arg = |what: &str| -> Result<&str, String> {
+            rest.get(1)
+                .copied()
+                .ok_or_else(|| format!("break {what} needs a value"))
+        };
+        match head {
+            "step" | "s" => Ok(Self::Step(count(1)?)),
+            "back" | "b" => Ok(Self::Back(count(1)?)),
+            "goto" | "g" => rest
+                .first()
+                .ok_or_else(|| "goto needs a position".to_string())
+                .and_then(|_| count(0))
+                .map(Self::Goto),
+            "continue" | "c" => Ok(Self::Continue),
+            "restart" => Ok(Self::Restart),
+            "break" | "br" => match rest.first().copied() {
+                Some("step") => arg("step")?
+                    .parse::<usize>()
+                    .map(|idx| Self::Break(Breakpoint::Step(idx)))
+                    .map_err(|_| "break step needs a step index".to_string()),
+                Some("event") => Ok(Self::Break(Breakpoint::Event(arg("event")?.to_string()))),
+                Some("decision") => Ok(Self::Break(Breakpoint::Decision(
+                    arg("decision")?.to_string(),
+                ))),
+                Some("time") => {
+                    let raw = arg("time")?;
+                    raw.parse::<u64>()
+                        .ok()
+                        .or_else(|| {
+                            crate::parse_duration(raw)
+                                .ok()
+                                .map(|d| d.as_millis().min(u128::from(u64::MAX)) as u64)
+                        })
+                        .map(|ms| Self::Break(Breakpoint::Time(ms)))
+                        .ok_or_else(|| "break time needs milliseconds or a duration".to_string())
+                }
+                _ => Err("break needs one of: step, event, decision, time".to_string()),
+            },
+            "breaks" => Ok(Self::Breaks),
+            "delete" | "d" => rest
+                .first()
+                .ok_or_else(|| "delete needs a breakpoint number".to_string())
+                .and_then(|_| count(0))
+                .map(Self::Delete),
+            "clear" => Ok(Self::Clear),
+            "where" | "w" => Ok(Self::Where),
+            "kv" => Ok(Self::Kv),
+            "fs" => Ok(Self::Fs),
+            "net" => Ok(Self::Net),
+            "memory" | "mem" => Ok(Self::Memory),
+            "clock" => Ok(Self::Clock),
+            "state" => Ok(Self::State),
+            "events" => Ok(Self::Events(count(10)?)),
+            "decisions" => Ok(Self::Decisions(count(10)?)),
+            "next" | "n" => Ok(Self::Next),
+            "help" | "h" | "?" => Ok(Self::Help),
+            "quit" | "q" | "exit" => Ok(Self::Quit),
+            other => Err(format!("unknown command {other:?} (try `help`)")),
+        }
+    }
+}
+
+struct ScheduledStep {
+    index: usize,
+    pick: Option<(u64, String)>,
+}
+
+struct DebugCheckpoint {
+    exec: ExecCheckpoint,
+    replay: usize,
+    decisions: usize,
+    events: usize,
+    findings: usize,
+    executed_steps: usize,
+}
+
+pub(crate) struct ReplayDebugger<'a> {
+    ctx: ExecCtx<'a>,
+    scenario: &'a ScenarioV1Steps,
+    scenario_path: PathBuf,
+    trace: DecisionCursor<'a>,
+    schedule: Vec<ScheduledStep>,
+    position: usize,
+    checkpoints: BTreeMap<usize, DebugCheckpoint>,
+    breakpoints: Vec<Breakpoint>,
+    failure: Option<Finding>,
+}
+
+impl<'a> ReplayDebugger<'a> {
+    pub(crate) fn new(
+        scenario: &'a ScenarioV1Steps,
+        scenario_path: &str,
+        seed: u64,
+        decisions: &'a [Decision],
+        memory: MemoryOptions,
+        redactor: Option<crate::Redactor>,
+    ) -> FozzyResult<Self> {
+        if scenario.version != 1 {
+            return Err(FozzyError::Scenario(format!(
+                "unsupported scenario version {} (expected 1)",
+                scenario.version
+            )));
+        }
+        let mut ctx = ExecCtx::new(
+            seed,
+            true,
+            None,
+            ProcBackend::Scripted,
+            FsBackend::Virtual,
+            HttpBackend::Scripted,
+            memory,
+        );
+        ctx.replay = Some(ReplayCursor::new(ReplayDecisions::Recorded(decisions)));
+        ctx.redactor = redactor;
+
+        let schedule = if decisions
+            .iter()
+            .any(|d| matches!(d, Decision::SchedulerPick { .. }))
+        {
+            let mut scheduler =
+                crate::DeterministicScheduler::new(crate::SchedulerMode::Fifo, seed);
+            for (idx, step) in scenario.steps.iter().enumerate() {
+                scheduler.enqueue(step.kind_name().to_string(), idx);
+            }
+            std::iter::from_fn(|| scheduler.pop_next())
+                .map(|item| ScheduledStep {
+                    index: item.payload,
+                    pick: Some((item.id, item.label)),
+                })
+                .collect()
+        } else {
+            (0..scenario.steps.len())
+                .map(|index| ScheduledStep { index, pick: None })
+                .collect()
+        };
+
+        let mut debugger = Self {
+            ctx,
+            scenario,
+            scenario_path: PathBuf::from(scenario_path),
+            trace: DecisionCursor::new(decisions),
+            schedule,
+            position: 0,
+            checkpoints: BTreeMap::new(),
+            breakpoints: Vec::new(),
+            failure: None,
+        };
+        debugger.save_checkpoint();
+        Ok(debugger)
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn total_steps(&self) -> usize {
+        self.schedule.len()
+    }
+
+    /// Status so far: failed, passed once every step ran, otherwise `None`.
+    pub(crate) fn status(&self) -> Option<ExitStatus> {
+        if let Some(finding) = &self.failure {
+            return Some(if finding.kind == FindingKind::Hang {
+                ExitStatus::Timeout
+            } else {
+                ExitStatus::Fail
+            });
+        }
+        (self.position >= self.schedule.len()).then_some(ExitStatus::Pass)
+    }
+
+    fn replay_position(&self) -> usize {
+        self.ctx
+            .replay
+            .as_ref()
+            .map(ReplayCursor::position)
+            .unwrap_or_default()
+    }
+
+    fn finished(&self) -> bool {
+        self.failure.is_some() || self.position >= self.schedule.len()
+    }
+
+    fn save_checkpoint(&mut self) {
+        self.checkpoints.insert(
+            self.position,
+            DebugCheckpoint {
+                exec: self.ctx.checkpoint(),
+                replay: self.replay_position(),
+                decisions: self.ctx.decisions.decisions.len(),
+                events: self.ctx.events.len(),
+                findings: self.ctx.findings.len(),
+                executed_steps: self.ctx.executed_steps,
+            },
+        );
+    }
+
+    fn restore_checkpoint(&mut self, at: usize) {
+        let Some(cp) = self.checkpoints.get(&at) else {
+            return;
+        };
+        self.ctx.restore(cp.exec.clone());
+        if let Some(cursor) = self.ctx.replay.as_mut() {
+            cursor.seek(cp.replay);
+        }
+        self.ctx.decisions.decisions.truncate(cp.decisions);
+        self.ctx.events.truncate(cp.events);
+        self.ctx.findings.truncate(cp.findings);
+        self.ctx.executed_steps = cp.executed_steps;
+        self.trace.seek(cp.replay);
+        self.position = at;
+        self.failure = None;
+    }
+
+    /// Executes the next scheduled step; returns `false` once replay is finished.
+    fn step_once(&mut self) -> bool {
+        if self.finished() {
+            return false;
+        }
+        let scheduled = &self.schedule[self.position];
+        let step_def = &self.scenario.steps[scheduled.index];
+        let pick = scheduled
+            .pick
+            .as_ref()
+            .map(|(task_id, label)| (*task_id, label.as_str()));
+        let outcome = replay_step(
+            &mut self.ctx,
+            &self.scenario_path,
+            scheduled.index,
+            step_def,
+            pick,
+        );
+        self.failure = match outcome {
+            Ok(finding) => finding,
+            Err(err) => Some(Finding {
+                kind: FindingKind::Checker,
+                title: "replay_drift".to_string(),
+                message: err.to_string(),
+                location: None,
+            }),
+        };
+        self.trace.seek(self.replay_position());
+        self.position += 1;
+        if self.position.is_multiple_of(CHECKPOINT_EVERY) {
+            self.save_checkpoint();
+        }
+        true
+    }
+
+    fn seek(&mut self, target: usize) {
+        let target = target.min(self.schedule.len());
+        if target < self.position || (target == self.position && self.failure.is_some()) {
+            let at = self
+                .checkpoints
+                .range(..=target)
+                .next_back()
+                .map(|(at, _)| *at)
+                .unwrap_or_default();
+            self.restore_checkpoint(at);
+        }
+        while self.position < target && self.step_once() {}
+    }
+
+    /// Runs forward until a breakpoint is hit or replay finishes.
+    fn run_to_break(&mut self) -> Option<(usize, Breakpoint)> {
+        let mut first = true;
+        while !self.finished() {
+            let next_index = self.schedule[self.position].index;
+            if !first && let Some(hit) = self.find_break(|bp| *bp == Breakpoint::Step(next_index)) {
+                return Some(hit);
+            }
+            first = false;
+            let events_before = self.ctx.events.len();
+            let trace_before = self.trace.position();
+            self.step_once();
+            let events = &self.ctx.events[events_before..];
+            let consumed = self.trace.consumed_since(trace_before);
+            let now = self.ctx.clock.now_ms();
+            let hit = self.find_break(|bp| match bp {
+                Breakpoint::Step(_) => false,
+                Breakpoint::Event(name) => events.iter().any(|e| &e.name == name),
+                Breakpoint::Decision(kind) => consumed.iter().any(|d| decision_kind(d) == *kind),
+                Breakpoint::Time(ms) => now >= *ms,
+            });
+            if hit.is_some() {
+                return hit;
+            }
+        }
+        None
+    }
+
+    fn find_break(&self, pred: impl Fn(&Breakpoint) -> bool) -> Option<(usize, Breakpoint)> {
+        self.breakpoints
+            .iter()
+            .enumerate()
+            .find(|(_, bp)| pred(bp))
+            .map(|(i, bp)| (i, bp.clone()))
+    }
+
+    pub(crate) fn execute(&mut self, command: &DebugCommand) -> serde_json::Value {
+        match command {
+            DebugCommand::Step(n) => {
+                self.seek(self.position.saturating_add(*n));
+                self.where_json()
+            }
+            DebugCommand::Back(n) => {
+                self.seek(self.position.saturating_sub(*n));
+                self.where_json()
+            }
+            DebugCommand::Goto(pos) => {
+                self.seek(*pos);
+                self.where_json()
+            }
+            DebugCommand::Restart => {
+                self.seek(0);
+                self.where_json()
+            }
+            DebugCommand::Continue => {
+                let hit = self.run_to_break();
+                let mut out = self.where_json();
+                if let Some((id, bp)) = hit {
+                    out["breakpoint"] = serde_json::json!({ "id": id, "breakpoint": bp });
+                }
+                out
+            }
+            DebugCommand::Break(bp) => {
+                self.breakpoints.push(bp.clone());
+                serde_json::json!({ "id": self.breakpoints.len() - 1, "breakpoint": bp })
+            }
+            DebugCommand::Breaks => serde_json::json!({ "breakpoints": self.breakpoints }),
+            DebugCommand::Delete(id) => {
+                if *id < self.breakpoints.len() {
+                    let removed = self.breakpoints.remove(*id);
+                    serde_json::json!({ "removed": removed })
+                } else {
+                    serde_json::json!({ "error": format!("no breakpoint {id}") })
+                }
+            }
+            DebugCommand::Clear => {
+                self.breakpoints.clear();
+                serde_json::json!({ "breakpoints": [] })
+            }
+            DebugCommand::Where => self.where_json(),
+            DebugCommand::Kv => serde_json::json!({ "kv": self.ctx.kv }),
+            DebugCommand::Fs => self.fs_json(),
+            DebugCommand::Net => self.net_json(),
+            DebugCommand::Memory => self.memory_json(),
+            DebugCommand::Clock => self.clock_json(),
+            DebugCommand::State => {
+                let mut out = self.where_json();
+                out["kv"] = serde_json::json!(self.ctx.kv);
+                out["fs"] = self.fs_json()["fs"].take();
+                out["net"] = self.net_json()["net"].take();
+                out["memory"] = self.memory_json()["memory"].take();
+                out["clock"] = self.clock_json()["clock"].take();
+                out
+            }
+            DebugCommand::Events(n) => {
+                let events = &self.ctx.events;
+                let tail = &events[events.len().saturating_sub(*n)..];
+                serde_json::json!({ "events": tail })
+            }
+            DebugCommand::Decisions(n) => {
+                let consumed = self.trace.consumed_since(0);
+                let tail = &consumed[consumed.len().saturating_sub(*n)..];
+                serde_json::json!({ "decisions": tail })
+            }
+            DebugCommand::Next => serde_json::json!({ "nextDecision": self.trace.peek() }),
+            DebugCommand::Help => serde_json::json!({ "help": HELP }),
+            DebugCommand::Quit => self.where_json(),
+        }
+    }
+
+    fn where_json(&self) -> serde_json::Value {
+        let next = self.schedule.get(self.position).map(|s| {
+            serde_json::json!({
+                "index": s.index,
+                "kind": self.scenario.steps[s.index].kind_name(),
+            })
+        });
+        let mut out = serde_json::json!({
+            "position": self.position,
+            "totalSteps": self.schedule.len(),
+            "timeMs": self.ctx.clock.now_ms(),
+            "decisionIndex": self.trace.position(),
+            "nextStep": next,
+            "finished": self.finished(),
+        });
+        if let Some(status) = self.status() {
+            out["status"] = serde_json::json!(status);
+        }
+        if let Some(finding) = &self.failure {
+            out["finding"] = serde_json::json!(finding);
+        }
+        out
+    }
+
+    fn fs_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "fs": {
+                "files": self.ctx.fs,
+                "snapshots": self.ctx.fs_snapshots.keys().collect::<Vec<_>>(),
+            }
+        })
+    }
+
+    fn net_json(&self) -> serde_json::Value {
+        let message = |m: &super::helpers::NetMessage| serde_json::json!({ "id": m.id, "from": m.from, "to": m.to, "payload": m.payload });
+        let inbox: BTreeMap<&String, Vec<serde_json::Value>> = self
+            .ctx
+            .net_inbox
+            .iter()
+            .map(|(node, msgs)| (node, msgs.iter().map(message).collect()))
+            .collect();
+        serde_json::json!({
+            "net": {
+                "queue": self.ctx.net_queue.iter().map(message).collect::<Vec<_>>(),
+                "inbox": inbox,
+                "partitions": self.ctx.net_partitions,
+                "dropRate": self.ctx.net_drop_rate,
+                "reorder": self.ctx.net_reorder,
+            }
+        })
+    }
+
+    fn memory_json(&self) -> serde_json::Value {
+        let live: Vec<serde_json::Value> = self
+            .ctx
+            .memory
+            .live_allocations()
+            .map(|(id, record)| {
+                serde_json::json!({
+                    "id": id,
+                    "bytes": record.bytes,
+                    "callsite": record.callsite_hash,
+                    "tag": record.tag,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "memory": {
+                "inUseBytes": self.ctx.memory.in_use_bytes(),
+                "peakBytes": self.ctx.memory.peak_bytes(),
+                "live": live,
+            }
+        })
+    }
+
+    fn clock_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "clock": {
+                "nowMs": self.ctx.clock.now_ms(),
+                "frozen": self.ctx.clock.is_frozen(),
+            }
+        })
+    }
+}
+
+fn decision_kind(decision: &Decision) -> String {
+    serde_json::to_value(decision)
+        .ok()
+        .and_then(|v| v.get("kind").and_then(|k| k.as_str()).map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Reads debugger commands line by line until `quit` or end of input.
+///
+/// Blank lines and `#` comments are skipped. In text mode each response is written
+/// to `out` as it happens; the full transcript is always returned.
+pub(crate) fn run_debugger_session(
+    debugger: &mut ReplayDebugger<'_>,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    echo: bool,
+    prompt: bool,
+) -> FozzyResult<Vec<serde_json::Value>> {
+    let mut transcript = Vec::new();
+    let mut line = String::new();
+    loop {
+        if prompt {
+            write!(out, "(fozzy) ")?;
+            out.flush()?;
+        }
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (response, quit) = match DebugCommand::parse(trimmed) {
+            Ok(command) => (debugger.execute(&command), command == DebugCommand::Quit),
+            Err(error) => (serde_json::json!({ "error": error }), false),
+        };
+        if echo {
+            writeln!(out, "{}", render_response(trimmed, &response))?;
+        }
+        transcript.push(serde_json::json!({ "command": trimmed, "result": response }));
+        if quit {
+            break;
+        }
+    }
+    Ok(transcript)
+}
+
+fn render_response(command: &str, response: &serde_json::Value) -> String {
+    if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+        return format!("error: {error}");
+    }
+    if let Some(help) = response.get("help").and_then(|h| h.as_str()) {
+        return help.to_string();
+    }
+    if response.get("position").is_some() && !command.starts_with("state") {
+        let mut text = format!(
+            "at {}/{} t={}ms decision={}",
+            response["position"],
+            response["totalSteps"],
+            response["timeMs"],
+            response["decisionIndex"]
+        );
+        if let Some(next) = response.get("nextStep").filter(|n| !n.is_null()) {
+            text.push_str(&format!(
+                " next=#{} {}",
+                next["index"],
+                next["kind"].as_str().unwrap_or_default()
+            ));
+        }
+        if let Some(status) = response.get("status").and_then(|s| s.as_str()) {
+            text.push_str(&format!(" status={status}"));
+        }
+        if let Some(finding) = response.get("finding") {
+            text.push_str(&format!(
+                " finding={}: {}",
+                finding["title"].as_str().unwrap_or_default(),
+                finding["message"].as_str().unwrap_or_default()
+            ));
+        }
+        if let Some(hit) = response.get("breakpoint") {
+            text.push_str(&format!(" (breakpoint {})", hit["id"]));
+        }
+        return text;
+    }
+    serde_json::to_string_pretty(response).unwrap_or_default()
+}
@@ -8,7 +8,7 @@ use crate::{
 
 use super::exec::ExecCtx;
 use super::exec::step_is_declaration_only;
-use super::helpers::{ReplayCursor, should_emit_heavy_artifacts};
+use super::helpers::{ReplayCursor, ReplayDecisions, ReplayOverrides, should_emit_heavy_artifacts};
 use super::types::{FsBackend, HttpBackend, ProcBackend, ProfileCaptureLevel, ScenarioRun};
 
 pub(crate) fn shrink_status_matches(target: ExitStatus, candidate: ExitStatus) -> bool {
@@ -30,9 +30,11 @@ pub fn should_emit_profile_artifacts(
     }
 }
 
+/// Runs a scenario file; with `recording`, the trace is streamed to disk as steps run.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run_scenario_inner(
-    _config: &Config,
-    _mode: RunMode,
+    config: &Config,
+    mode: RunMode,
     scenario_path: ScenarioPath,
     seed: u64,
     det: bool,
@@ -41,9 +43,14 @@ pub(crate) fn run_scenario_inner(
     fs_backend: FsBackend,
     http_backend: HttpBackend,
     memory: MemoryOptions,
+    mut recording: Option<&mut crate::TraceRecording>,
 ) -> FozzyResult<ScenarioRun> {
     let loaded = Scenario::load(&scenario_path)?;
     loaded.validate()?;
+    let timeout = match timeout {
+        Some(timeout) => Some(timeout),
+        None => crate::ScenarioMeta::load(scenario_path.as_path())?.timeout_duration()?,
+    };
 
     let embedded = ScenarioV1Steps {
         version: 1,
@@ -51,7 +58,18 @@ pub(crate) fn run_scenario_inner(
         steps: loaded.steps.clone(),
     };
 
-    run_embedded_scenario_inner(
+    // Host fs contents live outside the engine, so a snapshot could not restore them.
+    let snapshot_every = if fs_backend == FsBackend::Host {
+        0
+    } else {
+        config.snapshots.every_steps
+    };
+
+    let redactor = crate::Redactor::for_record(config)?;
+    if let Some(recording) = recording.as_deref_mut() {
+        recording.begin(mode, scenario_path.as_path(), &embedded, redactor.clone())?;
+    }
+    let mut run = run_embedded_scenario_inner(
         embedded,
         scenario_path.as_path().to_path_buf(),
         seed,
@@ -61,7 +79,15 @@ pub(crate) fn run_scenario_inner(
         fs_backend,
         http_backend,
         memory,
-    )
+        snapshot_every,
+        recording,
+    )?;
+    if let Some(redactor) = redactor {
+        redactor.redact_run(&mut run)?;
+        // Snapshot state holds unredacted values.
+        run.snapshots.clear();
+    }
+    Ok(run)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -75,6 +101,8 @@ pub(crate) fn run_embedded_scenario_inner(
     fs_backend: FsBackend,
     http_backend: HttpBackend,
     memory: MemoryOptions,
+    snapshot_every: usize,
+    mut recording: Option<&mut crate::TraceRecording>,
 ) -> FozzyResult<ScenarioRun> {
     let started_at = crate::wall_time_iso_utc();
     let started = Instant::now();
@@ -94,6 +122,7 @@ pub(crate) fn run_embedded_scenario_inner(
         scheduler.enqueue(step.kind_name().to_string(), idx);
     }
 
+    let mut position = 0usize;
     while let Some(item) = scheduler.pop_next() {
         let idx = item.payload;
         let step = &scenario.steps[idx];
@@ -188,6 +217,9 @@ pub(crate) fn run_embedded_scenario_inner(
                 ),
             ]),
         });
+        if let Some(recording) = recording.as_deref_mut() {
+            recording.sync(&ctx.decisions.decisions, &ctx.events)?;
+        }
 
         if timeout_reached(&ctx, det, timeout, deadline, start_virtual_ms) {
             ctx.findings.push(Finding {
@@ -204,6 +236,14 @@ pub(crate) fn run_embedded_scenario_inner(
                 started.elapsed(),
             ));
         }
+
+        position += 1;
+        if snapshot_every > 0
+            && position.is_multiple_of(snapshot_every)
+            && position < scenario.steps.len()
+        {
+            ctx.take_snapshot(position);
+        }
     }
 
     if ctx.executed_steps == 0
@@ -252,6 +292,8 @@ pub(crate) fn run_embedded_steps_for_fuzz(
         FsBackend::Virtual,
         HttpBackend::Scripted,
         memory,
+        0,
+        None,
     )
 }
 
@@ -284,13 +326,16 @@ pub(crate) fn run_scenario_replay_inner<'a>(
     scenario: &ScenarioV1Steps,
     scenario_path: &str,
     seed: u64,
-    decisions: Option<&'a [Decision]>,
+    decisions: Option<ReplayDecisions<'a>>,
     until: Option<Duration>,
     step: bool,
     proc_backend: ProcBackend,
     fs_backend: FsBackend,
     http_backend: HttpBackend,
     memory: MemoryOptions,
+    redactor: Option<crate::Redactor>,
+    overrides: Option<&'a ReplayOverrides>,
+    resume: Option<&crate::TraceSnapshot>,
 ) -> FozzyResult<ScenarioRun> {
     if scenario.version != 1 {
         return Err(FozzyError::Scenario(format!(
@@ -299,13 +344,6 @@ pub(crate) fn run_scenario_replay_inner<'a>(
         )));
     }
 
-    let has_scheduler_pick = decisions
-        .map(|d| {
-            d.iter()
-                .any(|x| matches!(x, Decision::SchedulerPick { .. }))
-        })
-        .unwrap_or(false);
-
     let started_at = crate::wall_time_iso_utc();
     let started = Instant::now();
     let deadline = until.map(|t| started + t);
@@ -319,14 +357,39 @@ pub(crate) fn run_scenario_replay_inner<'a>(
         memory,
     );
     if let Some(d) = decisions {
-        ctx.replay = Some(ReplayCursor::new(d));
+        let mut cursor = ReplayCursor::new(d);
+        if let Some(overrides) = overrides {
+            cursor = cursor.with_overrides(&overrides.overridden);
+            ctx.live_after = overrides.live_after;
+        }
+        ctx.replay = Some(cursor);
     }
+    ctx.redactor = redactor;
+    let has_scheduler_pick = ctx
+        .replay
+        .as_ref()
+        .is_some_and(ReplayCursor::has_scheduler_picks);
+    let resume_at = match resume {
+        Some(snapshot) => {
+            match ctx.replay.as_mut() {
+                Some(cursor) => cursor.resume_at(snapshot),
+                None => snapshot.verify(&[]),
+            }
+            .map_err(FozzyError::Trace)?;
+            ctx.resume_from_snapshot(snapshot)?;
+            snapshot.position
+        }
+        None => 0,
+    };
 
     if has_scheduler_pick {
         let mut scheduler = crate::DeterministicScheduler::new(crate::SchedulerMode::Fifo, seed);
         for (idx, step) in scenario.steps.iter().enumerate() {
             scheduler.enqueue(step.kind_name().to_string(), idx);
         }
+        for _ in 0..resume_at {
+            scheduler.pop_next();
+        }
         while let Some(item) = scheduler.pop_next() {
             let idx = item.payload;
             let step_def = &scenario.steps[idx];
@@ -352,50 +415,13 @@ pub(crate) fn run_scenario_replay_inner<'a>(
                 std::thread::sleep(Duration::from_millis(10));
             }
 
-            ctx.expect_scheduler_pick(item.id, &item.label)?;
-            let step_kind = step_def.kind_name().to_string();
-            let span_id = format!("step-{idx}");
-            let step_start_ms = ctx.clock.now_ms();
-            ctx.events.push(TraceEvent {
-                time_ms: step_start_ms,
-                name: "sched_pick".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("task_id".to_string(), serde_json::json!(item.id)),
-                    ("step_index".to_string(), serde_json::json!(idx as u64)),
-                    (
-                        "step_kind".to_string(),
-                        serde_json::json!(step_kind.clone()),
-                    ),
-                ]),
-            });
-            ctx.events.push(TraceEvent {
-                time_ms: step_start_ms,
-                name: "span_start".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("span".to_string(), serde_json::json!(span_id.clone())),
-                    ("task".to_string(), serde_json::json!("step")),
-                    ("step_index".to_string(), serde_json::json!(idx as u64)),
-                    (
-                        "step_kind".to_string(),
-                        serde_json::json!(step_kind.clone()),
-                    ),
-                ]),
-            });
-            ctx.set_active_step(Path::new(scenario_path), idx);
-            if let Err(finding) = ctx.exec_step(step_def) {
-                let end_ms = ctx.clock.now_ms();
-                ctx.events.push(TraceEvent {
-                    time_ms: end_ms,
-                    name: "span_end".to_string(),
-                    fields: serde_json::Map::from_iter([
-                        ("span".to_string(), serde_json::json!(span_id)),
-                        ("status".to_string(), serde_json::json!("error")),
-                        (
-                            "duration_ms".to_string(),
-                            serde_json::json!(end_ms.saturating_sub(step_start_ms)),
-                        ),
-                    ]),
-                });
+            if let Some(finding) = replay_step(
+                &mut ctx,
+                Path::new(scenario_path),
+                idx,
+                step_def,
+                Some((item.id, &item.label)),
+            )? {
                 let status = if finding_is_timeout(&finding) {
                     ExitStatus::Timeout
                 } else {
@@ -410,23 +436,9 @@ pub(crate) fn run_scenario_replay_inner<'a>(
                     started.elapsed(),
                 ));
             }
-            ctx.mark_step_executed(step_def);
-            let end_ms = ctx.clock.now_ms();
-            ctx.events.push(TraceEvent {
-                time_ms: end_ms,
-                name: "span_end".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("span".to_string(), serde_json::json!(span_id)),
-                    ("status".to_string(), serde_json::json!("ok")),
-                    (
-                        "duration_ms".to_string(),
-                        serde_json::json!(end_ms.saturating_sub(step_start_ms)),
-                    ),
-                ]),
-            });
         }
     } else {
-        for (idx, step_def) in scenario.steps.iter().enumerate() {
+        for (idx, step_def) in scenario.steps.iter().enumerate().skip(resume_at) {
             if let Some(dl) = deadline
                 && Instant::now() > dl
             {
@@ -449,50 +461,9 @@ pub(crate) fn run_scenario_replay_inner<'a>(
                 std::thread::sleep(Duration::from_millis(10));
             }
 
-            ctx.expect_step(idx)?;
-            let step_kind = step_def.kind_name().to_string();
-            let span_id = format!("step-{idx}");
-            let step_start_ms = ctx.clock.now_ms();
-            ctx.events.push(TraceEvent {
-                time_ms: step_start_ms,
-                name: "sched_pick".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("task_id".to_string(), serde_json::json!(idx as u64 + 1)),
-                    ("step_index".to_string(), serde_json::json!(idx as u64)),
-                    (
-                        "step_kind".to_string(),
-                        serde_json::json!(step_kind.clone()),
-                    ),
-                ]),
-            });
-            ctx.events.push(TraceEvent {
-                time_ms: step_start_ms,
-                name: "span_start".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("span".to_string(), serde_json::json!(span_id.clone())),
-                    ("task".to_string(), serde_json::json!("step")),
-                    ("step_index".to_string(), serde_json::json!(idx as u64)),
-                    (
-                        "step_kind".to_string(),
-                        serde_json::json!(step_kind.clone()),
-                    ),
-                ]),
-            });
-            ctx.set_active_step(Path::new(scenario_path), idx);
-            if let Err(finding) = ctx.exec_step(step_def) {
-                let end_ms = ctx.clock.now_ms();
-                ctx.events.push(TraceEvent {
-                    time_ms: end_ms,
-                    name: "span_end".to_string(),
-                    fields: serde_json::Map::from_iter([
-                        ("span".to_string(), serde_json::json!(span_id)),
-                        ("status".to_string(), serde_json::json!("error")),
-                        (
-                            "duration_ms".to_string(),
-                            serde_json::json!(end_ms.saturating_sub(step_start_ms)),
-                        ),
-                    ]),
-                });
+            if let Some(finding) =
+                replay_step(&mut ctx, Path::new(scenario_path), idx, step_def, None)?
+            {
                 let status = if finding_is_timeout(&finding) {
                     ExitStatus::Timeout
                 } else {
@@ -507,20 +478,6 @@ pub(crate) fn run_scenario_replay_inner<'a>(
                     started.elapsed(),
                 ));
             }
-            ctx.mark_step_executed(step_def);
-            let end_ms = ctx.clock.now_ms();
-            ctx.events.push(TraceEvent {
-                time_ms: end_ms,
-                name: "span_end".to_string(),
-                fields: serde_json::Map::from_iter([
-                    ("span".to_string(), serde_json::json!(span_id)),
-                    ("status".to_string(), serde_json::json!("ok")),
-                    (
-                        "duration_ms".to_string(),
-                        serde_json::json!(end_ms.saturating_sub(step_start_ms)),
-                    ),
-                ]),
-            });
         }
     }
 
@@ -576,3 +533,90 @@ pub(crate) fn run_scenario_replay_inner<'a>(
         started.elapsed(),
     ))
 }
+
+/// Replays one step against the decision cursor and emits its spans.
+///
+/// `pick` is the scheduler pick recorded for the step; traces without scheduler
+/// picks are matched by `step` decisions instead. Returns the finding when the
+/// step fails.
+pub(super) fn replay_step(
+    ctx: &mut ExecCtx<'_>,
+    scenario_path: &Path,
+    idx: usize,
+    step_def: &crate::Step,
+    pick: Option<(u64, &str)>,
+) -> FozzyResult<Option<Finding>> {
+    let outcome = replay_scheduled_step(ctx, scenario_path, idx, step_def, pick);
+    // A streamed trace that fails to read looks like missing decisions; report the cause.
+    if let Some(err) = ctx.replay.as_mut().and_then(ReplayCursor::take_error) {
+        return Err(err);
+    }
+    outcome
+}
+
+fn replay_scheduled_step(
+    ctx: &mut ExecCtx<'_>,
+    scenario_path: &Path,
+    idx: usize,
+    step_def: &crate::Step,
+    pick: Option<(u64, &str)>,
+) -> FozzyResult<Option<Finding>> {
+    let task_id = match pick {
+        Some((task_id, label)) => {
+            ctx.expect_scheduler_pick(task_id, label)?;
+            task_id
+        }
+        None => {
+            ctx.expect_step(idx)?;
+            idx as u64 + 1
+        }
+    };
+    let step_kind = step_def.kind_name().to_string();
+    let span_id = format!("step-{idx}");
+    let step_start_ms = ctx.clock.now_ms();
+    ctx.events.push(TraceEvent {
+        time_ms: step_start_ms,
+        name: "sched_pick".to_string(),
+        fields: serde_json::Map::from_iter([
+            ("task_id".to_string(), serde_json::json!(task_id)),
+            ("step_index".to_string(), serde_json::json!(idx as u64)),
+            (
+                "step_kind".to_string(),
+                serde_json::json!(step_kind.clone()),
+            ),
+        ]),
+    });
+    ctx.events.push(TraceEvent {
+        time_ms: step_start_ms,
+        name: "span_start".to_string(),
+        fields: serde_json::Map::from_iter([
+            ("span".to_string(), serde_json::json!(span_id.clone())),
+            ("task".to_string(), serde_json::json!("step")),
+            ("step_index".to_string(), serde_json::json!(idx as u64)),
+            ("step_kind".to_string(), serde_json::json!(step_kind)),
+        ]),
+    });
+    ctx.set_active_step(scenario_path, idx);
+    let result = ctx.exec_step(step_def);
+    if result.is_ok() {
+        ctx.mark_step_executed(step_def);
+    }
+    let end_ms = ctx.clock.now_ms();
+    ctx.events.push(TraceEvent {
+        time_ms: end_ms,
+        name: "span_end".to_string(),
+        fields: serde_json::Map::from_iter([
+            ("span".to_string(), serde_json::json!(span_id)),
+            (
+                "status".to_string(),
+                serde_json::json!(if result.is_ok() { "ok" } else { "error" }),
+            ),
+            (
+                "duration_ms".to_string(),
+                serde_json::json!(end_ms.saturating_sub(step_start_ms)),
+            ),
+        ]),
+    });
+    ctx.settle_live_continuation();
+    Ok(result.err())
+}
@@ -55,6 +55,14 @@ pub(crate) struct ExecCtx<'a> {
     pub(super) replay: Option<ReplayCursor<'a>>,
     pub(super) current_step_index: Option<usize>,
     pub(super) scenario_path: Option<PathBuf>,
+    /// Rules of a redacted trace being replayed; expectations are compared through them.
+    pub(super) redactor: Option<crate::Redactor>,
+    /// Counterfactual replay leaves the trace once this decision index is consumed.
+    pub(super) live_after: Option<usize>,
+    /// Recorded and produced decision counts at the point execution went live.
+    pub(super) live_from: Option<(usize, usize)>,
+    /// Engine snapshots taken while recording.
+    pub(super) snapshots: Vec<crate::TraceSnapshot>,
 }
 
 impl<'a> ExecCtx<'a> {
@@ -100,6 +108,10 @@ impl<'a> ExecCtx<'a> {
             replay: None,
             current_step_index: None,
             scenario_path: None,
+            redactor: None,
+            live_after: None,
+            live_from: None,
+            snapshots: Vec::new(),
         }
     }
 
@@ -203,6 +215,40 @@ impl<'a> ExecCtx<'a> {
             finished_at,
             duration_ms,
             duration_ns,
+            redaction: None,
+            replay_consumed: self.replay.as_ref().map_or(0, |c| c.position()),
+            live_from: self.live_from,
+            snapshots: self.snapshots,
+        }
+    }
+
+    pub(super) fn observed_text_matches(&self, expected: &str, got: &str) -> bool {
+        match &self.redactor {
+            Some(redactor) => redactor.text_matches(expected, got),
+            None => expected == got,
+        }
+    }
+
+    pub(super) fn observed_json_matches(
+        &self,
+        expected: &serde_json::Value,
+        got: &serde_json::Value,
+    ) -> bool {
+        match &self.redactor {
+            Some(redactor) => redactor.json_matches(expected, got),
+            None => expected == got,
+        }
+    }
+
+    pub(super) fn observed_header_matches(
+        &self,
+        name: &str,
+        expected: &str,
+        got: Option<&String>,
+    ) -> bool {
+        match (&self.redactor, got) {
+            (Some(redactor), Some(got)) => redactor.header_matches(name, expected, got),
+            (_, got) => got.map(String::as_str) == Some(expected),
         }
     }
 
@@ -214,6 +260,12 @@ impl<'a> ExecCtx<'a> {
 
     pub(super) fn expect_step(&mut self, idx: usize) -> FozzyResult<()> {
         let Some(cursor) = self.replay.as_mut() else {
+            if self.live_from.is_some() {
+                self.decisions.push(Decision::Step {
+                    index: idx,
+                    name: format!("step-{idx}"),
+                });
+            }
             return Ok(());
         };
         match cursor.next() {
@@ -227,8 +279,14 @@ impl<'a> ExecCtx<'a> {
         }
     }
 
-    pub(super) fn expect_scheduler_pick(&mut self, task_id: u64, _label: &str) -> FozzyResult<()> {
+    pub(super) fn expect_scheduler_pick(&mut self, task_id: u64, label: &str) -> FozzyResult<()> {
         let Some(cursor) = self.replay.as_mut() else {
+            if self.live_from.is_some() {
+                self.decisions.push(Decision::SchedulerPick {
+                    task_id,
+                    label: label.to_string(),
+                });
+            }
             return Ok(());
         };
         match cursor.next() {
@@ -245,6 +303,39 @@ impl<'a> ExecCtx<'a> {
         }
     }
 
+    /// Takes the next recorded decision when a counterfactual override replaced it.
+    pub(super) fn replay_take_override(&mut self) -> Option<Decision> {
+        let cursor = self.replay.as_mut()?;
+        if cursor.at_override() {
+            cursor.next().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Drops the replay cursor once every override has been consumed in live mode.
+    pub(super) fn settle_live_continuation(&mut self) {
+        let (Some(live_after), Some(cursor)) = (self.live_after, self.replay.as_ref()) else {
+            return;
+        };
+        if cursor.position() <= live_after {
+            return;
+        }
+        let recorded = cursor.position();
+        let produced = self.decisions.decisions.len();
+        self.replay = None;
+        self.live_after = None;
+        self.live_from = Some((recorded, produced));
+        self.events.push(TraceEvent {
+            time_ms: self.clock.now_ms(),
+            name: "counterfactual_live".to_string(),
+            fields: serde_json::Map::from_iter([(
+                "decision_index".to_string(),
+                serde_json::json!(recorded as u64),
+            )]),
+        });
+    }
+
     pub(super) fn replay_peek(&self) -> Option<&Decision> {
         self.replay.as_ref().and_then(|c| c.peek())
     }
@@ -17,9 +17,24 @@ impl ExecCtx<'_> {
             }
 
             crate::Step::RandU64 { key } => {
-                let value = self.rng.next_u64();
+                let mut value = self.rng.next_u64();
+                let overridden = match self.replay_take_override() {
+                    Some(Decision::RandU64 { value: forced }) => {
+                        value = forced;
+                        true
+                    }
+                    Some(other) => {
+                        return Err(Finding {
+                            kind: FindingKind::Checker,
+                            title: "replay_drift".to_string(),
+                            message: format!("expected overridden RandU64, got {other:?}"),
+                            location: None,
+                        });
+                    }
+                    None => false,
+                };
                 self.decisions.push(Decision::RandU64 { value });
-                if let Some(cur) = self.replay.as_mut() {
+                if !overridden && let Some(cur) = self.replay.as_mut() {
                     match cur.next() {
                         Some(Decision::RandU64 { value: expected }) if *expected == value => {}
                         Some(other) => {
@@ -708,7 +708,7 @@ impl ExecCtx<'_> {
                 }
 
                 if let Some(expected) = expect_body
-                    && resp_body != *expected
+                    && !self.observed_text_matches(expected, &resp_body)
                 {
                     return Err(Finding {
                         kind: FindingKind::Assertion,
@@ -726,7 +726,7 @@ impl ExecCtx<'_> {
                             message: e.to_string(),
                             location: None,
                         })?;
-                    if got != *expected {
+                    if !self.observed_json_matches(expected, &got) {
                         return Err(Finding {
                             kind: FindingKind::Assertion,
                             title: "http_json".to_string(),
@@ -740,7 +740,7 @@ impl ExecCtx<'_> {
                     let expected = canonical_headers(Some(expected_headers))?;
                     for (k, v) in expected {
                         let got = resp_headers.get(&k);
-                        if got != Some(&v) {
+                        if !self.observed_header_matches(&k, &v, got) {
                             return Err(Finding {
                                 kind: FindingKind::Assertion,
                                 title: "http_headers".to_string(),
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{Decision, DecisionLog, Finding, FindingKind, TraceEvent};
+use crate::{Decision, DecisionLog, Finding, FindingKind, FozzyError, FozzyResult, TraceEvent};
 
 use super::super::helpers::{ExecCheckpoint, duration_to_ms};
 use super::ExecCtx;
@@ -343,9 +343,8 @@ impl ExecCtx<'_> {
     }
 
     fn exec_expect_failure(&mut self, title: &str, steps: &[crate::Step]) -> Result<(), Finding> {
-        let replay = self.replay;
+        let replay = self.replay.take();
         let checkpoint = self.checkpoint();
-        self.replay = None;
         self.decisions = DecisionLog::default();
         self.events.clear();
         self.findings.clear();
@@ -375,7 +374,7 @@ impl ExecCtx<'_> {
         }
     }
 
-    fn checkpoint(&self) -> ExecCheckpoint {
+    pub(in crate::engine) fn checkpoint(&self) -> ExecCheckpoint {
         ExecCheckpoint {
             rng: self.rng.clone(),
             clock: self.clock.clone(),
@@ -398,7 +397,7 @@ impl ExecCtx<'_> {
         }
     }
 
-    fn restore(&mut self, checkpoint: ExecCheckpoint) {
+    pub(in crate::engine) fn restore(&mut self, checkpoint: ExecCheckpoint) {
         self.rng = checkpoint.rng;
         self.clock = checkpoint.clock;
         self.kv = checkpoint.kv;
@@ -418,4 +417,47 @@ impl ExecCtx<'_> {
         self.net_reorder = checkpoint.net_reorder;
         self.memory = checkpoint.memory;
     }
+
+    /// Records a snapshot after `position` scheduled steps have completed.
+    pub(in crate::engine) fn take_snapshot(&mut self, position: usize) {
+        let Ok(state) = serde_json::to_value(self.checkpoint()) else {
+            return;
+        };
+        let decision_index = self.decisions.decisions.len();
+        self.snapshots.push(crate::TraceSnapshot {
+            position,
+            time_ms: self.clock.now_ms(),
+            decision_index,
+            executed_steps: self.executed_steps,
+            decisions_digest: crate::decisions_digest(&self.decisions.decisions),
+            state,
+        });
+    }
+
+    /// Restores engine state from a verified snapshot and moves the replay cursor past
+    /// the decisions it covers.
+    pub(in crate::engine) fn resume_from_snapshot(
+        &mut self,
+        snapshot: &crate::TraceSnapshot,
+    ) -> FozzyResult<()> {
+        let state: ExecCheckpoint = serde_json::from_value(snapshot.state.clone())
+            .map_err(|err| FozzyError::Trace(format!("invalid trace snapshot: {err}")))?;
+        self.restore(state);
+        self.executed_steps = snapshot.executed_steps;
+        if let Some(cursor) = self.replay.as_mut() {
+            cursor.seek(snapshot.decision_index);
+        }
+        self.events.push(TraceEvent {
+            time_ms: snapshot.time_ms,
+            name: "snapshot_resume".to_string(),
+            fields: serde_json::Map::from_iter([
+                ("position".to_string(), serde_json::json!(snapshot.position)),
+                (
+                    "decision_index".to_string(),
+                    serde_json::json!(snapshot.decision_index),
+                ),
+            ]),
+        });
+        Ok(())
+    }
 }
@@ -397,7 +397,7 @@ impl ExecCtx<'_> {
                     });
                 }
                 if let Some(expected) = expect_stdout
-                    && &rule.stdout != expected
+                    && !self.observed_text_matches(expected, &rule.stdout)
                 {
                     return Err(Finding {
                         kind: FindingKind::Assertion,
@@ -407,7 +407,7 @@ impl ExecCtx<'_> {
                     });
                 }
                 if let Some(expected) = expect_stderr
-                    && &rule.stderr != expected
+                    && !self.observed_text_matches(expected, &rule.stderr)
                 {
                     return Err(Finding {
                         kind: FindingKind::Assertion,
@@ -5,7 +5,9 @@ use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use crate::{Decision, Finding, FindingKind, FindingLocation, MemoryState};
+use serde::{Deserialize, Serialize};
+
+use crate::{Decision, Finding, FindingKind, FindingLocation, FozzyError, MemoryState};
 
 pub(super) fn should_emit_heavy_artifacts(
     status: crate::ExitStatus,
@@ -25,7 +27,7 @@ pub(super) fn rng_from_seed(seed: u64) -> ChaCha20Rng {
     ChaCha20Rng::from_seed(seed32)
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub(super) struct ExecCheckpoint {
     pub(super) rng: ChaCha20Rng,
     pub(super) clock: crate::VirtualClock,
@@ -47,7 +49,7 @@ pub(super) struct ExecCheckpoint {
     pub(super) memory: MemoryState,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct HttpRule {
     pub(super) method: String,
     pub(super) path: String,
@@ -59,7 +61,7 @@ pub(super) struct HttpRule {
     pub(super) remaining: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct ProcRule {
     pub(super) cmd: String,
     pub(super) args: Vec<String>,
@@ -86,7 +88,7 @@ pub(super) fn proc_rule(
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct NetMessage {
     pub(super) id: u64,
     pub(super) from: String,
@@ -102,32 +104,125 @@ pub(super) fn sorted_pair(a: &str, b: &str) -> (String, String) {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Counterfactual replay settings: which recorded decisions were overridden, and
+/// whether execution leaves the trace once the last override is consumed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReplayOverrides {
+    /// Sorted decision indexes whose recorded values were replaced.
+    pub(crate) overridden: Vec<usize>,
+    pub(crate) live_after: Option<usize>,
+}
+
+/// Recorded decisions a replay consumes.
+pub(crate) enum ReplayDecisions<'a> {
+    /// Decisions of a trace held in memory.
+    Recorded(&'a [Decision]),
+    /// Decisions of a binary trace, read chunk by chunk; seeks only move forward.
+    Streamed(Box<crate::TraceDecisionStream>),
+}
+
 pub(super) struct ReplayCursor<'a> {
-    decisions: &'a [Decision],
+    decisions: ReplayDecisions<'a>,
     index: usize,
+    overridden: &'a [usize],
 }
 
 impl<'a> ReplayCursor<'a> {
-    pub(super) fn new(decisions: &'a [Decision]) -> Self {
+    pub(super) fn new(decisions: ReplayDecisions<'a>) -> Self {
         Self {
             decisions,
             index: 0,
+            overridden: &[],
         }
     }
 
+    pub(super) fn with_overrides(mut self, overridden: &'a [usize]) -> Self {
+        self.overridden = overridden;
+        self
+    }
+
+    /// The next decision was overridden and must be taken as-is, not re-derived.
+    pub(super) fn at_override(&self) -> bool {
+        self.overridden.binary_search(&self.index).is_ok()
+    }
+
     pub(super) fn next(&mut self) -> Option<&Decision> {
-        let d = self.decisions.get(self.index);
         self.index = self.index.saturating_add(1);
-        d
+        match &mut self.decisions {
+            ReplayDecisions::Recorded(decisions) => decisions.get(self.index - 1),
+            ReplayDecisions::Streamed(stream) => stream.next(),
+        }
     }
 
     pub(super) fn peek(&self) -> Option<&Decision> {
-        self.decisions.get(self.index)
+        match &self.decisions {
+            ReplayDecisions::Recorded(decisions) => decisions.get(self.index),
+            ReplayDecisions::Streamed(stream) => stream.peek(),
+        }
     }
 
     pub(super) fn remaining(&self) -> usize {
-        self.decisions.len().saturating_sub(self.index)
+        self.len().saturating_sub(self.index)
+    }
+
+    pub(super) fn position(&self) -> usize {
+        self.index
+    }
+
+    pub(super) fn seek(&mut self, index: usize) {
+        let index = index.min(self.len());
+        if let ReplayDecisions::Streamed(stream) = &mut self.decisions {
+            while self.index < index && stream.next().is_some() {
+                self.index += 1;
+            }
+        }
+        self.index = index;
+    }
+
+    /// The trace schedules steps through `SchedulerPick` decisions.
+    pub(super) fn has_scheduler_picks(&self) -> bool {
+        match &self.decisions {
+            ReplayDecisions::Recorded(decisions) => decisions
+                .iter()
+                .any(|d| matches!(d, Decision::SchedulerPick { .. })),
+            ReplayDecisions::Streamed(stream) => stream.scheduler_picks(),
+        }
+    }
+
+    /// Moves to the snapshot's decision index after checking the decisions before
+    /// it against the snapshot digest.
+    pub(super) fn resume_at(&mut self, snapshot: &crate::TraceSnapshot) -> Result<(), String> {
+        match &mut self.decisions {
+            ReplayDecisions::Recorded(decisions) => snapshot.verify(decisions)?,
+            ReplayDecisions::Streamed(stream) => {
+                let total = stream.total();
+                let mut digest = crate::DecisionsDigest::default();
+                for _ in self.index..snapshot.decision_index {
+                    match stream.next() {
+                        Some(decision) => digest.update(decision),
+                        None => break,
+                    }
+                }
+                snapshot.verify_digest(&digest.finish(), total)?;
+            }
+        }
+        self.index = snapshot.decision_index;
+        Ok(())
+    }
+
+    /// Error that cut a streamed trace short, if any.
+    pub(super) fn take_error(&mut self) -> Option<FozzyError> {
+        match &mut self.decisions {
+            ReplayDecisions::Recorded(_) => None,
+            ReplayDecisions::Streamed(stream) => stream.take_error(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.decisions {
+            ReplayDecisions::Recorded(decisions) => decisions.len(),
+            ReplayDecisions::Streamed(stream) => stream.total(),
+        }
     }
 }
 
@@ -138,7 +138,14 @@ pub struct RunOptions {
     pub record_trace_to: Option<PathBuf>,
     pub filter: Option<String>,
     pub jobs: Option<usize>,
+    /// Run only this partition of the filtered scenario set.
+    pub shard: Option<crate::TestShard>,
+    pub shard_strategy: crate::ShardStrategy,
     pub fail_fast: bool,
+    /// Retries for a failing test scenario; overrides the scenario's own `retries`.
+    pub retries: Option<u32>,
+    /// Quarantine list file for `fozzy test`.
+    pub quarantine: Option<PathBuf>,
     pub record_collision: RecordCollisionPolicy,
     pub profile_capture: ProfileCaptureLevel,
     pub proc_backend: ProcBackend,
@@ -154,6 +161,8 @@ pub struct ReplayOptions {
     pub dump_events: bool,
     pub profile_capture: ProfileCaptureLevel,
     pub reporter: Reporter,
+    /// Resume from the nearest recorded snapshot instead of the first step.
+    pub start: Option<crate::ReplayStart>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -205,6 +214,11 @@ pub struct ShrinkOptions {
     pub budget: Option<Duration>,
     pub aggressive: bool,
     pub minimize: ShrinkMinimize,
+    pub preserve: crate::ShrinkPreserve,
+    /// Regex a finding's title or message must match; implies `preserve: finding`.
+    pub finding_match: Option<String>,
+    /// Candidates evaluated concurrently; the shrunk trace does not depend on it.
+    pub jobs: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -231,12 +245,16 @@ impl clap::ValueEnum for RecordCollisionPolicy {
 #[derive(Debug, Clone)]
 pub struct RunResult {
     pub summary: RunSummary,
+    /// Non-fatal problems for the CLI to report (e.g. unreadable earlier run artifacts).
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ShrinkResult {
     pub out_trace_path: String,
     pub result: RunResult,
+    /// Accepted reductions, in order (also written to `<out>.lineage.json`).
+    pub lineage: Vec<crate::ShrinkReduction>,
 }
 
 #[derive(Debug, Clone)]
@@ -252,4 +270,10 @@ pub(crate) struct ScenarioRun {
     pub(crate) finished_at: String,
     pub(crate) duration_ms: u64,
     pub(crate) duration_ns: u64,
+    pub(crate) redaction: Option<crate::TraceRedaction>,
+    /// Recorded decisions consumed by a replay that stayed on the trace.
+    pub(crate) replay_consumed: usize,
+    /// Recorded and produced decision counts where a counterfactual replay went live.
+    pub(crate) live_from: Option<(usize, usize)>,
+    pub(crate) snapshots: Vec<crate::TraceSnapshot>,
 }
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use crate::engine::ScenarioRun;
 use crate::{
     ExitStatus, FozzyResult, ManifestProfileMetadata, MemorySummary, RecordCollisionPolicy,
-    Reporter, RunIdentity, RunMode, RunSummary, TestCounts, TraceFile,
+    Reporter, RunIdentity, RunMode, RunSummary, TestCounts, TraceEncoding, TraceFile,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -40,6 +40,7 @@ pub(crate) fn build_run_summary(
         tests,
         memory,
         findings,
+        retries: Vec::new(),
     }
 }
 
@@ -78,15 +79,19 @@ pub(crate) fn build_single_scenario_trace(
         summary,
     );
     trace.memory = run.memory.as_ref().map(|m| m.to_trace());
+    trace.redaction = run.redaction.clone();
+    trace.snapshots = run.snapshots.clone();
     trace
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn write_single_scenario_trace(
     requested_path: &Path,
     run: &ScenarioRun,
     run_id: &str,
     seed: u64,
     policy: RecordCollisionPolicy,
+    encoding: TraceEncoding,
     mode: RunMode,
     report_path: Option<String>,
     artifacts_dir: Option<String>,
@@ -94,7 +99,7 @@ pub(crate) fn write_single_scenario_trace(
     let target = crate::resolve_record_target(requested_path, policy)?;
     let trace =
         build_single_scenario_trace(&target, run, run_id, seed, mode, report_path, artifacts_dir);
-    crate::write_trace_to_target(&trace, &target)?;
+    crate::write_trace_to_target(&trace, &target, encoding)?;
     Ok(target)
 }
 
@@ -149,17 +154,11 @@ pub(crate) fn write_reporter_artifacts(
     artifacts_dir: &Path,
     reporter: Reporter,
 ) -> FozzyResult<()> {
-    if matches!(reporter, Reporter::Junit) {
-        std::fs::write(
-            artifacts_dir.join("junit.xml"),
-            crate::render_junit_xml(summary),
-        )?;
-    }
-    if matches!(reporter, Reporter::Html) {
-        std::fs::write(
-            artifacts_dir.join("report.html"),
-            crate::render_html(summary),
-        )?;
+    if let (Some(name), Some(content)) = (
+        crate::reporter_artifact_name(reporter),
+        crate::render_reporter(summary, reporter),
+    ) {
+        std::fs::write(artifacts_dir.join(name), content)?;
     }
     Ok(())
 }
@@ -1,5 +1,7 @@
 //! Deterministic memory capability runtime.
 
+use serde::{Deserialize, Serialize};
+
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
@@ -7,7 +9,7 @@ use crate::{
     MemorySummary, MemoryTimelineEntry,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllocRecord {
     pub bytes: u64,
     pub callsite_hash: String,
@@ -22,7 +24,7 @@ pub struct AllocOutcome {
     pub effective_bytes: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryState {
     pub options: MemoryOptions,
     next_alloc_id: u64,
@@ -230,6 +232,14 @@ impl MemoryState {
         self.in_use_bytes
     }
 
+    pub fn peak_bytes(&self) -> u64 {
+        self.peak_bytes
+    }
+
+    pub fn live_allocations(&self) -> impl Iterator<Item = (u64, &AllocRecord)> {
+        self.live.iter().map(|(id, record)| (*id, record))
+    }
+
     pub fn has_activity(&self) -> bool {
         self.alloc_ops > 0
             || self.free_count > 0
@@ -1,22 +1,25 @@
-use std::path::PathBuf;
+use std::io::IsTerminal as _;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use rand_core::RngCore as _;
 use uuid::Uuid;
 
 use crate::engine::{
-    FsBackend, HttpBackend, ProcBackend, ProfileCaptureLevel, ReplayOptions, RunOptions, RunResult,
-    ShrinkMinimize, ShrinkOptions, ShrinkResult, run_embedded_scenario_inner, run_scenario_inner,
+    FsBackend, HttpBackend, ProcBackend, ProfileCaptureLevel, ReplayDebugger, ReplayDecisions,
+    ReplayOptions, RunOptions, RunResult, ShrinkMinimize, ShrinkOptions, ShrinkResult,
+    run_debugger_session, run_embedded_scenario_inner, run_scenario_inner,
     run_scenario_replay_inner,
 };
 use crate::finalize::{
-    build_run_summary, build_shrink_preview_trace, trace_timing_for_run, write_reporter_artifacts,
-    write_single_scenario_trace, write_summary_report,
+    build_run_summary, build_shrink_preview_trace, build_single_scenario_trace,
+    trace_timing_for_run, write_reporter_artifacts, write_single_scenario_trace,
+    write_summary_report,
 };
 use crate::{
     Config, ExitStatus, Finding, FindingKind, FozzyError, FozzyResult, HeapBudgetPolicy,
-    MemoryRunReport, RunMode, ScenarioPath, ScenarioV1Steps, TraceFile, TracePath,
-    heap_budget_findings_from_trace, wall_time_iso_utc, write_memory_artifacts,
+    MemoryRunReport, RunMode, ScenarioPath, ScenarioV1Steps, TraceEncoding, TraceFile, TracePath,
+    TraceRecording, heap_budget_findings_from_trace, wall_time_iso_utc, write_memory_artifacts,
     write_memory_delta_artifact, write_profile_artifacts_from_trace_with_source,
 };
 
@@ -27,6 +30,20 @@ pub fn run_scenario(
 ) -> FozzyResult<RunResult> {
     let seed = opt.seed.unwrap_or_else(gen_seed);
     let run_id = Uuid::new_v4().to_string();
+    let artifacts_dir = config.runs_dir().join(&run_id);
+    let requested_trace = opt
+        .record_trace_to
+        .clone()
+        .unwrap_or_else(|| artifacts_dir.join("trace.fozzy"));
+    // Binary traces are streamed while the scenario runs when `--record` guarantees
+    // the trace is kept; a failure trace of an unrecorded run is written at the end.
+    let mut recording = match (config.trace_encoding, &opt.record_trace_to) {
+        (TraceEncoding::Json, _) | (_, None) => None,
+        (encoding, Some(_)) => TraceRecording::new(
+            &crate::resolve_record_target(&requested_trace, opt.record_collision)?,
+            encoding,
+        ),
+    };
 
     let run = run_scenario_inner(
         config,
@@ -39,10 +56,10 @@ pub fn run_scenario(
         opt.fs_backend,
         opt.http_backend,
         opt.memory.clone(),
+        recording.as_mut(),
     )?;
     let (started_at, finished_at, duration_ms, duration_ns) = trace_timing_for_run(&run);
 
-    let artifacts_dir = config.runs_dir().join(&run_id);
     std::fs::create_dir_all(&artifacts_dir)?;
 
     let report_path = artifacts_dir.join("report.json");
@@ -99,20 +116,34 @@ pub fn run_scenario(
     }
     let should_record = opt.record_trace_to.is_some() || run.status != ExitStatus::Pass;
     if should_record {
-        let path = opt
-            .record_trace_to
-            .clone()
-            .unwrap_or_else(|| artifacts_dir.join("trace.fozzy"));
-        let written = write_single_scenario_trace(
-            &path,
-            &run,
-            &run_id,
-            seed,
-            opt.record_collision,
-            RunMode::Run,
-            Some(report_path.to_string_lossy().to_string()),
-            Some(artifacts_dir.to_string_lossy().to_string()),
-        )?;
+        let report = Some(report_path.to_string_lossy().to_string());
+        let artifacts = Some(artifacts_dir.to_string_lossy().to_string());
+        let written = match recording.take() {
+            Some(recording) => {
+                let target = recording.path().to_path_buf();
+                recording.finish(&build_single_scenario_trace(
+                    &target,
+                    &run,
+                    &run_id,
+                    seed,
+                    RunMode::Run,
+                    report,
+                    artifacts,
+                ))?;
+                target
+            }
+            None => write_single_scenario_trace(
+                &requested_trace,
+                &run,
+                &run_id,
+                seed,
+                opt.record_collision,
+                config.trace_encoding,
+                RunMode::Run,
+                report,
+                artifacts,
+            )?,
+        };
         trace_path = Some(written);
     }
 
@@ -134,8 +165,21 @@ pub fn run_scenario(
         &artifacts_dir,
         profile_metadata.as_ref(),
     )?;
+    let warnings = crate::record_run_history(
+        config,
+        &summary,
+        vec![crate::HistoryScenario::from_run(
+            &run,
+            seed,
+            Duration::from_nanos(duration_ns),
+            None,
+        )],
+    );
 
-    Ok(RunResult { summary })
+    Ok(RunResult {
+        summary,
+        warnings: warnings.into_iter().collect(),
+    })
 }
 
 pub fn replay_trace(
@@ -143,7 +187,13 @@ pub fn replay_trace(
     trace_path: TracePath,
     opt: &ReplayOptions,
 ) -> FozzyResult<RunResult> {
-    let trace = TraceFile::read_json(trace_path.as_path())?;
+    let (trace, streamed) = match binary_scenario_outline(trace_path.as_path())? {
+        Some(outline) => {
+            let stream = outline.decision_stream()?;
+            (outline.trace, Some((stream, outline.replay_warnings)))
+        }
+        None => (TraceFile::read_json(trace_path.as_path())?, None),
+    };
     if trace.fuzz.is_some() && trace.scenario.is_none() {
         return crate::replay_fuzz_trace(config, &trace, trace_path.as_path(), opt);
     }
@@ -166,19 +216,33 @@ pub fn replay_trace(
     let started_at = wall_time_iso_utc();
     let started = Instant::now();
 
+    let overrides = crate::replay_overrides(&trace);
+    let resume = opt
+        .start
+        .and_then(|start| crate::nearest_snapshot(&trace.snapshots, start));
+    let (decisions, replay_warnings) = match streamed {
+        Some((stream, warnings)) => (ReplayDecisions::Streamed(Box::new(stream)), warnings),
+        None => (
+            ReplayDecisions::Recorded(&trace.decisions),
+            crate::trace_replay_warnings(&trace),
+        ),
+    };
     let run = run_scenario_replay_inner(
         config,
         RunMode::Replay,
         &scenario,
         &scenario_path,
         seed,
-        Some(&trace.decisions),
+        Some(decisions),
         opt.until,
         opt.step,
         ProcBackend::Scripted,
         FsBackend::Virtual,
         HttpBackend::Scripted,
         replay_memory_options(&trace),
+        replay_redactor(config, &trace)?,
+        overrides.as_ref(),
+        resume,
     )?;
 
     let finished_at = wall_time_iso_utc();
@@ -191,7 +255,7 @@ pub fn replay_trace(
     let mut findings = run.findings.clone();
     for warning in crate::trace_schema_warnings(trace.version)
         .into_iter()
-        .chain(crate::trace_replay_warnings(&trace))
+        .chain(replay_warnings)
     {
         findings.push(Finding {
             kind: FindingKind::Checker,
@@ -306,7 +370,99 @@ pub fn replay_trace(
         &artifacts_dir,
         profile_metadata.as_ref(),
     )?;
-    Ok(RunResult { summary })
+    Ok(RunResult {
+        summary,
+        warnings: Vec::new(),
+    })
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReplayDebugReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub trace: String,
+    pub position: usize,
+    #[serde(rename = "totalSteps")]
+    pub total_steps: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ExitStatus>,
+    pub transcript: Vec<serde_json::Value>,
+}
+
+/// Outline of `path` when it is a binary scenario trace, whose decisions replay
+/// streams instead of loading them whole.
+fn binary_scenario_outline(path: &Path) -> FozzyResult<Option<crate::TraceOutline>> {
+    if !crate::is_binary_trace_file(path) {
+        return Ok(None);
+    }
+    let outline = crate::TraceOutline::read(path)?;
+    Ok(outline.trace.scenario.is_some().then_some(outline))
+}
+
+/// Runs the replay debugger over a scenario trace.
+///
+/// Commands come from `script` when given, otherwise from stdin. With `stream`
+/// set, each response is printed as it happens (with a prompt on a TTY).
+pub fn debug_replay_trace(
+    config: &Config,
+    trace_path: TracePath,
+    script: Option<&Path>,
+    stream: bool,
+) -> FozzyResult<ReplayDebugReport> {
+    let trace = TraceFile::read_json(trace_path.as_path())?;
+    let scenario = trace.scenario.as_ref().ok_or_else(|| {
+        FozzyError::Trace(
+            "replay debugger needs a scenario trace (fuzz and explore traces are not supported)"
+                .to_string(),
+        )
+    })?;
+    let scenario_path = trace
+        .scenario_path
+        .clone()
+        .unwrap_or_else(|| trace_path.as_path().to_string_lossy().to_string());
+    let mut debugger = ReplayDebugger::new(
+        scenario,
+        &scenario_path,
+        trace.summary.identity.seed,
+        &trace.decisions,
+        replay_memory_options(&trace),
+        replay_redactor(config, &trace)?,
+    )?;
+
+    let stdout = std::io::stdout();
+    let mut sink = std::io::sink();
+    let mut out: Box<dyn std::io::Write> = if stream {
+        Box::new(stdout.lock())
+    } else {
+        Box::new(&mut sink)
+    };
+    let transcript = match script {
+        Some(path) => {
+            let file = std::fs::File::open(path).map_err(|e| {
+                FozzyError::InvalidArgument(format!(
+                    "failed to open debug script {}: {e}",
+                    path.display()
+                ))
+            })?;
+            let mut input = std::io::BufReader::new(file);
+            run_debugger_session(&mut debugger, &mut input, &mut out, stream, false)?
+        }
+        None => {
+            let stdin = std::io::stdin();
+            let prompt = stream && stdin.is_terminal();
+            run_debugger_session(&mut debugger, &mut stdin.lock(), &mut out, stream, prompt)?
+        }
+    };
+    drop(out);
+
+    Ok(ReplayDebugReport {
+        schema_version: "fozzy.replay_debug.v1".to_string(),
+        trace: trace_path.as_path().display().to_string(),
+        position: debugger.position(),
+        total_steps: debugger.total_steps(),
+        status: debugger.status(),
+        transcript,
+    })
 }
 
 pub fn shrink_trace(
@@ -321,7 +477,7 @@ pub fn shrink_trace_with_predicate(
     config: &Config,
     trace_path: TracePath,
     opt: &ShrinkOptions,
-    objective: &dyn Fn(&TraceFile) -> FozzyResult<bool>,
+    objective: &(dyn Fn(&TraceFile) -> FozzyResult<bool> + Sync),
 ) -> FozzyResult<ShrinkResult> {
     shrink_trace_inner(config, trace_path, opt, Some(objective))
 }
@@ -330,7 +486,7 @@ fn shrink_trace_inner(
     config: &Config,
     trace_path: TracePath,
     opt: &ShrinkOptions,
-    objective: Option<&dyn Fn(&TraceFile) -> FozzyResult<bool>>,
+    objective: Option<&(dyn Fn(&TraceFile) -> FozzyResult<bool> + Sync)>,
 ) -> FozzyResult<ShrinkResult> {
     let trace = TraceFile::read_json(trace_path.as_path())?;
     if trace.fuzz.is_some() && trace.scenario.is_none() {
@@ -339,18 +495,16 @@ fn shrink_trace_inner(
     if trace.explore.is_some() && trace.scenario.is_none() {
         return crate::shrink_explore_trace(config, trace_path, opt);
     }
-    let target_status = trace.summary.status;
+    let target = crate::ShrinkTarget::from_trace(&trace, opt)?;
     let seed = trace.summary.identity.seed;
 
     let scenario = trace.scenario.clone().ok_or_else(|| {
         FozzyError::Trace("trace missing embedded scenario; cannot shrink".to_string())
     })?;
 
-    if opt.minimize != ShrinkMinimize::All && opt.minimize != ShrinkMinimize::Input {
-        return Err(FozzyError::InvalidArgument(
-            "v0.1 shrink only supports input/step shrinking (use --minimize input|all)".to_string(),
-        ));
-    }
+    let shrink_input = matches!(opt.minimize, ShrinkMinimize::All | ShrinkMinimize::Input);
+    let shrink_faults = matches!(opt.minimize, ShrinkMinimize::All | ShrinkMinimize::Faults);
+    let shrink_schedule = matches!(opt.minimize, ShrinkMinimize::All | ShrinkMinimize::Schedule);
 
     let budget = opt.budget.unwrap_or(Duration::from_secs(15));
     let deadline = Instant::now() + budget;
@@ -374,13 +528,16 @@ fn shrink_trace_inner(
                 &steps,
                 label,
                 seed,
-                Some(&trace.decisions),
+                Some(ReplayDecisions::Recorded(&trace.decisions)),
                 None,
                 false,
                 ProcBackend::Scripted,
                 FsBackend::Virtual,
                 HttpBackend::Scripted,
                 replay_memory_options(&trace),
+                replay_redactor(config, &trace)?,
+                None,
+                None,
             )
         } else {
             run_embedded_scenario_inner(
@@ -393,13 +550,15 @@ fn shrink_trace_inner(
                 FsBackend::Virtual,
                 HttpBackend::Scripted,
                 replay_memory_options(&trace),
+                0,
+                None,
             )
         }
     };
     let mut best_run = run_candidate(scenario.clone(), "<shrink-baseline>")?;
-    if !crate::shrink_status_matches(target_status, best_run.status) {
+    if !target.matches(best_run.status, &best_run.findings) {
         return Err(FozzyError::Trace(
-            "baseline trace no longer matches shrink target status".to_string(),
+            "baseline trace no longer matches shrink target status/finding".to_string(),
         ));
     }
     if let Some(pred) = objective {
@@ -407,38 +566,50 @@ fn shrink_trace_inner(
         let _ = pred(&preview)?;
     }
 
+    // A trial is kept when it still shows the target failure (and the objective, if any).
+    let keeps_target =
+        |trial_scenario: &ScenarioV1Steps, res: &crate::ScenarioRun| -> FozzyResult<bool> {
+            if !target.matches(res.status, &res.findings) {
+                return Ok(false);
+            }
+            match objective {
+                Some(pred) => pred(&build_shrink_preview_trace(trial_scenario, seed, res)),
+                None => Ok(true),
+            }
+        };
+    let try_steps = |steps: &[crate::Step]| -> FozzyResult<Option<crate::ScenarioRun>> {
+        let trial_scenario = ScenarioV1Steps {
+            version: 1,
+            name: scenario.name.clone(),
+            steps: steps.to_vec(),
+        };
+        let res = run_candidate(trial_scenario.clone(), "<shrunk>")?;
+        Ok(keeps_target(&trial_scenario, &res)?.then_some(res))
+    };
+    let mut steps_changed = false;
+
     let mut chunk = candidate.len().max(1).div_ceil(2);
-    while chunk > 0 && Instant::now() < deadline && candidate.len() > 1 {
+    while shrink_input && chunk > 0 && Instant::now() < deadline && candidate.len() > 1 {
         let mut improved = false;
         let mut i = 0usize;
         while i < candidate.len() && Instant::now() < deadline {
-            let end = (i + chunk).min(candidate.len());
-            let trial = remove_step_range(&candidate, i, end);
-            if trial.is_empty() {
-                i += chunk;
-                continue;
-            }
-
-            let trial_scenario = ScenarioV1Steps {
-                version: 1,
-                name: scenario.name.clone(),
-                steps: trial.clone(),
-            };
-
-            let res = run_candidate(trial_scenario.clone(), "<shrunk>")?;
-            if !crate::shrink_status_matches(target_status, res.status) {
-                i += chunk;
-                continue;
-            }
-            if let Some(pred) = objective {
-                let preview = build_shrink_preview_trace(&trial_scenario, seed, &res);
-                if !pred(&preview)? {
-                    i += chunk;
-                    continue;
+            let starts: Vec<usize> = (i..candidate.len()).step_by(chunk).collect();
+            let accepted = target.first_accepted(&starts, Some(deadline), |&start| {
+                let end = (start + chunk).min(candidate.len());
+                let trial = remove_step_range(&candidate, start, end);
+                if trial.is_empty() {
+                    return Ok(None);
                 }
-            }
+                Ok(try_steps(&trial)?.map(|res| (trial, res)))
+            })?;
+            let Some((pos, (trial, res))) = accepted else {
+                break;
+            };
+            target.record("steps", "steps", candidate.len(), trial.len());
+            i = starts[pos];
             candidate = trial;
             best_run = res;
+            steps_changed = true;
             improved = true;
         }
 
@@ -450,6 +621,112 @@ fn shrink_trace_inner(
         }
     }
 
+    // Second phase: simplify the values inside the remaining steps, one step at a time,
+    // restarting a step's candidates whenever one of them is accepted.
+    let mut i = 0usize;
+    while shrink_input && i < candidate.len() && Instant::now() < deadline {
+        let simpler = crate::step_value_candidates(&candidate[i]);
+        let accepted = target.first_accepted(&simpler, Some(deadline), |step| {
+            let mut trial = candidate.clone();
+            trial[i] = step.clone();
+            Ok(try_steps(&trial)?.map(|res| (trial, res)))
+        })?;
+        let Some((_, (trial, res))) = accepted else {
+            i += 1;
+            continue;
+        };
+        target.record_reduction(crate::ShrinkReduction {
+            phase: "values".to_string(),
+            unit: "steps".to_string(),
+            before: candidate.len(),
+            after: trial.len(),
+            step: Some(i),
+        });
+        candidate = trial;
+        best_run = res;
+        steps_changed = true;
+    }
+
+    // Faults: drop partitions, drop rates and memory failure injections that the
+    // failure does not need.
+    let mut i = 0usize;
+    while shrink_faults && candidate.len() > 1 && Instant::now() < deadline {
+        let faults: Vec<usize> = (i..candidate.len())
+            .filter(|&index| crate::is_fault_step(&candidate[index]))
+            .collect();
+        let accepted = target.first_accepted(&faults, Some(deadline), |&index| {
+            let trial = remove_step_range(&candidate, index, index + 1);
+            Ok(try_steps(&trial)?.map(|res| (trial, res)))
+        })?;
+        let Some((pos, (trial, res))) = accepted else {
+            break;
+        };
+        target.record("faults", "steps", candidate.len(), trial.len());
+        i = faults[pos];
+        candidate = trial;
+        best_run = res;
+        steps_changed = true;
+    }
+
+    // Schedule: replay the final steps under delivery orders closer to FIFO. Trials
+    // that drift (a pick names an undeliverable message) are rejected.
+    let mut out_decisions = None;
+    if shrink_schedule {
+        let schedule_scenario = ScenarioV1Steps {
+            version: 1,
+            name: scenario.name.clone(),
+            steps: candidate.clone(),
+        };
+        let replay_schedule =
+            |decisions: &[crate::Decision]| -> FozzyResult<Option<crate::ScenarioRun>> {
+                let Ok(res) = run_scenario_replay_inner(
+                    config,
+                    RunMode::Replay,
+                    &schedule_scenario,
+                    "<shrunk>",
+                    seed,
+                    Some(ReplayDecisions::Recorded(decisions)),
+                    None,
+                    false,
+                    ProcBackend::Scripted,
+                    FsBackend::Virtual,
+                    HttpBackend::Scripted,
+                    replay_memory_options(&trace),
+                    replay_redactor(config, &trace)?,
+                    None,
+                    None,
+                ) else {
+                    return Ok(None);
+                };
+                if res.findings.iter().any(|f| f.title == "replay_drift") {
+                    return Ok(None);
+                }
+                Ok(keeps_target(&schedule_scenario, &res)?.then_some(res))
+            };
+        let mut decisions = if steps_changed {
+            best_run.decisions.decisions.clone()
+        } else {
+            trace.decisions.clone()
+        };
+        while Instant::now() < deadline {
+            let trials = crate::net_schedule_candidates(&decisions);
+            let accepted =
+                target.first_accepted(&trials, Some(deadline), |trial| replay_schedule(trial))?;
+            let Some((pos, res)) = accepted else {
+                break;
+            };
+            target.record(
+                "schedule",
+                "inversions",
+                crate::net_schedule_inversions(&decisions),
+                crate::net_schedule_inversions(&trials[pos]),
+            );
+            decisions = trials[pos].clone();
+            best_run = res;
+        }
+        out_decisions = Some(decisions);
+    }
+
     let out_scenario = ScenarioV1Steps {
         version: 1,
         name: scenario.name.clone(),
@@ -484,17 +761,19 @@ fn shrink_trace_inner(
         RunMode::Replay,
         None,
         Some(out_scenario),
-        best_run.decisions.decisions.clone(),
+        out_decisions.unwrap_or_else(|| best_run.decisions.decisions.clone()),
         best_run.events.clone(),
         summary.clone(),
     );
     trace_out.memory = best_run.memory.as_ref().map(|m| m.to_trace());
-    trace_out.write_json(&out_path).map_err(|err| {
-        FozzyError::Trace(format!(
-            "failed to write shrunk trace to {}: {err}",
-            out_path.display()
-        ))
-    })?;
+    trace_out
+        .write(&out_path, config.trace_encoding)
+        .map_err(|err| {
+            FozzyError::Trace(format!(
+                "failed to write shrunk trace to {}: {err}",
+                out_path.display()
+            ))
+        })?;
     if let (Some(before), Some(after)) = (trace.memory.as_ref(), best_run.memory.as_ref()) {
         let before_report = MemoryRunReport {
             schema_version: "fozzy.memory_report.v1".to_string(),
@@ -511,9 +790,14 @@ fn shrink_trace_inner(
         )?;
     }
 
+    let lineage = target.write_lineage(trace_path.as_path(), &out_path)?;
     Ok(ShrinkResult {
         out_trace_path: out_path.to_string_lossy().to_string(),
-        result: RunResult { summary },
+        result: RunResult {
+            summary,
+            warnings: Vec::new(),
+        },
+        lineage,
     })
 }
 
@@ -548,7 +832,7 @@ fn heap_budget_policy(config: &Config) -> HeapBudgetPolicy {
     }
 }
 
-fn replay_memory_options(trace: &TraceFile) -> crate::MemoryOptions {
+pub(crate) fn replay_memory_options(trace: &TraceFile) -> crate::MemoryOptions {
     trace
         .memory
         .as_ref()
@@ -560,6 +844,16 @@ fn replay_memory_options(trace: &TraceFile) -> crate::MemoryOptions {
         })
 }
 
+pub(crate) fn replay_redactor(
+    config: &Config,
+    trace: &TraceFile,
+) -> FozzyResult<Option<crate::Redactor>> {
+    let Some(redaction) = &trace.redaction else {
+        return Ok(None);
+    };
+    crate::Redactor::for_trace(redaction, config.redact.key()?.as_deref()).map(Some)
+}
+
 fn pass_checker_warning_drift(
     expected: &crate::RunSummary,
     actual_findings: &[crate::Finding],
@@ -643,8 +937,14 @@ mod tests {
                 tests: None,
                 memory: None,
                 findings: Vec::new(),
+                retries: Vec::new(),
             },
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
         };
         trace.write_json(&trace_path).expect("write trace");
 
@@ -662,8 +962,11 @@ mod tests {
             mem_artifacts: false,
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
             mem_fragmentation_seed: None,
             mem_pressure_wave: None,
+            trace_encoding: Default::default(),
         };
 
         let result = shrink_trace(
@@ -674,6 +977,9 @@ mod tests {
                 budget: None,
                 aggressive: false,
                 minimize: ShrinkMinimize::All,
+                preserve: crate::ShrinkPreserve::Status,
+                finding_match: None,
+                jobs: None,
             },
         )
         .expect("shrink");
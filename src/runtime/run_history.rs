@@ -0,0 +1,192 @@
+//! Append-only run history index (`.fozzy/history.jsonl`).
+//!
+//! Every `fozzy run` and `fozzy test` appends one JSON line per scenario with its
+//! outcome, seed, duration, profile metrics and the git commit it ran against, so
+//! `fozzy history` can answer trend questions without scanning `.fozzy/runs`.
+
+use serde::{Deserialize, Serialize};
+
+use std::io::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::engine::ScenarioRun;
+use crate::finalize::trace_timing_for_run;
+use crate::{
+    Config, ExitStatus, FailureClassification, FozzyResult, ProfileSource, RunMode, RunSummary,
+    ScenarioRetry, profile_metrics_from_events,
+};
+
+/// One scenario outcome from one run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRecord {
+    pub run_id: String,
+    pub mode: RunMode,
+    pub finished_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(flatten)]
+    pub scenario: HistoryScenario,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryScenario {
+    pub path: String,
+    /// Status of the first attempt; retries are reflected in `classification`.
+    pub status: ExitStatus,
+    pub seed: u64,
+    pub duration_ms: u64,
+    pub duration_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<FailureClassification>,
+    #[serde(default)]
+    pub quarantined: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<HistoryProfile>,
+}
+
+/// The subset of profile metrics worth trending across runs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryProfile {
+    pub cpu_time_ms: u64,
+    pub alloc_bytes: u64,
+    pub p50_latency_ms: u64,
+    pub p95_latency_ms: u64,
+    pub p99_latency_ms: u64,
+    pub max_latency_ms: u64,
+}
+
+impl HistoryScenario {
+    pub(crate) fn from_run(
+        run: &ScenarioRun,
+        seed: u64,
+        duration: Duration,
+        retry: Option<&ScenarioRetry>,
+    ) -> Self {
+        let metrics = profile_metrics_from_events(ProfileSource {
+            run_id: "history",
+            seed,
+            duration_ms: trace_timing_for_run(run).2,
+            memory_in_use_bytes: run
+                .memory
+                .as_ref()
+                .map(|m| m.summary.in_use_bytes)
+                .unwrap_or(0),
+            events: &run.events,
+        });
+        let (duration_ms, duration_ns) = crate::duration_fields(duration);
+        Self {
+            path: run.scenario_path.display().to_string(),
+            status: run.status,
+            seed,
+            duration_ms,
+            duration_ns,
+            classification: retry.and_then(|retry| retry.classification),
+            quarantined: retry.is_some_and(|retry| retry.quarantined),
+            profile: Some(HistoryProfile {
+                cpu_time_ms: metrics.cpu_time_ms,
+                alloc_bytes: metrics.alloc_bytes,
+                p50_latency_ms: metrics.p50_latency_ms,
+                p95_latency_ms: metrics.p95_latency_ms,
+                p99_latency_ms: metrics.p99_latency_ms,
+                max_latency_ms: metrics.max_latency_ms,
+            }),
+        }
+    }
+}
+
+impl HistoryRecord {
+    /// Whether the run counted this scenario as passed (a flaky failure that passed on
+    /// its same-seed retry counts).
+    pub fn passed(&self) -> bool {
+        self.scenario.status == ExitStatus::Pass
+            || self.scenario.classification == Some(FailureClassification::Flaky)
+    }
+}
+
+/// Records the finished run in the history index. History is advisory, so a failed
+/// append is returned as a warning instead of failing a run whose report is already
+/// written.
+pub(crate) fn record_run_history(
+    config: &Config,
+    summary: &RunSummary,
+    scenarios: Vec<HistoryScenario>,
+) -> Option<String> {
+    append_run_history(config, summary, scenarios)
+        .err()
+        .map(|err| {
+            format!(
+                "failed to append run history {}: {err}",
+                config.history_path().display()
+            )
+        })
+}
+
+/// Appends the scenarios of the finished run `summary` to the history index.
+pub(crate) fn append_run_history(
+    config: &Config,
+    summary: &RunSummary,
+    scenarios: Vec<HistoryScenario>,
+) -> FozzyResult<()> {
+    if scenarios.is_empty() {
+        return Ok(());
+    }
+    let git_commit = crate::git_head_commit();
+    let mut lines = Vec::new();
+    for scenario in scenarios {
+        let record = HistoryRecord {
+            run_id: summary.identity.run_id.clone(),
+            mode: summary.mode,
+            finished_at: summary.finished_at.clone(),
+            git_commit: git_commit.clone(),
+            scenario,
+        };
+        serde_json::to_writer(&mut lines, &record)?;
+        lines.push(b'\n');
+    }
+    let path = config.history_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(&lines)?;
+    Ok(())
+}
+
+/// The parsed history index.
+#[derive(Debug, Clone, Default)]
+pub struct RunHistory {
+    /// Every readable record, oldest first.
+    pub records: Vec<HistoryRecord>,
+    /// Lines that could not be parsed (e.g. a write torn by a crash) and were skipped.
+    pub skipped: usize,
+}
+
+/// Every record in the history index at `path`. A missing index is empty; unparsable
+/// lines are skipped and counted rather than failing every query.
+pub fn load_run_history(path: &Path) -> FozzyResult<RunHistory> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(RunHistory::default());
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let mut history = RunHistory::default();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(record) => history.records.push(record),
+            Err(_) => history.skipped += 1,
+        }
+    }
+    Ok(history)
+}
@@ -0,0 +1,93 @@
+//! Schedule and fault minimization for step scenarios.
+//!
+//! The delivery schedule of a step trace is the sequence of `NetDeliverPick` decisions.
+//! Candidates reorder it toward FIFO (ascending message ids) by swapping picks, keeping
+//! each message's `NetDrop` outcome attached to it. Faults are the scenario steps that
+//! inject them: partitions, drop rates and memory failure injection.
+
+use crate::{Decision, Step};
+
+/// Whether `step` injects a fault that fault minimization may remove.
+pub(crate) fn is_fault_step(step: &Step) -> bool {
+    match step {
+        Step::NetSetDropRate { rate } => *rate > 0.0,
+        Step::NetPartition { .. }
+        | Step::MemoryLimitMb { .. }
+        | Step::MemoryFailAfterAllocs { .. }
+        | Step::MemoryFragmentation { .. }
+        | Step::MemoryPressureWave { .. } => true,
+        _ => false,
+    }
+}
+
+/// Delivery schedules closer to FIFO than `decisions`, each with strictly fewer
+/// out-of-order deliveries. The earliest inversion is tried first: the smallest later
+/// message is pulled forward, then adjacent out-of-order picks are swapped.
+pub(crate) fn net_schedule_candidates(decisions: &[Decision]) -> Vec<Vec<Decision>> {
+    let picks: Vec<(usize, u64)> = decisions
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, decision)| match decision {
+            Decision::NetDeliverPick { message_id } => Some((idx, *message_id)),
+            _ => None,
+        })
+        .collect();
+    let mut out = Vec::new();
+    for (pos, (_, id)) in picks.iter().enumerate() {
+        let Some((later, min_id)) = picks[pos + 1..]
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, later_id))| *later_id)
+            .map(|(offset, (_, later_id))| (pos + 1 + offset, *later_id))
+        else {
+            continue;
+        };
+        if min_id < *id {
+            out.push(swap_deliveries(decisions, picks[pos].0, picks[later].0));
+        }
+    }
+    for pair in picks.windows(2) {
+        let [(first, first_id), (second, second_id)] = pair else {
+            continue;
+        };
+        if second_id < first_id {
+            let swapped = swap_deliveries(decisions, *first, *second);
+            if !out.contains(&swapped) {
+                out.push(swapped);
+            }
+        }
+    }
+    out
+}
+
+/// Number of out-of-order delivery pairs in the recorded schedule.
+pub(crate) fn net_schedule_inversions(decisions: &[Decision]) -> usize {
+    let ids: Vec<u64> = decisions
+        .iter()
+        .filter_map(|decision| match decision {
+            Decision::NetDeliverPick { message_id } => Some(*message_id),
+            _ => None,
+        })
+        .collect();
+    ids.iter()
+        .enumerate()
+        .map(|(pos, id)| ids[pos + 1..].iter().filter(|later| *later < id).count())
+        .sum()
+}
+
+/// Exchanges the messages delivered at picks `a` and `b`, along with the `NetDrop`
+/// decision recorded right after each pick.
+fn swap_deliveries(decisions: &[Decision], a: usize, b: usize) -> Vec<Decision> {
+    let mut out = decisions.to_vec();
+    out.swap(a, b);
+    if let (Some(Decision::NetDrop { .. }), Some(Decision::NetDrop { .. })) =
+        (decisions.get(a + 1), decisions.get(b + 1))
+    {
+        out.swap(a + 1, b + 1);
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "schedule_shrink/tests.rs"]
+mod tests;
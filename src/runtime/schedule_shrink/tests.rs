@@ -0,0 +1,74 @@
+use super::*;
+
+fn pick(message_id: u64, dropped: bool) -> [Decision; 2] {
+    [
+        Decision::NetDeliverPick { message_id },
+        Decision::NetDrop {
+            message_id,
+            dropped,
+        },
+    ]
+}
+
+fn order(decisions: &[Decision]) -> Vec<(u64, bool)> {
+    decisions
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [
+                Decision::NetDeliverPick { message_id },
+                Decision::NetDrop {
+                    message_id: drop_id,
+                    dropped,
+                },
+            ] => {
+                assert_eq!(message_id, drop_id, "drop must follow its pick");
+                Some((*message_id, *dropped))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn schedule_candidates_move_toward_fifo_and_keep_drop_outcomes() {
+    let mut decisions = vec![Decision::Step {
+        index: 0,
+        name: "step-0".to_string(),
+    }];
+    decisions.extend(pick(3, false));
+    decisions.extend(pick(2, false));
+    decisions.extend(pick(1, true));
+
+    let candidates = net_schedule_candidates(&decisions);
+    let orders: Vec<Vec<(u64, bool)>> = candidates.iter().map(|c| order(c)).collect();
+    assert_eq!(
+        orders,
+        vec![
+            vec![(1, true), (2, false), (3, false)],
+            vec![(3, false), (1, true), (2, false)],
+            vec![(2, false), (3, false), (1, true)],
+        ]
+    );
+    assert!(candidates.iter().all(|c| c[0] == decisions[0]));
+    assert!(net_schedule_candidates(&candidates[0]).is_empty());
+}
+
+#[test]
+fn fault_steps_are_partitions_drop_rates_and_memory_injection() {
+    let step = |value: serde_json::Value| serde_json::from_value::<Step>(value).expect("step");
+    for fault in [
+        serde_json::json!({"type": "net_partition", "a": "n1", "b": "n2"}),
+        serde_json::json!({"type": "net_set_drop_rate", "rate": 0.5}),
+        serde_json::json!({"type": "memory_fail_after_allocs", "count": 3}),
+        serde_json::json!({"type": "memory_limit_mb", "mb": 1}),
+    ] {
+        assert!(is_fault_step(&step(fault.clone())), "{fault}");
+    }
+    for other in [
+        serde_json::json!({"type": "net_set_drop_rate", "rate": 0.0}),
+        serde_json::json!({"type": "net_heal", "a": "n1", "b": "n2"}),
+        serde_json::json!({"type": "net_deliver_one"}),
+    ] {
+        assert!(!is_fault_step(&step(other.clone())), "{other}");
+    }
+}
@@ -0,0 +1,257 @@
+//! What a shrink must preserve, and the lineage of reductions it accepted.
+//!
+//! By default a candidate is kept when it fails the same way (`shrink_status_matches`).
+//! `--preserve finding` additionally requires the original primary finding (kind, title
+//! and normalized message) and `--finding-match <regex>` requires a finding whose title
+//! or message matches, so a subtle invariant failure cannot shrink into an unrelated one.
+//!
+//! Candidates are evaluated `--jobs` at a time (`first_accepted`), but the lowest-index
+//! candidate that keeps the target always wins, so the result matches a sequential
+//! scan regardless of thread timing.
+
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::{ExitStatus, Finding, FindingKind, FozzyError, FozzyResult, ShrinkOptions, TraceFile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShrinkPreserve {
+    Status,
+    Finding,
+}
+
+impl clap::ValueEnum for ShrinkPreserve {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Status, Self::Finding]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            Self::Status => clap::builder::PossibleValue::new("status"),
+            Self::Finding => clap::builder::PossibleValue::new("finding"),
+        })
+    }
+}
+
+/// One accepted reduction: the shrink phase and the size it went from and to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShrinkReduction {
+    pub phase: String,
+    /// What `before`/`after` count: `steps`, `bytes`, `decisions` or `inversions`.
+    pub unit: String,
+    pub before: usize,
+    pub after: usize,
+    /// Step simplified in place (value shrinking).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShrinkLineageReport {
+    pub schema_version: String,
+    pub source_trace: String,
+    pub out_trace: String,
+    pub preserve: ShrinkPreserve,
+    pub target_status: ExitStatus,
+    /// The preserved finding (`kind: title: message`) or `--finding-match` pattern.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_finding: Option<String>,
+    pub reductions: Vec<ShrinkReduction>,
+}
+
+enum FindingMatcher {
+    Identity {
+        kind: FindingKind,
+        title: String,
+        message: String,
+    },
+    Pattern(regex::Regex),
+}
+
+pub(crate) struct ShrinkTarget {
+    status: ExitStatus,
+    finding: Option<FindingMatcher>,
+    jobs: usize,
+    lineage: Mutex<Vec<ShrinkReduction>>,
+}
+
+impl ShrinkTarget {
+    /// Preserves only the failure class of `status`.
+    pub(crate) fn status(status: ExitStatus) -> Self {
+        Self {
+            status,
+            finding: None,
+            jobs: 1,
+            lineage: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn from_trace(trace: &TraceFile, opt: &ShrinkOptions) -> FozzyResult<Self> {
+        let mut target = Self::status(trace.summary.status);
+        target.jobs = opt.jobs.unwrap_or(1).max(1);
+        if let Some(pattern) = opt.finding_match.as_deref() {
+            let regex = regex::Regex::new(pattern).map_err(|err| {
+                FozzyError::InvalidArgument(format!("invalid --finding-match {pattern:?}: {err}"))
+            })?;
+            target.finding = Some(FindingMatcher::Pattern(regex));
+        } else if opt.preserve == ShrinkPreserve::Finding {
+            let primary = trace.summary.findings.first().ok_or_else(|| {
+                FozzyError::InvalidArgument(
+                    "trace has no finding to preserve; use --preserve status".to_string(),
+                )
+            })?;
+            target.finding = Some(FindingMatcher::Identity {
+                kind: primary.kind.clone(),
+                title: primary.title.clone(),
+                message: crate::normalize_crash_message(&primary.message),
+            });
+        }
+        Ok(target)
+    }
+
+    /// Whether a candidate run still shows the failure being shrunk.
+    pub(crate) fn matches(&self, status: ExitStatus, findings: &[Finding]) -> bool {
+        if !crate::shrink_status_matches(self.status, status) {
+            return false;
+        }
+        match &self.finding {
+            None => true,
+            Some(FindingMatcher::Identity {
+                kind,
+                title,
+                message,
+            }) => findings.iter().any(|finding| {
+                finding.kind == *kind
+                    && finding.title == *title
+                    && crate::normalize_crash_message(&finding.message) == *message
+            }),
+            Some(FindingMatcher::Pattern(regex)) => findings
+                .iter()
+                .any(|finding| regex.is_match(&finding.title) || regex.is_match(&finding.message)),
+        }
+    }
+
+    pub(crate) fn record(&self, phase: &str, unit: &str, before: usize, after: usize) {
+        self.record_reduction(ShrinkReduction {
+            phase: phase.to_string(),
+            unit: unit.to_string(),
+            before,
+            after,
+            step: None,
+        });
+    }
+
+    pub(crate) fn record_reduction(&self, reduction: ShrinkReduction) {
+        self.lineage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(reduction);
+    }
+
+    /// Evaluates `candidates` in windows of `jobs` and returns the lowest-index one that
+    /// `eval` accepts, with its result. An error is returned only when no lower-index
+    /// candidate was accepted, exactly as a one-at-a-time scan would. Stops (returning
+    /// `None`) once `deadline` passes between windows.
+    pub(crate) fn first_accepted<C, R>(
+        &self,
+        candidates: &[C],
+        deadline: Option<Instant>,
+        eval: impl Fn(&C) -> FozzyResult<Option<R>> + Sync,
+    ) -> FozzyResult<Option<(usize, R)>>
+    where
+        C: Sync,
+        R: Send,
+    {
+        for (window_index, window) in candidates.chunks(self.jobs).enumerate() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Ok(None);
+            }
+            let results: Vec<FozzyResult<Option<R>>> = if window.len() == 1 {
+                vec![eval(&window[0])]
+            } else {
+                std::thread::scope(|scope| {
+                    let eval = &eval;
+                    let handles: Vec<_> = window
+                        .iter()
+                        .map(|candidate| scope.spawn(move || eval(candidate)))
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle
+                                .join()
+                                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                        })
+                        .collect()
+                })
+            };
+            for (offset, result) in results.into_iter().enumerate() {
+                if let Some(accepted) = result? {
+                    return Ok(Some((window_index * self.jobs + offset, accepted)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn describe_finding(&self) -> Option<String> {
+        self.finding.as_ref().map(|finding| match finding {
+            FindingMatcher::Identity {
+                kind,
+                title,
+                message,
+            } => format!(
+                "{}: {title}: {message}",
+                serde_json::to_value(kind)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default()
+            ),
+            FindingMatcher::Pattern(regex) => regex.as_str().to_string(),
+        })
+    }
+
+    /// Writes `<out>.lineage.json` next to the shrunk trace and returns the reductions.
+    pub(crate) fn write_lineage(
+        self,
+        source: &Path,
+        out: &Path,
+    ) -> FozzyResult<Vec<ShrinkReduction>> {
+        let report = ShrinkLineageReport {
+            schema_version: "fozzy.shrink_lineage.v1".to_string(),
+            source_trace: source.display().to_string(),
+            out_trace: out.display().to_string(),
+            preserve: if self.finding.is_some() {
+                ShrinkPreserve::Finding
+            } else {
+                ShrinkPreserve::Status
+            },
+            target_status: self.status,
+            target_finding: self.describe_finding(),
+            reductions: self
+                .lineage
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        };
+        std::fs::write(
+            shrink_lineage_path(out),
+            serde_json::to_vec_pretty(&report)?,
+        )?;
+        Ok(report.reductions)
+    }
+}
+
+/// Lineage report path for a shrunk trace: `run.min.fozzy` → `run.min.lineage.json`.
+pub fn shrink_lineage_path(out_trace: &Path) -> std::path::PathBuf {
+    out_trace.with_extension("lineage.json")
+}
+
+#[cfg(test)]
+#[path = "shrink_target/tests.rs"]
+mod tests;
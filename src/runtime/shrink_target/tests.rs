@@ -0,0 +1,170 @@
+use super::*;
+use crate::{RunIdentity, RunMode, RunSummary, ShrinkMinimize};
+
+fn finding(kind: FindingKind, title: &str, message: &str) -> Finding {
+    Finding {
+        kind,
+        title: title.to_string(),
+        message: message.to_string(),
+        location: None,
+    }
+}
+
+fn failing_trace(findings: Vec<Finding>) -> TraceFile {
+    TraceFile::new(
+        RunMode::Run,
+        None,
+        None,
+        Vec::new(),
+        Vec::new(),
+        RunSummary {
+            status: ExitStatus::Fail,
+            identity: RunIdentity {
+                run_id: "r".to_string(),
+                seed: 1,
+                trace_path: None,
+                report_path: None,
+                artifacts_dir: None,
+            },
+            findings,
+            ..RunSummary::fixture()
+        },
+    )
+}
+
+fn options(preserve: ShrinkPreserve, finding_match: Option<&str>) -> ShrinkOptions {
+    ShrinkOptions {
+        out_trace_path: None,
+        budget: None,
+        aggressive: false,
+        minimize: ShrinkMinimize::All,
+        preserve,
+        finding_match: finding_match.map(str::to_string),
+        jobs: None,
+    }
+}
+
+#[test]
+fn finding_identity_ignores_run_specific_numbers_but_not_other_failures() {
+    let trace = failing_trace(vec![finding(
+        FindingKind::Invariant,
+        "kv_all_equal",
+        "nodes disagree on key x after 12 deliveries",
+    )]);
+    let target =
+        ShrinkTarget::from_trace(&trace, &options(ShrinkPreserve::Finding, None)).expect("target");
+
+    let same = finding(
+        FindingKind::Invariant,
+        "kv_all_equal",
+        "nodes disagree on key x after 3 deliveries",
+    );
+    let unrelated = finding(FindingKind::Assertion, "assert_ok", "boom");
+    assert!(target.matches(ExitStatus::Fail, std::slice::from_ref(&same)));
+    assert!(target.matches(ExitStatus::Fail, &[unrelated.clone(), same.clone()]));
+    assert!(!target.matches(ExitStatus::Fail, std::slice::from_ref(&unrelated)));
+    assert!(!target.matches(ExitStatus::Pass, &[same]));
+
+    let status_only =
+        ShrinkTarget::from_trace(&trace, &options(ShrinkPreserve::Status, None)).expect("target");
+    assert!(status_only.matches(ExitStatus::Crash, &[unrelated]));
+}
+
+#[test]
+fn finding_match_regex_checks_titles_and_messages() {
+    let trace = failing_trace(Vec::new());
+    let target = ShrinkTarget::from_trace(
+        &trace,
+        &options(ShrinkPreserve::Status, Some("^kv_.*|disagree")),
+    )
+    .expect("target");
+    assert!(target.matches(
+        ExitStatus::Fail,
+        &[finding(FindingKind::Invariant, "kv_present", "missing")]
+    ));
+    assert!(target.matches(
+        ExitStatus::Fail,
+        &[finding(FindingKind::Assertion, "x", "nodes disagree")]
+    ));
+    assert!(!target.matches(
+        ExitStatus::Fail,
+        &[finding(FindingKind::Assertion, "assert_ok", "boom")]
+    ));
+
+    let invalid = ShrinkTarget::from_trace(&trace, &options(ShrinkPreserve::Status, Some("(")));
+    assert!(matches!(invalid, Err(FozzyError::InvalidArgument(_))));
+    let nothing = ShrinkTarget::from_trace(&trace, &options(ShrinkPreserve::Finding, None));
+    assert!(matches!(nothing, Err(FozzyError::InvalidArgument(_))));
+}
+
+#[test]
+fn lineage_report_lists_reductions_in_order() {
+    let trace = failing_trace(vec![finding(FindingKind::Assertion, "fail", "boom")]);
+    let target =
+        ShrinkTarget::from_trace(&trace, &options(ShrinkPreserve::Finding, None)).expect("target");
+    target.record("steps", "steps", 8, 4);
+    target.record("faults", "steps", 4, 3);
+
+    let dir = std::env::temp_dir().join(format!("fozzy-lineage-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("dir");
+    let out = dir.join("run.min.fozzy");
+    let lineage = target
+        .write_lineage(&dir.join("run.fozzy"), &out)
+        .expect("lineage");
+    assert_eq!(lineage.len(), 2);
+
+    let report: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(dir.join("run.min.lineage.json")).expect("report"))
+            .expect("json");
+    assert_eq!(report["schemaVersion"], "fozzy.shrink_lineage.v1");
+    assert_eq!(report["preserve"], "finding");
+    assert_eq!(report["targetFinding"], "assertion: fail: boom");
+    assert_eq!(report["reductions"][0]["before"], 8);
+    assert_eq!(report["reductions"][1]["phase"], "faults");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn first_accepted_picks_the_lowest_index_regardless_of_timing() {
+    let trace = failing_trace(Vec::new());
+    let mut opt = options(ShrinkPreserve::Status, None);
+    opt.jobs = Some(4);
+    let target = ShrinkTarget::from_trace(&trace, &opt).expect("target");
+
+    // Earlier accepted candidates finish last, so completion order would pick 9.
+    let candidates: Vec<u64> = (0..12).collect();
+    let accepted = target
+        .first_accepted(&candidates, None, |&c| {
+            std::thread::sleep(std::time::Duration::from_millis(20 - c));
+            Ok((c == 5 || c == 6 || c == 9).then_some(c * 10))
+        })
+        .expect("accepted");
+    assert_eq!(accepted, Some((5, 50)));
+
+    let none = target
+        .first_accepted(&candidates, None, |_| Ok(None::<()>))
+        .expect("none");
+    assert!(none.is_none());
+}
+
+#[test]
+fn first_accepted_reports_errors_only_before_an_earlier_success() {
+    let trace = failing_trace(Vec::new());
+    let mut opt = options(ShrinkPreserve::Status, None);
+    opt.jobs = Some(3);
+    let target = ShrinkTarget::from_trace(&trace, &opt).expect("target");
+    let candidates = [0u8, 1, 2, 3];
+
+    let later_error = target.first_accepted(&candidates, None, |&c| match c {
+        1 => Ok(Some(c)),
+        2 => Err(FozzyError::Trace("boom".to_string())),
+        _ => Ok(None),
+    });
+    assert_eq!(later_error.expect("accepted"), Some((1, 1)));
+
+    let earlier_error = target.first_accepted(&candidates, None, |&c| match c {
+        0 => Err(FozzyError::Trace("boom".to_string())),
+        _ => Ok(Some(c)),
+    });
+    assert!(matches!(earlier_error, Err(FozzyError::Trace(_))));
+}
@@ -0,0 +1,157 @@
+//! Retrying failed `fozzy test` scenarios and the flaky-test quarantine list.
+//!
+//! A failing scenario gets up to `--retries N` (or its own `retries` metadata) extra
+//! attempts: the first reuses the original seed, later ones use fresh seeds derived from
+//! it. A same-seed pass classifies the failure as flaky and the scenario counts as
+//! passed; otherwise it is a deterministic failure. Scenarios on the quarantine list
+//! still run and are reported, but their failures do not fail the run.
+
+use globset::{Glob, GlobMatcher};
+
+use std::path::Path;
+
+use crate::engine::{RunOptions, ScenarioRun, run_scenario_inner};
+use crate::{
+    Config, ExitStatus, FailureClassification, FozzyError, FozzyResult, RetryAttempt, RunMode,
+    ScenarioPath, ScenarioRetry,
+};
+
+/// Scenario paths or globs whose failures are reported but do not fail a test run.
+///
+/// One entry per line; blank lines and `#` comments are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct QuarantineList {
+    entries: Vec<GlobMatcher>,
+}
+
+impl QuarantineList {
+    pub fn load(path: &Path) -> FozzyResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            FozzyError::InvalidArgument(format!(
+                "failed to read quarantine list {}: {err}",
+                path.display()
+            ))
+        })?;
+        let mut entries = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let glob = Glob::new(entry.trim_start_matches("./")).map_err(|err| {
+                FozzyError::InvalidArgument(format!(
+                    "invalid quarantine entry {entry:?} at {}:{}: {err}",
+                    path.display(),
+                    line_no + 1
+                ))
+            })?;
+            entries.push(glob.compile_matcher());
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn contains(&self, scenario: &Path) -> bool {
+        let scenario = scenario.strip_prefix(".").unwrap_or(scenario);
+        self.entries.iter().any(|entry| entry.is_match(scenario))
+    }
+}
+
+/// A test scenario's first run plus, when it failed, its retry evidence.
+#[derive(Debug, Clone)]
+pub(crate) struct TestAttempt {
+    pub(crate) run: ScenarioRun,
+    pub(crate) retry: Option<ScenarioRetry>,
+}
+
+pub(crate) fn run_test_scenario(
+    config: &Config,
+    opt: &RunOptions,
+    path: &Path,
+    seed: u64,
+    quarantine: &QuarantineList,
+) -> FozzyResult<TestAttempt> {
+    let run = run_once(config, opt, path, seed)?;
+    if run.status == ExitStatus::Pass {
+        return Ok(TestAttempt { run, retry: None });
+    }
+
+    let budget = match opt.retries {
+        Some(retries) => retries,
+        None => crate::ScenarioMeta::load(path)?.retries.unwrap_or(0),
+    };
+    let mut attempts = Vec::new();
+    for attempt in 1..=budget {
+        let same_seed = attempt == 1;
+        let attempt_seed = if same_seed {
+            seed
+        } else {
+            derive_retry_seed(seed, attempt)
+        };
+        let retry = run_once(config, opt, path, attempt_seed)?;
+        attempts.push(RetryAttempt {
+            seed: attempt_seed,
+            same_seed,
+            status: retry.status,
+            findings: retry
+                .findings
+                .iter()
+                .map(|finding| finding.title.clone())
+                .collect(),
+        });
+        if retry.status == ExitStatus::Pass {
+            break;
+        }
+    }
+    let classification = (!attempts.is_empty()).then(|| {
+        if attempts
+            .iter()
+            .any(|attempt| attempt.same_seed && attempt.status == ExitStatus::Pass)
+        {
+            FailureClassification::Flaky
+        } else {
+            FailureClassification::Deterministic
+        }
+    });
+    let quarantined = quarantine.contains(path);
+    let retry = (classification.is_some() || quarantined).then(|| ScenarioRetry {
+        path: path.display().to_string(),
+        seed,
+        status: run.status,
+        classification,
+        quarantined,
+        attempts,
+    });
+    Ok(TestAttempt { run, retry })
+}
+
+fn run_once(config: &Config, opt: &RunOptions, path: &Path, seed: u64) -> FozzyResult<ScenarioRun> {
+    run_scenario_inner(
+        config,
+        RunMode::Test,
+        ScenarioPath::new(path.to_path_buf()),
+        seed,
+        opt.det,
+        opt.timeout,
+        opt.proc_backend,
+        opt.fs_backend,
+        opt.http_backend,
+        opt.memory.clone(),
+        None,
+    )
+}
+
+/// Fresh seed for retry `attempt` (>= 2), stable for a given original seed.
+fn derive_retry_seed(seed: u64, attempt: u32) -> u64 {
+    let mut bytes = Vec::with_capacity(20);
+    bytes.extend_from_slice(b"retry");
+    bytes.extend_from_slice(&seed.to_le_bytes());
+    bytes.extend_from_slice(&attempt.to_le_bytes());
+    let hash = blake3::hash(&bytes);
+    let mut seed_bytes = [0u8; 8];
+    seed_bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(seed_bytes)
+}
+
+#[cfg(test)]
+#[path = "test_retry/tests.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+
+use std::path::PathBuf;
+
+use crate::{
+    FsBackend, HttpBackend, MemoryOptions, ProcBackend, ProfileCaptureLevel, RecordCollisionPolicy,
+    Reporter,
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fozzy-retry-{name}-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    dir
+}
+
+fn options(retries: Option<u32>) -> RunOptions {
+    RunOptions {
+        det: true,
+        seed: Some(7),
+        timeout: None,
+        reporter: Reporter::Json,
+        record_trace_to: None,
+        filter: None,
+        jobs: None,
+        shard: None,
+        shard_strategy: crate::ShardStrategy::Count,
+        fail_fast: false,
+        retries,
+        quarantine: None,
+        record_collision: RecordCollisionPolicy::Overwrite,
+        profile_capture: ProfileCaptureLevel::Baseline,
+        proc_backend: ProcBackend::Host,
+        fs_backend: FsBackend::Virtual,
+        http_backend: HttpBackend::Scripted,
+        memory: MemoryOptions::default(),
+    }
+}
+
+fn config(root: &Path) -> Config {
+    Config {
+        base_dir: root.join(".fozzy"),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn quarantine_list_matches_paths_and_globs_and_skips_comments() {
+    let dir = temp_dir("quarantine");
+    let list = dir.join("quarantine.txt");
+    std::fs::write(
+        &list,
+        "# known flakes\n\ntests/net.fozzy.json  # owner: payments\n./tests/slow/*.fozzy.json\n",
+    )
+    .expect("write list");
+
+    let quarantine = QuarantineList::load(&list).expect("load");
+    assert!(quarantine.contains(Path::new("tests/net.fozzy.json")));
+    assert!(quarantine.contains(Path::new("./tests/slow/soak.fozzy.json")));
+    assert!(!quarantine.contains(Path::new("tests/other.fozzy.json")));
+
+    std::fs::write(&list, "tests/[bad\n").expect("write list");
+    let err = QuarantineList::load(&list).expect_err("bad glob");
+    assert!(err.to_string().contains("quarantine.txt:1"), "{err}");
+}
+
+#[test]
+fn retry_seeds_are_stable_and_distinct_per_attempt() {
+    assert_eq!(derive_retry_seed(7, 2), derive_retry_seed(7, 2));
+    assert_ne!(derive_retry_seed(7, 2), derive_retry_seed(7, 3));
+    assert_ne!(derive_retry_seed(7, 2), derive_retry_seed(8, 2));
+}
+
+#[test]
+fn deterministic_failures_use_the_seed_then_fresh_seeds() {
+    let dir = temp_dir("deterministic");
+    let scenario = dir.join("fail.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{"version":1,"name":"fail","retries":1,"steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+    let cfg = config(&dir);
+
+    let attempt = run_test_scenario(
+        &cfg,
+        &options(Some(3)),
+        &scenario,
+        11,
+        &QuarantineList::default(),
+    )
+    .expect("run");
+    let retry = attempt.retry.expect("retry evidence");
+    assert_eq!(
+        retry.classification,
+        Some(FailureClassification::Deterministic)
+    );
+    let seeds: Vec<(u64, bool)> = retry
+        .attempts
+        .iter()
+        .map(|attempt| (attempt.seed, attempt.same_seed))
+        .collect();
+    assert_eq!(
+        seeds,
+        vec![
+            (11, true),
+            (derive_retry_seed(11, 2), false),
+            (derive_retry_seed(11, 3), false),
+        ]
+    );
+    assert_eq!(retry.seeds_by_outcome().0, Vec::<u64>::new());
+
+    // Without --retries the scenario's own `retries` applies.
+    let attempt = run_test_scenario(
+        &cfg,
+        &options(None),
+        &scenario,
+        11,
+        &QuarantineList::default(),
+    )
+    .expect("run");
+    assert_eq!(attempt.retry.expect("retry evidence").attempts.len(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_same_seed_pass_is_flaky_and_quarantine_is_recorded() {
+    let dir = temp_dir("flaky");
+    let marker = dir.join("marker");
+    let scenario = dir.join("flaky.fozzy.json");
+    std::fs::write(
+        &scenario,
+        serde_json::json!({
+            "version": 1,
+            "name": "flaky",
+            "steps": [{
+                "type": "proc_spawn",
+                "cmd": "/bin/sh",
+                "args": ["-c", format!("test -f {0} || {{ touch {0}; exit 1; }}", marker.display())],
+                "expect_exit": 0
+            }]
+        })
+        .to_string(),
+    )
+    .expect("write scenario");
+    let list = dir.join("quarantine.txt");
+    std::fs::write(&list, format!("{}\n", scenario.display())).expect("write list");
+
+    let attempt = run_test_scenario(
+        &config(&dir),
+        &options(Some(2)),
+        &scenario,
+        5,
+        &QuarantineList::load(&list).expect("load"),
+    )
+    .expect("run");
+    assert_ne!(attempt.run.status, ExitStatus::Pass);
+    let retry = attempt.retry.expect("retry evidence");
+    assert_eq!(retry.classification, Some(FailureClassification::Flaky));
+    assert!(retry.quarantined);
+    assert_eq!(retry.attempts.len(), 1, "stops at the first passing retry");
+    assert_eq!(retry.seeds_by_outcome(), (vec![5], vec![5]));
+}
@@ -1,15 +1,14 @@
 use rand_core::RngCore as _;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
-use crate::engine::{
-    RecordCollisionPolicy, RunOptions, RunResult, ScenarioRun, run_scenario_inner,
-};
+use crate::engine::{RecordCollisionPolicy, RunOptions, RunResult, ScenarioRun};
 use crate::finalize::{
-    build_run_summary, write_reporter_artifacts, write_single_scenario_trace, write_summary_report,
+    build_run_summary, build_single_scenario_trace, write_reporter_artifacts,
+    write_single_scenario_trace, write_summary_report,
 };
 use crate::{
     Config, ExitStatus, Finding, FindingKind, FozzyError, FozzyResult, RunMode, ScenarioPath,
@@ -17,11 +16,7 @@ use crate::{
 };
 
 pub fn run_tests(config: &Config, globs: &[String], opt: &RunOptions) -> FozzyResult<RunResult> {
-    let patterns = if globs.is_empty() {
-        vec!["tests/**/*.fozzy.json".to_string()]
-    } else {
-        globs.to_vec()
-    };
+    let patterns = test_scenario_patterns(globs);
 
     let resolved_inputs = crate::resolve_matching_files(&patterns)?;
     if !resolved_inputs.missing_literal_files.is_empty() {
@@ -47,17 +42,18 @@ pub fn run_tests(config: &Config, globs: &[String], opt: &RunOptions) -> FozzyRe
     let seed = opt.seed.unwrap_or_else(gen_seed);
     let run_id = Uuid::new_v4().to_string();
 
-    let mut filtered_paths = Vec::new();
-    let mut skipped = 0u64;
-    for p in scenario_paths {
-        if let Some(filter) = &opt.filter
-            && !p.to_string_lossy().contains(filter)
-        {
-            skipped += 1;
-            continue;
+    let discovered = scenario_paths.len();
+    let mut warnings = Vec::new();
+    let filtered_paths = match &opt.filter {
+        Some(filter) => {
+            let selection =
+                crate::select_scenarios(&scenario_paths, Some(filter), &config.runs_dir())?;
+            warnings.extend(selection.warnings.iter().cloned());
+            selection.selected_paths()
         }
-        filtered_paths.push(p);
-    }
+        None => scenario_paths,
+    };
+    let mut skipped = (discovered - filtered_paths.len()) as u64;
 
     let mut distributed_paths = Vec::new();
     for path in &filtered_paths {
@@ -76,16 +72,57 @@ pub fn run_tests(config: &Config, globs: &[String], opt: &RunOptions) -> FozzyRe
         )));
     }
 
+    let total = filtered_paths.len();
+    let scenarios = match opt.shard {
+        Some(shard) => {
+            skipped = crate::shard_skipped(skipped, shard);
+            let selection = crate::select_shard(
+                &filtered_paths,
+                shard,
+                opt.shard_strategy,
+                &config.runs_dir(),
+            )?;
+            warnings.extend(selection.warnings);
+            selection.scenarios
+        }
+        None => filtered_paths.into_iter().enumerate().collect(),
+    };
+
+    let quarantine = match &opt.quarantine {
+        Some(path) => crate::QuarantineList::load(path)?,
+        None => crate::QuarantineList::default(),
+    };
     let jobs = if opt.fail_fast {
         1
     } else {
         opt.jobs.unwrap_or(1).max(1)
     };
     let mut outcome = TestOutcome::new(skipped, opt.record_trace_to.is_some());
-    if jobs == 1 || filtered_paths.len() <= 1 {
-        run_serial_tests(config, &filtered_paths, opt, seed, &mut outcome)?;
+    outcome.stream = crate::ReporterEventStream::for_reporter(opt.reporter, &run_id);
+    outcome
+        .stream
+        .run_started(RunMode::Test, seed, &started_at)?;
+    if jobs == 1 || scenarios.len() <= 1 {
+        run_serial_tests(
+            config,
+            &scenarios,
+            total,
+            opt,
+            seed,
+            &quarantine,
+            &mut outcome,
+        )?;
     } else {
-        run_parallel_tests(config, &filtered_paths, opt, seed, jobs, &mut outcome);
+        run_parallel_tests(
+            config,
+            &scenarios,
+            total,
+            opt,
+            seed,
+            jobs,
+            &quarantine,
+            &mut outcome,
+        )?;
     }
 
     let finished_at = wall_time_iso_utc();
@@ -100,7 +137,7 @@ pub fn run_tests(config: &Config, globs: &[String], opt: &RunOptions) -> FozzyRe
     std::fs::create_dir_all(&artifacts_dir)?;
     let report_path = artifacts_dir.join("report.json");
 
-    let summary = build_run_summary(
+    let mut summary = build_run_summary(
         status,
         RunMode::Test,
         run_id,
@@ -116,46 +153,79 @@ pub fn run_tests(config: &Config, globs: &[String], opt: &RunOptions) -> FozzyRe
             passed: outcome.passed,
             failed: outcome.failed,
             skipped: outcome.skipped,
+            flaky: outcome.flaky,
+            quarantined: outcome.quarantined,
         }),
         outcome.memory_summary(),
         crate::collapse_findings(outcome.findings.clone()),
     );
+    summary.retries = std::mem::take(&mut outcome.retries);
 
     if let Some(record_base) = &opt.record_trace_to {
-        write_test_traces(record_base, &outcome.trace_runs, opt.record_collision)?;
+        write_test_traces(
+            record_base,
+            &outcome.trace_runs,
+            opt.record_collision,
+            config.trace_encoding,
+        )?;
     }
     write_reporter_artifacts(&summary, &artifacts_dir, opt.reporter)?;
+    write_test_timings(&summary, &artifacts_dir, opt.shard, &outcome.timings)?;
     write_summary_report(&summary, &report_path, &artifacts_dir, None)?;
+    warnings.extend(crate::record_run_history(
+        config,
+        &summary,
+        std::mem::take(&mut outcome.history),
+    ));
+    outcome.stream.run_finished(&summary)?;
+
+    Ok(RunResult { summary, warnings })
+}
 
-    Ok(RunResult { summary })
+/// Records per-scenario durations for `--shard-strategy timing` in later runs.
+fn write_test_timings(
+    summary: &crate::RunSummary,
+    artifacts_dir: &Path,
+    shard: Option<crate::TestShard>,
+    timings: &[crate::ScenarioTiming],
+) -> FozzyResult<()> {
+    let report = crate::TestTimingsReport {
+        schema_version: "fozzy.test_timings.v1".to_string(),
+        run_id: summary.identity.run_id.clone(),
+        finished_at: summary.finished_at.clone(),
+        shard,
+        scenarios: timings.to_vec(),
+    };
+    std::fs::write(
+        artifacts_dir.join(crate::TEST_TIMINGS_FILE),
+        serde_json::to_vec_pretty(&report)?,
+    )?;
+    Ok(())
 }
 
 fn run_serial_tests(
     config: &Config,
-    filtered_paths: &[PathBuf],
+    scenarios: &[(usize, PathBuf)],
+    total: usize,
     opt: &RunOptions,
     seed: u64,
+    quarantine: &crate::QuarantineList,
     outcome: &mut TestOutcome,
 ) -> FozzyResult<()> {
-    for path in filtered_paths {
-        let scenario_seed =
-            derive_test_seed(seed, filtered_paths.len(), outcome.total_runs(), path);
-        let run = run_scenario_inner(
-            config,
-            RunMode::Test,
-            ScenarioPath::new(path.clone()),
-            scenario_seed,
-            opt.det,
-            opt.timeout,
-            opt.proc_backend,
-            opt.fs_backend,
-            opt.http_backend,
-            opt.memory.clone(),
-        )?;
+    for (position, path) in scenarios {
+        let scenario_seed = derive_test_seed(seed, total, *position, path);
+        outcome
+            .stream
+            .scenario_started(&path.display().to_string(), None, scenario_seed)?;
+        let started = Instant::now();
+        let attempt = crate::run_test_scenario(config, opt, path, scenario_seed, quarantine)?;
+        outcome.stream_scenario_finished(scenario_seed, started.elapsed(), &attempt.run)?;
         outcome.record_run(TestRunRecord {
             ordinal: outcome.total_runs(),
             seed: scenario_seed,
-            run,
+            duration: started.elapsed(),
+            run: attempt.run,
+            retry: attempt.retry,
         });
         if opt.fail_fast && outcome.failed > 0 {
             break;
@@ -164,87 +234,93 @@ fn run_serial_tests(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_parallel_tests(
     config: &Config,
-    filtered_paths: &[PathBuf],
+    scenarios: &[(usize, PathBuf)],
+    total: usize,
     opt: &RunOptions,
     seed: u64,
     jobs: usize,
+    quarantine: &crate::QuarantineList,
     outcome: &mut TestOutcome,
-) {
+) -> FozzyResult<()> {
     let (tx, rx) = mpsc::channel();
-    std::thread::scope(|scope| {
+    std::thread::scope(|scope| -> FozzyResult<()> {
         let mut in_flight = 0usize;
         let mut next = 0usize;
-        while next < filtered_paths.len() || in_flight > 0 {
-            while next < filtered_paths.len() && in_flight < jobs {
-                let path = filtered_paths[next].clone();
+        while next < scenarios.len() || in_flight > 0 {
+            while next < scenarios.len() && in_flight < jobs {
+                let (position, path) = scenarios[next].clone();
                 let tx = tx.clone();
-                let memory = opt.memory.clone();
-                let timeout = opt.timeout;
-                let proc_backend = opt.proc_backend;
-                let fs_backend = opt.fs_backend;
-                let http_backend = opt.http_backend;
-                let det = opt.det;
-                let scenario_seed = derive_test_seed(seed, filtered_paths.len(), next, &path);
+                let scenario_seed = derive_test_seed(seed, total, position, &path);
                 let ordinal = next;
+                outcome.stream.scenario_started(
+                    &path.display().to_string(),
+                    None,
+                    scenario_seed,
+                )?;
                 scope.spawn(move || {
-                    let result = run_scenario_inner(
-                        config,
-                        RunMode::Test,
-                        ScenarioPath::new(path),
-                        scenario_seed,
-                        det,
-                        timeout,
-                        proc_backend,
-                        fs_backend,
-                        http_backend,
-                        memory,
-                    );
-                    let _ = tx.send((ordinal, scenario_seed, result));
+                    let started = Instant::now();
+                    let result =
+                        crate::run_test_scenario(config, opt, &path, scenario_seed, quarantine);
+                    let _ = tx.send((ordinal, scenario_seed, started.elapsed(), result));
                 });
                 next += 1;
                 in_flight += 1;
             }
 
             if in_flight > 0 {
-                if let Ok((ordinal, scenario_seed, result)) = rx.recv() {
+                if let Ok(result) = rx.recv() {
                     in_flight = in_flight.saturating_sub(1);
-                    outcome
-                        .parallel_results
-                        .push((ordinal, scenario_seed, result));
+                    if let (_, scenario_seed, duration, Ok(attempt)) = &result {
+                        outcome.stream_scenario_finished(
+                            *scenario_seed,
+                            *duration,
+                            &attempt.run,
+                        )?;
+                    }
+                    outcome.parallel_results.push(result);
                 } else {
                     break;
                 }
             }
         }
-    });
+        Ok(())
+    })?;
     outcome
         .parallel_results
-        .sort_by_key(|(ordinal, _, _)| *ordinal);
+        .sort_by_key(|(ordinal, _, _, _)| *ordinal);
     let parallel_results = std::mem::take(&mut outcome.parallel_results);
-    for (ordinal, scenario_seed, result) in parallel_results {
+    for (ordinal, scenario_seed, duration, result) in parallel_results {
         match result {
-            Ok(run) => outcome.record_run(TestRunRecord {
+            Ok(attempt) => outcome.record_run(TestRunRecord {
                 ordinal,
                 seed: scenario_seed,
-                run,
+                duration,
+                run: attempt.run,
+                retry: attempt.retry,
             }),
             Err(err) => outcome.record_worker_error(err),
         }
     }
+    Ok(())
 }
 
 fn write_test_traces(
     record_base: &Path,
     runs: &[TestRunRecord],
     policy: RecordCollisionPolicy,
+    encoding: crate::TraceEncoding,
 ) -> FozzyResult<()> {
     if runs.is_empty() {
         return Ok(());
     }
     let mut ordered_runs = runs.to_vec();
     ordered_runs.sort_by_key(|run| run.ordinal);
+    if crate::is_trace_archive_path(record_base) {
+        return write_test_trace_archive(record_base, &ordered_runs, policy);
+    }
     if ordered_runs.len() == 1 {
         let run = &ordered_runs[0];
         write_single_scenario_trace(
@@ -253,6 +329,7 @@ fn write_test_traces(
             &Uuid::new_v4().to_string(),
             run.seed,
             policy,
+            encoding,
             RunMode::Test,
             None,
             None,
@@ -282,6 +359,7 @@ fn write_test_traces(
             &Uuid::new_v4().to_string(),
             run.seed,
             policy,
+            encoding,
             RunMode::Test,
             None,
             None,
@@ -290,6 +368,30 @@ fn write_test_traces(
     Ok(())
 }
 
+fn write_test_trace_archive(
+    record_base: &Path,
+    ordered_runs: &[TestRunRecord],
+    policy: RecordCollisionPolicy,
+) -> FozzyResult<()> {
+    crate::record_into_archive(record_base, policy, |archive_path, archive| {
+        for run in ordered_runs {
+            let name = archive.next_name(&crate::archive_entry_base(&run.run.scenario_path));
+            let trace = build_single_scenario_trace(
+                &crate::archive_entry_path(archive_path, &name),
+                &run.run,
+                &Uuid::new_v4().to_string(),
+                run.seed,
+                RunMode::Test,
+                None,
+                None,
+            );
+            archive.push(name, trace)?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
 #[derive(Default)]
 struct TestOutcome {
     passed: u64,
@@ -300,7 +402,13 @@ struct TestOutcome {
     memory_summary: crate::MemorySummary,
     has_memory: bool,
     record_traces: bool,
-    parallel_results: Vec<(usize, u64, FozzyResult<ScenarioRun>)>,
+    timings: Vec<crate::ScenarioTiming>,
+    flaky: u64,
+    quarantined: u64,
+    retries: Vec<crate::ScenarioRetry>,
+    history: Vec<crate::HistoryScenario>,
+    stream: crate::ReporterEventStream,
+    parallel_results: Vec<(usize, u64, Duration, FozzyResult<crate::TestAttempt>)>,
 }
 
 impl TestOutcome {
@@ -313,16 +421,63 @@ impl TestOutcome {
     }
 
     fn total_runs(&self) -> usize {
-        (self.passed + self.failed) as usize
+        (self.passed + self.failed + self.quarantined) as usize
+    }
+
+    fn stream_scenario_finished(
+        &mut self,
+        seed: u64,
+        duration: Duration,
+        run: &ScenarioRun,
+    ) -> FozzyResult<()> {
+        self.stream.scenario_finished(
+            crate::ScenarioEvents {
+                path: &run.scenario_path.display().to_string(),
+                iteration: None,
+                seed,
+                events: &run.events,
+            },
+            run.status,
+            duration,
+            &run.findings,
+        )
     }
 
     fn record_run(&mut self, record: TestRunRecord) {
         let run = &record.run;
-        self.findings.extend(run.findings.clone());
+        let (duration_ms, duration_ns) = crate::duration_fields(record.duration);
+        self.timings.push(crate::ScenarioTiming {
+            path: run.scenario_path.display().to_string(),
+            duration_ms,
+            duration_ns,
+            status: run.status,
+        });
+        self.history.push(crate::HistoryScenario::from_run(
+            run,
+            record.seed,
+            record.duration,
+            record.retry.as_ref(),
+        ));
+        let flaky = record
+            .retry
+            .as_ref()
+            .is_some_and(|retry| retry.classification == Some(crate::FailureClassification::Flaky));
+        let quarantined = record.retry.as_ref().is_some_and(|retry| retry.quarantined);
         if run.status == ExitStatus::Pass {
             self.passed += 1;
+            self.findings.extend(run.findings.clone());
+        } else if flaky {
+            // The failing attempt's findings stay in the retry evidence only.
+            self.passed += 1;
+            self.flaky += 1;
+        } else if quarantined {
+            self.quarantined += 1;
         } else {
             self.failed += 1;
+            self.findings.extend(run.findings.clone());
+        }
+        if let Some(retry) = &record.retry {
+            self.retries.push(retry.clone());
         }
         if let Some(mem) = run.memory.as_ref() {
             self.has_memory = true;
@@ -381,7 +536,28 @@ impl TestOutcome {
 struct TestRunRecord {
     ordinal: usize,
     seed: u64,
+    duration: Duration,
     run: ScenarioRun,
+    retry: Option<crate::ScenarioRetry>,
+}
+
+/// The scenarios `fozzy test` would select for `globs` and `filter` (`--list`).
+pub fn list_test_scenarios(
+    config: &Config,
+    globs: &[String],
+    filter: Option<&str>,
+) -> FozzyResult<crate::ScenarioSelectionReport> {
+    let paths = crate::resolve_matching_files(&test_scenario_patterns(globs))?.files;
+    crate::select_scenarios(&paths, filter, &config.runs_dir())
+}
+
+/// `fozzy test` scenario globs, defaulting to `tests/**/*.fozzy.json`.
+pub(crate) fn test_scenario_patterns(globs: &[String]) -> Vec<String> {
+    if globs.is_empty() {
+        vec!["tests/**/*.fozzy.json".to_string()]
+    } else {
+        globs.to_vec()
+    }
 }
 
 fn derive_test_seed(suite_seed: u64, total: usize, ordinal: usize, path: &Path) -> u64 {
@@ -405,6 +581,7 @@ fn gen_seed() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::run_scenario_inner;
     use crate::{
         FsBackend, HttpBackend, MemoryOptions, ProcBackend, ProfileCaptureLevel,
         RecordCollisionPolicy, Reporter, TraceFile,
@@ -462,7 +639,11 @@ mod tests {
             record_trace_to: None,
             filter: None,
             jobs: None,
+            shard: None,
+            shard_strategy: crate::ShardStrategy::Count,
             fail_fast: false,
+            retries: None,
+            quarantine: None,
             record_collision: RecordCollisionPolicy::Overwrite,
             profile_capture: ProfileCaptureLevel::Baseline,
             proc_backend: ProcBackend::Scripted,
@@ -487,8 +668,11 @@ mod tests {
             mem_artifacts: false,
             profile_heap_alloc_budget: None,
             profile_heap_in_use_budget: None,
+            redact: Default::default(),
+            snapshots: Default::default(),
             mem_fragmentation_seed: None,
             mem_pressure_wave: None,
+            trace_encoding: Default::default(),
         }
     }
 
@@ -579,6 +763,7 @@ mod tests {
                 artifacts: false,
                 ..MemoryOptions::default()
             },
+            None,
         )
         .expect("run scenario");
 
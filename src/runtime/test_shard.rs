@@ -0,0 +1,218 @@
+//! Deterministic sharding of `fozzy test` across machines.
+//!
+//! `--shard i/n` keeps the `i`-th of `n` partitions of the discovered and filtered
+//! scenario set. The default `count` strategy deals sorted paths round-robin. The
+//! `timing` strategy balances shards by the per-scenario durations that earlier test
+//! runs wrote to `.fozzy/runs/<run>/timings.json`, so every shard must see the same
+//! run history (e.g. a restored CI cache) to agree on the partition.
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::{ExitStatus, FozzyResult};
+
+/// One-based shard selector parsed from `i/n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestShard {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl FromStr for TestShard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid shard {s:?}: expected i/n with 1 <= i <= n");
+        let (index, count) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let index: usize = index.trim().parse().map_err(|_| invalid())?;
+        let count: usize = count.trim().parse().map_err(|_| invalid())?;
+        if index == 0 || index > count {
+            return Err(invalid());
+        }
+        Ok(Self { index, count })
+    }
+}
+
+impl fmt::Display for TestShard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShardStrategy {
+    /// Round-robin over sorted scenario paths.
+    Count,
+    /// Balance recorded durations from previous runs.
+    Timing,
+}
+
+impl clap::ValueEnum for ShardStrategy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Count, Self::Timing]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            Self::Count => clap::builder::PossibleValue::new("count"),
+            Self::Timing => clap::builder::PossibleValue::new("timing"),
+        })
+    }
+}
+
+/// Per-scenario wall-clock durations of one test run (`timings.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTimingsReport {
+    pub schema_version: String,
+    pub run_id: String,
+    pub finished_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard: Option<TestShard>,
+    pub scenarios: Vec<ScenarioTiming>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioTiming {
+    pub path: String,
+    pub duration_ms: u64,
+    pub duration_ns: u64,
+    pub status: ExitStatus,
+}
+
+pub(crate) const TEST_TIMINGS_FILE: &str = "timings.json";
+
+/// The scenarios one shard runs, and warnings from reading the recorded timings.
+#[derive(Debug, Clone)]
+pub(crate) struct ShardSelection {
+    pub scenarios: Vec<(usize, PathBuf)>,
+    pub warnings: Vec<String>,
+}
+
+/// The scenarios of `paths` (sorted, already filtered) that belong to `shard`, with
+/// their positions in `paths`. Positions keep per-scenario seeds identical to an
+/// unsharded run.
+pub(crate) fn select_shard(
+    paths: &[PathBuf],
+    shard: TestShard,
+    strategy: ShardStrategy,
+    runs_dir: &Path,
+) -> FozzyResult<ShardSelection> {
+    let mut warnings = Vec::new();
+    let owner = match strategy {
+        ShardStrategy::Count => (0..paths.len()).map(|pos| pos % shard.count).collect(),
+        ShardStrategy::Timing => {
+            let history = recorded_scenario_timings(runs_dir);
+            warnings = history.warnings;
+            let weights: Vec<Option<u64>> = paths
+                .iter()
+                .map(|path| {
+                    history
+                        .timings
+                        .get(&path.display().to_string())
+                        .map(|timing| timing.duration_ns)
+                })
+                .collect();
+            balance_by_duration(&weights, shard.count)
+        }
+    };
+    let scenarios = paths
+        .iter()
+        .enumerate()
+        .filter(|(pos, _)| owner[*pos] == shard.index - 1)
+        .map(|(pos, path)| (pos, path.clone()))
+        .collect();
+    Ok(ShardSelection {
+        scenarios,
+        warnings,
+    })
+}
+
+/// Longest-processing-time assignment: heaviest scenarios first, each to the least
+/// loaded shard (lowest shard on ties). Unknown durations count as the mean of the
+/// known ones, so new scenarios spread out instead of piling onto one shard.
+pub(crate) fn balance_by_duration(weights: &[Option<u64>], count: usize) -> Vec<usize> {
+    let known: Vec<u64> = weights.iter().flatten().copied().collect();
+    let fallback = if known.is_empty() {
+        1
+    } else {
+        (known.iter().sum::<u64>() / known.len() as u64).max(1)
+    };
+    let mut order: Vec<(usize, u64)> = weights
+        .iter()
+        .map(|weight| weight.unwrap_or(fallback).max(1))
+        .enumerate()
+        .collect();
+    order.sort_by(|(a_pos, a), (b_pos, b)| b.cmp(a).then(a_pos.cmp(b_pos)));
+    let mut load = vec![0u64; count.max(1)];
+    let mut owner = vec![0usize; weights.len()];
+    for (pos, weight) in order {
+        let lightest = (0..load.len())
+            .min_by_key(|shard| (load[*shard], *shard))
+            .unwrap_or(0);
+        load[lightest] = load[lightest].saturating_add(weight);
+        owner[pos] = lightest;
+    }
+    owner
+}
+
+/// Timings recorded by earlier test runs, and one warning per `timings.json` that
+/// could not be parsed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RecordedTimings {
+    pub timings: BTreeMap<String, ScenarioTiming>,
+    pub warnings: Vec<String>,
+}
+
+/// Latest recorded timing (duration and status) for each scenario path; the newest
+/// run wins. Also backs `last:` terms in `--filter`. An unreadable `timings.json` is
+/// skipped and reported in `warnings` so one corrupt run cannot block later test runs.
+pub(crate) fn recorded_scenario_timings(runs_dir: &Path) -> RecordedTimings {
+    let mut recorded = RecordedTimings::default();
+    let mut reports = Vec::new();
+    let Ok(entries) = std::fs::read_dir(runs_dir) else {
+        return recorded;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path().join(TEST_TIMINGS_FILE);
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        match serde_json::from_slice::<TestTimingsReport>(&bytes) {
+            Ok(report) => reports.push(report),
+            Err(err) => recorded.warnings.push(format!(
+                "skipping invalid test timings {}: {err}",
+                path.display()
+            )),
+        }
+    }
+    reports.sort_by(|a, b| {
+        a.finished_at
+            .cmp(&b.finished_at)
+            .then(a.run_id.cmp(&b.run_id))
+    });
+    for report in reports {
+        for timing in report.scenarios {
+            recorded.timings.insert(timing.path.clone(), timing);
+        }
+    }
+    recorded
+}
+
+/// Filtered-out scenarios are counted as skipped by exactly one shard, so merged
+/// shard reports add up to the counts of an unsharded run.
+pub(crate) fn shard_skipped(skipped: u64, shard: TestShard) -> u64 {
+    (0..skipped)
+        .filter(|pos| *pos as usize % shard.count == shard.index - 1)
+        .count() as u64
+}
+
+#[cfg(test)]
+#[path = "test_shard/tests.rs"]
+mod tests;
@@ -0,0 +1,96 @@
+use super::*;
+
+fn paths(names: &[&str]) -> Vec<PathBuf> {
+    names.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn shard_parses_one_based_i_of_n() {
+    assert_eq!(
+        "2/3".parse::<TestShard>(),
+        Ok(TestShard { index: 2, count: 3 })
+    );
+    assert_eq!(
+        "2/3".parse::<TestShard>().expect("shard").to_string(),
+        "2/3"
+    );
+    for bad in ["0/3", "4/3", "3", "a/b", "1/0"] {
+        assert!(bad.parse::<TestShard>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn count_shards_partition_every_scenario_exactly_once() {
+    let all = paths(&["a", "b", "c", "d", "e"]);
+    let runs = std::env::temp_dir().join(format!("fozzy-shard-{}", uuid::Uuid::new_v4()));
+    let mut seen = Vec::new();
+    for index in 1..=3 {
+        let shard = TestShard { index, count: 3 };
+        let selection = select_shard(&all, shard, ShardStrategy::Count, &runs).expect("shard");
+        assert!(selection.warnings.is_empty());
+        seen.extend(selection.scenarios);
+    }
+    seen.sort();
+    assert_eq!(seen, all.into_iter().enumerate().collect::<Vec<_>>());
+
+    let skipped: u64 = (1..=3)
+        .map(|index| shard_skipped(7, TestShard { index, count: 3 }))
+        .sum();
+    assert_eq!(skipped, 7);
+}
+
+#[test]
+fn timing_shards_balance_recorded_durations() {
+    // One slow scenario gets a shard to itself; unknown ones count as the mean.
+    let owner = balance_by_duration(&[Some(100), Some(10), Some(10), Some(10), None], 2);
+    assert_eq!(owner, vec![0, 1, 1, 1, 1]);
+    assert_eq!(balance_by_duration(&[None, None, None], 2), vec![0, 1, 0]);
+
+    let runs = std::env::temp_dir().join(format!("fozzy-shard-{}", uuid::Uuid::new_v4()));
+    let write = |run_id: &str, finished_at: &str, timings: &[(&str, u64)]| {
+        let dir = runs.join(run_id);
+        std::fs::create_dir_all(&dir).expect("dir");
+        let report = TestTimingsReport {
+            schema_version: "fozzy.test_timings.v1".to_string(),
+            run_id: run_id.to_string(),
+            finished_at: finished_at.to_string(),
+            shard: None,
+            scenarios: timings
+                .iter()
+                .map(|(path, duration_ns)| ScenarioTiming {
+                    path: path.to_string(),
+                    duration_ms: duration_ns / 1_000_000,
+                    duration_ns: *duration_ns,
+                    status: ExitStatus::Pass,
+                })
+                .collect(),
+        };
+        std::fs::write(
+            dir.join(TEST_TIMINGS_FILE),
+            serde_json::to_vec(&report).expect("json"),
+        )
+        .expect("write");
+    };
+    write(
+        "old",
+        "2026-01-01T00:00:00Z",
+        &[("a", 1), ("b", 1), ("c", 90)],
+    );
+    write("new", "2026-01-02T00:00:00Z", &[("a", 90), ("c", 1)]);
+    // A corrupt timings file from an interrupted run is skipped with a warning, not fatal.
+    std::fs::create_dir_all(runs.join("broken")).expect("dir");
+    std::fs::write(runs.join("broken").join(TEST_TIMINGS_FILE), b"{").expect("write");
+
+    let all = paths(&["a", "b", "c"]);
+    let first = select_shard(
+        &all,
+        TestShard { index: 1, count: 2 },
+        ShardStrategy::Timing,
+        &runs,
+    )
+    .expect("shard");
+    assert_eq!(first.scenarios, vec![(0, PathBuf::from("a"))]);
+    assert_eq!(first.warnings.len(), 1);
+    assert!(first.warnings[0].contains("broken"), "{:?}", first.warnings);
+    let _ = std::fs::remove_dir_all(runs);
+}
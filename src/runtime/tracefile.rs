@@ -10,6 +10,44 @@ use crate::{
     RunMode, RunSummary, ScenarioV1Steps, VersionInfo,
 };
 
+#[path = "tracefile/archive.rs"]
+mod archive;
+#[path = "tracefile/binary.rs"]
+mod binary;
+#[path = "tracefile/migrate.rs"]
+mod migrate;
+#[path = "tracefile/redact.rs"]
+mod redact;
+#[path = "tracefile/signing.rs"]
+mod signing;
+#[path = "tracefile/snapshots.rs"]
+mod snapshots;
+
+pub use archive::{
+    TRACE_ARCHIVE_EXTENSION, TRACE_ARCHIVE_FORMAT, TRACE_ARCHIVE_VERSION, TraceArchive,
+    TraceArchiveEntry, archive_entry_path, is_trace_archive_path, split_archive_selector,
+};
+pub(crate) use archive::{archive_entry_base, record_into_archive};
+pub use binary::{
+    BINARY_TRACE_MAGIC, TraceChunk, TraceCursor, TraceEncoding, TraceFooter, TraceHeader,
+    TraceStreamWriter, is_binary_trace_file,
+};
+pub(crate) use binary::{TraceDecisionStream, TraceOutline, TraceRecording};
+pub use migrate::{
+    TraceMigration, TraceUpgradeCheck, TraceUpgradeCheckReport, TraceUpgradeReport,
+    TraceUpgradeStatus, check_trace_upgrades, upgrade_trace_file, upgrade_trace_value,
+};
+pub use redact::{
+    RedactConfig, RedactRules, Redactor, TraceRedactReport, TraceRedaction, redact_trace_file,
+};
+pub use signing::{
+    HostFingerprint, TRACE_SIGN_KEY_ENV, TraceKeygenReport, TraceProvenance, TraceSignReport,
+    TraceSignature, TraceSignatureScheme, TraceSignatureStatus, TraceSigningKey,
+    generate_signing_key, sign_trace_file, verify_trace_signature,
+};
+pub(crate) use snapshots::{DecisionsDigest, decisions_digest};
+pub use snapshots::{ReplayStart, SnapshotConfig, TraceSnapshot, nearest_snapshot};
+
 pub const CURRENT_TRACE_VERSION: u32 = 4;
 pub const TRACE_FORMAT: &str = "fozzy-trace";
 
@@ -45,6 +83,8 @@ pub fn is_trace_path(path: &Path) -> bool {
     path.extension()
         .and_then(|s| s.to_str())
         .is_some_and(|s| s.eq_ignore_ascii_case("fozzy"))
+        || split_archive_selector(path).is_some()
+        || is_binary_trace_file(path)
 }
 
 pub fn normalize_run_or_trace_selector(selector: &str) -> String {
@@ -91,7 +131,17 @@ pub struct TraceFile {
     pub events: Vec<TraceEvent>,
     pub summary: RunSummary,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<TraceRedaction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<TraceMigration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterfactual: Option<crate::TraceCounterfactual>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<TraceSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub checksum: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<TraceSignature>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,7 +174,12 @@ impl TraceFile {
             decisions,
             events,
             summary,
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
         }
     }
 
@@ -150,7 +205,12 @@ impl TraceFile {
             decisions: Vec::new(),
             events,
             summary,
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
         }
     }
 
@@ -173,10 +233,43 @@ impl TraceFile {
             decisions,
             events,
             summary,
+            redaction: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+            snapshots: Vec::new(),
             checksum: None,
+            signature: None,
+        }
+    }
+
+    /// Writes the trace in `encoding`, signing it first when `FOZZY_TRACE_SIGN_KEY`
+    /// names a key file.
+    pub fn write(&self, path: &Path, encoding: TraceEncoding) -> FozzyResult<()> {
+        match self.signed_from_env()? {
+            Some(signed) => signed.write_unsigned(path, encoding),
+            None => self.write_unsigned(path, encoding),
+        }
+    }
+
+    /// Writes the trace as-is, keeping whatever signature it already carries.
+    pub fn write_unsigned(&self, path: &Path, encoding: TraceEncoding) -> FozzyResult<()> {
+        match encoding {
+            TraceEncoding::Json => self.write_json(path),
+            TraceEncoding::Binary => self.write_binary(path, false),
+            TraceEncoding::BinaryDeflate => self.write_binary(path, true),
         }
     }
 
+    /// A copy signed with the `FOZZY_TRACE_SIGN_KEY` key, when one is set.
+    pub(crate) fn signed_from_env(&self) -> FozzyResult<Option<TraceFile>> {
+        let Some(key) = TraceSigningKey::from_env()? else {
+            return Ok(None);
+        };
+        let mut signed = self.clone();
+        signed.sign(&key)?;
+        Ok(Some(signed))
+    }
+
     pub fn write_json(&self, path: &Path) -> FozzyResult<()> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -194,7 +287,12 @@ impl TraceFile {
             decisions: &self.decisions,
             events: &self.events,
             summary: &self.summary,
+            redaction: self.redaction.as_ref(),
+            migrations: &self.migrations,
+            counterfactual: self.counterfactual.as_ref(),
+            snapshots: &self.snapshots,
             checksum: None,
+            signature: self.signature.as_ref(),
         })?;
         let checksum = blake3::hash(&canonical).to_hex().to_string();
 
@@ -215,7 +313,12 @@ impl TraceFile {
                 decisions: &self.decisions,
                 events: &self.events,
                 summary: &self.summary,
+                redaction: self.redaction.as_ref(),
+                migrations: &self.migrations,
+                counterfactual: self.counterfactual.as_ref(),
+                snapshots: &self.snapshots,
                 checksum: Some(checksum.as_str()),
+                signature: self.signature.as_ref(),
             })?
         } else {
             serde_json::to_vec(&TraceWriteView {
@@ -231,27 +334,31 @@ impl TraceFile {
                 decisions: &self.decisions,
                 events: &self.events,
                 summary: &self.summary,
+                redaction: self.redaction.as_ref(),
+                migrations: &self.migrations,
+                counterfactual: self.counterfactual.as_ref(),
+                snapshots: &self.snapshots,
                 checksum: Some(checksum.as_str()),
+                signature: self.signature.as_ref(),
             })?
         };
         // Atomic replace to avoid concurrent writer corruption on shared paths.
-        let parent = path.parent().unwrap_or_else(|| Path::new("."));
-        let file_name = path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("trace.fozzy");
-        let tmp_name = format!(
-            ".{file_name}.{}.{}.tmp",
-            std::process::id(),
-            uuid::Uuid::new_v4()
-        );
-        let tmp_path = parent.join(tmp_name);
+        let tmp_path = tmp_trace_path(path);
         std::fs::write(&tmp_path, bytes)?;
         std::fs::rename(&tmp_path, path)?;
         Ok(())
     }
 
+    /// Reads a trace in either encoding; binary traces are detected by their magic.
     pub fn read_json(path: &Path) -> FozzyResult<Self> {
+        if let Some((archive_path, selector)) = split_archive_selector(path) {
+            return archive::read_archived_trace(&archive_path, &selector);
+        }
+        if is_binary_trace_file(path) {
+            let t = TraceCursor::open(path)?.read_to_trace()?;
+            validate_trace_header(&t, path)?;
+            return Ok(t);
+        }
         let bytes = std::fs::read(path)?;
         let t: TraceFile = serde_json::from_slice(&bytes).map_err(|e| {
             FozzyError::Trace(format!("failed to parse trace {}: {e}", path.display()))
@@ -262,6 +369,19 @@ impl TraceFile {
     }
 }
 
+fn tmp_trace_path(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("trace.fozzy");
+    parent.join(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        uuid::Uuid::new_v4()
+    ))
+}
+
 #[derive(Serialize)]
 struct TraceWriteView<'a> {
     format: &'a str,
@@ -279,8 +399,18 @@ struct TraceWriteView<'a> {
     decisions: &'a [Decision],
     events: &'a [TraceEvent],
     summary: &'a RunSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redaction: Option<&'a TraceRedaction>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    migrations: &'a [TraceMigration],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    counterfactual: Option<&'a crate::TraceCounterfactual>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    snapshots: &'a [TraceSnapshot],
     #[serde(default, skip_serializing_if = "Option::is_none")]
     checksum: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<&'a TraceSignature>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -292,6 +422,8 @@ pub struct TraceVerifyReport {
     pub checksum_present: bool,
     #[serde(rename = "checksumValid")]
     pub checksum_valid: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<TraceSignatureStatus>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
 }
@@ -299,7 +431,7 @@ pub struct TraceVerifyReport {
 pub fn trace_schema_warnings(version: u32) -> Vec<String> {
     if version < CURRENT_TRACE_VERSION {
         vec![format!(
-            "trace schema v{version} is stale; current schema is v{CURRENT_TRACE_VERSION}"
+            "trace schema v{version} is stale; current schema is v{CURRENT_TRACE_VERSION} (run `fozzy trace upgrade`)"
         )]
     } else {
         Vec::new()
@@ -307,8 +439,25 @@ pub fn trace_schema_warnings(version: u32) -> Vec<String> {
 }
 
 pub fn verify_trace_file(path: &Path) -> FozzyResult<TraceVerifyReport> {
+    verify_trace_file_with_key(path, None)
+}
+
+/// Verifies a trace and checks its signature against `key` when one is given.
+pub fn verify_trace_file_with_key(
+    path: &Path,
+    key: Option<&TraceSigningKey>,
+) -> FozzyResult<TraceVerifyReport> {
     let path = normalize_trace_path(path);
+    if let Some((archive_path, selector)) = split_archive_selector(&path) {
+        return archive::verify_archived_trace(&archive_path, &selector, key);
+    }
     let display_path = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    if is_trace_archive_path(&path) {
+        return archive::verify_trace_archive(&path, display_path);
+    }
+    if is_binary_trace_file(&path) {
+        return verify_binary_trace(&path, display_path, key);
+    }
     let bytes = std::fs::read(&path)?;
     let t: TraceFile = serde_json::from_slice(&bytes)
         .map_err(|e| FozzyError::Trace(format!("failed to parse trace {}: {e}", path.display())))?;
@@ -322,6 +471,29 @@ pub fn verify_trace_file(path: &Path) -> FozzyResult<TraceVerifyReport> {
         version: t.version,
         checksum_present: t.checksum.is_some(),
         checksum_valid: checksum_valid.unwrap_or(false),
+        signature: verify_trace_signature(&t, key)?,
+        warnings,
+    })
+}
+
+fn verify_binary_trace(
+    path: &Path,
+    display_path: PathBuf,
+    key: Option<&TraceSigningKey>,
+) -> FozzyResult<TraceVerifyReport> {
+    let file = std::fs::File::open(path)?;
+    let mut cursor = TraceCursor::new(std::io::BufReader::new(file), false)?;
+    let t = cursor.read_to_trace()?;
+    validate_trace_header(&t, path)?;
+    let mut warnings = trace_schema_warnings(t.version);
+    warnings.extend(trace_replay_warnings(&t));
+    Ok(TraceVerifyReport {
+        ok: true,
+        path: display_path.display().to_string(),
+        version: t.version,
+        checksum_present: true,
+        checksum_valid: cursor.digests_valid(),
+        signature: verify_trace_signature(&t, key)?,
         warnings,
     })
 }
@@ -330,82 +502,104 @@ pub fn write_trace_with_policy(
     trace: &TraceFile,
     requested: &Path,
     policy: RecordCollisionPolicy,
+    encoding: TraceEncoding,
 ) -> FozzyResult<PathBuf> {
     let target = resolve_record_target(requested, policy)?;
-    write_trace_to_target(trace, &target)?;
+    write_trace_to_target(trace, &target, encoding)?;
     Ok(target)
 }
 
-pub(crate) fn write_trace_to_target(trace: &TraceFile, target: &Path) -> FozzyResult<()> {
+pub(crate) fn write_trace_to_target(
+    trace: &TraceFile,
+    target: &Path,
+    encoding: TraceEncoding,
+) -> FozzyResult<()> {
     let _lock = acquire_record_lock(&target)?;
-    trace.write_json(&target)?;
+    trace.write(target, encoding)?;
     Ok(())
 }
 
 pub fn trace_replay_warnings(trace: &TraceFile) -> Vec<String> {
-    let used_host_fs = trace.events.iter().any(|e| {
-        e.name == "capability_fs"
-            && e.fields
-                .get("backend")
-                .and_then(|v| v.as_str())
-                .is_some_and(|backend| backend == "host")
-    });
-    let has_fs_decisions = trace.decisions.iter().any(|d| {
-        matches!(
-            d,
-            Decision::FsWrite { .. }
-                | Decision::FsReadAssert { .. }
-                | Decision::FsSnapshot { .. }
-                | Decision::FsRestore { .. }
-        )
-    });
-    let used_host_proc = trace.events.iter().any(|e| {
-        e.name == "proc_spawn"
-            && e.fields
-                .get("backend")
-                .and_then(|v| v.as_str())
-                .is_some_and(|backend| backend == "host")
-    });
-    let has_proc_decisions = trace.decisions.iter().any(|d| {
-        matches!(
-            d,
-            Decision::ProcSpawn { .. } | Decision::ProcSpawnTimeout { .. }
-        )
-    });
-    let used_host_http = trace.events.iter().any(|e| {
-        e.name == "http_request"
-            && e.fields
-                .get("backend")
-                .and_then(|v| v.as_str())
-                .is_some_and(|backend| backend == "host")
-    });
-    let has_http_decisions = trace.decisions.iter().any(|d| {
-        matches!(
-            d,
-            Decision::HttpRequest { .. } | Decision::HttpRequestTimeout { .. }
-        )
-    });
-
-    let mut warnings = Vec::new();
-    if used_host_fs && !has_fs_decisions {
-        warnings.push(
-            "trace used host fs backend but does not include fs decisions; replay may drift"
-                .to_string(),
-        );
+    let mut scan = ReplayWarningScan::default();
+    scan.observe_decisions(&trace.decisions);
+    scan.observe_events(&trace.events);
+    scan.warnings()
+}
+
+/// What `trace_replay_warnings` looks for, accumulated chunk by chunk so binary
+/// traces can be checked without holding every decision and event.
+#[derive(Debug, Default)]
+pub(crate) struct ReplayWarningScan {
+    used_host_fs: bool,
+    has_fs_decisions: bool,
+    used_host_proc: bool,
+    has_proc_decisions: bool,
+    used_host_http: bool,
+    has_http_decisions: bool,
+}
+
+impl ReplayWarningScan {
+    pub(crate) fn observe_events(&mut self, events: &[TraceEvent]) {
+        let used_host = |name: &str| {
+            events.iter().any(|e| {
+                e.name == name
+                    && e.fields
+                        .get("backend")
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|backend| backend == "host")
+            })
+        };
+        self.used_host_fs |= used_host("capability_fs");
+        self.used_host_proc |= used_host("proc_spawn");
+        self.used_host_http |= used_host("http_request");
     }
-    if used_host_proc && !has_proc_decisions {
-        warnings.push(
-            "trace used host proc backend but does not include proc decisions; replay may drift"
-                .to_string(),
-        );
+
+    pub(crate) fn observe_decisions(&mut self, decisions: &[Decision]) {
+        self.has_fs_decisions |= decisions.iter().any(|d| {
+            matches!(
+                d,
+                Decision::FsWrite { .. }
+                    | Decision::FsReadAssert { .. }
+                    | Decision::FsSnapshot { .. }
+                    | Decision::FsRestore { .. }
+            )
+        });
+        self.has_proc_decisions |= decisions.iter().any(|d| {
+            matches!(
+                d,
+                Decision::ProcSpawn { .. } | Decision::ProcSpawnTimeout { .. }
+            )
+        });
+        self.has_http_decisions |= decisions.iter().any(|d| {
+            matches!(
+                d,
+                Decision::HttpRequest { .. } | Decision::HttpRequestTimeout { .. }
+            )
+        });
     }
-    if used_host_http && !has_http_decisions {
-        warnings.push(
-            "trace used host http backend but does not include http decisions; replay may drift"
-                .to_string(),
-        );
+
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.used_host_fs && !self.has_fs_decisions {
+            warnings.push(
+                "trace used host fs backend but does not include fs decisions; replay may drift"
+                    .to_string(),
+            );
+        }
+        if self.used_host_proc && !self.has_proc_decisions {
+            warnings.push(
+                "trace used host proc backend but does not include proc decisions; replay may drift"
+                    .to_string(),
+            );
+        }
+        if self.used_host_http && !self.has_http_decisions {
+            warnings.push(
+                "trace used host http backend but does not include http decisions; replay may drift"
+                    .to_string(),
+            );
+        }
+        warnings
     }
-    warnings
 }
 
 pub(crate) fn resolve_record_target(
@@ -413,6 +607,12 @@ pub(crate) fn resolve_record_target(
     policy: RecordCollisionPolicy,
 ) -> FozzyResult<PathBuf> {
     let path = normalize_trace_path(path);
+    if is_trace_archive_path(&path) || split_archive_selector(&path).is_some() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "{} is a trace archive; archives are recorded by `fozzy test --record <file>.{TRACE_ARCHIVE_EXTENSION}`",
+            path.display()
+        )));
+    }
     match policy {
         RecordCollisionPolicy::Overwrite => Ok(path),
         RecordCollisionPolicy::Error => {
@@ -0,0 +1,322 @@
+//! Multi-trace archives (`.fozzya`): one container for every trace a test run recorded.
+//!
+//! An archive holds an index (name, scenario, status, seed, checksum per entry) next to
+//! the embedded JSON traces. `archive.fozzya#<scenario>` selects a single trace anywhere
+//! a trace path is accepted.
+
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+use super::{
+    TraceFile, TraceSigningKey, TraceVerifyReport, checksum_digest, checksum_validity,
+    normalize_trace_path, trace_replay_warnings, trace_schema_warnings,
+};
+use crate::{ExitStatus, FozzyError, FozzyResult, RecordCollisionPolicy, RunMode, VersionInfo};
+
+pub const TRACE_ARCHIVE_FORMAT: &str = "fozzy-trace-archive";
+pub const TRACE_ARCHIVE_VERSION: u32 = 1;
+pub const TRACE_ARCHIVE_EXTENSION: &str = "fozzya";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceArchive {
+    pub format: String,
+    pub version: u32,
+    pub engine: VersionInfo,
+    pub index: Vec<TraceArchiveEntry>,
+    /// Embedded traces, in index order.
+    pub traces: Vec<TraceFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceArchiveEntry {
+    /// Selector name, unique within the archive (`archive.fozzya#<name>`).
+    pub name: String,
+    pub scenario_path: Option<String>,
+    pub mode: RunMode,
+    pub status: ExitStatus,
+    pub seed: u64,
+    pub run_id: String,
+    /// Checksum of the embedded trace.
+    pub checksum: String,
+}
+
+impl Default for TraceArchive {
+    fn default() -> Self {
+        Self {
+            format: TRACE_ARCHIVE_FORMAT.to_string(),
+            version: TRACE_ARCHIVE_VERSION,
+            engine: crate::version_info(),
+            index: Vec::new(),
+            traces: Vec::new(),
+        }
+    }
+}
+
+impl TraceArchive {
+    pub fn read(path: &Path) -> FozzyResult<Self> {
+        let bytes = std::fs::read(path)?;
+        let archive: TraceArchive = serde_json::from_slice(&bytes).map_err(|e| {
+            FozzyError::Trace(format!(
+                "failed to parse trace archive {}: {e}",
+                path.display()
+            ))
+        })?;
+        if archive.format != TRACE_ARCHIVE_FORMAT || archive.version != TRACE_ARCHIVE_VERSION {
+            return Err(FozzyError::Trace(format!(
+                "unsupported trace archive {}: got {} v{}, expected {TRACE_ARCHIVE_FORMAT} v{TRACE_ARCHIVE_VERSION}",
+                path.display(),
+                archive.format,
+                archive.version
+            )));
+        }
+        if archive.index.len() != archive.traces.len() {
+            return Err(FozzyError::Trace(format!(
+                "trace archive {} indexes {} traces but embeds {}",
+                path.display(),
+                archive.index.len(),
+                archive.traces.len()
+            )));
+        }
+        Ok(archive)
+    }
+
+    pub fn write(&self, path: &Path) -> FozzyResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = super::tmp_trace_path(path);
+        std::fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Name the next trace with this base name would get, e.g. `net`, then `net.2`.
+    pub fn next_name(&self, base: &str) -> String {
+        let taken = |name: &str| self.index.iter().any(|entry| entry.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{base}.{n}"))
+            .find(|name| !taken(name))
+            .expect("unbounded name search")
+    }
+
+    /// Adds a trace under `name`, signing it when `FOZZY_TRACE_SIGN_KEY` is set.
+    pub fn push(&mut self, name: String, mut trace: TraceFile) -> FozzyResult<()> {
+        if let Some(key) = TraceSigningKey::from_env()? {
+            trace.sign(&key)?;
+        }
+        trace.checksum = None;
+        let checksum = checksum_digest(&trace)?;
+        trace.checksum = Some(checksum.clone());
+        self.index.push(TraceArchiveEntry {
+            name,
+            scenario_path: trace.scenario_path.clone(),
+            mode: trace.mode,
+            status: trace.summary.status,
+            seed: trace.summary.identity.seed,
+            run_id: trace.summary.identity.run_id.clone(),
+            checksum,
+        });
+        self.traces.push(trace);
+        Ok(())
+    }
+
+    /// Position of the entry named by `selector`: an entry name, a scenario path
+    /// (or its trailing components), or the scenario's own `name`.
+    pub fn find(&self, selector: &str) -> FozzyResult<usize> {
+        if let Some(idx) = self.index.iter().position(|entry| entry.name == selector) {
+            return Ok(idx);
+        }
+        let matches: Vec<usize> =
+            self.index
+                .iter()
+                .zip(&self.traces)
+                .enumerate()
+                .filter(|(_, (entry, trace))| {
+                    entry.scenario_path.as_deref().is_some_and(|path| {
+                        path == selector || path.ends_with(&format!("/{selector}"))
+                    }) || trace
+                        .scenario
+                        .as_ref()
+                        .is_some_and(|scenario| scenario.name == selector)
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+        match matches.as_slice() {
+            [idx] => Ok(*idx),
+            [] => Err(FozzyError::InvalidArgument(format!(
+                "no trace {selector:?} in archive (entries: {})",
+                self.entry_names()
+            ))),
+            _ => Err(FozzyError::InvalidArgument(format!(
+                "trace selector {selector:?} is ambiguous in archive ({}); use an entry name",
+                matches
+                    .iter()
+                    .map(|idx| self.index[*idx].name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    fn entry_names(&self) -> String {
+        self.index
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Problems with entry `idx`: checksum mismatches and index fields that disagree
+    /// with the embedded trace.
+    fn entry_problems(&self, idx: usize) -> FozzyResult<Vec<String>> {
+        let entry = &self.index[idx];
+        let trace = &self.traces[idx];
+        let mut problems = Vec::new();
+        if checksum_validity(trace)? != Some(true) {
+            problems.push("embedded trace checksum invalid".to_string());
+        }
+        if trace.checksum.as_deref() != Some(entry.checksum.as_str()) {
+            problems.push("index checksum does not match the embedded trace".to_string());
+        }
+        if entry.status != trace.summary.status || entry.seed != trace.summary.identity.seed {
+            problems.push("index status/seed do not match the embedded trace".to_string());
+        }
+        Ok(problems)
+    }
+}
+
+pub fn is_trace_archive_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case(TRACE_ARCHIVE_EXTENSION))
+}
+
+/// Splits `archive.fozzya#<scenario>` into the archive path and the entry selector.
+pub fn split_archive_selector(path: &Path) -> Option<(PathBuf, String)> {
+    let text = path.to_str()?;
+    let (archive, selector) = text.rsplit_once('#')?;
+    let archive = PathBuf::from(archive);
+    (is_trace_archive_path(&archive) && !selector.is_empty())
+        .then(|| (archive, selector.to_string()))
+}
+
+/// The selector that names entry `name` of `archive`.
+pub fn archive_entry_path(archive: &Path, name: &str) -> PathBuf {
+    PathBuf::from(format!("{}#{name}", archive.display()))
+}
+
+/// Reads one trace out of an archive, checking it against the archive index.
+pub(crate) fn read_archived_trace(archive_path: &Path, selector: &str) -> FozzyResult<TraceFile> {
+    let mut archive = TraceArchive::read(archive_path)?;
+    let idx = archive.find(selector)?;
+    if let Some(problem) = archive.entry_problems(idx)?.into_iter().next() {
+        return Err(FozzyError::Trace(format!(
+            "{}#{}: {problem}",
+            archive_path.display(),
+            archive.index[idx].name
+        )));
+    }
+    Ok(archive.traces.swap_remove(idx))
+}
+
+/// Records into the archive at `path` under the record lock, honouring the collision
+/// policy: `overwrite` starts a new archive, `error` refuses an existing one and
+/// `append` adds entries to it.
+pub(crate) fn record_into_archive(
+    path: &Path,
+    policy: RecordCollisionPolicy,
+    fill: impl FnOnce(&Path, &mut TraceArchive) -> FozzyResult<()>,
+) -> FozzyResult<PathBuf> {
+    let path = normalize_trace_path(path);
+    let _lock = super::acquire_record_lock(&path)?;
+    let mut archive = match policy {
+        RecordCollisionPolicy::Overwrite => TraceArchive::default(),
+        RecordCollisionPolicy::Error if path.exists() => {
+            return Err(FozzyError::Trace(format!(
+                "record collision: {} already exists (--record-collision=error). rerun with --record-collision overwrite (replace) or --record-collision append (add entries)",
+                path.display(),
+            )));
+        }
+        RecordCollisionPolicy::Error => TraceArchive::default(),
+        RecordCollisionPolicy::Append if path.exists() => TraceArchive::read(&path)?,
+        RecordCollisionPolicy::Append => TraceArchive::default(),
+    };
+    fill(&path, &mut archive)?;
+    archive.write(&path)?;
+    Ok(path)
+}
+
+/// Archive entry base name for a recorded scenario: its file name without `.fozzy.json`.
+pub(crate) fn archive_entry_base(scenario_path: &Path) -> String {
+    let file_name = scenario_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("trace");
+    file_name
+        .strip_suffix(".fozzy.json")
+        .or_else(|| file_name.strip_suffix(".json"))
+        .unwrap_or(file_name)
+        .to_string()
+}
+
+/// `trace verify` for a whole archive: every entry must pass.
+pub(crate) fn verify_trace_archive(
+    path: &Path,
+    display_path: PathBuf,
+) -> FozzyResult<TraceVerifyReport> {
+    let archive = TraceArchive::read(path)?;
+    let mut checksum_present = true;
+    let mut checksum_valid = true;
+    let mut warnings = Vec::new();
+    for (idx, (entry, trace)) in archive.index.iter().zip(&archive.traces).enumerate() {
+        checksum_present &= trace.checksum.is_some();
+        let problems = archive.entry_problems(idx)?;
+        checksum_valid &= problems.is_empty();
+        warnings.extend(
+            problems
+                .into_iter()
+                .chain(trace_schema_warnings(trace.version))
+                .chain(trace_replay_warnings(trace))
+                .map(|warning| format!("{}: {warning}", entry.name)),
+        );
+    }
+    Ok(TraceVerifyReport {
+        ok: true,
+        path: display_path.display().to_string(),
+        version: archive.version,
+        checksum_present,
+        checksum_valid,
+        signature: None,
+        warnings,
+    })
+}
+
+/// `trace verify` for `archive.fozzya#<scenario>`.
+pub(crate) fn verify_archived_trace(
+    archive_path: &Path,
+    selector: &str,
+    key: Option<&TraceSigningKey>,
+) -> FozzyResult<TraceVerifyReport> {
+    let archive = TraceArchive::read(archive_path)?;
+    let idx = archive.find(selector)?;
+    let trace = &archive.traces[idx];
+    let problems = archive.entry_problems(idx)?;
+    let mut warnings = trace_schema_warnings(trace.version);
+    warnings.extend(trace_replay_warnings(trace));
+    warnings.extend(problems.iter().cloned());
+    Ok(TraceVerifyReport {
+        ok: true,
+        path: archive_entry_path(archive_path, &archive.index[idx].name)
+            .display()
+            .to_string(),
+        version: trace.version,
+        checksum_present: trace.checksum.is_some(),
+        checksum_valid: problems.is_empty(),
+        signature: super::verify_trace_signature(trace, key)?,
+        warnings,
+    })
+}
@@ -0,0 +1,858 @@
+//! Chunked binary trace encoding.
+//!
+//! A binary trace is `BINARY_TRACE_MAGIC` followed by length-prefixed CBOR frames:
+//! `[kind u8][flags u8][len u32 le][blake3 32B][payload]`. The digest covers the
+//! decoded CBOR payload so compression does not change it. Frames are a header,
+//! any number of decision/event chunks, and a footer that carries the summary and
+//! a digest over every preceding frame digest.
+
+use serde::{Deserialize, Serialize};
+
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::{
+    CURRENT_TRACE_VERSION, Redactor, ReplayWarningScan, TRACE_FORMAT, TraceEvent, TraceFile,
+    TraceMigration, TraceRedaction, TraceSignature, TraceSnapshot, acquire_record_lock,
+    tmp_trace_path, validate_trace_header,
+};
+use crate::{
+    Decision, ExploreTrace, FozzyError, FozzyResult, FuzzTrace, MemoryTrace, RunMode, RunSummary,
+    ScenarioV1Steps, VersionInfo,
+};
+
+pub const BINARY_TRACE_MAGIC: &[u8; 8] = b"FZYTRC\x00\x01";
+
+const FRAME_HEADER: u8 = 1;
+const FRAME_DECISIONS: u8 = 2;
+const FRAME_EVENTS: u8 = 3;
+const FRAME_FOOTER: u8 = 4;
+const FLAG_DEFLATE: u8 = 0x01;
+const CHUNK_ITEMS: usize = 1024;
+const MAX_FRAME_BYTES: usize = 256 * 1024 * 1024;
+
+/// On-disk encoding used when a trace is written (`--trace-encoding`, `trace_encoding`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TraceEncoding {
+    #[default]
+    Json,
+    Binary,
+    BinaryDeflate,
+}
+
+impl clap::ValueEnum for TraceEncoding {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Json, Self::Binary, Self::BinaryDeflate]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            Self::Json => clap::builder::PossibleValue::new("json"),
+            Self::Binary => clap::builder::PossibleValue::new("binary"),
+            Self::BinaryDeflate => clap::builder::PossibleValue::new("binary-deflate"),
+        })
+    }
+}
+
+impl TraceEncoding {
+    /// Reads `FOZZY_TRACE_ENCODING` (`json`, `binary`, `binary+deflate`), if set.
+    pub fn from_env() -> Option<Self> {
+        match std::env::var("FOZZY_TRACE_ENCODING")
+            .map(|v| v.to_ascii_lowercase())
+            .as_deref()
+        {
+            Ok("json") => Some(Self::Json),
+            Ok("binary") | Ok("cbor") => Some(Self::Binary),
+            Ok("binary+deflate") | Ok("binary-deflate") | Ok("cbor+deflate") => {
+                Some(Self::BinaryDeflate)
+            }
+            _ => None,
+        }
+    }
+
+    /// Encoding of an existing trace file, so rewritten traces keep it.
+    pub fn of_file(path: &Path) -> Self {
+        if !is_binary_trace_file(path) {
+            return Self::Json;
+        }
+        let compressed = std::fs::File::open(path)
+            .ok()
+            .and_then(|mut file| {
+                let mut head = [0u8; BINARY_TRACE_MAGIC.len() + 2];
+                file.read_exact(&mut head).ok()?;
+                Some(head[BINARY_TRACE_MAGIC.len() + 1] & FLAG_DEFLATE != 0)
+            })
+            .unwrap_or(false);
+        if compressed {
+            Self::BinaryDeflate
+        } else {
+            Self::Binary
+        }
+    }
+
+    fn compress(self) -> bool {
+        self == Self::BinaryDeflate
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceHeader {
+    pub format: String,
+    pub version: u32,
+    pub engine: VersionInfo,
+    pub mode: RunMode,
+    pub scenario_path: Option<String>,
+    pub scenario: Option<ScenarioV1Steps>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzz: Option<FuzzTrace>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explore: Option<ExploreTrace>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<TraceMigration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterfactual: Option<crate::TraceCounterfactual>,
+}
+
+impl TraceHeader {
+    pub fn new(mode: RunMode) -> Self {
+        Self {
+            format: TRACE_FORMAT.to_string(),
+            version: CURRENT_TRACE_VERSION,
+            engine: crate::version_info(),
+            mode,
+            scenario_path: None,
+            scenario: None,
+            fuzz: None,
+            explore: None,
+            migrations: Vec::new(),
+            counterfactual: None,
+        }
+    }
+
+    fn from_trace(trace: &TraceFile) -> Self {
+        Self {
+            format: trace.format.clone(),
+            version: trace.version,
+            engine: trace.engine.clone(),
+            mode: trace.mode,
+            scenario_path: trace.scenario_path.clone(),
+            scenario: trace.scenario.clone(),
+            fuzz: trace.fuzz.clone(),
+            explore: trace.explore.clone(),
+            migrations: trace.migrations.clone(),
+            counterfactual: trace.counterfactual.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceFooter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryTrace>,
+    pub summary: RunSummary,
+    pub decisions: u64,
+    pub events: u64,
+    pub digest: String,
+    /// Lives in the footer because recording redacts chunks as they are written
+    /// and only knows the final count at the end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<TraceRedaction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<TraceSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<TraceSignature>,
+}
+
+/// Incremental writer: decisions and events are flushed in fixed-size chunks as they
+/// arrive, and the file only appears at its final path once `finish` succeeds.
+pub struct TraceStreamWriter {
+    out: Option<BufWriter<std::fs::File>>,
+    tmp_path: PathBuf,
+    path: PathBuf,
+    compress: bool,
+    chain: blake3::Hasher,
+    decisions: Vec<Decision>,
+    events: Vec<TraceEvent>,
+    decision_count: u64,
+    event_count: u64,
+    redaction: Option<TraceRedaction>,
+    snapshots: Vec<TraceSnapshot>,
+    signature: Option<TraceSignature>,
+}
+
+impl TraceStreamWriter {
+    pub fn create(path: &Path, header: &TraceHeader, compress: bool) -> FozzyResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = tmp_trace_path(path);
+        let mut out = BufWriter::new(std::fs::File::create(&tmp_path)?);
+        out.write_all(BINARY_TRACE_MAGIC)?;
+        let mut writer = Self {
+            out: Some(out),
+            tmp_path,
+            path: path.to_path_buf(),
+            compress,
+            chain: blake3::Hasher::new(),
+            decisions: Vec::new(),
+            events: Vec::new(),
+            decision_count: 0,
+            event_count: 0,
+            redaction: None,
+            snapshots: Vec::new(),
+            signature: None,
+        };
+        writer.write_frame(FRAME_HEADER, header)?;
+        Ok(writer)
+    }
+
+    pub fn push_decision(&mut self, decision: Decision) -> FozzyResult<()> {
+        self.decisions.push(decision);
+        if self.decisions.len() >= CHUNK_ITEMS {
+            self.flush_decisions()?;
+        }
+        Ok(())
+    }
+
+    pub fn push_event(&mut self, event: TraceEvent) -> FozzyResult<()> {
+        self.events.push(event);
+        if self.events.len() >= CHUNK_ITEMS {
+            self.flush_events()?;
+        }
+        Ok(())
+    }
+
+    /// Redaction metadata to store in the footer.
+    pub fn set_redaction(&mut self, redaction: Option<TraceRedaction>) {
+        self.redaction = redaction;
+    }
+
+    /// Engine snapshots to store in the footer.
+    pub fn set_snapshots(&mut self, snapshots: Vec<TraceSnapshot>) {
+        self.snapshots = snapshots;
+    }
+
+    /// Signature to store in the footer; it must cover the trace as finished.
+    pub fn set_signature(&mut self, signature: Option<TraceSignature>) {
+        self.signature = signature;
+    }
+
+    pub fn finish(mut self, memory: Option<MemoryTrace>, summary: RunSummary) -> FozzyResult<()> {
+        self.flush_decisions()?;
+        self.flush_events()?;
+        let footer = TraceFooter {
+            memory,
+            summary,
+            decisions: self.decision_count,
+            events: self.event_count,
+            digest: self.chain.finalize().to_hex().to_string(),
+            redaction: self.redaction.take(),
+            snapshots: std::mem::take(&mut self.snapshots),
+            signature: self.signature.take(),
+        };
+        self.write_frame(FRAME_FOOTER, &footer)?;
+        let mut out = self.out.take().expect("stream writer is open until finish");
+        out.flush()?;
+        drop(out);
+        std::fs::rename(&self.tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn flush_decisions(&mut self) -> FozzyResult<()> {
+        if self.decisions.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.decisions);
+        self.decision_count += chunk.len() as u64;
+        self.write_frame(FRAME_DECISIONS, &chunk)
+    }
+
+    fn flush_events(&mut self) -> FozzyResult<()> {
+        if self.events.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.events);
+        self.event_count += chunk.len() as u64;
+        self.write_frame(FRAME_EVENTS, &chunk)
+    }
+
+    fn write_frame<T: Serialize>(&mut self, kind: u8, value: &T) -> FozzyResult<()> {
+        let mut payload = Vec::new();
+        ciborium::into_writer(value, &mut payload)
+            .map_err(|e| FozzyError::Trace(format!("failed to encode trace frame: {e}")))?;
+        if payload.len() > MAX_FRAME_BYTES {
+            return Err(FozzyError::Trace(
+                "trace frame exceeds size limit".to_string(),
+            ));
+        }
+        let digest = blake3::hash(&payload);
+        if kind != FRAME_FOOTER {
+            self.chain.update(digest.as_bytes());
+        }
+        let (flags, stored) = if self.compress {
+            let mut enc =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            enc.write_all(&payload)?;
+            (FLAG_DEFLATE, enc.finish()?)
+        } else {
+            (0, payload)
+        };
+        let len = u32::try_from(stored.len())
+            .ok()
+            .filter(|len| (*len as usize) <= MAX_FRAME_BYTES)
+            .ok_or_else(|| FozzyError::Trace("trace frame exceeds size limit".to_string()))?;
+        let out = self
+            .out
+            .as_mut()
+            .expect("stream writer is open until finish");
+        out.write_all(&[kind, flags])?;
+        out.write_all(&len.to_le_bytes())?;
+        out.write_all(digest.as_bytes())?;
+        out.write_all(&stored)?;
+        Ok(())
+    }
+}
+
+impl Drop for TraceStreamWriter {
+    fn drop(&mut self) {
+        if self.out.take().is_some() {
+            let _ = std::fs::remove_file(&self.tmp_path);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TraceChunk {
+    Decisions(Vec<Decision>),
+    Events(Vec<TraceEvent>),
+}
+
+/// Streaming reader over a binary trace, yielding one chunk at a time.
+///
+/// In strict mode any digest mismatch is an error; otherwise mismatches are
+/// recorded and reported through `digests_valid`.
+pub struct TraceCursor<R> {
+    reader: R,
+    header: TraceHeader,
+    footer: Option<TraceFooter>,
+    chain: blake3::Hasher,
+    strict: bool,
+    digests_valid: bool,
+    frames: u64,
+    decisions: u64,
+    events: u64,
+}
+
+impl TraceCursor<BufReader<std::fs::File>> {
+    pub fn open(path: &Path) -> FozzyResult<Self> {
+        Self::new(BufReader::new(std::fs::File::open(path)?), true)
+            .map_err(|e| with_trace_path(e, path))
+    }
+}
+
+impl<R: Read> TraceCursor<R> {
+    pub fn new(mut reader: R, strict: bool) -> FozzyResult<Self> {
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| FozzyError::Trace("truncated binary trace".to_string()))?;
+        if &magic != BINARY_TRACE_MAGIC {
+            return Err(FozzyError::Trace("not a binary trace".to_string()));
+        }
+        let mut cursor = Self {
+            reader,
+            header: TraceHeader::new(RunMode::Run),
+            footer: None,
+            chain: blake3::Hasher::new(),
+            strict,
+            digests_valid: true,
+            frames: 0,
+            decisions: 0,
+            events: 0,
+        };
+        let Some((kind, payload)) = cursor.read_frame()? else {
+            return Err(FozzyError::Trace("binary trace has no header".to_string()));
+        };
+        if kind != FRAME_HEADER {
+            return Err(FozzyError::Trace(
+                "binary trace does not start with a header frame".to_string(),
+            ));
+        }
+        cursor.header = decode(&payload)?;
+        Ok(cursor)
+    }
+
+    pub fn header(&self) -> &TraceHeader {
+        &self.header
+    }
+
+    /// Footer, available once `next_chunk` has returned `None`.
+    pub fn footer(&self) -> Option<&TraceFooter> {
+        self.footer.as_ref()
+    }
+
+    pub fn digests_valid(&self) -> bool {
+        self.digests_valid
+    }
+
+    pub fn next_chunk(&mut self) -> FozzyResult<Option<TraceChunk>> {
+        if self.footer.is_some() {
+            return Ok(None);
+        }
+        let Some((kind, payload)) = self.read_frame()? else {
+            return Err(FozzyError::Trace(
+                "binary trace ended without a footer".to_string(),
+            ));
+        };
+        match kind {
+            FRAME_DECISIONS => {
+                let chunk: Vec<Decision> = decode(&payload)?;
+                self.decisions += chunk.len() as u64;
+                Ok(Some(TraceChunk::Decisions(chunk)))
+            }
+            FRAME_EVENTS => {
+                let chunk: Vec<TraceEvent> = decode(&payload)?;
+                self.events += chunk.len() as u64;
+                Ok(Some(TraceChunk::Events(chunk)))
+            }
+            FRAME_FOOTER => {
+                let footer: TraceFooter = decode(&payload)?;
+                let chain = self.chain.finalize().to_hex().to_string();
+                if footer.digest != chain
+                    || footer.decisions != self.decisions
+                    || footer.events != self.events
+                {
+                    self.mismatch("binary trace footer does not match its frames")?;
+                }
+                self.footer = Some(footer);
+                Ok(None)
+            }
+            other => Err(FozzyError::Trace(format!(
+                "unknown binary trace frame kind {other}"
+            ))),
+        }
+    }
+
+    /// Drains the remaining chunks into a full in-memory trace.
+    pub fn read_to_trace(&mut self) -> FozzyResult<TraceFile> {
+        let mut decisions = Vec::new();
+        let mut events = Vec::new();
+        while let Some(chunk) = self.next_chunk()? {
+            match chunk {
+                TraceChunk::Decisions(chunk) => decisions.extend(chunk),
+                TraceChunk::Events(chunk) => events.extend(chunk),
+            }
+        }
+        Ok(self.assemble(decisions, events))
+    }
+
+    fn assemble(&self, decisions: Vec<Decision>, events: Vec<TraceEvent>) -> TraceFile {
+        let footer = self
+            .footer
+            .clone()
+            .expect("footer is set once chunks are drained");
+        let header = self.header.clone();
+        TraceFile {
+            format: header.format,
+            version: header.version,
+            engine: header.engine,
+            mode: header.mode,
+            scenario_path: header.scenario_path,
+            scenario: header.scenario,
+            fuzz: header.fuzz,
+            explore: header.explore,
+            memory: footer.memory,
+            decisions,
+            events,
+            summary: footer.summary,
+            redaction: footer.redaction,
+            migrations: header.migrations,
+            counterfactual: header.counterfactual,
+            snapshots: footer.snapshots,
+            checksum: None,
+            signature: footer.signature,
+        }
+    }
+
+    fn read_frame(&mut self) -> FozzyResult<Option<(u8, Vec<u8>)>> {
+        let mut head = [0u8; 38];
+        match self.reader.read(&mut head[..1])? {
+            0 => return Ok(None),
+            _ => self
+                .reader
+                .read_exact(&mut head[1..])
+                .map_err(|_| FozzyError::Trace("truncated binary trace frame".to_string()))?,
+        }
+        let kind = head[0];
+        let flags = head[1];
+        let len = u32::from_le_bytes([head[2], head[3], head[4], head[5]]) as usize;
+        if len > MAX_FRAME_BYTES {
+            return Err(FozzyError::Trace(
+                "binary trace frame exceeds size limit".to_string(),
+            ));
+        }
+        let mut stored = vec![0u8; len];
+        self.reader
+            .read_exact(&mut stored)
+            .map_err(|_| FozzyError::Trace("truncated binary trace frame".to_string()))?;
+        let payload = if flags & FLAG_DEFLATE != 0 {
+            // Bound the inflated size too: a small compressed frame can expand without limit.
+            let mut out = Vec::new();
+            flate2::read::DeflateDecoder::new(stored.as_slice())
+                .take(MAX_FRAME_BYTES as u64 + 1)
+                .read_to_end(&mut out)
+                .map_err(|e| FozzyError::Trace(format!("corrupt compressed trace frame: {e}")))?;
+            if out.len() > MAX_FRAME_BYTES {
+                return Err(FozzyError::Trace(
+                    "binary trace frame exceeds size limit".to_string(),
+                ));
+            }
+            out
+        } else {
+            stored
+        };
+        let digest = blake3::hash(&payload);
+        if digest.as_bytes()[..] != head[6..] {
+            let frame = self.frames;
+            self.mismatch(&format!("binary trace frame {frame} digest mismatch"))?;
+        }
+        if kind != FRAME_FOOTER {
+            self.chain.update(&head[6..]);
+        }
+        self.frames += 1;
+        Ok(Some((kind, payload)))
+    }
+
+    fn mismatch(&mut self, message: &str) -> FozzyResult<()> {
+        self.digests_valid = false;
+        if self.strict {
+            return Err(FozzyError::Trace(message.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Binary trace written while a scenario run is in progress.
+///
+/// The engine hands over what it produced at every step boundary; `finish`
+/// appends the rest plus the footer once the finished trace is built. Dropping a
+/// recording that was never finished removes its temporary file.
+pub(crate) struct TraceRecording {
+    path: PathBuf,
+    compress: bool,
+    header: Option<TraceHeader>,
+    writer: Option<TraceStreamWriter>,
+    redactor: Option<Redactor>,
+    decisions: usize,
+    events: usize,
+    streamed: RecordingDigest,
+}
+
+/// Hash of the decisions and events already streamed, to check them against the
+/// finished trace.
+#[derive(Default)]
+struct RecordingDigest {
+    decisions: blake3::Hasher,
+    events: blake3::Hasher,
+}
+
+impl RecordingDigest {
+    fn of(decisions: &[Decision], events: &[TraceEvent]) -> FozzyResult<Self> {
+        let mut digest = Self::default();
+        for decision in decisions {
+            digest.decision(decision)?;
+        }
+        for event in events {
+            digest.event(event)?;
+        }
+        Ok(digest)
+    }
+
+    fn decision(&mut self, decision: &Decision) -> FozzyResult<()> {
+        self.decisions.update(&serde_json::to_vec(decision)?);
+        self.decisions.update(b"\n");
+        Ok(())
+    }
+
+    fn event(&mut self, event: &TraceEvent) -> FozzyResult<()> {
+        self.events.update(&serde_json::to_vec(event)?);
+        self.events.update(b"\n");
+        Ok(())
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.decisions.finalize() == other.decisions.finalize()
+            && self.events.finalize() == other.events.finalize()
+    }
+}
+
+impl TraceRecording {
+    /// A recording into `path`, or `None` for JSON, which is written once the run ends.
+    pub(crate) fn new(path: &Path, encoding: TraceEncoding) -> Option<Self> {
+        (encoding != TraceEncoding::Json).then(|| Self {
+            path: path.to_path_buf(),
+            compress: encoding.compress(),
+            header: None,
+            writer: None,
+            redactor: None,
+            decisions: 0,
+            events: 0,
+            streamed: RecordingDigest::default(),
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the header once the scenario is loaded; chunks are redacted with
+    /// `redactor` as they are streamed.
+    pub(crate) fn begin(
+        &mut self,
+        mode: RunMode,
+        scenario_path: &Path,
+        scenario: &ScenarioV1Steps,
+        redactor: Option<Redactor>,
+    ) -> FozzyResult<()> {
+        let mut header = TraceHeader::new(mode);
+        header.scenario_path = Some(scenario_path.to_string_lossy().to_string());
+        header.scenario = Some(match &redactor {
+            Some(redactor) => redactor.redact_scenario_headers(scenario, &mut 0)?,
+            None => scenario.clone(),
+        });
+        self.writer = Some(TraceStreamWriter::create(
+            &self.path,
+            &header,
+            self.compress,
+        )?);
+        self.header = Some(header);
+        self.redactor = redactor;
+        Ok(())
+    }
+
+    /// Streams the decisions and events produced since the previous call.
+    pub(crate) fn sync(
+        &mut self,
+        decisions: &[Decision],
+        events: &[TraceEvent],
+    ) -> FozzyResult<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        let mut redacted = 0u64;
+        for decision in decisions.get(self.decisions..).unwrap_or_default() {
+            let mut decision = decision.clone();
+            if let Some(redactor) = &self.redactor {
+                redactor.redact_decisions(std::slice::from_mut(&mut decision), &mut redacted);
+            }
+            self.streamed.decision(&decision)?;
+            writer.push_decision(decision)?;
+        }
+        for event in events.get(self.events..).unwrap_or_default() {
+            let mut event = event.clone();
+            if let Some(redactor) = &self.redactor {
+                redactor.redact_events(std::slice::from_mut(&mut event), &mut redacted);
+            }
+            self.streamed.event(&event)?;
+            writer.push_event(event)?;
+        }
+        self.decisions = decisions.len();
+        self.events = events.len();
+        Ok(())
+    }
+
+    /// Streams what `trace` holds beyond the last sync and writes the footer.
+    ///
+    /// `trace` is the finished form of the recorded run; it is signed first when
+    /// `FOZZY_TRACE_SIGN_KEY` names a key, as `TraceFile::write` does. When the run
+    /// rewrote decisions or events after they were streamed, the streamed file is
+    /// dropped and `trace` is written whole.
+    pub(crate) fn finish(mut self, trace: &TraceFile) -> FozzyResult<()> {
+        let (Some(header), Some(mut writer)) = (self.header.take(), self.writer.take()) else {
+            return Err(FozzyError::Trace(format!(
+                "trace recording for {} was never started",
+                self.path.display()
+            )));
+        };
+        let signed = trace.signed_from_env()?;
+        let trace = signed.as_ref().unwrap_or(trace);
+        let streamed_prefix_matches = self.decisions <= trace.decisions.len()
+            && self.events <= trace.events.len()
+            && serde_json::to_value(&header)?
+                == serde_json::to_value(TraceHeader::from_trace(trace))?
+            && self.streamed.matches(&RecordingDigest::of(
+                &trace.decisions[..self.decisions],
+                &trace.events[..self.events],
+            )?);
+        if !streamed_prefix_matches {
+            drop(writer);
+            let _lock = acquire_record_lock(&self.path)?;
+            return trace.write_binary(&self.path, self.compress);
+        }
+        for decision in &trace.decisions[self.decisions..] {
+            writer.push_decision(decision.clone())?;
+        }
+        for event in &trace.events[self.events..] {
+            writer.push_event(event.clone())?;
+        }
+        writer.set_redaction(trace.redaction.clone());
+        writer.set_snapshots(trace.snapshots.clone());
+        writer.set_signature(trace.signature.clone());
+        let _lock = acquire_record_lock(&self.path)?;
+        writer.finish(trace.memory.clone(), trace.summary.clone())
+    }
+}
+
+/// Everything in a binary trace except its decisions and events, gathered in one
+/// streaming pass so replay can start without loading the chunks.
+#[derive(Debug, Clone)]
+pub(crate) struct TraceOutline {
+    /// The trace with empty `decisions` and `events`.
+    pub(crate) trace: TraceFile,
+    pub(crate) decisions: usize,
+    pub(crate) scheduler_picks: bool,
+    pub(crate) replay_warnings: Vec<String>,
+    path: PathBuf,
+}
+
+impl TraceOutline {
+    pub(crate) fn read(path: &Path) -> FozzyResult<Self> {
+        let mut cursor = TraceCursor::open(path)?;
+        let mut scan = ReplayWarningScan::default();
+        let mut scheduler_picks = false;
+        while let Some(chunk) = cursor.next_chunk()? {
+            match chunk {
+                TraceChunk::Decisions(chunk) => {
+                    scheduler_picks |= chunk
+                        .iter()
+                        .any(|d| matches!(d, Decision::SchedulerPick { .. }));
+                    scan.observe_decisions(&chunk);
+                }
+                TraceChunk::Events(chunk) => scan.observe_events(&chunk),
+            }
+        }
+        let trace = cursor.assemble(Vec::new(), Vec::new());
+        validate_trace_header(&trace, path)?;
+        Ok(Self {
+            trace,
+            decisions: cursor.decisions as usize,
+            scheduler_picks,
+            replay_warnings: scan.warnings(),
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Opens the trace again to read its decisions one chunk at a time.
+    pub(crate) fn decision_stream(&self) -> FozzyResult<TraceDecisionStream> {
+        TraceDecisionStream::open(&self.path, self)
+    }
+}
+
+/// Decisions of a binary trace, read one chunk at a time as a replay consumes them.
+///
+/// One decision is always buffered ahead so it can be peeked. A read error ends
+/// the stream and is kept for `take_error`.
+pub(crate) struct TraceDecisionStream {
+    cursor: TraceCursor<BufReader<std::fs::File>>,
+    chunk: std::vec::IntoIter<Decision>,
+    ahead: Option<Decision>,
+    taken: Option<Decision>,
+    error: Option<FozzyError>,
+    total: usize,
+    scheduler_picks: bool,
+}
+
+impl TraceDecisionStream {
+    fn open(path: &Path, outline: &TraceOutline) -> FozzyResult<Self> {
+        let mut stream = Self {
+            cursor: TraceCursor::open(path)?,
+            chunk: Vec::new().into_iter(),
+            ahead: None,
+            taken: None,
+            error: None,
+            total: outline.decisions,
+            scheduler_picks: outline.scheduler_picks,
+        };
+        stream.ahead = stream.pull();
+        Ok(stream)
+    }
+
+    /// Decision count recorded in the trace footer.
+    pub(crate) fn total(&self) -> usize {
+        self.total
+    }
+
+    pub(crate) fn scheduler_picks(&self) -> bool {
+        self.scheduler_picks
+    }
+
+    pub(crate) fn peek(&self) -> Option<&Decision> {
+        self.ahead.as_ref()
+    }
+
+    pub(crate) fn next(&mut self) -> Option<&Decision> {
+        self.taken = self.ahead.take();
+        if self.taken.is_some() {
+            self.ahead = self.pull();
+        }
+        self.taken.as_ref()
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<FozzyError> {
+        self.error.take()
+    }
+
+    fn pull(&mut self) -> Option<Decision> {
+        loop {
+            if let Some(decision) = self.chunk.next() {
+                return Some(decision);
+            }
+            match self.cursor.next_chunk() {
+                Ok(Some(TraceChunk::Decisions(chunk))) => self.chunk = chunk.into_iter(),
+                Ok(Some(TraceChunk::Events(_))) => {}
+                Ok(None) => return None,
+                Err(err) => {
+                    self.error = Some(err);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl TraceFile {
+    pub fn write_binary(&self, path: &Path, compress: bool) -> FozzyResult<()> {
+        let mut writer = TraceStreamWriter::create(path, &TraceHeader::from_trace(self), compress)?;
+        for decision in &self.decisions {
+            writer.push_decision(decision.clone())?;
+        }
+        for event in &self.events {
+            writer.push_event(event.clone())?;
+        }
+        writer.set_redaction(self.redaction.clone());
+        writer.set_snapshots(self.snapshots.clone());
+        writer.set_signature(self.signature.clone());
+        writer.finish(self.memory.clone(), self.summary.clone())
+    }
+}
+
+pub fn is_binary_trace_file(path: &Path) -> bool {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic).is_ok() && &magic == BINARY_TRACE_MAGIC
+}
+
+fn decode<T: serde::de::DeserializeOwned>(payload: &[u8]) -> FozzyResult<T> {
+    ciborium::from_reader(payload)
+        .map_err(|e| FozzyError::Trace(format!("failed to decode binary trace frame: {e}")))
+}
+
+fn with_trace_path(err: FozzyError, path: &Path) -> FozzyError {
+    match err {
+        FozzyError::Trace(msg) => FozzyError::Trace(format!("{msg} ({})", path.display())),
+        other => other,
+    }
+}
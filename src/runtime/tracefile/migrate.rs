@@ -0,0 +1,417 @@
+//! Trace schema migrations.
+//!
+//! Older traces are upgraded one schema version at a time on the raw JSON value,
+//! so a step only has to know the shape it reads and the shape it writes. Each
+//! applied step is recorded in the trace's `migrations` lineage.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use std::path::{Path, PathBuf};
+
+use super::{
+    CURRENT_TRACE_VERSION, TRACE_FORMAT, TraceEncoding, TraceFile, is_binary_trace_file,
+    is_trace_path, trace_replay_warnings,
+};
+use crate::{FozzyError, FozzyResult};
+
+/// One applied schema migration, recorded in upgraded traces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceMigration {
+    pub from: u32,
+    pub to: u32,
+    /// Fozzy version that performed the upgrade.
+    pub tool: String,
+    /// Checksum carried by the trace before it was upgraded, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_checksum: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceUpgradeReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub input: String,
+    pub out: String,
+    #[serde(rename = "fromVersion")]
+    pub from_version: u32,
+    #[serde(rename = "toVersion")]
+    pub to_version: u32,
+    pub upgraded: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<TraceMigration>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceUpgradeCheckReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    #[serde(rename = "currentVersion")]
+    pub current_version: u32,
+    pub current: usize,
+    pub upgradable: usize,
+    pub unsupported: usize,
+    pub traces: Vec<TraceUpgradeCheck>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceUpgradeStatus {
+    /// Already on the current schema.
+    Current,
+    /// Replayable after `fozzy trace upgrade`.
+    Upgradable,
+    /// Cannot be read or migrated by this build.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceUpgradeCheck {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    pub status: TraceUpgradeStatus,
+    pub replayable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+type MigrationFn = fn(&mut Map<String, Value>) -> Vec<String>;
+
+/// Migration from `from` to `from + 1`.
+struct MigrationStep {
+    from: u32,
+    apply: MigrationFn,
+}
+
+const MIGRATION_STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        apply: migrate_v1_to_v2,
+    },
+    MigrationStep {
+        from: 2,
+        apply: migrate_v2_to_v3,
+    },
+    MigrationStep {
+        from: 3,
+        apply: migrate_v3_to_v4,
+    },
+];
+
+/// Event fields renamed in v2, keyed by event name.
+const V2_EVENT_FIELD_RENAMES: &[(&str, &str, &str)] = &[
+    ("sched_pick", "step", "step_index"),
+    ("sched_pick", "kind", "step_kind"),
+    ("span_start", "step", "step_index"),
+    ("span_start", "kind", "step_kind"),
+];
+
+const TIMED_HOST_DECISIONS: &[&str] = &[
+    "fs_write",
+    "fs_read_assert",
+    "fs_snapshot",
+    "fs_restore",
+    "http_request",
+    "http_request_timeout",
+    "proc_spawn",
+    "proc_spawn_timeout",
+];
+
+const PROC_DECISIONS: &[&str] = &["proc_spawn", "proc_spawn_timeout"];
+
+/// v2 replaced per-step markers with scheduler picks and renamed span fields.
+fn migrate_v1_to_v2(trace: &mut Map<String, Value>) -> Vec<String> {
+    let mut changes = Vec::new();
+    if let Some(decisions) = trace.get_mut("decisions").and_then(Value::as_array_mut) {
+        let has_picks = decisions
+            .iter()
+            .any(|d| decision_kind(d) == Some("scheduler_pick"));
+        if has_picks {
+            let before = decisions.len();
+            decisions.retain(|d| decision_kind(d) != Some("step"));
+            let dropped = before - decisions.len();
+            if dropped > 0 {
+                changes.push(format!(
+                    "dropped {dropped} step decisions superseded by scheduler picks"
+                ));
+            }
+        }
+    }
+    let mut renamed = 0usize;
+    if let Some(events) = trace.get_mut("events").and_then(Value::as_array_mut) {
+        for event in events.iter_mut().filter_map(Value::as_object_mut) {
+            let name = event
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let Some(fields) = event.get_mut("fields").and_then(Value::as_object_mut) else {
+                continue;
+            };
+            for (_, old, new) in V2_EVENT_FIELD_RENAMES
+                .iter()
+                .filter(|(event_name, _, _)| *event_name == name)
+            {
+                if fields.contains_key(*new) {
+                    continue;
+                }
+                if let Some(value) = fields.remove(*old) {
+                    fields.insert((*new).to_string(), value);
+                    renamed += 1;
+                }
+            }
+        }
+    }
+    if renamed > 0 {
+        changes.push(format!("renamed {renamed} legacy event fields"));
+    }
+    changes
+}
+
+/// v3 timed host decisions and added nanosecond run duration.
+fn migrate_v2_to_v3(trace: &mut Map<String, Value>) -> Vec<String> {
+    let mut changes = Vec::new();
+    let filled = fill_decision_fields(trace, TIMED_HOST_DECISIONS, &["duration_ms"]);
+    if filled > 0 {
+        changes.push(format!("added duration_ms to {filled} host decisions"));
+    }
+    if let Some(summary) = trace.get_mut("summary").and_then(Value::as_object_mut)
+        && !summary.contains_key("durationNs")
+    {
+        let ms = summary
+            .get("durationMs")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        summary.insert(
+            "durationNs".to_string(),
+            Value::from(ms.saturating_mul(1_000_000)),
+        );
+        changes.push("derived summary.durationNs from durationMs".to_string());
+    }
+    changes
+}
+
+/// v4 sampled child-process memory on proc decisions.
+fn migrate_v3_to_v4(trace: &mut Map<String, Value>) -> Vec<String> {
+    let mut changes = Vec::new();
+    let filled = fill_decision_fields(
+        trace,
+        PROC_DECISIONS,
+        &["peak_rss_bytes", "rss_sample_count"],
+    );
+    if filled > 0 {
+        changes.push(format!(
+            "added rss sampling fields to {filled} proc decisions"
+        ));
+    }
+    if let Some(summary) = trace.get_mut("summary").and_then(Value::as_object_mut)
+        && summary.get("memory").is_some_and(Value::is_null)
+    {
+        summary.remove("memory");
+        changes.push("dropped null summary.memory".to_string());
+    }
+    changes
+}
+
+fn decision_kind(decision: &Value) -> Option<&str> {
+    decision.get("kind").and_then(Value::as_str)
+}
+
+fn fill_decision_fields(trace: &mut Map<String, Value>, kinds: &[&str], fields: &[&str]) -> usize {
+    let Some(decisions) = trace.get_mut("decisions").and_then(Value::as_array_mut) else {
+        return 0;
+    };
+    let mut filled = 0usize;
+    for decision in decisions.iter_mut() {
+        if !decision_kind(decision).is_some_and(|kind| kinds.contains(&kind)) {
+            continue;
+        }
+        let Some(obj) = decision.as_object_mut() else {
+            continue;
+        };
+        let mut touched = false;
+        for field in fields {
+            if !obj.contains_key(*field) {
+                obj.insert((*field).to_string(), Value::from(0u64));
+                touched = true;
+            }
+        }
+        if touched {
+            filled += 1;
+        }
+    }
+    filled
+}
+
+/// Applies every migration step between the trace's version and the current one.
+pub fn upgrade_trace_value(value: Value) -> FozzyResult<(TraceFile, Vec<TraceMigration>)> {
+    let Value::Object(mut map) = value else {
+        return Err(FozzyError::Trace("trace is not a JSON object".to_string()));
+    };
+    match map.get("format").and_then(Value::as_str) {
+        Some(TRACE_FORMAT) => {}
+        other => {
+            return Err(FozzyError::Trace(format!(
+                "unsupported trace format: got {}, expected {TRACE_FORMAT}",
+                other.unwrap_or("<missing>")
+            )));
+        }
+    }
+    let version = map
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| FozzyError::Trace("trace is missing a schema version".to_string()))?;
+    if !(1..=CURRENT_TRACE_VERSION).contains(&version) {
+        return Err(FozzyError::Trace(format!(
+            "no migration path from trace schema v{version} (supported: 1..={CURRENT_TRACE_VERSION})"
+        )));
+    }
+    let source_checksum = map
+        .remove("checksum")
+        .and_then(|v| v.as_str().map(str::to_string));
+    let tool = crate::version_info().version;
+    let mut applied = Vec::new();
+    for step in MIGRATION_STEPS.iter().filter(|s| s.from >= version) {
+        let changes = (step.apply)(&mut map);
+        map.insert("version".to_string(), Value::from(step.from + 1));
+        applied.push(TraceMigration {
+            from: step.from,
+            to: step.from + 1,
+            tool: tool.clone(),
+            source_checksum: source_checksum.clone(),
+            changes,
+        });
+    }
+    let mut trace: TraceFile = serde_json::from_value(Value::Object(map)).map_err(|e| {
+        FozzyError::Trace(format!(
+            "trace v{version} does not match schema v{CURRENT_TRACE_VERSION} after migration: {e}"
+        ))
+    })?;
+    trace.migrations.extend(applied.iter().cloned());
+    if !applied.is_empty() {
+        // A signature over the old schema cannot cover the migrated trace.
+        trace.signature = None;
+    }
+    Ok((trace, applied))
+}
+
+/// Upgrades a trace to the current schema and writes it to `out` with a fresh checksum.
+pub fn upgrade_trace_file(input: &Path, out: &Path) -> FozzyResult<TraceUpgradeReport> {
+    let (trace, applied) = load_upgraded(input)?;
+    trace.write(out, TraceEncoding::of_file(input))?;
+    let warnings = trace_replay_warnings(&trace);
+    Ok(TraceUpgradeReport {
+        schema_version: "fozzy.trace_upgrade.v1".to_string(),
+        input: input.display().to_string(),
+        out: out.display().to_string(),
+        from_version: applied.first().map(|m| m.from).unwrap_or(trace.version),
+        to_version: trace.version,
+        upgraded: !applied.is_empty(),
+        migrations: applied,
+        warnings,
+    })
+}
+
+fn load_upgraded(path: &Path) -> FozzyResult<(TraceFile, Vec<TraceMigration>)> {
+    if is_binary_trace_file(path) {
+        // Binary traces postdate every migration step.
+        return Ok((TraceFile::read_json(path)?, Vec::new()));
+    }
+    let bytes = std::fs::read(path)?;
+    let value: Value = serde_json::from_slice(&bytes)
+        .map_err(|e| FozzyError::Trace(format!("failed to parse trace {}: {e}", path.display())))?;
+    let is_current = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .is_some_and(|v| v == u64::from(CURRENT_TRACE_VERSION));
+    if is_current {
+        return Ok((TraceFile::read_json(path)?, Vec::new()));
+    }
+    upgrade_trace_value(value).map_err(|e| match e {
+        FozzyError::Trace(msg) => FozzyError::Trace(format!("{}: {msg}", path.display())),
+        other => other,
+    })
+}
+
+/// Reports which traces under `paths` replay as-is, after upgrade, or not at all.
+pub fn check_trace_upgrades(paths: &[PathBuf]) -> FozzyResult<TraceUpgradeCheckReport> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_trace_files(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let traces: Vec<TraceUpgradeCheck> = files.iter().map(|p| check_one(p)).collect();
+    let count = |status| traces.iter().filter(|t| t.status == status).count();
+    Ok(TraceUpgradeCheckReport {
+        schema_version: "fozzy.trace_upgrade_check.v1".to_string(),
+        current_version: CURRENT_TRACE_VERSION,
+        current: count(TraceUpgradeStatus::Current),
+        upgradable: count(TraceUpgradeStatus::Upgradable),
+        unsupported: count(TraceUpgradeStatus::Unsupported),
+        traces,
+    })
+}
+
+fn check_one(path: &Path) -> TraceUpgradeCheck {
+    let version = if is_binary_trace_file(path) {
+        None
+    } else {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .and_then(|v| v.get("version").and_then(Value::as_u64))
+            .and_then(|v| u32::try_from(v).ok())
+    };
+    let (status, reason, warnings) = match load_upgraded(path) {
+        Ok((trace, applied)) if applied.is_empty() => (
+            TraceUpgradeStatus::Current,
+            None,
+            trace_replay_warnings(&trace),
+        ),
+        Ok((trace, _)) => (
+            TraceUpgradeStatus::Upgradable,
+            None,
+            trace_replay_warnings(&trace),
+        ),
+        Err(err) => (
+            TraceUpgradeStatus::Unsupported,
+            Some(err.to_string()),
+            Vec::new(),
+        ),
+    };
+    TraceUpgradeCheck {
+        path: path.display().to_string(),
+        version: version
+            .or_else(|| (status == TraceUpgradeStatus::Current).then_some(CURRENT_TRACE_VERSION)),
+        status,
+        replayable: status != TraceUpgradeStatus::Unsupported,
+        reason,
+        warnings,
+    }
+}
+
+fn collect_trace_files(dir: &Path, out: &mut Vec<PathBuf>) -> FozzyResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_trace_files(&path, out)?;
+        } else if is_trace_path(&path) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
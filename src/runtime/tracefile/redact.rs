@@ -0,0 +1,616 @@
+//! Secret redaction for recorded traces.
+//!
+//! Redacted values become `[redacted:<hash>]` placeholders: a hash of the secret keyed
+//! by a per-trace salt and the optional `[redact] key_file` secret. Equal secrets map to
+//! equal placeholders within a trace, and replay compares expectations by running them
+//! through the rules and salt recorded in the trace.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use rand_core::RngCore as _;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::{TraceEncoding, TraceEvent, TraceFile};
+use crate::{Config, Decision, Finding, FozzyError, FozzyResult};
+
+const PLACEHOLDER_PREFIX: &str = "[redacted:";
+const PLACEHOLDER_HASH_LEN: usize = 12;
+const TRUNCATED_SUFFIX: &str = "...[truncated]";
+const PLACEHOLDER_KEY_CONTEXT: &str = "fozzy trace redaction placeholders v1";
+
+/// Redaction rules, as configured and as recorded in redacted traces.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactRules {
+    /// Header names whose values are always redacted (case-insensitive).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<String>,
+    /// JSON paths (`$.token`, `$.items[*].secret`, `$..password`) redacted in JSON payloads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub json_paths: Vec<String>,
+    /// Regexes redacted in any recorded text; capture group 1, when present, limits the span.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+}
+
+impl RedactRules {
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.json_paths.is_empty() && self.patterns.is_empty()
+    }
+
+    fn merge(&mut self, other: &RedactRules) {
+        for (ours, theirs) in [
+            (&mut self.headers, &other.headers),
+            (&mut self.json_paths, &other.json_paths),
+            (&mut self.patterns, &other.patterns),
+        ] {
+            for rule in theirs {
+                if !ours.contains(rule) {
+                    ours.push(rule.clone());
+                }
+            }
+        }
+    }
+}
+
+/// `[redact]` table in fozzy.toml.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactConfig {
+    /// Redact traces and run artifacts as they are recorded.
+    #[serde(default)]
+    pub on_record: bool,
+    /// File holding a secret mixed into placeholder hashes. Without it anyone holding
+    /// the trace can confirm a guessed secret; replay needs the same key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<PathBuf>,
+    #[serde(flatten)]
+    pub rules: RedactRules,
+}
+
+impl RedactConfig {
+    /// Contents of `key_file`, when configured.
+    pub fn key(&self) -> FozzyResult<Option<Vec<u8>>> {
+        self.key_file
+            .as_deref()
+            .map(|path| {
+                std::fs::read(path).map_err(|e| {
+                    FozzyError::Config(format!(
+                        "failed to read redact key_file {}: {e}",
+                        path.display()
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Marks a trace as redacted and keeps the rules replay needs to compare expectations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceRedaction {
+    pub rules: RedactRules,
+    /// Random per-trace salt of the placeholder hashes; empty in traces redacted before
+    /// salting, whose placeholders are unkeyed.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub salt: String,
+    pub redacted_values: u64,
+    /// Fozzy version that applied the most recent redaction.
+    pub tool: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceRedactReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub input: String,
+    pub out: String,
+    #[serde(rename = "redactedValues")]
+    pub redacted_values: u64,
+    pub rules: RedactRules,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+    Descend(String),
+}
+
+/// Compiled redaction rules.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    rules: RedactRules,
+    salt: String,
+    placeholder_key: Option<[u8; 32]>,
+    headers: Vec<String>,
+    json_paths: Vec<Vec<PathSegment>>,
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    /// Redactor for a new trace, with a fresh salt and the optional `[redact]` key.
+    pub fn new(rules: &RedactRules, key: Option<&[u8]>) -> FozzyResult<Self> {
+        let mut salt = [0u8; 16];
+        rand_core::OsRng.fill_bytes(&mut salt);
+        Self::with_salt(rules, super::bytes_to_hex(&salt), key)
+    }
+
+    /// Redactor that reproduces the placeholders of an already redacted trace.
+    pub fn for_trace(redaction: &TraceRedaction, key: Option<&[u8]>) -> FozzyResult<Self> {
+        Self::with_salt(&redaction.rules, redaction.salt.clone(), key)
+    }
+
+    fn with_salt(rules: &RedactRules, salt: String, key: Option<&[u8]>) -> FozzyResult<Self> {
+        let json_paths = rules
+            .json_paths
+            .iter()
+            .map(|p| parse_json_path(p))
+            .collect::<FozzyResult<Vec<_>>>()?;
+        let patterns = rules
+            .patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p)
+                    .map_err(|e| FozzyError::Config(format!("invalid redact pattern {p:?}: {e}")))
+            })
+            .collect::<FozzyResult<Vec<_>>>()?;
+        Ok(Self {
+            rules: rules.clone(),
+            placeholder_key: placeholder_key(&salt, key),
+            salt,
+            headers: rules
+                .headers
+                .iter()
+                .map(|h| h.to_ascii_lowercase())
+                .collect(),
+            json_paths,
+            patterns,
+        })
+    }
+
+    /// Redactor for record-time redaction, when `[redact] on_record` is set.
+    pub fn for_record(config: &Config) -> FozzyResult<Option<Self>> {
+        if !config.redact.on_record || config.redact.rules.is_empty() {
+            return Ok(None);
+        }
+        Self::new(&config.redact.rules, config.redact.key()?.as_deref()).map(Some)
+    }
+
+    pub fn rules(&self) -> &RedactRules {
+        &self.rules
+    }
+
+    pub fn redact_text(&self, text: &str, count: &mut u64) -> String {
+        let mut out = text.to_string();
+        if !self.json_paths.is_empty() {
+            if out.ends_with(TRUNCATED_SUFFIX) && looks_like_json(&out) {
+                // Truncated JSON cannot be parsed, so it goes wholesale.
+                *count += 1;
+                return self.placeholder(text);
+            }
+            if let Ok(mut value) = serde_json::from_str::<Value>(&out)
+                && (value.is_object() || value.is_array())
+            {
+                let before = *count;
+                self.redact_json_paths(&mut value, count);
+                if *count > before {
+                    out = serde_json::to_string(&value).unwrap_or(out);
+                }
+            }
+        }
+        for pattern in &self.patterns {
+            out = redact_pattern(pattern, &out, self.placeholder_key.as_ref(), count);
+        }
+        out
+    }
+
+    fn placeholder(&self, secret: &str) -> String {
+        placeholder(self.placeholder_key.as_ref(), secret)
+    }
+
+    pub fn redact_header(&self, name: &str, value: &str, count: &mut u64) -> String {
+        if self.headers.iter().any(|h| h.eq_ignore_ascii_case(name)) {
+            if is_placeholder(value) {
+                return value.to_string();
+            }
+            *count += 1;
+            return self.placeholder(value);
+        }
+        self.redact_text(value, count)
+    }
+
+    pub fn redact_headers(&self, headers: &mut BTreeMap<String, String>, count: &mut u64) {
+        for (name, value) in headers.iter_mut() {
+            *value = self.redact_header(name, value, count);
+        }
+    }
+
+    /// Applies JSON paths to `value` and patterns to every string inside it.
+    pub fn redact_json(&self, value: &mut Value, count: &mut u64) {
+        self.redact_json_paths(value, count);
+        self.redact_json_strings(value, count);
+    }
+
+    fn redact_json_paths(&self, value: &mut Value, count: &mut u64) {
+        for path in &self.json_paths {
+            redact_at_path(value, path, self.placeholder_key.as_ref(), count);
+        }
+    }
+
+    fn redact_json_strings(&self, value: &mut Value, count: &mut u64) {
+        match value {
+            Value::String(s) => *s = self.redact_text(s, count),
+            Value::Array(items) => {
+                for item in items {
+                    self.redact_json_strings(item, count);
+                }
+            }
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    match item {
+                        Value::Object(headers) if key == "headers" => {
+                            for (name, header) in headers.iter_mut() {
+                                if let Value::String(s) = header {
+                                    *s = self.redact_header(name, s, count);
+                                }
+                            }
+                        }
+                        _ => self.redact_json_strings(item, count),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn redact_decisions(&self, decisions: &mut [Decision], count: &mut u64) {
+        for decision in decisions {
+            match decision {
+                Decision::HttpRequest { headers, body, .. } => {
+                    self.redact_headers(headers, count);
+                    *body = self.redact_text(body, count);
+                }
+                Decision::ProcSpawn { stdout, stderr, .. }
+                | Decision::ProcSpawnTimeout { stdout, stderr, .. } => {
+                    *stdout = self.redact_text(stdout, count);
+                    *stderr = self.redact_text(stderr, count);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn redact_events(&self, events: &mut [TraceEvent], count: &mut u64) {
+        for event in events {
+            for value in event.fields.values_mut() {
+                self.redact_json_strings(value, count);
+            }
+        }
+    }
+
+    pub fn redact_findings(&self, findings: &mut [Finding], count: &mut u64) {
+        for finding in findings {
+            finding.message = self.redact_text(&finding.message, count);
+            if let Some(details) = finding
+                .location
+                .as_mut()
+                .and_then(|location| location.details.as_mut())
+            {
+                self.redact_json_strings(details, count);
+            }
+        }
+    }
+
+    /// Redacts decisions, events, findings and embedded scenario headers in place.
+    pub fn redact_trace(&self, trace: &mut TraceFile) -> FozzyResult<u64> {
+        let mut count = 0u64;
+        self.redact_decisions(&mut trace.decisions, &mut count);
+        self.redact_events(&mut trace.events, &mut count);
+        self.redact_findings(&mut trace.summary.findings, &mut count);
+        if let Some(scenario) = trace.scenario.as_mut() {
+            *scenario = self.redact_scenario_headers(scenario, &mut count)?;
+        }
+        self.mark(&mut trace.redaction, count);
+        Ok(count)
+    }
+
+    /// Redacts a finished run before any trace or artifact is written from it.
+    pub(crate) fn redact_run(&self, run: &mut crate::engine::ScenarioRun) -> FozzyResult<()> {
+        let mut count = 0u64;
+        self.redact_decisions(&mut run.decisions.decisions, &mut count);
+        self.redact_events(&mut run.events, &mut count);
+        self.redact_findings(&mut run.findings, &mut count);
+        run.scenario_embedded = self.redact_scenario_headers(&run.scenario_embedded, &mut count)?;
+        self.mark(&mut run.redaction, count);
+        Ok(())
+    }
+
+    /// Merges this redactor's rules into existing redaction metadata.
+    pub fn mark(&self, redaction: &mut Option<TraceRedaction>, count: u64) {
+        let meta = redaction.get_or_insert_with(|| TraceRedaction {
+            rules: RedactRules::default(),
+            salt: String::new(),
+            redacted_values: 0,
+            tool: String::new(),
+        });
+        meta.rules.merge(&self.rules);
+        meta.salt = self.salt.clone();
+        meta.redacted_values = meta.redacted_values.saturating_add(count);
+        meta.tool = crate::version_info().version;
+    }
+
+    pub(crate) fn redact_scenario_headers(
+        &self,
+        scenario: &crate::ScenarioV1Steps,
+        count: &mut u64,
+    ) -> FozzyResult<crate::ScenarioV1Steps> {
+        let mut value = serde_json::to_value(scenario)?;
+        self.redact_scenario_value(&mut value, count);
+        Ok(serde_json::from_value(value)?)
+    }
+
+    // Only header maps are touched: other scenario strings are expectations that
+    // replay redacts itself before comparing.
+    fn redact_scenario_value(&self, value: &mut Value, count: &mut u64) {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    self.redact_scenario_value(item, count);
+                }
+            }
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    match item {
+                        Value::Object(headers) if key.ends_with("headers") => {
+                            for (name, header) in headers.iter_mut() {
+                                if let Value::String(s) = header {
+                                    *s = self.redact_header(name, s, count);
+                                }
+                            }
+                        }
+                        _ => self.redact_scenario_value(item, count),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// True when `expected` and `got` agree once both are redacted.
+    pub fn text_matches(&self, expected: &str, got: &str) -> bool {
+        let mut count = 0;
+        self.redact_text(expected, &mut count) == self.redact_text(got, &mut count)
+    }
+
+    pub fn json_matches(&self, expected: &Value, got: &Value) -> bool {
+        let mut count = 0;
+        let mut expected = expected.clone();
+        let mut got = got.clone();
+        self.redact_json(&mut expected, &mut count);
+        self.redact_json(&mut got, &mut count);
+        expected == got
+    }
+
+    pub fn header_matches(&self, name: &str, expected: &str, got: &str) -> bool {
+        let mut count = 0;
+        self.redact_header(name, expected, &mut count) == self.redact_header(name, got, &mut count)
+    }
+}
+
+/// Redacts a trace file with the configured rules and writes it with a fresh checksum.
+pub fn redact_trace_file(
+    config: &Config,
+    input: &Path,
+    out: &Path,
+) -> FozzyResult<TraceRedactReport> {
+    if config.redact.rules.is_empty() {
+        return Err(FozzyError::Config(
+            "no redaction rules configured; add headers, json_paths or patterns under [redact] in fozzy.toml"
+                .to_string(),
+        ));
+    }
+    let key = config.redact.key()?;
+    let mut trace = TraceFile::read_json(input)?;
+    // Keep an already redacted trace's salt so its placeholders stay comparable.
+    let redactor = match &trace.redaction {
+        Some(existing) => {
+            Redactor::with_salt(&config.redact.rules, existing.salt.clone(), key.as_deref())?
+        }
+        None => Redactor::new(&config.redact.rules, key.as_deref())?,
+    };
+    let redacted_values = redactor.redact_trace(&mut trace)?;
+    // The old signature no longer covers the redacted content, and snapshot state
+    // holds the unredacted values.
+    trace.signature = None;
+    trace.snapshots.clear();
+    trace.write(out, TraceEncoding::of_file(input))?;
+    Ok(TraceRedactReport {
+        schema_version: "fozzy.trace_redact.v1".to_string(),
+        input: input.display().to_string(),
+        out: out.display().to_string(),
+        redacted_values,
+        rules: trace
+            .redaction
+            .map(|r| r.rules)
+            .unwrap_or_else(|| redactor.rules.clone()),
+    })
+}
+
+/// Hash key for placeholders; `None` only for traces redacted before salting.
+fn placeholder_key(salt: &str, key: Option<&[u8]>) -> Option<[u8; 32]> {
+    if salt.is_empty() && key.is_none() {
+        return None;
+    }
+    let mut hasher = blake3::Hasher::new_derive_key(PLACEHOLDER_KEY_CONTEXT);
+    hasher.update(salt.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(key.unwrap_or_default());
+    Some(*hasher.finalize().as_bytes())
+}
+
+fn placeholder(key: Option<&[u8; 32]>, secret: &str) -> String {
+    let hash = match key {
+        Some(key) => blake3::keyed_hash(key, secret.as_bytes()),
+        None => blake3::hash(format!("fozzy-redact:{secret}").as_bytes()),
+    }
+    .to_hex();
+    format!("{PLACEHOLDER_PREFIX}{}]", &hash[..PLACEHOLDER_HASH_LEN])
+}
+
+fn looks_like_json(text: &str) -> bool {
+    text.trim_start().starts_with(['{', '['])
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\[redacted:[0-9a-f]{12}\]").expect("placeholder regex"))
+}
+
+fn is_placeholder(value: &str) -> bool {
+    placeholder_regex()
+        .find(value)
+        .is_some_and(|m| m.start() == 0 && m.end() == value.len())
+}
+
+fn redact_pattern(pattern: &Regex, text: &str, key: Option<&[u8; 32]>, count: &mut u64) -> String {
+    let existing: Vec<(usize, usize)> = placeholder_regex()
+        .find_iter(text)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0usize;
+    for caps in pattern.captures_iter(text) {
+        let Some(span) = caps.get(1).or_else(|| caps.get(0)) else {
+            continue;
+        };
+        if span.start() < last
+            || span.is_empty()
+            || existing
+                .iter()
+                .any(|(s, e)| span.start() < *e && *s < span.end())
+        {
+            continue;
+        }
+        out.push_str(&text[last..span.start()]);
+        out.push_str(&placeholder(key, span.as_str()));
+        last = span.end();
+        *count += 1;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn redact_at_path(
+    value: &mut Value,
+    path: &[PathSegment],
+    hash_key: Option<&[u8; 32]>,
+    count: &mut u64,
+) {
+    let Some((head, rest)) = path.split_first() else {
+        let secret = match &*value {
+            Value::String(s) if is_placeholder(s) => return,
+            Value::String(s) => s.clone(),
+            Value::Null => return,
+            other => other.to_string(),
+        };
+        *value = Value::String(placeholder(hash_key, &secret));
+        *count += 1;
+        return;
+    };
+    match head {
+        PathSegment::Key(key) => {
+            if let Some(child) = value.get_mut(key.as_str()) {
+                redact_at_path(child, rest, hash_key, count);
+            }
+        }
+        PathSegment::Index(idx) => {
+            if let Some(child) = value.get_mut(*idx) {
+                redact_at_path(child, rest, hash_key, count);
+            }
+        }
+        PathSegment::Wildcard => match value {
+            Value::Array(items) => {
+                for item in items {
+                    redact_at_path(item, rest, hash_key, count);
+                }
+            }
+            Value::Object(map) => {
+                for item in map.values_mut() {
+                    redact_at_path(item, rest, hash_key, count);
+                }
+            }
+            _ => {}
+        },
+        PathSegment::Descend(key) => {
+            if let Some(child) = value.get_mut(key.as_str()) {
+                redact_at_path(child, rest, hash_key, count);
+            }
+            match value {
+                Value::Array(items) => {
+                    for item in items {
+                        redact_at_path(item, path, hash_key, count);
+                    }
+                }
+                Value::Object(map) => {
+                    for (name, item) in map.iter_mut() {
+                        if name != key {
+                            redact_at_path(item, path, hash_key, count);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn parse_json_path(path: &str) -> FozzyResult<Vec<PathSegment>> {
+    let invalid =
+        |why: &str| FozzyError::Config(format!("invalid redact json path {path:?}: {why}"));
+    let Some(mut rest) = path.strip_prefix('$') else {
+        return Err(invalid("must start with `$`"));
+    };
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(invalid("`..` must be followed by a key"));
+            }
+            segments.push(PathSegment::Descend(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            match &after[..end] {
+                "" => return Err(invalid("empty key")),
+                "*" => segments.push(PathSegment::Wildcard),
+                key => segments.push(PathSegment::Key(key.to_string())),
+            }
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let Some(end) = after.find(']') else {
+                return Err(invalid("unclosed `[`"));
+            };
+            let inner = after[..end].trim();
+            if inner == "*" {
+                segments.push(PathSegment::Wildcard);
+            } else if let Ok(idx) = inner.parse::<usize>() {
+                segments.push(PathSegment::Index(idx));
+            } else {
+                let key = inner.trim_matches(|c| c == '\'' || c == '"');
+                segments.push(PathSegment::Key(key.to_string()));
+            }
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid("expected `.` or `[`"));
+        }
+    }
+    if segments.is_empty() {
+        return Err(invalid("path selects the whole document"));
+    }
+    Ok(segments)
+}
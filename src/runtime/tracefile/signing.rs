@@ -0,0 +1,410 @@
+//! Trace signing and provenance attestation.
+//!
+//! A signature covers the canonical trace (no checksum, no signature) plus a
+//! provenance block, hashed under a dedicated blake3 context. Keyed signatures
+//! need the shared key to verify; ed25519 signatures embed the public key and can
+//! additionally be pinned to a trusted key.
+
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+
+use super::{TraceEncoding, TraceFile, bytes_to_hex};
+use crate::{FozzyError, FozzyResult, VersionInfo};
+
+const SIGNING_CONTEXT: &str = "fozzy trace signature v1";
+const KEYED_KEY_CONTEXT: &str = "fozzy trace signing key v1";
+const KEY_ID_CONTEXT: &str = "fozzy trace key id v1";
+const KEY_ID_LEN: usize = 16;
+
+/// Environment variable naming a key file used to sign every trace as it is written.
+pub const TRACE_SIGN_KEY_ENV: &str = "FOZZY_TRACE_SIGN_KEY";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceSignatureScheme {
+    Blake3Keyed,
+    Ed25519,
+}
+
+impl TraceSignatureScheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Blake3Keyed => "blake3_keyed",
+            Self::Ed25519 => "ed25519",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostFingerprint {
+    pub os: String,
+    pub arch: String,
+    /// Hash of the host name, so traces do not leak it verbatim.
+    pub host_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceProvenance {
+    pub engine: VersionInfo,
+    pub command_line: Vec<String>,
+    pub host: HostFingerprint,
+    pub signed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSignature {
+    pub scheme: TraceSignatureScheme,
+    pub key_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    pub provenance: TraceProvenance,
+    pub value: String,
+}
+
+/// Signature state reported by `trace verify` and `ci --require-signed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSignatureStatus {
+    pub scheme: TraceSignatureScheme,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    /// `None` when a keyed signature is checked without its key.
+    pub valid: Option<bool>,
+    /// The signature was made by the key passed for verification.
+    pub trusted: bool,
+    pub provenance: TraceProvenance,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSignReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub input: String,
+    pub out: String,
+    pub signature: TraceSignatureStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceKeygenReport {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: String,
+    pub scheme: TraceSignatureScheme,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    #[serde(rename = "secretKey")]
+    pub secret_key: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Ed25519KeyFile {
+    scheme: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secret_key: Option<String>,
+    public_key: String,
+}
+
+/// Key material loaded from a key file.
+///
+/// Files written by `fozzy trace keygen` hold ed25519 keys; any other file is
+/// treated as a shared secret for keyed blake3.
+#[derive(Clone)]
+pub enum TraceSigningKey {
+    Keyed([u8; 32]),
+    Ed25519Secret(SigningKey),
+    Ed25519Public(VerifyingKey),
+}
+
+impl std::fmt::Debug for TraceSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TraceSigningKey({}, {})",
+            self.scheme().as_str(),
+            self.key_id()
+        )
+    }
+}
+
+impl TraceSigningKey {
+    pub fn load(path: &Path) -> FozzyResult<Self> {
+        let bytes = std::fs::read(path).map_err(|e| {
+            FozzyError::Config(format!("failed to read key file {}: {e}", path.display()))
+        })?;
+        let Ok(file) = serde_json::from_slice::<Ed25519KeyFile>(&bytes) else {
+            if bytes.is_empty() {
+                return Err(FozzyError::Config(format!(
+                    "key file {} is empty",
+                    path.display()
+                )));
+            }
+            return Ok(Self::Keyed(blake3::derive_key(KEYED_KEY_CONTEXT, &bytes)));
+        };
+        if file.scheme != "ed25519" {
+            return Err(FozzyError::Config(format!(
+                "unsupported key scheme {:?} in {}",
+                file.scheme,
+                path.display()
+            )));
+        }
+        let invalid = |what: &str| {
+            FozzyError::Config(format!("invalid ed25519 {what} in {}", path.display()))
+        };
+        match file.secret_key {
+            Some(secret) => {
+                let bytes: [u8; 32] = crate::hex_decode(&secret)?
+                    .try_into()
+                    .map_err(|_| invalid("secret key"))?;
+                Ok(Self::Ed25519Secret(SigningKey::from_bytes(&bytes)))
+            }
+            None => {
+                let bytes: [u8; 32] = crate::hex_decode(&file.public_key)?
+                    .try_into()
+                    .map_err(|_| invalid("public key"))?;
+                VerifyingKey::from_bytes(&bytes)
+                    .map(Self::Ed25519Public)
+                    .map_err(|_| invalid("public key"))
+            }
+        }
+    }
+
+    /// Key named by `FOZZY_TRACE_SIGN_KEY`, if set.
+    pub fn from_env() -> FozzyResult<Option<Self>> {
+        match std::env::var(TRACE_SIGN_KEY_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::load(Path::new(path.trim())).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn scheme(&self) -> TraceSignatureScheme {
+        match self {
+            Self::Keyed(_) => TraceSignatureScheme::Blake3Keyed,
+            Self::Ed25519Secret(_) | Self::Ed25519Public(_) => TraceSignatureScheme::Ed25519,
+        }
+    }
+
+    pub fn key_id(&self) -> String {
+        let hash = match self {
+            Self::Keyed(key) => blake3::derive_key(KEY_ID_CONTEXT, key),
+            Self::Ed25519Secret(key) => {
+                blake3::derive_key(KEY_ID_CONTEXT, key.verifying_key().as_bytes())
+            }
+            Self::Ed25519Public(key) => blake3::derive_key(KEY_ID_CONTEXT, key.as_bytes()),
+        };
+        bytes_to_hex(&hash[..KEY_ID_LEN / 2])
+    }
+
+    fn verifying_key(&self) -> Option<VerifyingKey> {
+        match self {
+            Self::Keyed(_) => None,
+            Self::Ed25519Secret(key) => Some(key.verifying_key()),
+            Self::Ed25519Public(key) => Some(*key),
+        }
+    }
+}
+
+/// Writes a new ed25519 key pair: `out` holds the secret key, `out.pub` the public key.
+pub fn generate_signing_key(out: &Path) -> FozzyResult<TraceKeygenReport> {
+    let key = SigningKey::generate(&mut rand_core::OsRng);
+    let public_key = bytes_to_hex(key.verifying_key().as_bytes());
+    let secret = Ed25519KeyFile {
+        scheme: "ed25519".to_string(),
+        secret_key: Some(bytes_to_hex(&key.to_bytes())),
+        public_key: public_key.clone(),
+    };
+    let public = Ed25519KeyFile {
+        secret_key: None,
+        ..secret.clone()
+    };
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_private(out, &serde_json::to_vec_pretty(&secret)?)?;
+    let pub_path = std::path::PathBuf::from(format!("{}.pub", out.display()));
+    std::fs::write(&pub_path, serde_json::to_vec_pretty(&public)?)?;
+    Ok(TraceKeygenReport {
+        schema_version: "fozzy.trace_keygen.v1".to_string(),
+        scheme: TraceSignatureScheme::Ed25519,
+        key_id: TraceSigningKey::Ed25519Secret(key).key_id(),
+        secret_key: out.display().to_string(),
+        public_key: pub_path.display().to_string(),
+    })
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, bytes: &[u8]) -> FozzyResult<()> {
+    use std::io::Write as _;
+    use std::os::unix::fs::OpenOptionsExt as _;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(bytes)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, bytes: &[u8]) -> FozzyResult<()> {
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+impl TraceFile {
+    /// Signs the trace in place, replacing any previous signature.
+    pub fn sign(&mut self, key: &TraceSigningKey) -> FozzyResult<()> {
+        let provenance = TraceProvenance {
+            engine: crate::version_info(),
+            command_line: std::env::args().collect(),
+            host: host_fingerprint(),
+            signed_at: crate::wall_time_iso_utc(),
+        };
+        let digest = signing_digest(self, &provenance)?;
+        let (value, public_key) = match key {
+            TraceSigningKey::Keyed(secret) => (
+                blake3::keyed_hash(secret, &digest).to_hex().to_string(),
+                None,
+            ),
+            TraceSigningKey::Ed25519Secret(secret) => (
+                bytes_to_hex(&secret.sign(&digest).to_bytes()),
+                Some(bytes_to_hex(secret.verifying_key().as_bytes())),
+            ),
+            TraceSigningKey::Ed25519Public(_) => {
+                return Err(FozzyError::Config(
+                    "cannot sign with an ed25519 public key file; pass the secret key".to_string(),
+                ));
+            }
+        };
+        self.signature = Some(TraceSignature {
+            scheme: key.scheme(),
+            key_id: key.key_id(),
+            public_key,
+            provenance,
+            value,
+        });
+        Ok(())
+    }
+}
+
+/// Checks the trace signature, optionally against a trusted key.
+pub fn verify_trace_signature(
+    trace: &TraceFile,
+    trusted: Option<&TraceSigningKey>,
+) -> FozzyResult<Option<TraceSignatureStatus>> {
+    let Some(signature) = trace.signature.as_ref() else {
+        return Ok(None);
+    };
+    let digest = signing_digest(trace, &signature.provenance)?;
+    let mut status = TraceSignatureStatus {
+        scheme: signature.scheme,
+        key_id: signature.key_id.clone(),
+        valid: None,
+        trusted: false,
+        provenance: signature.provenance.clone(),
+        reason: None,
+    };
+    if let Some(key) = trusted
+        && key.scheme() != signature.scheme
+    {
+        status.valid = Some(false);
+        status.reason = Some(format!(
+            "trace is signed with {} but the verification key is {}",
+            signature.scheme.as_str(),
+            key.scheme().as_str()
+        ));
+        return Ok(Some(status));
+    }
+    match signature.scheme {
+        TraceSignatureScheme::Blake3Keyed => match trusted {
+            Some(TraceSigningKey::Keyed(secret)) => {
+                let expected = blake3::keyed_hash(secret, &digest).to_hex().to_string();
+                let valid = expected == signature.value;
+                status.valid = Some(valid);
+                status.trusted = valid;
+                if !valid {
+                    status.reason = Some("keyed signature mismatch".to_string());
+                }
+            }
+            _ => status.reason = Some("keyed signature requires the signing key".to_string()),
+        },
+        TraceSignatureScheme::Ed25519 => {
+            let embedded = signature
+                .public_key
+                .as_deref()
+                .and_then(|hex| crate::hex_decode(hex).ok())
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+            let Some(embedded) = embedded else {
+                status.valid = Some(false);
+                status.reason = Some("signature carries no valid ed25519 public key".to_string());
+                return Ok(Some(status));
+            };
+            let sig = crate::hex_decode(&signature.value)
+                .ok()
+                .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+                .map(|bytes| ed25519_dalek::Signature::from_bytes(&bytes));
+            let valid = sig.is_some_and(|sig| embedded.verify(&digest, &sig).is_ok())
+                && TraceSigningKey::Ed25519Public(embedded).key_id() == signature.key_id;
+            status.valid = Some(valid);
+            if !valid {
+                status.reason = Some("ed25519 signature mismatch".to_string());
+            }
+            status.trusted = valid
+                && trusted
+                    .and_then(TraceSigningKey::verifying_key)
+                    .is_some_and(|key| key == embedded);
+        }
+    }
+    Ok(Some(status))
+}
+
+/// Signs a trace file with `key` and writes it with a fresh checksum.
+pub fn sign_trace_file(
+    input: &Path,
+    out: &Path,
+    key: &TraceSigningKey,
+) -> FozzyResult<TraceSignReport> {
+    let mut trace = TraceFile::read_json(input)?;
+    trace.sign(key)?;
+    trace.write_unsigned(out, TraceEncoding::of_file(input))?;
+    let signature = verify_trace_signature(&trace, Some(key))?
+        .ok_or_else(|| FozzyError::Trace("signed trace carries no signature".to_string()))?;
+    Ok(TraceSignReport {
+        schema_version: "fozzy.trace_sign.v1".to_string(),
+        input: input.display().to_string(),
+        out: out.display().to_string(),
+        signature,
+    })
+}
+
+fn signing_digest(trace: &TraceFile, provenance: &TraceProvenance) -> FozzyResult<[u8; 32]> {
+    let mut canonical = trace.clone();
+    canonical.checksum = None;
+    canonical.signature = None;
+    let mut hasher = blake3::Hasher::new_derive_key(SIGNING_CONTEXT);
+    hasher.update(&serde_json::to_vec(&canonical)?);
+    hasher.update(&serde_json::to_vec(provenance)?);
+    Ok(*hasher.finalize().as_bytes())
+}
+
+fn host_fingerprint() -> HostFingerprint {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.trim().is_empty())
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+        })
+        .unwrap_or_default();
+    HostFingerprint {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        host_hash: blake3::hash(host.as_bytes()).to_hex()[..KEY_ID_LEN].to_string(),
+    }
+}
@@ -0,0 +1,118 @@
+//! Mid-run engine snapshots stored in scenario traces.
+//!
+//! A snapshot captures engine state (kv, virtual fs, clock, RNG, net queues,
+//! memory tracker) after a scheduled step, together with a digest of the
+//! decisions recorded up to that point. `replay --from-step`/`--from-time`
+//! restores the nearest snapshot and replays only the remaining steps.
+
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+use crate::Decision;
+
+const DEFAULT_SNAPSHOT_EVERY_STEPS: usize = 1000;
+
+/// `[snapshots]` table in fozzy.toml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotConfig {
+    /// Snapshot engine state every this many scheduled steps while recording; 0 disables.
+    #[serde(default = "default_snapshot_every_steps")]
+    pub every_steps: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            every_steps: default_snapshot_every_steps(),
+        }
+    }
+}
+
+fn default_snapshot_every_steps() -> usize {
+    DEFAULT_SNAPSHOT_EVERY_STEPS
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSnapshot {
+    /// Scheduled steps completed before the snapshot was taken.
+    pub position: usize,
+    /// Virtual clock at the snapshot.
+    pub time_ms: u64,
+    /// Decisions recorded before the snapshot.
+    pub decision_index: usize,
+    pub executed_steps: usize,
+    /// blake3 of the first `decision_index` decisions.
+    pub decisions_digest: String,
+    /// Serialized engine state; opaque outside the engine.
+    pub state: serde_json::Value,
+}
+
+impl TraceSnapshot {
+    /// Checks that the snapshot was taken over this decision log.
+    pub fn verify(&self, decisions: &[Decision]) -> Result<(), String> {
+        let prefix = decisions.get(..self.decision_index).unwrap_or_default();
+        self.verify_digest(&decisions_digest(prefix), decisions.len())
+    }
+
+    /// Like `verify`, given the digest of the decisions before the snapshot and
+    /// the trace's total decision count.
+    pub(crate) fn verify_digest(&self, digest: &str, decisions: usize) -> Result<(), String> {
+        if self.decision_index > decisions {
+            return Err(format!(
+                "snapshot at step {} expects {} decisions but the trace has {}",
+                self.position, self.decision_index, decisions
+            ));
+        }
+        if digest != self.decisions_digest {
+            return Err(format!(
+                "snapshot at step {} does not match the recorded decisions",
+                self.position
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Where `replay` should resume from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayStart {
+    Step(usize),
+    Time(Duration),
+}
+
+/// The latest snapshot taken at or before `start`.
+pub fn nearest_snapshot(snapshots: &[TraceSnapshot], start: ReplayStart) -> Option<&TraceSnapshot> {
+    snapshots
+        .iter()
+        .filter(|snapshot| match start {
+            ReplayStart::Step(step) => snapshot.position <= step,
+            ReplayStart::Time(time) => u128::from(snapshot.time_ms) <= time.as_millis(),
+        })
+        .max_by_key(|snapshot| snapshot.position)
+}
+
+pub(crate) fn decisions_digest(decisions: &[Decision]) -> String {
+    let mut digest = DecisionsDigest::default();
+    for decision in decisions {
+        digest.update(decision);
+    }
+    digest.finish()
+}
+
+/// Incremental form of `decisions_digest` for decisions read one at a time.
+#[derive(Default)]
+pub(crate) struct DecisionsDigest(blake3::Hasher);
+
+impl DecisionsDigest {
+    pub(crate) fn update(&mut self, decision: &Decision) {
+        if let Ok(bytes) = serde_json::to_vec(decision) {
+            self.0.update(&bytes);
+        }
+        self.0.update(b"\n");
+    }
+
+    pub(crate) fn finish(&self) -> String {
+        self.0.finalize().to_hex().to_string()
+    }
+}
@@ -26,6 +26,7 @@ fn sample_summary(trace_path: Option<String>) -> RunSummary {
         tests: None,
         memory: None,
         findings: Vec::new(),
+        retries: Vec::new(),
     }
 }
 
@@ -133,8 +134,13 @@ fn record_collision_error_policy_rejects_existing_target() {
         Vec::new(),
         sample_summary(Some(path.to_string_lossy().to_string())),
     );
-    let err = write_trace_with_policy(&trace, &path, RecordCollisionPolicy::Error)
-        .expect_err("must fail");
+    let err = write_trace_with_policy(
+        &trace,
+        &path,
+        RecordCollisionPolicy::Error,
+        TraceEncoding::Json,
+    )
+    .expect_err("must fail");
     assert!(err.to_string().contains("record collision"));
 }
 
@@ -154,8 +160,13 @@ fn record_collision_append_policy_picks_numbered_path() {
         Vec::new(),
         sample_summary(None),
     );
-    let out =
-        write_trace_with_policy(&trace, &path, RecordCollisionPolicy::Append).expect("append");
+    let out = write_trace_with_policy(
+        &trace,
+        &path,
+        RecordCollisionPolicy::Append,
+        TraceEncoding::Json,
+    )
+    .expect("append");
     assert_ne!(out, path);
     assert!(out.to_string_lossy().contains(".1.fozzy"));
     let loaded = TraceFile::read_json(&out).expect("trace exists");
@@ -295,3 +306,619 @@ fn verify_warns_on_legacy_host_fs_trace_without_fs_decisions() {
             .any(|w| w.contains("host fs backend") && w.contains("replay may drift"))
     );
 }
+
+fn chunked_trace() -> TraceFile {
+    let decisions: Vec<Decision> = serde_json::from_str(
+        r#"[{"kind":"scheduler_pick","task_id":1,"label":"NetDeliverOne"},
+            {"kind":"net_deliver_pick","message_id":42}]"#,
+    )
+    .expect("decisions parse");
+    let events = (0..2500)
+        .map(|i| {
+            let mut fields = serde_json::Map::new();
+            fields.insert("i".to_string(), serde_json::json!(i));
+            fields.insert("ratio".to_string(), serde_json::json!(i as f64 / 4.0));
+            TraceEvent {
+                time_ms: i,
+                name: if i == 1234 { "needle" } else { "tick" }.to_string(),
+                fields,
+            }
+        })
+        .collect();
+    TraceFile::new(
+        RunMode::Run,
+        Some("tests/example.fozzy.json".to_string()),
+        Some(ScenarioV1Steps {
+            version: 1,
+            name: "x".to_string(),
+            steps: Vec::new(),
+        }),
+        decisions,
+        events,
+        sample_summary(None),
+    )
+}
+
+#[test]
+fn binary_trace_round_trips_and_verifies() {
+    let trace = chunked_trace();
+    for compress in [false, true] {
+        let path = temp_file("trace.bin");
+        trace.write_binary(&path, compress).expect("write binary");
+        assert!(is_trace_path(&path));
+        let loaded = TraceFile::read_json(&path).expect("read binary");
+        assert_eq!(
+            serde_json::to_value(&loaded).expect("loaded json"),
+            serde_json::to_value(&trace).expect("original json")
+        );
+        let report = verify_trace_file(&path).expect("verify binary");
+        assert!(report.checksum_present && report.checksum_valid);
+    }
+}
+
+#[test]
+fn binary_trace_cursor_streams_chunks() {
+    let path = temp_file("stream.fozzy");
+    let trace = chunked_trace();
+    trace.write_binary(&path, false).expect("write binary");
+    let mut cursor = TraceCursor::open(&path).expect("open cursor");
+    assert_eq!(cursor.header().mode, RunMode::Run);
+    let mut event_chunks = 0;
+    let mut events = 0;
+    while let Some(chunk) = cursor.next_chunk().expect("chunk") {
+        if let TraceChunk::Events(chunk) = chunk {
+            event_chunks += 1;
+            events += chunk.len();
+        }
+    }
+    assert!(event_chunks > 1);
+    assert_eq!(events, trace.events.len());
+    assert_eq!(cursor.footer().expect("footer").events, events as u64);
+}
+
+#[test]
+fn binary_trace_corruption_is_detected() {
+    let path = temp_file("corrupt.fozzy");
+    chunked_trace()
+        .write_binary(&path, false)
+        .expect("write binary");
+    let original = std::fs::read(&path).expect("read");
+    let mut bytes = original.clone();
+    let at = bytes
+        .windows(6)
+        .position(|w| w == b"needle")
+        .expect("needle present");
+    bytes[at] = b'N';
+    std::fs::write(&path, &bytes).expect("write corrupt");
+    let err = TraceFile::read_json(&path).expect_err("must reject corrupt frame");
+    assert!(err.to_string().contains("digest mismatch"));
+    let report = verify_trace_file(&path).expect("verify still reads frames");
+    assert!(!report.checksum_valid);
+
+    let truncated = temp_file("truncated.fozzy");
+    std::fs::write(&truncated, &original[..original.len() - 10]).expect("write truncated");
+    let err = TraceFile::read_json(&truncated).expect_err("must reject truncated trace");
+    assert!(err.to_string().contains("truncated"));
+}
+
+#[test]
+fn upgrade_migrates_v1_trace_and_records_lineage() {
+    let raw = serde_json::json!({
+        "format": "fozzy-trace",
+        "version": 1,
+        "engine": {"version": "0.1.0"},
+        "mode": "run",
+        "scenario_path": "tests/example.fozzy.json",
+        "scenario": {"version": 1, "name": "example", "steps": []},
+        "decisions": [
+            {"kind": "scheduler_pick", "task_id": 1, "label": "step0"},
+            {"kind": "step", "index": 0, "name": "legacy-step"},
+            {"kind": "proc_spawn", "cmd": "true", "args": [], "exit_code": 0, "stdout": "", "stderr": ""}
+        ],
+        "events": [
+            {"time_ms": 0, "name": "sched_pick", "fields": {"task_id": 1, "step": 0, "kind": "trace_event"}}
+        ],
+        "summary": {
+            "status": "pass",
+            "mode": "run",
+            "identity": {"runId": "r1", "seed": 1},
+            "startedAt": "2026-01-01T00:00:00Z",
+            "finishedAt": "2026-01-01T00:00:00Z",
+            "durationMs": 3
+        },
+        "checksum": "legacy"
+    });
+
+    let (trace, applied) = upgrade_trace_value(raw).expect("upgrade");
+    assert_eq!(trace.version, CURRENT_TRACE_VERSION);
+    assert_eq!(
+        applied.iter().map(|m| (m.from, m.to)).collect::<Vec<_>>(),
+        vec![(1, 2), (2, 3), (3, 4)]
+    );
+    assert_eq!(trace.migrations, applied);
+    assert_eq!(applied[0].source_checksum.as_deref(), Some("legacy"));
+    assert_eq!(trace.decisions.len(), 2);
+    assert!(
+        !trace
+            .decisions
+            .iter()
+            .any(|d| matches!(d, Decision::Step { .. }))
+    );
+    assert_eq!(trace.summary.duration_ns, 3_000_000);
+    let fields = &trace.events[0].fields;
+    assert_eq!(fields.get("step_index"), Some(&serde_json::json!(0)));
+    assert_eq!(
+        fields.get("step_kind"),
+        Some(&serde_json::json!("trace_event"))
+    );
+    assert!(!fields.contains_key("step"));
+
+    let path = temp_file("upgraded.fozzy");
+    trace.write_json(&path).expect("write");
+    let reread = TraceFile::read_json(&path).expect("upgraded trace verifies");
+    assert_eq!(reread.migrations.len(), 3);
+}
+
+#[test]
+fn upgrade_check_classifies_traces() {
+    let dir = temp_file("archive");
+    std::fs::create_dir_all(&dir).expect("archive dir");
+    let current = TraceFile::new(
+        RunMode::Run,
+        None,
+        None,
+        Vec::new(),
+        Vec::new(),
+        sample_summary(None),
+    );
+    current
+        .write_json(&dir.join("current.fozzy"))
+        .expect("write");
+    let mut stale = serde_json::to_value(&current).expect("value");
+    stale["version"] = serde_json::json!(2);
+    std::fs::write(
+        dir.join("stale.fozzy"),
+        serde_json::to_vec(&stale).expect("bytes"),
+    )
+    .expect("write");
+    std::fs::write(dir.join("broken.fozzy"), b"not a trace").expect("write");
+
+    let report = check_trace_upgrades(&[dir]).expect("check");
+    assert_eq!(
+        (report.current, report.upgradable, report.unsupported),
+        (1, 1, 1)
+    );
+    let status = |name: &str| {
+        report
+            .traces
+            .iter()
+            .find(|t| t.path.ends_with(name))
+            .map(|t| (t.status, t.replayable))
+            .expect("trace listed")
+    };
+    assert_eq!(status("current.fozzy"), (TraceUpgradeStatus::Current, true));
+    assert_eq!(
+        status("stale.fozzy"),
+        (TraceUpgradeStatus::Upgradable, true)
+    );
+    assert_eq!(
+        status("broken.fozzy"),
+        (TraceUpgradeStatus::Unsupported, false)
+    );
+}
+
+fn redactor() -> Redactor {
+    Redactor::new(
+        &RedactRules {
+            headers: vec!["Authorization".to_string()],
+            json_paths: vec!["$.token".to_string(), "$..password".to_string()],
+            patterns: vec!["key=(ghp_[a-z]+)".to_string()],
+        },
+        None,
+    )
+    .expect("rules compile")
+}
+
+#[test]
+fn redaction_uses_stable_placeholders_and_is_idempotent() {
+    let r = redactor();
+    let mut count = 0;
+    let body = r#"{"token":"t1","nested":{"password":"p"},"note":"key=ghp_abc"}"#;
+    let once = r.redact_text(body, &mut count);
+    assert_eq!(count, 3);
+    assert!(!once.contains("t1") && !once.contains("\"p\"") && !once.contains("ghp_abc"));
+    assert!(once.contains("key=[redacted:"));
+    assert_eq!(
+        r.redact_text(body, &mut count),
+        once,
+        "placeholders are stable"
+    );
+
+    let mut again = 0;
+    assert_eq!(r.redact_text(&once, &mut again), once);
+    assert_eq!(again, 0, "redacting twice changes nothing");
+
+    let header = r.redact_header("authorization", "Bearer secret", &mut count);
+    assert!(header.starts_with("[redacted:"));
+    assert!(r.header_matches("authorization", "Bearer secret", &header));
+    assert!(r.text_matches(body, &once));
+}
+
+#[test]
+fn redact_trace_covers_decisions_and_records_metadata() {
+    let mut trace = TraceFile::new(
+        RunMode::Run,
+        None,
+        None,
+        vec![
+            Decision::HttpRequest {
+                method: "GET".to_string(),
+                path: "https://example.test/me".to_string(),
+                status_code: 200,
+                headers: std::collections::BTreeMap::from([(
+                    "authorization".to_string(),
+                    "Bearer x".to_string(),
+                )]),
+                body: r#"{"token":"abc"}"#.to_string(),
+                duration_ms: 1,
+            },
+            Decision::ProcSpawn {
+                cmd: "gh".to_string(),
+                args: Vec::new(),
+                exit_code: 0,
+                stdout: "key=ghp_secret".to_string(),
+                stderr: String::new(),
+                peak_rss_bytes: 0,
+                rss_sample_count: 0,
+                duration_ms: 0,
+            },
+        ],
+        Vec::new(),
+        sample_summary(None),
+    );
+    let count = redactor().redact_trace(&mut trace).expect("redact");
+    assert_eq!(count, 3);
+    let rendered = serde_json::to_string(&trace.decisions).expect("json");
+    assert!(
+        !rendered.contains("Bearer x")
+            && !rendered.contains("abc")
+            && !rendered.contains("ghp_secret")
+    );
+    let meta = trace.redaction.as_ref().expect("redaction metadata");
+    assert_eq!(meta.redacted_values, 3);
+    assert_eq!(meta.rules.headers, vec!["Authorization".to_string()]);
+
+    let path = temp_file("redacted.fozzy");
+    trace.write_json(&path).expect("write");
+    let reread = TraceFile::read_json(&path).expect("redacted trace verifies");
+    assert_eq!(reread.redaction, trace.redaction);
+}
+
+#[test]
+fn redaction_placeholders_are_keyed_per_trace() {
+    let r = redactor();
+    let mut count = 0;
+    let secret = r.redact_header("authorization", "Bearer secret", &mut count);
+    let other = redactor().redact_header("authorization", "Bearer secret", &mut count);
+    assert_ne!(secret, other, "each trace gets its own salt");
+
+    let mut redaction = None;
+    r.mark(&mut redaction, 1);
+    let redaction = redaction.expect("redaction metadata");
+    assert_eq!(redaction.salt.len(), 32);
+    let replay = Redactor::for_trace(&redaction, None).expect("replay redactor");
+    assert!(replay.header_matches("authorization", "Bearer secret", &secret));
+    let keyed = Redactor::for_trace(&redaction, Some(b"key")).expect("keyed redactor");
+    assert!(!keyed.header_matches("authorization", "Bearer secret", &secret));
+
+    let legacy = TraceRedaction {
+        salt: String::new(),
+        ..redaction
+    };
+    let legacy = Redactor::for_trace(&legacy, None).expect("legacy redactor");
+    let unkeyed = format!(
+        "[redacted:{}]",
+        &blake3::hash(b"fozzy-redact:Bearer secret").to_hex()[..12]
+    );
+    assert!(legacy.header_matches("authorization", "Bearer secret", &unkeyed));
+}
+
+#[test]
+fn truncated_text_is_redacted_wholesale_only_when_it_is_json() {
+    let r = redactor();
+    let mut count = 0;
+    let json = r#"{"token":"t1","items":[1,2...[truncated]"#;
+    let redacted = r.redact_text(json, &mut count);
+    assert!(redacted.starts_with("[redacted:") && !redacted.contains("t1"));
+    assert_eq!(count, 1);
+
+    let stdout = "build ok, key=ghp_abc\nwarnings: 3...[truncated]";
+    let redacted = r.redact_text(stdout, &mut count);
+    assert!(
+        redacted.starts_with("build ok, key=[redacted:"),
+        "{redacted}"
+    );
+    assert!(
+        redacted.ends_with("warnings: 3...[truncated]"),
+        "{redacted}"
+    );
+}
+
+#[test]
+fn invalid_redact_rules_are_rejected() {
+    for rules in [
+        RedactRules {
+            json_paths: vec!["token".to_string()],
+            ..RedactRules::default()
+        },
+        RedactRules {
+            patterns: vec!["(".to_string()],
+            ..RedactRules::default()
+        },
+    ] {
+        assert!(Redactor::new(&rules, None).is_err());
+    }
+}
+
+#[test]
+fn ed25519_signature_verifies_and_detects_tampering() {
+    let key_path = temp_file("release.key");
+    let keygen = generate_signing_key(&key_path).expect("keygen");
+    let secret = TraceSigningKey::load(&key_path).expect("secret key");
+    let public = TraceSigningKey::load(Path::new(&keygen.public_key)).expect("public key");
+    assert_eq!(secret.key_id(), keygen.key_id);
+    assert_eq!(public.key_id(), keygen.key_id);
+
+    let path = temp_file("signed.fozzy");
+    let mut trace = chunked_trace();
+    trace.sign(&secret).expect("sign");
+    for compress in [None, Some(true)] {
+        match compress {
+            None => trace.write_json(&path).expect("write json"),
+            Some(compress) => trace.write_binary(&path, compress).expect("write binary"),
+        }
+        let status = verify_trace_file_with_key(&path, Some(&public))
+            .expect("verify")
+            .signature
+            .expect("signature status");
+        assert_eq!(status.valid, Some(true));
+        assert!(status.trusted);
+        assert_eq!(
+            status.provenance.engine.version,
+            crate::version_info().version
+        );
+    }
+
+    let other = temp_file("other.key");
+    generate_signing_key(&other).expect("keygen other");
+    let other = TraceSigningKey::load(&other).expect("other key");
+    let status = verify_trace_signature(&trace, Some(&other))
+        .expect("verify")
+        .expect("status");
+    assert_eq!(status.valid, Some(true));
+    assert!(!status.trusted);
+
+    trace.summary.identity.seed += 1;
+    let status = verify_trace_signature(&trace, None)
+        .expect("verify")
+        .expect("status");
+    assert_eq!(status.valid, Some(false));
+}
+
+#[test]
+fn keyed_signature_requires_the_shared_key() {
+    let key_path = temp_file("shared.key");
+    std::fs::write(&key_path, "shared secret").expect("write key");
+    let key = TraceSigningKey::load(&key_path).expect("keyed key");
+    assert_eq!(key.scheme(), TraceSignatureScheme::Blake3Keyed);
+
+    let path = temp_file("keyed.fozzy");
+    let source = temp_file("unsigned.fozzy");
+    chunked_trace().write_json(&source).expect("write");
+    let report = sign_trace_file(&source, &path, &key).expect("sign");
+    assert_eq!(report.signature.valid, Some(true));
+
+    let unkeyed = verify_trace_file(&path).expect("verify").signature;
+    assert_eq!(unkeyed.expect("status").valid, None);
+
+    let wrong_path = temp_file("wrong.key");
+    std::fs::write(&wrong_path, "other secret").expect("write key");
+    let wrong = TraceSigningKey::load(&wrong_path).expect("wrong key");
+    let status = verify_trace_file_with_key(&path, Some(&wrong))
+        .expect("verify")
+        .signature
+        .expect("status");
+    assert_eq!(status.valid, Some(false));
+    assert!(!status.trusted);
+}
+
+fn sample_snapshot(position: usize, time_ms: u64, decisions: &[Decision]) -> TraceSnapshot {
+    TraceSnapshot {
+        position,
+        time_ms,
+        decision_index: decisions.len(),
+        executed_steps: position,
+        decisions_digest: decisions_digest(decisions),
+        state: serde_json::json!({}),
+    }
+}
+
+#[test]
+fn nearest_snapshot_picks_latest_at_or_before_start_and_verifies_decisions() {
+    let decisions = vec![
+        Decision::RandU64 { value: 1 },
+        Decision::TimeSleepMs { ms: 10 },
+        Decision::RandU64 { value: 2 },
+    ];
+    let snapshots = vec![
+        sample_snapshot(2, 0, &decisions[..1]),
+        sample_snapshot(4, 10, &decisions[..3]),
+    ];
+
+    let pick = |start| nearest_snapshot(&snapshots, start).map(|s| s.position);
+    assert_eq!(pick(ReplayStart::Step(1)), None);
+    assert_eq!(pick(ReplayStart::Step(3)), Some(2));
+    assert_eq!(pick(ReplayStart::Step(9)), Some(4));
+    assert_eq!(pick(ReplayStart::Time(Duration::from_millis(9))), Some(2));
+    assert_eq!(pick(ReplayStart::Time(Duration::from_millis(10))), Some(4));
+
+    assert!(snapshots[1].verify(&decisions).is_ok());
+    let mut edited = decisions.clone();
+    edited[0] = Decision::RandU64 { value: 7 };
+    assert!(snapshots[1].verify(&edited).is_err());
+    assert!(snapshots[1].verify(&decisions[..2]).is_err());
+}
+
+fn archived_trace(scenario_path: &str, name: &str) -> TraceFile {
+    TraceFile::new(
+        RunMode::Test,
+        Some(scenario_path.to_string()),
+        Some(ScenarioV1Steps {
+            version: 1,
+            name: name.to_string(),
+            steps: Vec::new(),
+        }),
+        Vec::new(),
+        Vec::new(),
+        sample_summary(None),
+    )
+}
+
+#[test]
+fn archive_entries_resolve_by_name_path_and_scenario_name() {
+    let path = temp_file("runs.fozzya");
+    record_into_archive(&path, RecordCollisionPolicy::Error, |_, archive| {
+        archive.push(
+            archive.next_name("net"),
+            archived_trace("tests/net.fozzy.json", "net-sim"),
+        )?;
+        archive.push(
+            archive.next_name("proc"),
+            archived_trace("tests/proc.fozzy.json", "proc-mock"),
+        )
+    })
+    .expect("record archive");
+    record_into_archive(&path, RecordCollisionPolicy::Append, |_, archive| {
+        archive.push(
+            archive.next_name("net"),
+            archived_trace("other/net.fozzy.json", "net-sim"),
+        )
+    })
+    .expect("append archive");
+
+    let archive = TraceArchive::read(&path).expect("read archive");
+    let names: Vec<&str> = archive.index.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["net", "proc", "net.2"]);
+    assert_eq!(archive.find("proc-mock").expect("scenario name"), 1);
+    assert_eq!(
+        archive.find("other/net.fozzy.json").expect("path suffix"),
+        2
+    );
+    assert!(archive.find("net-sim").is_err(), "ambiguous selector");
+    assert!(archive.find("missing").is_err());
+
+    let selected = TraceFile::read_json(&archive_entry_path(&path, "proc")).expect("selector");
+    assert_eq!(
+        selected.scenario_path.as_deref(),
+        Some("tests/proc.fozzy.json")
+    );
+    let report = verify_trace_file(&path).expect("verify archive");
+    assert!(report.checksum_valid && report.warnings.is_empty());
+
+    let err = record_into_archive(&path, RecordCollisionPolicy::Error, |_, _| Ok(()))
+        .expect_err("error policy refuses existing archive");
+    assert!(err.to_string().contains("record collision"));
+}
+
+#[test]
+fn archive_detects_tampered_entries() {
+    let path = temp_file("tampered.fozzya");
+    record_into_archive(&path, RecordCollisionPolicy::Overwrite, |_, archive| {
+        archive.push(
+            "net".to_string(),
+            archived_trace("tests/net.fozzy.json", "net-sim"),
+        )
+    })
+    .expect("record archive");
+    let mut doc: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&path).expect("read")).expect("json");
+    doc["traces"][0]["summary"]["status"] = serde_json::json!("fail");
+    std::fs::write(&path, serde_json::to_vec(&doc).expect("encode")).expect("write");
+
+    let err = TraceFile::read_json(&archive_entry_path(&path, "net")).expect_err("tampered");
+    assert!(err.to_string().contains("checksum"), "{err}");
+    let report = verify_trace_file(&path).expect("verify archive");
+    assert!(!report.checksum_valid);
+    assert!(report.warnings.iter().any(|w| w.starts_with("net: ")));
+}
+
+#[test]
+fn binary_trace_outline_streams_decisions_lazily() {
+    let path = temp_file("outline.fozzy");
+    let trace = chunked_trace();
+    trace.write_binary(&path, true).expect("write binary");
+
+    let outline = TraceOutline::read(&path).expect("outline");
+    assert!(outline.trace.decisions.is_empty() && outline.trace.events.is_empty());
+    assert_eq!(
+        outline.trace.summary.identity.seed,
+        trace.summary.identity.seed
+    );
+    assert_eq!(outline.decisions, trace.decisions.len());
+    assert!(outline.scheduler_picks);
+    assert_eq!(outline.replay_warnings, trace_replay_warnings(&trace));
+
+    let mut stream = outline.decision_stream().expect("stream");
+    assert_eq!(stream.total(), trace.decisions.len());
+    let mut streamed = Vec::new();
+    while let Some(decision) = stream.next() {
+        streamed.push(serde_json::to_value(decision).expect("decision json"));
+    }
+    assert!(stream.peek().is_none());
+    assert!(stream.take_error().is_none());
+    let expected: Vec<_> = trace
+        .decisions
+        .iter()
+        .map(|decision| serde_json::to_value(decision).expect("decision json"))
+        .collect();
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn binary_recording_streams_chunks_and_falls_back_when_the_run_rewrites_them() {
+    let trace = chunked_trace();
+    let scenario_path = PathBuf::from(trace.scenario_path.clone().expect("scenario path"));
+    let scenario = trace.scenario.clone().expect("scenario");
+
+    let path = temp_file("recorded.fozzy");
+    let mut recording = TraceRecording::new(&path, TraceEncoding::Binary).expect("binary");
+    recording
+        .begin(RunMode::Run, &scenario_path, &scenario, None)
+        .expect("begin");
+    recording
+        .sync(&trace.decisions[..1], &trace.events[..1500])
+        .expect("sync");
+    recording.finish(&trace).expect("finish");
+    let loaded = TraceFile::read_json(&path).expect("read");
+    assert_eq!(loaded.events.len(), trace.events.len());
+    assert_eq!(loaded.decisions.len(), trace.decisions.len());
+    assert!(verify_trace_file(&path).expect("verify").checksum_valid);
+
+    let path = temp_file("rewritten.fozzy");
+    let mut recording = TraceRecording::new(&path, TraceEncoding::Binary).expect("binary");
+    recording
+        .begin(RunMode::Run, &scenario_path, &scenario, None)
+        .expect("begin");
+    recording
+        .sync(&trace.decisions[1..], &trace.events[1..])
+        .expect("sync");
+    recording.finish(&trace).expect("finish");
+    let loaded = TraceFile::read_json(&path).expect("read");
+    assert_eq!(
+        serde_json::to_value(&loaded.events).expect("events"),
+        serde_json::to_value(&trace.events).expect("events")
+    );
+    assert!(verify_trace_file(&path).expect("verify").checksum_valid);
+
+    assert!(TraceRecording::new(&path, TraceEncoding::Json).is_none());
+}
@@ -0,0 +1,193 @@
+//! Value-level shrinking inside scenario steps.
+//!
+//! Step shrinking removes whole steps; this pass simplifies the fields of the steps
+//! that remain. Each step is edited through its JSON form and every candidate is
+//! strictly simpler than the value it replaces, so greedy acceptance terminates:
+//! strings and bodies lose character chunks (or JSON structure when they hold JSON),
+//! integers and durations move toward zero, booleans become `false`, and object
+//! keys (headers, optional fields) and array items (args, payload elements) are
+//! removed one at a time.
+
+use crate::Step;
+
+/// Strings longer than this are only cut in chunks of `len / CHAR_GRANULARITY`
+/// characters, which keeps each round small for large bodies.
+const CHAR_GRANULARITY: usize = 64;
+
+/// Simpler variants of `step`, coarsest edits first. Variants that no longer
+/// deserialize as a step (e.g. a required field was removed) or that round-trip to
+/// the original step (e.g. removing a `null` optional field) are dropped.
+pub(crate) fn step_value_candidates(step: &Step) -> Vec<Step> {
+    let Ok(value) = serde_json::to_value(step) else {
+        return Vec::new();
+    };
+    value_candidates(&value, true)
+        .into_iter()
+        .filter_map(|candidate| serde_json::from_value::<Step>(candidate).ok())
+        .filter(|candidate| serde_json::to_value(candidate).ok().as_ref() != Some(&value))
+        .collect()
+}
+
+/// Simpler variants of `value`; `is_step` protects the `type` tag of step objects.
+fn value_candidates(value: &serde_json::Value, is_step: bool) -> Vec<serde_json::Value> {
+    match value {
+        serde_json::Value::Object(fields) => object_candidates(fields, is_step),
+        serde_json::Value::Array(items) => array_candidates(items, false),
+        serde_json::Value::String(text) => string_candidates(text)
+            .into_iter()
+            .map(serde_json::Value::String)
+            .collect(),
+        serde_json::Value::Number(number) => number_candidates(number),
+        serde_json::Value::Bool(true) => vec![serde_json::Value::Bool(false)],
+        serde_json::Value::Bool(false) | serde_json::Value::Null => Vec::new(),
+    }
+}
+
+fn object_candidates(
+    fields: &serde_json::Map<String, serde_json::Value>,
+    is_step: bool,
+) -> Vec<serde_json::Value> {
+    let editable = |key: &str| !(is_step && key == "type");
+    let mut out = Vec::new();
+    for (key, _) in fields
+        .iter()
+        .filter(|(key, field)| editable(key) && !field.is_null())
+    {
+        let mut fewer = fields.clone();
+        fewer.remove(key);
+        out.push(serde_json::Value::Object(fewer));
+    }
+    for (key, field) in fields.iter().filter(|(key, _)| editable(key)) {
+        let nested = match field {
+            // Nested step lists (`assert_throws`, `assert_rejects`) hold steps.
+            serde_json::Value::Array(items) if is_step && key == "steps" => {
+                array_candidates(items, true)
+            }
+            other => value_candidates(other, false),
+        };
+        for simpler in nested {
+            let mut edited = fields.clone();
+            edited.insert(key.clone(), simpler);
+            out.push(serde_json::Value::Object(edited));
+        }
+    }
+    out
+}
+
+fn array_candidates(items: &[serde_json::Value], steps: bool) -> Vec<serde_json::Value> {
+    let mut out = Vec::new();
+    if items.is_empty() {
+        return out;
+    }
+    out.push(serde_json::Value::Array(Vec::new()));
+    if items.len() > 2 {
+        let half = items.len() / 2;
+        out.push(serde_json::Value::Array(items[..half].to_vec()));
+        out.push(serde_json::Value::Array(items[half..].to_vec()));
+    }
+    if items.len() > 1 {
+        for index in 0..items.len() {
+            let mut fewer = items.to_vec();
+            fewer.remove(index);
+            out.push(serde_json::Value::Array(fewer));
+        }
+    }
+    for (index, item) in items.iter().enumerate() {
+        for simpler in value_candidates(item, steps) {
+            let mut edited = items.to_vec();
+            edited[index] = simpler;
+            out.push(serde_json::Value::Array(edited));
+        }
+    }
+    out
+}
+
+fn string_candidates(text: &str) -> Vec<String> {
+    if let Ok(duration) = crate::parse_duration(text) {
+        return duration_candidates(duration.as_millis());
+    }
+    if let Ok(parsed @ (serde_json::Value::Object(_) | serde_json::Value::Array(_))) =
+        serde_json::from_str::<serde_json::Value>(text)
+    {
+        return value_candidates(&parsed, false)
+            .into_iter()
+            .map(|simpler| simpler.to_string())
+            .filter(|simpler| simpler.len() < text.len())
+            .collect();
+    }
+    char_candidates(text)
+}
+
+/// Durations as millisecond strings, halving the distance to zero each time.
+fn duration_candidates(millis: u128) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut delta = millis;
+    while delta > 0 {
+        out.push(format!("{}ms", millis - delta));
+        delta /= 2;
+    }
+    out
+}
+
+/// Delta debugging over characters: drop the whole string, then each chunk at
+/// halving chunk sizes.
+fn char_candidates(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = Vec::new();
+    if chars.is_empty() {
+        return out;
+    }
+    out.push(String::new());
+    let min_chunk = (chars.len() / CHAR_GRANULARITY).max(1);
+    let mut chunk = chars.len() / 2;
+    while chunk >= min_chunk {
+        for start in (0..chars.len()).step_by(chunk) {
+            let end = (start + chunk).min(chars.len());
+            let mut fewer = chars[..start].to_vec();
+            fewer.extend_from_slice(&chars[end..]);
+            out.push(fewer.into_iter().collect());
+        }
+        if chunk == 1 {
+            break;
+        }
+        chunk /= 2;
+    }
+    out.dedup();
+    out
+}
+
+fn number_candidates(number: &serde_json::Number) -> Vec<serde_json::Value> {
+    if let Some(value) = number.as_u64() {
+        let mut out = Vec::new();
+        let mut delta = value;
+        while delta > 0 {
+            out.push(serde_json::json!(value - delta));
+            delta /= 2;
+        }
+        return out;
+    }
+    if let Some(value) = number.as_i64() {
+        // Negative: move up toward zero.
+        let mut out = Vec::new();
+        let mut delta = value;
+        while delta != 0 {
+            out.push(serde_json::json!(value - delta));
+            delta /= 2;
+        }
+        return out;
+    }
+    match number.as_f64() {
+        Some(value) if value != 0.0 && value.is_finite() => {
+            let mut out = vec![serde_json::json!(0.0)];
+            if value.trunc() != value {
+                out.push(serde_json::json!(value.trunc()));
+            }
+            out
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+#[path = "value_shrink/tests.rs"]
+mod tests;
@@ -0,0 +1,116 @@
+use super::*;
+
+fn step(value: serde_json::Value) -> Step {
+    serde_json::from_value(value).expect("step")
+}
+
+fn candidates(value: serde_json::Value) -> Vec<serde_json::Value> {
+    step_value_candidates(&step(value))
+        .iter()
+        .map(|candidate| serde_json::to_value(candidate).expect("candidate json"))
+        .collect()
+}
+
+#[test]
+fn durations_and_counts_move_toward_zero() {
+    let sleeps: Vec<serde_json::Value> =
+        candidates(serde_json::json!({"type": "sleep", "duration": "30s"}))
+            .into_iter()
+            .map(|candidate| candidate["duration"].clone())
+            .collect();
+    assert_eq!(sleeps[0], "0ms");
+    assert_eq!(sleeps[1], "15000ms");
+    assert!(sleeps.iter().all(|d| d != "30000ms"));
+
+    let whens = candidates(serde_json::json!({
+        "type": "proc_when", "cmd": "git", "exit_code": 3, "times": 8,
+    }));
+    assert!(whens.iter().any(|c| c["times"].is_null()));
+    assert!(whens.iter().any(|c| c["times"] == 4));
+    assert!(whens.iter().any(|c| c["exit_code"] == 0));
+    assert!(whens.iter().all(|c| c["type"] == "proc_when"));
+}
+
+#[test]
+fn headers_and_args_are_removed_one_at_a_time() {
+    let requests = candidates(serde_json::json!({
+        "type": "http_request", "method": "GET", "path": "/",
+        "headers": {"a": "1", "b": "2"},
+    }));
+    assert!(
+        requests
+            .iter()
+            .any(|c| c["headers"] == serde_json::json!({"b": "2"}))
+    );
+    assert!(
+        requests
+            .iter()
+            .any(|c| c["headers"] == serde_json::json!({"a": "1"}))
+    );
+
+    let spawns = candidates(serde_json::json!({
+        "type": "proc_spawn", "cmd": "git", "args": ["a", "b", "c"],
+    }));
+    assert!(
+        spawns
+            .iter()
+            .any(|c| c["args"] == serde_json::json!(["a", "c"]))
+    );
+    assert!(spawns.iter().any(|c| c["args"] == serde_json::json!([])));
+}
+
+#[test]
+fn bodies_shrink_by_characters_or_json_structure() {
+    let text = "x".repeat(4096);
+    let plain = candidates(serde_json::json!({
+        "type": "http_when", "method": "GET", "path": "/", "status": 200, "body": text,
+    }));
+    let lengths: Vec<usize> = plain
+        .iter()
+        .filter_map(|c| c["body"].as_str().map(str::len))
+        .filter(|len| *len < text.len())
+        .collect();
+    assert!(lengths.contains(&0));
+    assert!(lengths.contains(&2048));
+    // Large strings are cut in coarse chunks so a round stays small.
+    assert!(lengths.len() < 4 * CHAR_GRANULARITY);
+
+    let json_body = candidates(serde_json::json!({
+        "type": "http_when", "method": "GET", "path": "/", "status": 200,
+        "body": r#"{"items":[1,2,3],"ok":true}"#,
+    }));
+    assert!(
+        json_body
+            .iter()
+            .any(|c| c["body"] == r#"{"items":[1,3],"ok":true}"#)
+    );
+    assert!(json_body.iter().any(|c| c["body"] == r#"{"ok":true}"#));
+    for candidate in json_body.iter().filter_map(|c| c["body"].as_str()) {
+        serde_json::from_str::<serde_json::Value>(candidate).expect("body stays JSON");
+    }
+}
+
+#[test]
+fn nested_steps_keep_their_tags_and_no_candidate_is_a_no_op() {
+    let original = serde_json::json!({
+        "type": "assert_throws",
+        "steps": [
+            {"type": "sleep", "duration": "1s"},
+            {"type": "assert_ok", "value": false, "msg": null},
+        ],
+    });
+    let nested = candidates(original.clone());
+    let normalized = serde_json::to_value(step(original)).expect("json");
+    assert!(nested.iter().all(|c| *c != normalized));
+    assert!(
+        nested
+            .iter()
+            .any(|c| c["steps"].as_array().is_some_and(|s| s.len() == 1))
+    );
+    assert!(nested.iter().any(|c| c["steps"][0]["duration"] == "0ms"));
+    assert!(nested.iter().all(|c| {
+        c["steps"]
+            .as_array()
+            .is_none_or(|steps| steps.iter().all(|s| s["type"].is_string()))
+    }));
+}
@@ -0,0 +1,441 @@
+//! `--watch` for `fozzy test` and `fozzy run`.
+//!
+//! A scenario depends on its own file, the grammar file of its `grammar` block, and
+//! the host files it reads with `fs_read_assert`. Scenario files have no include
+//! directive, so these are all of its inputs. Paths any scenario writes with
+//! `fs_write` are never watched, so a run cannot re-trigger itself. Files matched by
+//! `--watch-src` globs are attributed to scenarios through the `map suites` scenario
+//! facts; a source change that no scenario is attributed to re-runs every scenario.
+//!
+//! Changes are detected by comparing file size and mtime. On Linux, inotify on the
+//! watched directories wakes the watcher as soon as something changes, and a slower
+//! rescan picks up new subdirectories; elsewhere, or when inotify is unavailable, the
+//! watcher polls.
+
+use serde::Serialize;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::{Config, ExitStatus, Finding, FozzyError, FozzyResult, RunSummary, TestCounts};
+
+/// How often the polling fallback rescans watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// With inotify, a full rescan still happens this often to see new subdirectories.
+#[cfg(target_os = "linux")]
+const INOTIFY_RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+/// Quiet period after an inotify event, so an editor's write-and-rename is one change.
+#[cfg(target_os = "linux")]
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    /// Scenario globs, defaulting to `tests/**/*.fozzy.json` like `fozzy test`.
+    pub scenarios: Vec<String>,
+    /// `--watch-src` globs for source files the scenarios exercise.
+    pub sources: Vec<String>,
+}
+
+/// One batch of scenarios to (re-)run.
+#[derive(Debug, Clone)]
+pub struct WatchCycle {
+    /// `0` for the initial run of every scenario.
+    pub cycle: u64,
+    pub changed: Vec<PathBuf>,
+    pub scenarios: Vec<PathBuf>,
+}
+
+/// Compact per-cycle summary (`--json` prints one line per cycle).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchCycleReport {
+    pub schema_version: String,
+    pub cycle: u64,
+    pub changed: Vec<String>,
+    pub scenarios: Vec<String>,
+    pub status: ExitStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestCounts>,
+    pub duration_ms: u64,
+    pub run_id: String,
+    pub findings: Vec<Finding>,
+}
+
+impl WatchCycleReport {
+    pub fn new(cycle: &WatchCycle, summary: &RunSummary) -> Self {
+        Self {
+            schema_version: "fozzy.watch_cycle.v1".to_string(),
+            cycle: cycle.cycle,
+            changed: display_paths(&cycle.changed),
+            scenarios: display_paths(&cycle.scenarios),
+            status: summary.status,
+            tests: summary.tests.clone(),
+            duration_ms: summary.duration_ms,
+            run_id: summary.identity.run_id.clone(),
+            findings: summary.findings.clone(),
+        }
+    }
+}
+
+/// Calls `on_cycle` with every scenario, then again with the affected scenarios each
+/// time a watched file changes. Only returns on an error reading the scenario set.
+pub fn watch_scenarios(
+    config: &Config,
+    opt: &WatchOptions,
+    mut on_cycle: impl FnMut(&WatchCycle),
+) -> FozzyResult<()> {
+    let patterns = crate::test_scenario_patterns(&opt.scenarios);
+    let mut graph = WatchGraph::build(&patterns, &opt.sources)?;
+    if graph.scenarios.is_empty() {
+        return Err(FozzyError::InvalidArgument(format!(
+            "no scenario files matched (patterns={patterns:?})"
+        )));
+    }
+    let roots: Vec<PathBuf> = patterns
+        .iter()
+        .chain(&opt.sources)
+        .map(|pattern| pattern_root(pattern))
+        .collect();
+    let mut notifier = Notifier::new();
+    notifier.watch(&graph, &roots);
+    let mut fingerprints = graph.fingerprints();
+    on_cycle(&WatchCycle {
+        cycle: 0,
+        changed: Vec::new(),
+        scenarios: graph.scenarios.clone(),
+    });
+
+    let cache_dir = config.base_dir.join("cache");
+    let mut cycle = 0;
+    loop {
+        notifier.wait();
+        let next = WatchGraph::build(&patterns, &opt.sources)?;
+        let next_fingerprints = next.fingerprints();
+        let changed = changed_paths(&fingerprints, &next_fingerprints);
+        if changed.is_empty() {
+            continue;
+        }
+        let scenarios = affected_scenarios(&graph, &next, &changed, Some(&cache_dir));
+        notifier.watch(&next, &roots);
+        graph = next;
+        fingerprints = next_fingerprints;
+        if scenarios.is_empty() {
+            continue;
+        }
+        cycle += 1;
+        on_cycle(&WatchCycle {
+            cycle,
+            changed,
+            scenarios,
+        });
+    }
+}
+
+type Fingerprint = Option<(u64, SystemTime)>;
+
+#[derive(Debug, Default)]
+pub(crate) struct WatchGraph {
+    pub(crate) scenarios: Vec<PathBuf>,
+    /// Watched input → scenarios that read it. Each scenario depends on itself.
+    pub(crate) dependents: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+    pub(crate) sources: BTreeSet<PathBuf>,
+}
+
+impl WatchGraph {
+    pub(crate) fn build(patterns: &[String], source_patterns: &[String]) -> FozzyResult<Self> {
+        let scenarios = crate::resolve_matching_files(patterns)?.files;
+        let mut dependents = BTreeMap::<PathBuf, BTreeSet<PathBuf>>::new();
+        let mut written = BTreeSet::new();
+        for scenario in &scenarios {
+            let inputs = scenario_inputs(scenario);
+            written.extend(inputs.writes);
+            for dependency in inputs.reads {
+                dependents
+                    .entry(dependency)
+                    .or_default()
+                    .insert(scenario.clone());
+            }
+        }
+        for path in &written {
+            if !scenarios.contains(path) {
+                dependents.remove(path);
+            }
+        }
+        let sources = if source_patterns.is_empty() {
+            BTreeSet::new()
+        } else {
+            crate::resolve_matching_files(source_patterns)?
+                .files
+                .into_iter()
+                .filter(|path| !dependents.contains_key(path) && !written.contains(path))
+                .collect()
+        };
+        Ok(Self {
+            scenarios,
+            dependents,
+            sources,
+        })
+    }
+
+    fn fingerprints(&self) -> BTreeMap<PathBuf, Fingerprint> {
+        self.dependents
+            .keys()
+            .chain(&self.sources)
+            .map(|path| {
+                let fingerprint = std::fs::metadata(path).ok().map(|meta| {
+                    (
+                        meta.len(),
+                        meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    )
+                });
+                (path.clone(), fingerprint)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ScenarioInputs {
+    pub(crate) reads: BTreeSet<PathBuf>,
+    pub(crate) writes: BTreeSet<PathBuf>,
+}
+
+/// Files a scenario reads (itself, its grammar, `fs_read_assert` fixtures) and host
+/// paths it writes. An unreadable scenario still depends on itself.
+pub(crate) fn scenario_inputs(scenario: &Path) -> ScenarioInputs {
+    let mut inputs = ScenarioInputs::default();
+    inputs.reads.insert(scenario.to_path_buf());
+    let Some(doc) = std::fs::read(scenario)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+    else {
+        return inputs;
+    };
+    if let Some(file) = doc
+        .get("grammar")
+        .and_then(|grammar| grammar.get("file"))
+        .and_then(|file| file.as_str())
+    {
+        let dir = scenario.parent().unwrap_or_else(|| Path::new(""));
+        inputs.reads.insert(dir.join(file));
+    }
+    collect_fs_paths(&doc, &mut inputs);
+    let writes = inputs.writes.clone();
+    inputs.reads.retain(|path| !writes.contains(path));
+    inputs
+}
+
+fn collect_fs_paths(value: &serde_json::Value, inputs: &mut ScenarioInputs) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let path = map
+                .get("path")
+                .and_then(|path| path.as_str())
+                .filter(|path| is_host_relative(path))
+                .map(PathBuf::from);
+            match (map.get("type").and_then(|ty| ty.as_str()), path) {
+                (Some("fs_read_assert"), Some(path)) => {
+                    inputs.reads.insert(path);
+                }
+                (Some("fs_write"), Some(path)) => {
+                    inputs.writes.insert(path);
+                }
+                _ => {}
+            }
+            for child in map.values() {
+                collect_fs_paths(child, inputs);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for child in items {
+                collect_fs_paths(child, inputs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Host fs steps only accept paths relative to the working directory.
+fn is_host_relative(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+fn changed_paths(
+    before: &BTreeMap<PathBuf, Fingerprint>,
+    after: &BTreeMap<PathBuf, Fingerprint>,
+) -> Vec<PathBuf> {
+    before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| before.get(*path).copied().flatten() != after.get(*path).copied().flatten())
+        .cloned()
+        .collect()
+}
+
+/// Scenarios of `next` to re-run for `changed`: dependents of changed inputs (new
+/// scenarios depend on themselves), plus the scenarios attributed to changed sources,
+/// or every scenario when a changed source is attributed to none.
+pub(crate) fn affected_scenarios(
+    prev: &WatchGraph,
+    next: &WatchGraph,
+    changed: &[PathBuf],
+    cache_dir: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut affected = BTreeSet::new();
+    let mut changed_sources = Vec::new();
+    for path in changed {
+        if let Some(scenarios) = next.dependents.get(path) {
+            affected.extend(scenarios.iter().cloned());
+        } else if next.sources.contains(path) || prev.sources.contains(path) {
+            changed_sources.push(path);
+        }
+    }
+    if !changed_sources.is_empty() {
+        let facts = crate::build_scenario_facts(&next.scenarios, cache_dir).facts;
+        for source in changed_sources {
+            let attributed = crate::scenarios_attributed_to_source(source, &facts);
+            if attributed.is_empty() {
+                return next.scenarios.clone();
+            }
+            affected.extend(
+                next.scenarios
+                    .iter()
+                    .filter(|scenario| attributed.contains(&scenario.display().to_string()))
+                    .cloned(),
+            );
+        }
+    }
+    next.scenarios
+        .iter()
+        .filter(|scenario| affected.contains(*scenario))
+        .cloned()
+        .collect()
+}
+
+/// Directory a glob walks from: the path before its first glob metacharacter.
+fn pattern_root(pattern: &str) -> PathBuf {
+    let prefix = pattern
+        .split(['*', '?', '[', ']', '{', '}'])
+        .next()
+        .unwrap_or_default();
+    let dir = if prefix.len() < pattern.len() && prefix.ends_with('/') {
+        Path::new(prefix.trim_end_matches('/'))
+    } else {
+        Path::new(prefix).parent().unwrap_or_else(|| Path::new(""))
+    };
+    if dir.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+fn display_paths(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect()
+}
+
+enum Notifier {
+    #[cfg(target_os = "linux")]
+    Inotify {
+        inotify: inotify::Inotify,
+        dirs: BTreeSet<PathBuf>,
+    },
+    Poll,
+}
+
+impl Notifier {
+    fn new() -> Self {
+        #[cfg(target_os = "linux")]
+        if let Ok(inotify) = inotify::Inotify::init() {
+            return Self::Inotify {
+                inotify,
+                dirs: BTreeSet::new(),
+            };
+        }
+        Self::Poll
+    }
+
+    /// Watches the directories of every watched file plus the glob roots.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    fn watch(&mut self, graph: &WatchGraph, roots: &[PathBuf]) {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Inotify { inotify, dirs } => {
+                let parents = graph
+                    .dependents
+                    .keys()
+                    .chain(&graph.sources)
+                    .map(|path| match path.parent() {
+                        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                        _ => PathBuf::from("."),
+                    });
+                let mask = inotify::WatchMask::MODIFY
+                    | inotify::WatchMask::CLOSE_WRITE
+                    | inotify::WatchMask::ATTRIB
+                    | inotify::WatchMask::CREATE
+                    | inotify::WatchMask::DELETE
+                    | inotify::WatchMask::MOVED_FROM
+                    | inotify::WatchMask::MOVED_TO;
+                for dir in parents.chain(roots.iter().cloned()) {
+                    if !dirs.contains(&dir)
+                        && dir.is_dir()
+                        && inotify.watches().add(&dir, mask).is_ok()
+                    {
+                        dirs.insert(dir);
+                    }
+                }
+            }
+            Self::Poll => {}
+        }
+    }
+
+    /// Blocks until something may have changed.
+    fn wait(&mut self) {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Inotify { inotify, .. } => {
+                let deadline = std::time::Instant::now() + INOTIFY_RESCAN_INTERVAL;
+                let mut buffer = [0u8; 4096];
+                while std::time::Instant::now() < deadline {
+                    match inotify
+                        .read_events(&mut buffer)
+                        .map(|mut events| events.next().is_some())
+                    {
+                        Ok(true) => {
+                            std::thread::sleep(DEBOUNCE);
+                            while inotify
+                                .read_events(&mut buffer)
+                                .is_ok_and(|mut events| events.next().is_some())
+                            {
+                            }
+                            return;
+                        }
+                        Ok(false) => {}
+                        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(Duration::from_millis(20));
+                        }
+                        Err(_) => {
+                            std::thread::sleep(POLL_INTERVAL);
+                            return;
+                        }
+                    }
+                }
+            }
+            Self::Poll => std::thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "watch/tests.rs"]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fozzy-watch-{name}-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    dir
+}
+
+fn write_scenario(dir: &Path, file: &str, steps: &str) -> PathBuf {
+    let path = dir.join(file);
+    std::fs::write(
+        &path,
+        format!(r#"{{"version":1,"name":"{file}","steps":[{steps}]}}"#),
+    )
+    .expect("write scenario");
+    path
+}
+
+fn graph(dir: &Path, sources: &[String]) -> WatchGraph {
+    WatchGraph::build(&[format!("{}/*.fozzy.json", dir.display())], sources).expect("graph")
+}
+
+#[test]
+fn scenario_inputs_include_grammar_and_read_only_fixtures() {
+    let dir = temp_dir("inputs");
+    let scenario = dir.join("g.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{"version":1,"name":"g","grammar":{"file":"arith.ebnf"},"steps":[
+            {"type":"fs_read_assert","path":"fixtures/in.txt","equals":"x"},
+            {"type":"fs_write","path":"out.txt","data":"y"},
+            {"type":"fs_read_assert","path":"out.txt","equals":"y"},
+            {"type":"fs_read_assert","path":"../escape.txt","equals":"z"}
+        ]}"#,
+    )
+    .expect("write scenario");
+
+    let inputs = scenario_inputs(&scenario);
+    assert_eq!(
+        inputs.reads,
+        BTreeSet::from([
+            scenario.clone(),
+            dir.join("arith.ebnf"),
+            PathBuf::from("fixtures/in.txt"),
+        ])
+    );
+    assert_eq!(inputs.writes, BTreeSet::from([PathBuf::from("out.txt")]));
+
+    std::fs::write(&scenario, b"not json").expect("write scenario");
+    assert_eq!(
+        scenario_inputs(&scenario).reads,
+        BTreeSet::from([scenario.clone()])
+    );
+}
+
+#[test]
+fn changes_rerun_only_dependent_and_new_scenarios() {
+    let dir = temp_dir("affected");
+    let a = write_scenario(&dir, "a.fozzy.json", r#"{"type":"trace_event","name":"a"}"#);
+    let b = write_scenario(&dir, "b.fozzy.json", r#"{"type":"trace_event","name":"b"}"#);
+    let before = graph(&dir, &[]);
+    assert_eq!(before.scenarios, vec![a.clone(), b.clone()]);
+
+    let c = write_scenario(&dir, "c.fozzy.json", r#"{"type":"trace_event","name":"c"}"#);
+    let after = graph(&dir, &[]);
+    let changed = changed_paths(&before.fingerprints(), &after.fingerprints());
+    assert_eq!(changed, vec![c.clone()]);
+    assert_eq!(affected_scenarios(&before, &after, &changed, None), vec![c]);
+
+    let changed = vec![b.clone()];
+    assert_eq!(affected_scenarios(&before, &after, &changed, None), vec![b]);
+
+    let after_fingerprints = after.fingerprints();
+    std::fs::remove_file(&a).expect("remove");
+    let removed = graph(&dir, &[]);
+    let changed = changed_paths(&after_fingerprints, &removed.fingerprints());
+    assert_eq!(changed, vec![a]);
+    assert!(affected_scenarios(&after, &removed, &changed, None).is_empty());
+}
+
+#[test]
+fn source_changes_rerun_attributed_scenarios_or_everything() {
+    let dir = temp_dir("sources");
+    let checkout = write_scenario(
+        &dir,
+        "checkout_flow.fozzy.json",
+        r#"{"type":"trace_event","name":"checkout"}"#,
+    );
+    let login = write_scenario(
+        &dir,
+        "login.fozzy.json",
+        r#"{"type":"trace_event","name":"login"}"#,
+    );
+    // Kept apart from the scenario dir so only the file names share tokens.
+    let src = std::env::temp_dir().join(format!("fz-src-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&src).expect("src dir");
+    std::fs::write(src.join("checkout.rs"), b"fn checkout() {}").expect("write source");
+    std::fs::write(src.join("zzqx.rs"), b"fn zzqx() {}").expect("write source");
+    let graph = graph(&dir, &[format!("{}/*.rs", src.display())]);
+    assert_eq!(graph.sources.len(), 2);
+
+    let changed = vec![src.join("checkout.rs")];
+    assert_eq!(
+        affected_scenarios(&graph, &graph, &changed, None),
+        vec![checkout.clone()]
+    );
+
+    let changed = vec![src.join("zzqx.rs")];
+    assert_eq!(
+        affected_scenarios(&graph, &graph, &changed, None),
+        vec![checkout, login]
+    );
+}
+
+#[test]
+fn pattern_roots_stop_at_the_first_glob_metacharacter() {
+    assert_eq!(
+        pattern_root("tests/**/*.fozzy.json"),
+        PathBuf::from("tests")
+    );
+    assert_eq!(pattern_root("tests/a*.json"), PathBuf::from("tests"));
+    assert_eq!(pattern_root("tests/a.fozzy.json"), PathBuf::from("tests"));
+    assert_eq!(pattern_root("**/*.rs"), PathBuf::from("."));
+    assert_eq!(pattern_root("a.fozzy.json"), PathBuf::from("."));
+}
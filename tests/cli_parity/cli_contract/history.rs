@@ -0,0 +1,144 @@
+use super::*;
+
+#[test]
+fn history_records_test_and_run_outcomes_and_answers_queries() {
+    let ws = temp_workspace("history");
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    let ok = tests.join("ok.fozzy.json");
+    std::fs::write(
+        &ok,
+        r#"{"version":1,"name":"ok","steps":[{"type":"trace_event","name":"ok"}]}"#,
+    )
+    .expect("write scenario");
+    let toggle = tests.join("toggle.fozzy.json");
+    std::fs::write(
+        &toggle,
+        r#"{"version":1,"name":"toggle","steps":[{"type":"trace_event","name":"ok"}]}"#,
+    )
+    .expect("write scenario");
+
+    let test_run = |expected: i32| {
+        let out = run_cli_in(&ws, &["test".into(), "--det".into(), "--json".into()]);
+        assert_eq!(
+            out.status.code(),
+            Some(expected),
+            "stderr={}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        parse_json_stdout(&out)["identity"]["runId"]
+            .as_str()
+            .expect("run id")
+            .to_string()
+    };
+    test_run(0);
+    std::fs::write(
+        &toggle,
+        r#"{"version":1,"name":"toggle","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+    let first_fail = test_run(1);
+    let out = run_cli_in(
+        &ws,
+        &[
+            "run".into(),
+            "tests/toggle.fozzy.json".into(),
+            "--det".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(out.status.code(), Some(1));
+
+    let history = std::fs::read_to_string(ws.join(".fozzy/history.jsonl")).expect("history");
+    assert_eq!(history.lines().count(), 5);
+
+    let query = |args: &[&str]| {
+        let mut argv = vec!["history".to_string()];
+        argv.extend(args.iter().map(|arg| arg.to_string()));
+        argv.push("--json".to_string());
+        let out = run_cli_in(&ws, &argv);
+        assert_eq!(
+            out.status.code(),
+            Some(0),
+            "stderr={}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        parse_json_stdout(&out)
+    };
+
+    let pass_rate = query(&["pass-rate", "--last", "2"]);
+    assert_eq!(pass_rate["schemaVersion"], "fozzy.history.v1");
+    let scenarios = pass_rate["scenarios"].as_array().expect("scenarios");
+    assert_eq!(scenarios.len(), 2);
+    assert_eq!(scenarios[0]["path"], "tests/ok.fozzy.json");
+    assert_eq!(scenarios[0]["passRatePct"], 100.0);
+    assert_eq!(scenarios[1]["path"], "tests/toggle.fozzy.json");
+    assert_eq!(scenarios[1]["runs"], 2);
+    assert_eq!(scenarios[1]["passRatePct"], 0.0);
+
+    let first_failure = query(&["first-failure", "--scenario", "tests/toggle*"]);
+    let scenarios = first_failure["scenarios"].as_array().expect("scenarios");
+    assert_eq!(scenarios.len(), 1);
+    assert_eq!(scenarios[0]["failingRuns"], 2);
+    assert_eq!(scenarios[0]["firstFailure"]["runId"], first_fail.as_str());
+    assert!(scenarios[0]["lastPass"]["runId"].is_string());
+
+    let trend = query(&["trend", "--metric", "p99"]);
+    assert_eq!(
+        trend["scenarios"][1]["points"].as_array().map(Vec::len),
+        Some(3)
+    );
+
+    let flake_rate = query(&["flake-rate"]);
+    assert_eq!(flake_rate["scenarios"][1]["flips"], 1);
+    assert_eq!(flake_rate["scenarios"][1]["flaky"], 0);
+}
+
+#[test]
+fn unwritable_history_warns_without_failing_the_run() {
+    let ws = temp_workspace("history-unwritable");
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    std::fs::write(
+        tests.join("ok.fozzy.json"),
+        r#"{"version":1,"name":"ok","steps":[{"type":"trace_event","name":"ok"}]}"#,
+    )
+    .expect("write scenario");
+    // A directory where the index file belongs makes every append fail.
+    std::fs::create_dir_all(ws.join(".fozzy/history.jsonl")).expect("block history");
+
+    let out = run_cli_in(&ws, &["test".into(), "--det".into(), "--json".into()]);
+    assert_eq!(out.status.code(), Some(0));
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("failed to append run history"),
+        "stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let run_id = parse_json_stdout(&out)["identity"]["runId"]
+        .as_str()
+        .expect("run id")
+        .to_string();
+    assert!(
+        ws.join(".fozzy/runs")
+            .join(run_id)
+            .join("report.json")
+            .exists()
+    );
+
+    let single = run_cli_in(
+        &ws,
+        &[
+            "run".into(),
+            "tests/ok.fozzy.json".into(),
+            "--det".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(single.status.code(), Some(0));
+    parse_json_stdout(&single);
+    assert!(
+        String::from_utf8_lossy(&single.stderr).contains("failed to append run history"),
+        "stderr={}",
+        String::from_utf8_lossy(&single.stderr)
+    );
+}
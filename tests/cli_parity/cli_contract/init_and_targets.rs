@@ -68,3 +68,80 @@ fn fuzz_supports_scenario_target() {
     let doc = parse_json_stdout(&out);
     assert_eq!(doc.get("mode").and_then(|v| v.as_str()), Some("fuzz"));
 }
+
+#[test]
+fn fuzz_triage_buckets_crash_inputs() {
+    let ws = temp_workspace("fuzz-triage");
+    let scenario = ws.join("crash.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{"version":1,"name":"crash","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+    let crashes = ws.join("crashes");
+    std::fs::create_dir_all(&crashes).expect("crash dir");
+    std::fs::write(crashes.join("crash-1.bin"), b"first").expect("write crash");
+    std::fs::write(crashes.join("crash-2.bin"), b"second").expect("write crash");
+    let out = run_cli(&[
+        "fuzz".into(),
+        "triage".into(),
+        crashes.to_string_lossy().to_string(),
+        "--target".into(),
+        format!("scenario:{}", scenario.display()),
+        "--json".into(),
+    ]);
+    assert_eq!(
+        out.status.code(),
+        Some(0),
+        "fuzz triage should succeed, stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let doc = parse_json_stdout(&out);
+    assert_eq!(
+        doc.get("schemaVersion").and_then(|v| v.as_str()),
+        Some("fozzy.fuzz_triage.v1")
+    );
+    let buckets = doc
+        .get("buckets")
+        .and_then(|v| v.as_array())
+        .expect("buckets");
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0].get("count").and_then(|v| v.as_u64()), Some(2));
+    assert!(crashes.join("triage").join("triage.json").exists());
+}
+
+#[test]
+fn fuzz_openapi_scenarios_emits_starter_http_scenarios() {
+    let ws = temp_workspace("fuzz-openapi-scenarios");
+    let spec = ws.join("api.json");
+    std::fs::write(
+        &spec,
+        r#"{"openapi":"3.0.0","info":{"title":"t","version":"1"},
+            "servers":[{"url":"http://127.0.0.1:9/v1"}],
+            "paths":{"/health":{"get":{"responses":{"200":{"description":"ok"}}}}}}"#,
+    )
+    .expect("write spec");
+    let out_dir = ws.join("scenarios");
+    let out = run_cli(&[
+        "fuzz".into(),
+        "openapi-scenarios".into(),
+        spec.to_string_lossy().to_string(),
+        "--out".into(),
+        out_dir.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(
+        out.status.code(),
+        Some(0),
+        "openapi-scenarios should succeed, stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let doc = parse_json_stdout(&out);
+    assert_eq!(doc.get("operations").and_then(|v| v.as_u64()), Some(1));
+    let scenario: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(out_dir.join("get-health.fozzy.json")).expect("scenario"),
+    )
+    .expect("scenario json");
+    assert_eq!(scenario["steps"][0]["path"], "http://127.0.0.1:9/v1/health");
+    assert_eq!(scenario["steps"][0]["expect_status"], 200);
+}
@@ -3,6 +3,12 @@ use super::*;
 mod corpus_and_reporting;
 mod global_flags;
 mod help_and_schema;
+mod history;
 mod init_and_targets;
 mod scenario_validation;
+mod test_filters;
 mod test_mode_guards;
+mod test_reporters;
+mod test_retries;
+mod test_sharding;
+mod test_watch;
@@ -0,0 +1,155 @@
+use super::*;
+
+fn write_tagged_suite(ws: &Path) {
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    std::fs::write(
+        tests.join("checkout.fozzy.json"),
+        r#"{"version":1,"name":"checkout","tags":["smoke"],"owner":"payments","steps":[{"type":"trace_event","name":"checkout"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("soak.fozzy.json"),
+        r#"{"version":1,"name":"soak","tags":["smoke","slow"],"steps":[{"type":"trace_event","name":"soak"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("broken.fozzy.json"),
+        r#"{"version":1,"name":"broken","owner":"search","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+}
+
+fn cli(ws: &Path, args: &[&str]) -> std::process::Output {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    run_cli_in(ws, &args)
+}
+
+fn selected(doc: &serde_json::Value) -> Vec<String> {
+    doc["scenarios"]
+        .as_array()
+        .expect("scenarios")
+        .iter()
+        .filter(|entry| entry["selected"] == true)
+        .map(|entry| entry["path"].as_str().expect("path").to_string())
+        .collect()
+}
+
+#[test]
+fn test_filter_expressions_select_by_metadata_and_explain_with_list() {
+    let ws = temp_workspace("test-filters");
+    write_tagged_suite(&ws);
+
+    let out = cli(
+        &ws,
+        &[
+            "test",
+            "--filter",
+            "tag:smoke and not tag:slow",
+            "--list",
+            "--json",
+        ],
+    );
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let list = parse_json_stdout(&out);
+    assert_eq!(list["schemaVersion"], "fozzy.scenario_selection.v1");
+    assert_eq!(
+        (list["total"].as_u64(), list["selected"].as_u64()),
+        (Some(3), Some(1))
+    );
+    assert_eq!(selected(&list), vec!["tests/checkout.fozzy.json"]);
+    let soak = list["scenarios"]
+        .as_array()
+        .expect("scenarios")
+        .iter()
+        .find(|entry| entry["name"] == "soak")
+        .expect("soak entry");
+    assert_eq!(soak["reason"], "tag:smoke[yes] and not tag:slow[yes]");
+
+    let out = cli(
+        &ws,
+        &[
+            "test",
+            "--det",
+            "--filter",
+            "owner:payments or owner:search",
+            "--json",
+        ],
+    );
+    let run = parse_json_stdout(&out);
+    assert_eq!(run["tests"]["passed"], 1);
+    assert_eq!(run["tests"]["failed"], 1);
+    assert_eq!(run["tests"]["skipped"], 1);
+
+    let out = cli(
+        &ws,
+        &["test", "--filter", "last:failed", "--list", "--json"],
+    );
+    assert_eq!(
+        selected(&parse_json_stdout(&out)),
+        vec!["tests/broken.fozzy.json"]
+    );
+
+    let out = cli(&ws, &["test", "--filter", "tag:smoke and", "--json"]);
+    assert!(!out.status.success(), "malformed filter must be rejected");
+    assert!(
+        String::from_utf8_lossy(&out.stdout).contains("invalid filter")
+            || String::from_utf8_lossy(&out.stderr).contains("invalid filter")
+    );
+}
+
+#[test]
+fn gate_full_and_map_suites_share_the_filter_language() {
+    let ws = temp_workspace("filters-everywhere");
+    write_tagged_suite(&ws);
+
+    let out = cli(
+        &ws,
+        &[
+            "gate",
+            "--scope",
+            "checkout,soak",
+            "--filter",
+            "not tag:slow",
+            "--list",
+            "--json",
+        ],
+    );
+    let gate = parse_json_stdout(&out);
+    assert_eq!(gate["total"], 2);
+    assert_eq!(selected(&gate), vec!["tests/checkout.fozzy.json"]);
+
+    let out = cli(
+        &ws,
+        &[
+            "full",
+            "--scenario-filter",
+            "name~^(soak|broken)$",
+            "--list",
+            "--json",
+        ],
+    );
+    assert_eq!(
+        selected(&parse_json_stdout(&out)),
+        vec!["tests/broken.fozzy.json", "tests/soak.fozzy.json"]
+    );
+
+    let out = cli(
+        &ws,
+        &[
+            "map",
+            "suites",
+            "--filter",
+            "kind:distributed",
+            "--list",
+            "--json",
+        ],
+    );
+    let map = parse_json_stdout(&out);
+    assert_eq!(map["total"], 3);
+    assert!(selected(&map).is_empty());
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+fn reporters_workspace() -> std::path::PathBuf {
+    let ws = temp_workspace("reporters");
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    std::fs::write(
+        tests.join("ok.fozzy.json"),
+        r#"{"version":1,"name":"ok","steps":[{"type":"trace_event","name":"hello"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("boom.fozzy.json"),
+        r#"{"version":1,"name":"boom","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+    ws
+}
+
+fn artifacts_ls(ws: &std::path::Path, run_id: &str) -> serde_json::Value {
+    let out = run_cli_in(
+        ws,
+        &[
+            "artifacts".into(),
+            "ls".into(),
+            run_id.to_string(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(
+        out.status.code(),
+        Some(0),
+        "artifacts ls stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    parse_json_stdout(&out)
+}
+
+#[test]
+fn jsonl_reporter_streams_run_scenario_and_step_events() {
+    let ws = reporters_workspace();
+    let out = run_cli_in(
+        &ws,
+        &[
+            "test".into(),
+            "--det".into(),
+            "--reporter".into(),
+            "jsonl".into(),
+        ],
+    );
+    assert_eq!(
+        out.status.code(),
+        Some(1),
+        "stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let events: Vec<serde_json::Value> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every stdout line is an event"))
+        .collect();
+    let names: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(names.first(), Some(&"run_started"));
+    assert_eq!(names.last(), Some(&"run_finished"));
+    assert_eq!(
+        names
+            .iter()
+            .filter(|name| **name == "scenario_started")
+            .count(),
+        2
+    );
+    assert_eq!(
+        names
+            .iter()
+            .filter(|name| **name == "scenario_finished")
+            .count(),
+        2
+    );
+    assert!(
+        events
+            .iter()
+            .any(|event| event["event"] == "step" && event["name"] == "hello")
+    );
+    assert!(names.contains(&"finding"));
+    let run_id = events[0]["runId"].as_str().expect("run id").to_string();
+    assert!(events.iter().all(|event| {
+        event["schemaVersion"] == "fozzy.reporter_event.v1" && event["runId"] == run_id.as_str()
+    }));
+    assert_eq!(events.last().expect("last")["status"], "fail");
+
+    let artifact = std::fs::read_to_string(ws.join(format!(".fozzy/runs/{run_id}/report.jsonl")))
+        .expect("report.jsonl");
+    let run_level: Vec<String> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .zip(&names)
+        .filter(|(_, name)| matches!(**name, "run_started" | "finding" | "run_finished"))
+        .map(|(line, _)| line.to_string())
+        .collect();
+    assert_eq!(artifact.lines().collect::<Vec<_>>(), run_level);
+    assert!(
+        artifacts_ls(&ws, &run_id)
+            .to_string()
+            .contains("report.jsonl")
+    );
+}
+
+#[test]
+fn tap_sarif_and_github_reporters_write_validated_artifacts() {
+    let ws = reporters_workspace();
+    for (reporter, artifact) in [
+        ("tap", "report.tap"),
+        ("sarif", "report.sarif"),
+        ("github", "github-annotations.txt"),
+    ] {
+        let out = run_cli_in(
+            &ws,
+            &[
+                "test".into(),
+                "--det".into(),
+                "--reporter".into(),
+                reporter.into(),
+                "--json".into(),
+            ],
+        );
+        assert_eq!(out.status.code(), Some(1), "{reporter}");
+        let run_id = parse_json_stdout(&out)["identity"]["runId"]
+            .as_str()
+            .expect("run id")
+            .to_string();
+        let path = ws.join(format!(".fozzy/runs/{run_id}/{artifact}"));
+        let text = std::fs::read_to_string(&path).expect("reporter artifact");
+        match reporter {
+            "tap" => assert!(text.starts_with("TAP version 14\n1..1\nnot ok 1 - ")),
+            "sarif" => {
+                let doc: serde_json::Value = serde_json::from_str(&text).expect("sarif");
+                assert_eq!(doc["runs"][0]["results"][0]["level"], "error");
+            }
+            _ => assert!(text.starts_with("::error "), "{text}"),
+        }
+        assert!(artifacts_ls(&ws, &run_id).to_string().contains(artifact));
+
+        std::fs::write(&path, "tampered\n").expect("tamper");
+        let ls = run_cli_in(
+            &ws,
+            &[
+                "artifacts".into(),
+                "ls".into(),
+                run_id.clone(),
+                "--json".into(),
+            ],
+        );
+        assert_ne!(ls.status.code(), Some(0), "{reporter} tamper not detected");
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+#[cfg(unix)]
+#[test]
+fn test_retries_classify_flakes_and_quarantine_keeps_the_run_green() {
+    let ws = temp_workspace("test-retries");
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    std::fs::write(
+        tests.join("flaky.fozzy.json"),
+        r#"{"version":1,"name":"flaky","steps":[{"type":"proc_spawn","cmd":"/bin/sh","args":["-c","test -f marker || { touch marker; exit 1; }"],"expect_exit":0}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("broken.fozzy.json"),
+        r#"{"version":1,"name":"broken","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("ok.fozzy.json"),
+        r#"{"version":1,"name":"ok","steps":[{"type":"trace_event","name":"ok"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        ws.join("quarantine.txt"),
+        "# tracked in the bug tracker\ntests/broken.fozzy.json\n",
+    )
+    .expect("write quarantine list");
+
+    let out = run_cli_in(
+        &ws,
+        &[
+            "--proc-backend".into(),
+            "host".into(),
+            "test".into(),
+            "--det".into(),
+            "--retries".into(),
+            "2".into(),
+            "--quarantine".into(),
+            "quarantine.txt".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(
+        out.status.code(),
+        Some(0),
+        "stderr={}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let doc = parse_json_stdout(&out);
+    assert_eq!(doc["status"], "pass");
+    assert_eq!(doc["tests"]["passed"], 2);
+    assert_eq!(doc["tests"]["failed"], 0);
+    assert_eq!(doc["tests"]["flaky"], 1);
+    assert_eq!(doc["tests"]["quarantined"], 1);
+
+    let retries = doc["retries"].as_array().expect("retries");
+    let broken = retries
+        .iter()
+        .find(|retry| retry["path"] == "tests/broken.fozzy.json")
+        .expect("broken evidence");
+    assert_eq!(broken["classification"], "deterministic");
+    assert_eq!(broken["quarantined"], true);
+    assert_eq!(broken["attempts"].as_array().map(Vec::len), Some(2));
+    let flaky = retries
+        .iter()
+        .find(|retry| retry["path"] == "tests/flaky.fozzy.json")
+        .expect("flaky evidence");
+    assert_eq!(flaky["classification"], "flaky");
+    assert_eq!(flaky["attempts"][0]["seed"], flaky["seed"]);
+    assert_eq!(flaky["attempts"][0]["sameSeed"], true);
+
+    let run_id = doc["identity"]["runId"].as_str().expect("run id");
+    let report = run_cli_in(
+        &ws,
+        &[
+            "report".into(),
+            "flaky".into(),
+            run_id.into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(
+        report.status.code(),
+        Some(0),
+        "stderr={}",
+        String::from_utf8_lossy(&report.stderr)
+    );
+    let report = parse_json_stdout(&report);
+    assert_eq!(report["isFlaky"], true);
+    assert_eq!(report["flakeRatePct"], 50.0);
+    let scenario = report["scenarios"]
+        .as_array()
+        .expect("scenarios")
+        .iter()
+        .find(|scenario| scenario["path"] == "tests/flaky.fozzy.json")
+        .expect("flaky scenario");
+    assert_eq!(scenario["passedSeeds"], serde_json::json!([flaky["seed"]]));
+    assert_eq!(scenario["failedSeeds"], serde_json::json!([flaky["seed"]]));
+
+    let without_quarantine = run_cli_in(
+        &ws,
+        &[
+            "test".into(),
+            "--det".into(),
+            "--retries".into(),
+            "1".into(),
+            "tests/broken.fozzy.json".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(without_quarantine.status.code(), Some(1));
+}
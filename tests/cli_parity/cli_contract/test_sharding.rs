@@ -0,0 +1,241 @@
+use super::*;
+
+fn write_shard_suite(ws: &Path) {
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    for name in ["a", "b", "c", "d", "e"] {
+        std::fs::write(
+            tests.join(format!("{name}.fozzy.json")),
+            format!(
+                r#"{{"version":1,"name":"{name}","steps":[{{"type":"sleep","duration":"1ms"}}]}}"#
+            ),
+        )
+        .expect("write scenario");
+    }
+    std::fs::write(
+        tests.join("f.fozzy.json"),
+        r#"{"version":1,"name":"f","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("write failing scenario");
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).expect("create dir");
+    for entry in std::fs::read_dir(from).expect("read dir").flatten() {
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).expect("copy file");
+        }
+    }
+}
+
+fn shard_test(ws: &Path, shard: &str, strategy: &str) -> serde_json::Value {
+    let out = run_cli_in(
+        ws,
+        &[
+            "test".into(),
+            "--det".into(),
+            "--seed".into(),
+            "7".into(),
+            "--shard".into(),
+            shard.into(),
+            "--shard-strategy".into(),
+            strategy.into(),
+            "--json".into(),
+        ],
+    );
+    parse_json_stdout(&out)
+}
+
+#[test]
+fn test_shards_partition_scenarios_and_merge_into_one_summary() {
+    let ws = temp_workspace("test-shards");
+    write_shard_suite(&ws);
+
+    let shards: Vec<serde_json::Value> = (1..=3)
+        .map(|index| shard_test(&ws, &format!("{index}/3"), "count"))
+        .collect();
+    let ran: u64 = shards
+        .iter()
+        .map(|doc| {
+            doc["tests"]["passed"].as_u64().unwrap_or(0)
+                + doc["tests"]["failed"].as_u64().unwrap_or(0)
+        })
+        .sum();
+    assert_eq!(ran, 6, "every scenario runs in exactly one shard");
+
+    let artifacts: Vec<PathBuf> = shards
+        .iter()
+        .map(|doc| resolve_identity_artifacts_dir(&ws, doc))
+        .collect();
+    let timings: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(artifacts[0].join("timings.json")).expect("timings"))
+            .expect("timings json");
+    assert_eq!(timings["schemaVersion"], "fozzy.test_timings.v1");
+    assert_eq!(timings["scenarios"].as_array().map(Vec::len), Some(2));
+
+    let merged_xml = ws.join("merged.xml");
+    let merge = |format: &str, out: Option<&Path>| {
+        let mut args = vec!["report".to_string(), "merge".to_string()];
+        args.push(artifacts[0].join("report.json").display().to_string());
+        args.push(
+            shards[1]["identity"]["artifactsDir"]
+                .as_str()
+                .expect("artifacts dir")
+                .to_string(),
+        );
+        args.push(json_run_id(&shards[2]));
+        args.extend(["--format".to_string(), format.to_string()]);
+        if let Some(out) = out {
+            args.extend(["--out".to_string(), out.display().to_string()]);
+        }
+        args.push("--json".to_string());
+        let output = run_cli_in(&ws, &args);
+        assert_eq!(
+            output.status.code(),
+            Some(0),
+            "merge failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_json_stdout(&output)
+    };
+    let merged = merge("json", None);
+    assert_eq!(merged["status"], "fail");
+    assert_eq!(merged["mode"], "test");
+    assert_eq!(merged["tests"]["passed"], 5);
+    assert_eq!(merged["tests"]["failed"], 1);
+    assert_eq!(merged["tests"]["skipped"], 0);
+
+    let junit = merge("junit", Some(&merged_xml));
+    assert_eq!(junit["format"], "junit");
+    let xml = std::fs::read_to_string(&merged_xml).expect("merged junit");
+    assert!(xml.contains("<testsuite"), "{xml}");
+
+    let rejected = run_cli_in(
+        &ws,
+        &[
+            "report".into(),
+            "merge".into(),
+            json_run_id(&shards[0]),
+            json_run_id(&shards[0]),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(
+        rejected.status.code(),
+        Some(2),
+        "duplicate shards are rejected"
+    );
+
+    let partial = |extra: &[&str]| {
+        let mut args = vec![
+            "report".to_string(),
+            "merge".to_string(),
+            json_run_id(&shards[0]),
+            json_run_id(&shards[2]),
+            "--json".to_string(),
+        ];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        run_cli_in(&ws, &args)
+    };
+    let incomplete = partial(&[]);
+    assert_eq!(
+        incomplete.status.code(),
+        Some(2),
+        "a missing shard is rejected"
+    );
+    assert!(
+        String::from_utf8_lossy(&incomplete.stdout).contains("missing 2/3"),
+        "{}",
+        String::from_utf8_lossy(&incomplete.stdout)
+    );
+    let allowed = partial(&["--allow-partial"]);
+    assert_eq!(allowed.status.code(), Some(0));
+    let allowed = parse_json_stdout(&allowed);
+    let counted = allowed["tests"]["passed"].as_u64().unwrap_or(0)
+        + allowed["tests"]["failed"].as_u64().unwrap_or(0);
+    assert_eq!(counted, 4);
+}
+
+#[test]
+fn timing_shards_use_previous_run_durations_and_keep_scenario_seeds() {
+    let ws = temp_workspace("test-shards-timing");
+    write_shard_suite(&ws);
+
+    let full = run_cli_in(
+        &ws,
+        &[
+            "test".into(),
+            "--det".into(),
+            "--seed".into(),
+            "7".into(),
+            "--json".into(),
+        ],
+    );
+    let full = parse_json_stdout(&full);
+    assert_eq!(full["tests"]["failed"], 1);
+
+    // Every shard must partition against the same history, as CI machines restoring one
+    // cache would; otherwise shard 1's own timings could reshuffle shard 2.
+    let runs = ws.join(".fozzy/runs");
+    let history = ws.join("runs-history");
+    copy_dir(&runs, &history);
+    let shards: Vec<serde_json::Value> = (1..=2)
+        .map(|index| {
+            std::fs::remove_dir_all(&runs).expect("clear runs");
+            copy_dir(&history, &runs);
+            shard_test(&ws, &format!("{index}/2"), "timing")
+        })
+        .collect();
+    let failed: u64 = shards
+        .iter()
+        .map(|doc| doc["tests"]["failed"].as_u64().unwrap_or(0))
+        .sum();
+    let passed: u64 = shards
+        .iter()
+        .map(|doc| doc["tests"]["passed"].as_u64().unwrap_or(0))
+        .sum();
+    assert_eq!((passed, failed), (5, 1));
+    // Sharded runs record a finding with the same scenario seed as the full run.
+    let failing = shards
+        .iter()
+        .find(|doc| doc["tests"]["failed"] == 1)
+        .expect("failing shard");
+    assert_eq!(failing["findings"], full["findings"]);
+
+    // A corrupt timings.json is skipped and reported on stderr, keeping stdout JSON.
+    let broken = runs.join("broken");
+    std::fs::create_dir_all(&broken).expect("broken run dir");
+    std::fs::write(broken.join("timings.json"), b"{").expect("write broken timings");
+    let warned = run_cli_in(
+        &ws,
+        &[
+            "test".into(),
+            "--det".into(),
+            "--shard".into(),
+            "1/2".into(),
+            "--shard-strategy".into(),
+            "timing".into(),
+            "--json".into(),
+        ],
+    );
+    parse_json_stdout(&warned);
+    assert!(
+        String::from_utf8_lossy(&warned.stderr).contains("skipping invalid test timings"),
+        "{}",
+        String::from_utf8_lossy(&warned.stderr)
+    );
+
+    let invalid = run_cli_in(
+        &ws,
+        &[
+            "test".into(),
+            "--shard".into(),
+            "3/2".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(invalid.status.code(), Some(2));
+}
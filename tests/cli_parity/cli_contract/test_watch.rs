@@ -0,0 +1,89 @@
+use super::*;
+
+use std::io::BufRead;
+use std::process::Stdio;
+
+fn next_cycle(lines: &mpsc::Receiver<String>) -> serde_json::Value {
+    loop {
+        let line = lines
+            .recv_timeout(Duration::from_secs(20))
+            .expect("watch cycle line");
+        if let Ok(doc) = serde_json::from_str::<serde_json::Value>(&line)
+            && doc["schemaVersion"] == "fozzy.watch_cycle.v1"
+        {
+            return doc;
+        }
+    }
+}
+
+#[test]
+fn test_watch_reruns_only_scenarios_whose_inputs_changed() {
+    let ws = temp_workspace("test-watch");
+    let tests = ws.join("tests");
+    std::fs::create_dir_all(ws.join("fixtures")).expect("create fixtures dir");
+    std::fs::create_dir_all(&tests).expect("create tests dir");
+    std::fs::write(ws.join("fixtures/in.txt"), "hello").expect("write fixture");
+    std::fs::write(
+        tests.join("a.fozzy.json"),
+        r#"{"version":1,"name":"a","steps":[{"type":"fs_read_assert","path":"fixtures/in.txt","equals":"hello"}]}"#,
+    )
+    .expect("write scenario");
+    std::fs::write(
+        tests.join("b.fozzy.json"),
+        r#"{"version":1,"name":"b","steps":[{"type":"trace_event","name":"b"}]}"#,
+    )
+    .expect("write scenario");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fozzy"))
+        .current_dir(&ws)
+        .args(["test", "--watch", "--fs-backend", "host", "--json"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn watch");
+    let stdout = child.stdout.take().expect("watch stdout");
+    let (tx, lines) = mpsc::channel();
+    thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+        {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let initial = next_cycle(&lines);
+    assert_eq!(initial["cycle"], 0);
+    assert_eq!(
+        initial["scenarios"],
+        serde_json::json!(["tests/a.fozzy.json", "tests/b.fozzy.json"])
+    );
+    assert_eq!(initial["status"], "pass");
+
+    std::fs::write(
+        tests.join("b.fozzy.json"),
+        r#"{"version":1,"name":"b","steps":[{"type":"fail","message":"boom"}]}"#,
+    )
+    .expect("edit scenario");
+    let edited = next_cycle(&lines);
+    assert_eq!(edited["cycle"], 1);
+    assert_eq!(
+        edited["scenarios"],
+        serde_json::json!(["tests/b.fozzy.json"])
+    );
+    assert_eq!(edited["status"], "fail");
+
+    std::fs::write(ws.join("fixtures/in.txt"), "changed").expect("edit fixture");
+    let fixture = next_cycle(&lines);
+    assert_eq!(fixture["changed"], serde_json::json!(["fixtures/in.txt"]));
+    assert_eq!(
+        fixture["scenarios"],
+        serde_json::json!(["tests/a.fozzy.json"])
+    );
+    assert_eq!(fixture["tests"]["failed"], 1);
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
@@ -2,7 +2,16 @@ use super::*;
 
 mod artifact_manifests;
 mod recorded_identity;
+mod replay_debugger;
 mod replay_paths;
 mod replay_reports;
+mod replay_snapshots;
+mod replay_whatif;
 mod shrink_behavior;
 mod strict_validation;
+mod trace_archive;
+mod trace_diff;
+mod trace_export;
+mod trace_redact;
+mod trace_signing;
+mod trace_upgrade;
@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn replay_debugger_script_steps_back_and_breaks_on_decisions() {
+    let ws = temp_workspace("replay-debugger");
+    let trace = ws.join("example.fozzy");
+    let run = run_cli(&[
+        "run".into(),
+        "tests/example.fozzy.json".into(),
+        "--det".into(),
+        "--seed".into(),
+        "7".into(),
+        "--record".into(),
+        trace.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(0), "run should pass");
+
+    let script = ws.join("debug.txt");
+    std::fs::write(
+        &script,
+        "# comment lines are skipped\nbreak decision rand_u64\ncontinue\nkv\nback 1\nkv\nclock\ndelete 0\ncontinue\nquit\n",
+    )
+    .expect("write script");
+
+    let out = run_cli(&[
+        "replay".into(),
+        trace.to_string_lossy().to_string(),
+        "--debug-script".into(),
+        script.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(out.status.code(), Some(0), "debugger should exit cleanly");
+    let doc = parse_json_stdout(&out);
+    assert_eq!(
+        doc.get("schemaVersion").and_then(|v| v.as_str()),
+        Some("fozzy.replay_debug.v1")
+    );
+    let transcript = doc
+        .get("transcript")
+        .and_then(|v| v.as_array())
+        .expect("transcript");
+    assert_eq!(transcript.len(), 9);
+    let result = |i: usize| transcript[i].get("result").expect("result");
+
+    assert_eq!(result(1)["breakpoint"]["id"], 0);
+    assert_eq!(result(1)["position"], 2);
+    assert!(
+        result(2)["kv"].get("rand").is_some(),
+        "rand stored after step"
+    );
+    assert_eq!(result(3)["position"], 1);
+    assert_eq!(result(4)["kv"], serde_json::json!({}));
+    assert_eq!(result(5)["clock"]["nowMs"], 0);
+    assert_eq!(result(7)["status"], "pass");
+    assert_eq!(doc.get("status").and_then(|v| v.as_str()), Some("pass"));
+}
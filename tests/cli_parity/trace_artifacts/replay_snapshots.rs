@@ -0,0 +1,95 @@
+use super::*;
+
+#[test]
+fn replay_from_step_resumes_from_recorded_snapshot() {
+    let ws = temp_workspace("replay-snapshots");
+    std::fs::write(ws.join("fozzy.toml"), "[snapshots]\nevery_steps = 2\n").expect("config");
+    let steps: Vec<serde_json::Value> = (0..6)
+        .map(|i| {
+            serde_json::json!({"type": "set_kv", "key": format!("k{i}"), "value": format!("v{i}")})
+        })
+        .chain([
+            serde_json::json!({"type": "rand_u64", "key": "r"}),
+            serde_json::json!({"type": "get_kv_assert", "key": "k0", "equals": "v0"}),
+        ])
+        .collect();
+    std::fs::write(
+        ws.join("long.fozzy.json"),
+        serde_json::to_vec(&serde_json::json!({"version": 1, "name": "long", "steps": steps}))
+            .expect("scenario"),
+    )
+    .expect("write scenario");
+
+    let run = run_cli_in(
+        &ws,
+        &[
+            "run".into(),
+            "long.fozzy.json".into(),
+            "--det".into(),
+            "--seed".into(),
+            "5".into(),
+            "--record".into(),
+            "long.fozzy".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(run.status.code(), Some(0), "run should pass");
+    let trace = read_trace_json(&ws.join("long.fozzy"));
+    let positions: Vec<u64> = trace["snapshots"]
+        .as_array()
+        .expect("snapshots")
+        .iter()
+        .filter_map(|s| s["position"].as_u64())
+        .collect();
+    assert_eq!(positions, vec![2, 4, 6]);
+
+    let replay = run_cli_in(
+        &ws,
+        &[
+            "replay".into(),
+            "long.fozzy".into(),
+            "--from-step".into(),
+            "5".into(),
+            "--dump-events".into(),
+            "--json".into(),
+        ],
+    );
+    assert_eq!(replay.status.code(), Some(0));
+    let doc = parse_json_stdout(&replay);
+    assert_eq!(doc.get("status").and_then(|v| v.as_str()), Some("pass"));
+    let events: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(resolve_identity_artifacts_dir(&ws, &doc).join("events.json"))
+            .expect("events"),
+    )
+    .expect("events json");
+    assert_eq!(events[0]["name"].as_str(), Some("snapshot_resume"));
+    assert_eq!(events[0]["fields"]["position"].as_u64(), Some(4));
+
+    let mut tampered = trace.clone();
+    tampered["decisions"][0] =
+        serde_json::json!({"kind": "scheduler_pick", "task_id": 9, "label": "set_kv"});
+    tampered.as_object_mut().expect("object").remove("checksum");
+    std::fs::write(
+        ws.join("tampered.fozzy"),
+        serde_json::to_vec(&tampered).expect("bytes"),
+    )
+    .expect("write");
+    let rejected = run_cli_in(
+        &ws,
+        &[
+            "replay".into(),
+            "tampered.fozzy".into(),
+            "--from-time".into(),
+            "1s".into(),
+            "--json".into(),
+        ],
+    );
+    assert_ne!(rejected.status.code(), Some(0));
+    let doc = parse_json_stdout(&rejected);
+    assert!(
+        doc.get("message")
+            .and_then(|v| v.as_str())
+            .is_some_and(|m| m.contains("does not match the recorded decisions")),
+        "{doc}"
+    );
+}
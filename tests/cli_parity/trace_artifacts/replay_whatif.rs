@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn replay_override_records_parent_linked_counterfactual_trace() {
+    let ws = temp_workspace("replay-whatif");
+    let scenario = ws.join("proc.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{
+          "version":1,
+          "name":"whatif-proc",
+          "steps":[
+            {"type":"proc_when","cmd":"git","args":["status"],"exit_code":0,"stdout":"clean","times":1},
+            {"type":"proc_spawn","cmd":"git","args":["status"],"expect_exit":0}
+          ]
+        }"#,
+    )
+    .expect("write scenario");
+    let parent = ws.join("parent.fozzy");
+    let run = run_cli(&[
+        "run".into(),
+        scenario.to_string_lossy().to_string(),
+        "--det".into(),
+        "--record".into(),
+        parent.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(0), "baseline run should pass");
+
+    let child = ws.join("child.fozzy");
+    let whatif = run_cli(&[
+        "replay".into(),
+        parent.to_string_lossy().to_string(),
+        "--override".into(),
+        "proc_spawn#1:exit_code=2".into(),
+        "--record".into(),
+        child.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    let doc = parse_json_stdout(&whatif);
+    assert_eq!(doc.get("status").and_then(|v| v.as_str()), Some("fail"));
+    assert_eq!(doc.get("mode").and_then(|v| v.as_str()), Some("replay"));
+
+    let trace: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&child).expect("read child")).expect("json");
+    let lineage = trace.get("counterfactual").expect("counterfactual lineage");
+    assert_eq!(
+        lineage.get("parent").and_then(|v| v.as_str()),
+        Some(parent.to_string_lossy().as_ref())
+    );
+    assert_eq!(
+        lineage.get("continuation").and_then(|v| v.as_str()),
+        Some("scripted")
+    );
+    let applied = &lineage["overrides"][0];
+    assert_eq!(applied["recorded"]["exit_code"].as_i64(), Some(0));
+    assert_eq!(applied["value"]["exit_code"].as_i64(), Some(2));
+
+    let replay = run_cli(&[
+        "replay".into(),
+        child.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    let doc = parse_json_stdout(&replay);
+    assert_eq!(
+        doc.get("status").and_then(|v| v.as_str()),
+        Some("fail"),
+        "counterfactual trace replays to the same outcome"
+    );
+
+    let rejected = run_cli(&[
+        "replay".into(),
+        parent.to_string_lossy().to_string(),
+        "--override".into(),
+        "net_drop#1:flip".into(),
+        "--json".into(),
+    ]);
+    assert_eq!(rejected.status.code(), Some(2));
+}
@@ -100,3 +100,293 @@ fn shrink_rejects_unsupported_reporter_flag() {
         "stdout: {stdout}"
     );
 }
+
+#[test]
+fn shrink_simplifies_values_inside_remaining_steps() {
+    let ws = temp_workspace("shrink-values");
+    let scenario = ws.join("shrink-values.fozzy.json");
+    let trace = ws.join("shrink-values.fozzy");
+    let shrunk = ws.join("shrink-values.min.fozzy");
+    let body = "x".repeat(40 * 1024);
+    let doc = serde_json::json!({
+        "version": 1,
+        "name": "shrink-values",
+        "steps": [
+            {"type": "sleep", "duration": "30s"},
+            {"type": "proc_when", "cmd": "tool", "args": ["--a", "--b", "--c"], "exit_code": 7, "stdout": body, "times": 5},
+            {"type": "proc_spawn", "cmd": "tool", "args": ["--a", "--b", "--c"], "expect_exit": 0},
+        ],
+    });
+    std::fs::write(&scenario, doc.to_string()).expect("write scenario");
+
+    let run = run_cli(&[
+        "run".into(),
+        scenario.display().to_string(),
+        "--det".into(),
+        "--record".into(),
+        trace.display().to_string(),
+        "--json".into(),
+    ]);
+    assert_ne!(run.status.code(), Some(0), "source run should fail");
+
+    let shrink = run_cli(&[
+        "shrink".into(),
+        trace.display().to_string(),
+        "--out".into(),
+        shrunk.display().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(
+        shrink.status.code(),
+        Some(1),
+        "shrunk trace keeps the failing status; stderr: {}",
+        String::from_utf8_lossy(&shrink.stderr)
+    );
+    let shrunk_doc = read_trace_json(&shrunk);
+    assert_ne!(shrunk_doc["summary"]["status"], "pass");
+    let steps = shrunk_doc["scenario"]["steps"].to_string();
+    assert!(steps.len() < 200, "steps not simplified: {steps}");
+    assert!(!steps.contains("30s"), "{steps}");
+    assert!(!steps.contains("--b"), "{steps}");
+}
+
+#[test]
+fn shrink_minimizes_schedule_and_faults_for_step_traces() {
+    let ws = temp_workspace("shrink-schedule-faults");
+    let scenario = ws.join("sched.fozzy.json");
+    let trace = ws.join("sched.fozzy");
+    let doc = serde_json::json!({
+        "version": 1,
+        "name": "sched",
+        "steps": [
+            {"type": "net_set_reorder", "enabled": true},
+            {"type": "net_partition", "a": "n3", "b": "n4"},
+            {"type": "memory_fail_after_allocs", "count": 100},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "a"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "b"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "c"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "d"},
+            {"type": "net_deliver_one"},
+            {"type": "net_deliver_one"},
+            {"type": "net_deliver_one"},
+            {"type": "net_deliver_one"},
+            {"type": "fail", "message": "boom"},
+        ],
+    });
+    std::fs::write(&scenario, doc.to_string()).expect("write scenario");
+    let run = run_cli(&[
+        "run".into(),
+        scenario.display().to_string(),
+        "--det".into(),
+        "--seed".into(),
+        "3".into(),
+        "--record".into(),
+        trace.display().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(1), "source run should fail");
+    let picks = |doc: &serde_json::Value| -> Vec<u64> {
+        doc["decisions"]
+            .as_array()
+            .expect("decisions")
+            .iter()
+            .filter(|d| d["kind"] == "net_deliver_pick")
+            .filter_map(|d| d["message_id"].as_u64())
+            .collect()
+    };
+    let recorded = picks(&read_trace_json(&trace));
+    let mut fifo = recorded.clone();
+    fifo.sort_unstable();
+    assert_ne!(recorded, fifo, "seed must record an out-of-order schedule");
+
+    let shrink = |minimize: &str| {
+        let out = ws.join(format!("sched.{minimize}.min.fozzy"));
+        let shrink = run_cli(&[
+            "shrink".into(),
+            trace.display().to_string(),
+            "--minimize".into(),
+            minimize.into(),
+            "--out".into(),
+            out.display().to_string(),
+            "--json".into(),
+        ]);
+        assert_eq!(
+            shrink.status.code(),
+            Some(1),
+            "{minimize} shrink keeps the failure; stderr: {}",
+            String::from_utf8_lossy(&shrink.stderr)
+        );
+        let replay = run_cli(&["replay".into(), out.display().to_string(), "--json".into()]);
+        assert_eq!(
+            replay.status.code(),
+            Some(1),
+            "{minimize} output must replay"
+        );
+        read_trace_json(&out)
+    };
+
+    let scheduled = shrink("schedule");
+    assert_eq!(picks(&scheduled), fifo);
+    assert_eq!(
+        scheduled["scenario"]["steps"].as_array().map(Vec::len),
+        Some(12),
+        "schedule shrinking keeps every step"
+    );
+
+    let faulted = shrink("faults");
+    let kinds: Vec<&str> = faulted["scenario"]["steps"]
+        .as_array()
+        .expect("steps")
+        .iter()
+        .filter_map(|s| s["type"].as_str())
+        .collect();
+    assert!(!kinds.contains(&"net_partition"), "{kinds:?}");
+    assert!(!kinds.contains(&"memory_fail_after_allocs"), "{kinds:?}");
+    assert_eq!(kinds.len(), 10);
+}
+
+#[test]
+fn shrink_preserve_finding_keeps_the_original_failure_and_writes_lineage() {
+    let ws = temp_workspace("shrink-preserve-finding");
+    let scenario = ws.join("kv.fozzy.json");
+    let trace = ws.join("kv.fozzy");
+    let doc = serde_json::json!({
+        "version": 1,
+        "name": "kv",
+        "steps": [
+            {"type": "set_kv", "key": "k", "value": "v"},
+            {"type": "sleep", "duration": "5s"},
+            {"type": "get_kv_assert", "key": "k", "equals": "other"},
+        ],
+    });
+    std::fs::write(&scenario, doc.to_string()).expect("write scenario");
+    let run = run_cli(&[
+        "run".into(),
+        scenario.display().to_string(),
+        "--det".into(),
+        "--record".into(),
+        trace.display().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(1), "source run should fail");
+    let original = read_trace_json(&trace)["summary"]["findings"][0].clone();
+
+    let shrink = |out: &std::path::Path, extra: &[&str]| {
+        let mut args = vec![
+            "shrink".to_string(),
+            trace.display().to_string(),
+            "--out".to_string(),
+            out.display().to_string(),
+            "--json".to_string(),
+        ];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        run_cli(&args)
+    };
+
+    let by_status = ws.join("kv.status.min.fozzy");
+    assert_eq!(shrink(&by_status, &[]).status.code(), Some(1));
+    let status_doc = read_trace_json(&by_status);
+    assert_ne!(
+        status_doc["summary"]["findings"][0], original,
+        "status-only shrinking drifts to a different failure"
+    );
+
+    let by_finding = ws.join("kv.finding.min.fozzy");
+    assert_eq!(
+        shrink(&by_finding, &["--preserve", "finding"])
+            .status
+            .code(),
+        Some(1)
+    );
+    let finding_doc = read_trace_json(&by_finding);
+    assert_eq!(finding_doc["summary"]["findings"][0], original);
+    assert_eq!(
+        finding_doc["scenario"]["steps"].as_array().map(Vec::len),
+        Some(2)
+    );
+    let lineage: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(ws.join("kv.finding.min.lineage.json")).expect("lineage report"),
+    )
+    .expect("lineage json");
+    assert_eq!(lineage["preserve"], "finding");
+    assert_eq!(lineage["reductions"][0]["phase"], "steps");
+    assert_eq!(lineage["reductions"][0]["before"], 3);
+    assert_eq!(lineage["reductions"][0]["after"], 2);
+
+    let unmatched = shrink(
+        &ws.join("kv.regex.min.fozzy"),
+        &["--finding-match", "^never$"],
+    );
+    assert_eq!(unmatched.status.code(), Some(2));
+}
+
+#[test]
+fn shrink_jobs_produce_the_same_trace_as_a_sequential_shrink() {
+    let ws = temp_workspace("shrink-jobs");
+    let scenario = ws.join("jobs.fozzy.json");
+    let trace = ws.join("jobs.fozzy");
+    let doc = serde_json::json!({
+        "version": 1,
+        "name": "jobs",
+        "steps": [
+            {"type": "net_set_reorder", "enabled": true},
+            {"type": "net_partition", "a": "n3", "b": "n4"},
+            {"type": "set_kv", "key": "a", "value": "1"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "a"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "b"},
+            {"type": "net_send", "from": "n1", "to": "n2", "payload": "c"},
+            {"type": "set_kv", "key": "b", "value": "2"},
+            {"type": "net_deliver_one"},
+            {"type": "net_deliver_one"},
+            {"type": "net_deliver_one"},
+            {"type": "fail", "message": "boom"},
+        ],
+    });
+    std::fs::write(&scenario, doc.to_string()).expect("write scenario");
+    let run = run_cli(&[
+        "run".into(),
+        scenario.display().to_string(),
+        "--det".into(),
+        "--seed".into(),
+        "3".into(),
+        "--record".into(),
+        trace.display().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(1), "source run should fail");
+
+    let shrink = |jobs: &str| {
+        let out = ws.join(format!("jobs.{jobs}.min.fozzy"));
+        let shrink = run_cli(&[
+            "shrink".into(),
+            trace.display().to_string(),
+            "--jobs".into(),
+            jobs.into(),
+            "--out".into(),
+            out.display().to_string(),
+            "--json".into(),
+        ]);
+        assert_eq!(
+            shrink.status.code(),
+            Some(1),
+            "--jobs {jobs} shrink keeps the failure; stderr: {}",
+            String::from_utf8_lossy(&shrink.stderr)
+        );
+        let lineage: serde_json::Value = serde_json::from_slice(
+            &std::fs::read(ws.join(format!("jobs.{jobs}.min.lineage.json"))).expect("lineage"),
+        )
+        .expect("lineage json");
+        (read_trace_json(&out), lineage["reductions"].clone())
+    };
+
+    let (sequential, sequential_reductions) = shrink("1");
+    let (parallel, parallel_reductions) = shrink("4");
+    assert_eq!(sequential["scenario"], parallel["scenario"]);
+    assert_eq!(sequential["decisions"], parallel["decisions"]);
+    assert_eq!(sequential_reductions, parallel_reductions);
+    assert_eq!(
+        parallel["scenario"]["steps"].as_array().map(Vec::len),
+        Some(1),
+        "only the failing step is needed"
+    );
+}
@@ -0,0 +1,101 @@
+use super::*;
+
+#[test]
+fn test_record_writes_archive_and_selectors_reach_each_entry() {
+    let ws = temp_workspace("trace-archive");
+    let archive = ws.join("suite.fozzya");
+    let record = |extra: &[&str]| {
+        let mut args: Vec<String> = vec![
+            "test".into(),
+            "tests/proc.fozzy.json".into(),
+            "tests/http.fozzy.json".into(),
+            "--det".into(),
+            "--seed".into(),
+            "4".into(),
+            "--record".into(),
+            archive.to_string_lossy().to_string(),
+            "--json".into(),
+        ];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        run_cli(&args)
+    };
+    assert_eq!(record(&[]).status.code(), Some(0), "test should pass");
+    let again = record(&["--record-collision", "append"]);
+    assert_eq!(again.status.code(), Some(0), "append should pass");
+
+    let doc: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&archive).expect("archive")).expect("json");
+    assert_eq!(doc["format"], "fozzy-trace-archive");
+    let names: Vec<&str> = doc["index"]
+        .as_array()
+        .expect("index")
+        .iter()
+        .filter_map(|e| e["name"].as_str())
+        .collect();
+    assert_eq!(names, vec!["http", "proc", "http.2", "proc.2"]);
+    assert!(doc["index"][0]["checksum"].is_string());
+
+    let selector = format!("{}#proc", archive.display());
+    let replay = run_cli(&["replay".into(), selector.clone(), "--json".into()]);
+    assert_eq!(replay.status.code(), Some(0));
+    assert_eq!(
+        parse_json_stdout(&replay)
+            .get("status")
+            .and_then(|v| v.as_str()),
+        Some("pass")
+    );
+
+    let verify = run_cli(&[
+        "trace".into(),
+        "verify".into(),
+        selector.clone(),
+        "--json".into(),
+    ]);
+    assert_eq!(verify.status.code(), Some(0));
+    assert_eq!(
+        parse_json_stdout(&verify)
+            .get("checksumValid")
+            .and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    let verify_all = run_cli(&[
+        "trace".into(),
+        "verify".into(),
+        archive.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(verify_all.status.code(), Some(0));
+
+    let report = run_cli(&[
+        "report".into(),
+        "query".into(),
+        format!("{}#http-mock", archive.display()),
+        "--path".into(),
+        ".identity.tracePath".into(),
+        "--json".into(),
+    ]);
+    assert_eq!(report.status.code(), Some(2));
+    let ambiguous = String::from_utf8_lossy(&report.stdout).to_string()
+        + &String::from_utf8_lossy(&report.stderr);
+    assert!(ambiguous.contains("ambiguous"), "{ambiguous}");
+
+    let report = run_cli(&[
+        "report".into(),
+        "query".into(),
+        format!("{}#http.2", archive.display()),
+        "--path".into(),
+        ".identity.tracePath".into(),
+        "--json".into(),
+    ]);
+    assert_eq!(report.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&report.stdout).contains("suite.fozzya#http.2"));
+
+    let record_run = run_cli(&[
+        "run".into(),
+        "tests/proc.fozzy.json".into(),
+        "--record".into(),
+        archive.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_ne!(record_run.status.code(), Some(0));
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+#[test]
+fn trace_diff_reports_first_divergent_decision_between_seeds() {
+    let ws = temp_workspace("trace-diff");
+    let record = |seed: &str, name: &str| {
+        let trace = ws.join(name);
+        let run = run_cli(&[
+            "run".into(),
+            "tests/example.fozzy.json".into(),
+            "--det".into(),
+            "--seed".into(),
+            seed.into(),
+            "--record".into(),
+            trace.to_string_lossy().to_string(),
+            "--json".into(),
+        ]);
+        assert_eq!(run.status.code(), Some(0), "run should pass");
+        trace.to_string_lossy().to_string()
+    };
+    let a = record("1", "a.fozzy");
+    let b = record("2", "b.fozzy");
+
+    let same = run_cli(&[
+        "trace".into(),
+        "diff".into(),
+        a.clone(),
+        a.clone(),
+        "--json".into(),
+    ]);
+    assert_eq!(same.status.code(), Some(0));
+    let doc = parse_json_stdout(&same);
+    assert_eq!(
+        doc.get("schemaVersion").and_then(|v| v.as_str()),
+        Some("fozzy.trace_diff.v1")
+    );
+    assert_eq!(doc.get("identical").and_then(|v| v.as_bool()), Some(true));
+
+    let diff = run_cli(&["trace".into(), "diff".into(), a, b, "--json".into()]);
+    assert_eq!(diff.status.code(), Some(1));
+    let doc = parse_json_stdout(&diff);
+    assert_eq!(doc.get("identical").and_then(|v| v.as_bool()), Some(false));
+    let first = doc.get("firstDivergence").expect("firstDivergence");
+    assert_eq!(
+        first.get("leftKind").and_then(|v| v.as_str()),
+        Some("rand_u64")
+    );
+    assert_eq!(
+        first.get("differingFields"),
+        Some(&serde_json::json!(["value"]))
+    );
+}
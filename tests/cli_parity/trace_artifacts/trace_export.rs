@@ -0,0 +1,88 @@
+use super::*;
+
+#[test]
+fn trace_export_writes_chrome_and_perfetto_timelines() {
+    let ws = temp_workspace("trace-export");
+    let scenario = ws.join("mix.fozzy.json");
+    std::fs::write(
+        &scenario,
+        serde_json::to_vec(&serde_json::json!({
+            "version": 1,
+            "name": "mix",
+            "steps": [
+                {"type": "proc_when", "cmd": "git", "args": ["rev-parse"], "exit_code": 0, "stdout": "abc", "times": 1},
+                {"type": "proc_spawn", "cmd": "git", "args": ["rev-parse"], "expect_exit": 0},
+                {"type": "net_send", "from": "n1", "to": "n2", "payload": "hello"},
+                {"type": "net_deliver_one"},
+                {"type": "net_recv_assert", "node": "n2", "from": "n1", "payload": "hello"}
+            ]
+        }))
+        .expect("scenario"),
+    )
+    .expect("write scenario");
+    let trace = ws.join("mix.fozzy");
+    let run = run_cli(&[
+        "run".into(),
+        scenario.to_string_lossy().to_string(),
+        "--det".into(),
+        "--seed".into(),
+        "3".into(),
+        "--record".into(),
+        trace.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(0), "run should pass");
+
+    let chrome = ws.join("mix.json");
+    let out = run_cli(&[
+        "trace".into(),
+        "export".into(),
+        trace.to_string_lossy().to_string(),
+        "--format".into(),
+        "chrome".into(),
+        "--out".into(),
+        chrome.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(out.status.code(), Some(0));
+    let doc = parse_json_stdout(&out);
+    assert_eq!(
+        doc.get("schemaVersion").and_then(|v| v.as_str()),
+        Some("fozzy.trace_export.v1")
+    );
+    assert!(doc.get("slices").and_then(|v| v.as_u64()).unwrap_or(0) > 0);
+    let exported: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&chrome).expect("chrome export")).expect("json");
+    let events = exported["traceEvents"].as_array().expect("traceEvents");
+    let threads: Vec<&str> = events
+        .iter()
+        .filter(|e| e["name"] == "thread_name")
+        .filter_map(|e| e["args"]["name"].as_str())
+        .collect();
+    for track in ["steps", "proc", "node n1", "node n2"] {
+        assert!(threads.contains(&track), "missing {track}: {threads:?}");
+    }
+    assert!(
+        events
+            .iter()
+            .any(|e| e["ph"] == "X" && e["name"] == "net_send")
+    );
+    assert!(events.iter().any(|e| e["ph"] == "s"));
+    assert!(events.iter().any(|e| e["ph"] == "f"));
+
+    let perfetto = ws.join("mix.pftrace");
+    let out = run_cli(&[
+        "trace".into(),
+        "export".into(),
+        trace.to_string_lossy().to_string(),
+        "--format".into(),
+        "perfetto".into(),
+        "--out".into(),
+        perfetto.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(out.status.code(), Some(0));
+    let bytes = std::fs::read(&perfetto).expect("perfetto export");
+    // Every top-level record is `Trace.packet` (field 1, length-delimited).
+    assert_eq!(bytes.first(), Some(&0x0a));
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+#[cfg(unix)]
+#[test]
+fn record_time_redaction_scrubs_proc_stdout_and_replay_still_passes() {
+    let ws = temp_workspace("trace-redact");
+    let scenario = ws.join("secret.fozzy.json");
+    let raw = r#"{
+      "version":1,
+      "name":"secret",
+      "steps":[
+        {"type":"proc_spawn","cmd":"/bin/sh","args":["-c","printf 'token=%s' $((6*7))ab"],
+         "expect_exit":0,"expect_stdout":"token=42ab"}
+      ]
+    }"#;
+    std::fs::write(&scenario, raw).expect("write scenario");
+    let config = ws.join("fozzy.toml");
+    std::fs::write(
+        &config,
+        "[redact]\non_record = true\npatterns = [\"token=([0-9a-z]+)\"]\n",
+    )
+    .expect("write config");
+    let trace = ws.join("secret.fozzy");
+
+    let run = run_cli(&[
+        "--config".into(),
+        config.to_string_lossy().to_string(),
+        "--proc-backend".into(),
+        "host".into(),
+        "run".into(),
+        scenario.to_string_lossy().to_string(),
+        "--det".into(),
+        "--record".into(),
+        trace.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(
+        run.status.code(),
+        Some(0),
+        "stderr={}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+
+    let recorded = std::fs::read_to_string(&trace).expect("read trace");
+    let doc: serde_json::Value = serde_json::from_str(&recorded).expect("trace json");
+    let stdout = doc["decisions"]
+        .as_array()
+        .and_then(|d| d.iter().find(|d| d["kind"] == "proc_spawn"))
+        .and_then(|d| d["stdout"].as_str())
+        .expect("proc decision");
+    assert!(stdout.starts_with("token=[redacted:"), "stdout={stdout}");
+    for stream in ["decisions", "events"] {
+        assert!(
+            !doc[stream].to_string().contains("42ab"),
+            "{stream} must not carry the secret"
+        );
+    }
+    assert_eq!(doc["redaction"]["redacted_values"].as_u64(), Some(2));
+
+    let replay = run_cli(&[
+        "replay".into(),
+        trace.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(replay.status.code(), Some(0), "redacted trace replays");
+    let doc = parse_json_stdout(&replay);
+    assert_eq!(doc.get("status").and_then(|v| v.as_str()), Some("pass"));
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+#[test]
+fn signed_trace_verifies_and_ci_require_signed_pins_the_key() {
+    let ws = temp_workspace("trace-signing");
+    let scenario = ws.join("example.fozzy.json");
+    std::fs::write(&scenario, fixture("example.fozzy.json")).expect("write scenario");
+    let trace = ws.join("example.fozzy");
+    let key = ws.join("release.key");
+    let public_key = ws.join("release.key.pub");
+
+    let run = run_cli(&[
+        "run".into(),
+        scenario.to_string_lossy().to_string(),
+        "--det".into(),
+        "--record".into(),
+        trace.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(0));
+
+    let ci_args = |extra: &[&str]| {
+        let mut args = vec![
+            "ci".to_string(),
+            trace.to_string_lossy().to_string(),
+            "--require-signed".to_string(),
+        ];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        args.push("--json".to_string());
+        args
+    };
+    let keyless = run_cli(&ci_args(&[]));
+    assert_eq!(
+        keyless.status.code(),
+        Some(2),
+        "--require-signed needs --signing-key"
+    );
+
+    let keygen = run_cli(&[
+        "trace".into(),
+        "keygen".into(),
+        "--out".into(),
+        key.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(keygen.status.code(), Some(0));
+    assert!(public_key.exists());
+
+    let unsigned = run_cli(&ci_args(&["--signing-key", &public_key.to_string_lossy()]));
+    assert_eq!(unsigned.status.code(), Some(1), "unsigned trace fails ci");
+
+    let sign = run_cli(&[
+        "trace".into(),
+        "sign".into(),
+        trace.to_string_lossy().to_string(),
+        "--key".into(),
+        key.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(sign.status.code(), Some(0));
+    let doc = parse_json_stdout(&sign);
+    assert_eq!(doc["schemaVersion"], "fozzy.trace_sign.v1");
+    assert_eq!(doc["signature"]["scheme"], "ed25519");
+    let key_id = doc["signature"]["keyId"]
+        .as_str()
+        .expect("key id")
+        .to_string();
+
+    let verify = run_cli(&[
+        "trace".into(),
+        "verify".into(),
+        trace.to_string_lossy().to_string(),
+        "--signing-key".into(),
+        public_key.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(verify.status.code(), Some(0));
+    let doc = parse_json_stdout(&verify);
+    assert_eq!(doc["signature"]["valid"], true);
+    assert_eq!(doc["signature"]["trusted"], true);
+    assert!(doc["signature"]["provenance"]["command_line"].is_array());
+
+    let signed = run_cli(&ci_args(&["--signing-key", &public_key.to_string_lossy()]));
+    assert_eq!(signed.status.code(), Some(0));
+    let doc = parse_json_stdout(&signed);
+    let check = doc["checks"]
+        .as_array()
+        .and_then(|c| c.iter().find(|c| c["name"] == "trace_signature"))
+        .expect("trace_signature check");
+    assert_eq!(check["ok"], true);
+    let detail = check["detail"].as_str().expect("detail");
+    assert!(detail.contains(&format!("signer={key_id} ")), "{detail}");
+    assert!(detail.contains(" engine="), "{detail}");
+
+    let other = ws.join("other.key");
+    let keygen = run_cli(&[
+        "trace".into(),
+        "keygen".into(),
+        "--out".into(),
+        other.to_string_lossy().to_string(),
+    ]);
+    assert_eq!(keygen.status.code(), Some(0));
+    let untrusted = run_cli(&ci_args(&["--signing-key", &other.to_string_lossy()]));
+    assert_eq!(untrusted.status.code(), Some(1), "other key is not trusted");
+}
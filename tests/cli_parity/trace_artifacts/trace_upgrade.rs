@@ -0,0 +1,104 @@
+use super::*;
+
+#[test]
+fn trace_upgrade_migrates_stale_trace_to_replayable_current_schema() {
+    let ws = temp_workspace("trace-upgrade");
+    let recorded = ws.join("recorded.fozzy");
+    let run = run_cli(&[
+        "run".into(),
+        "tests/example.fozzy.json".into(),
+        "--det".into(),
+        "--seed".into(),
+        "7".into(),
+        "--record".into(),
+        recorded.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(run.status.code(), Some(0), "run should pass");
+
+    let mut stale: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&recorded).expect("read trace")).expect("json");
+    stale["version"] = serde_json::json!(2);
+    stale.as_object_mut().expect("object").remove("checksum");
+    stale["summary"]
+        .as_object_mut()
+        .expect("summary")
+        .remove("durationNs");
+    let stale_path = ws.join("stale.fozzy");
+    std::fs::write(&stale_path, serde_json::to_vec(&stale).expect("bytes")).expect("write");
+    std::fs::write(ws.join("broken.fozzy"), b"{}").expect("write");
+
+    let check = run_cli(&[
+        "trace".into(),
+        "upgrade".into(),
+        ws.to_string_lossy().to_string(),
+        "--check".into(),
+        "--json".into(),
+    ]);
+    assert_ne!(
+        check.status.code(),
+        Some(0),
+        "strict check rejects broken traces"
+    );
+
+    let check = run_cli(&[
+        "--unsafe".into(),
+        "trace".into(),
+        "upgrade".into(),
+        ws.to_string_lossy().to_string(),
+        "--check".into(),
+        "--json".into(),
+    ]);
+    assert_eq!(check.status.code(), Some(0));
+    let doc = parse_json_stdout(&check);
+    assert_eq!(
+        doc.get("schemaVersion").and_then(|v| v.as_str()),
+        Some("fozzy.trace_upgrade_check.v1")
+    );
+    assert_eq!(doc.get("current").and_then(|v| v.as_u64()), Some(1));
+    assert_eq!(doc.get("upgradable").and_then(|v| v.as_u64()), Some(1));
+    assert_eq!(doc.get("unsupported").and_then(|v| v.as_u64()), Some(1));
+
+    let upgraded = ws.join("upgraded.fozzy");
+    let out = run_cli(&[
+        "trace".into(),
+        "upgrade".into(),
+        stale_path.to_string_lossy().to_string(),
+        "--out".into(),
+        upgraded.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(out.status.code(), Some(0));
+    let doc = parse_json_stdout(&out);
+    assert_eq!(doc.get("fromVersion").and_then(|v| v.as_u64()), Some(2));
+    assert_eq!(doc.get("toVersion").and_then(|v| v.as_u64()), Some(4));
+    assert_eq!(doc.get("upgraded").and_then(|v| v.as_bool()), Some(true));
+
+    let verify = run_cli(&[
+        "trace".into(),
+        "verify".into(),
+        upgraded.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(
+        verify.status.code(),
+        Some(0),
+        "upgraded trace verifies strictly"
+    );
+    let trace: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&upgraded).expect("read")).expect("json");
+    assert_eq!(
+        trace
+            .get("migrations")
+            .and_then(|v| v.as_array())
+            .map(Vec::len),
+        Some(2)
+    );
+
+    let replay = run_cli(&[
+        "replay".into(),
+        upgraded.to_string_lossy().to_string(),
+        "--json".into(),
+    ]);
+    assert_eq!(replay.status.code(), Some(0), "upgraded trace replays");
+}
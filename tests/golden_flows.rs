@@ -3,8 +3,8 @@ use std::path::PathBuf;
 use fozzy::{
     Config, ExitStatus, ExploreOptions, FsBackend, FuzzMode, FuzzOptions, FuzzTarget, HttpBackend,
     InitTemplate, InitTestType, ProcBackend, RecordCollisionPolicy, ReplayOptions, Reporter,
-    RunOptions, ScenarioPath, ScheduleStrategy, ShrinkMinimize, ShrinkOptions, TracePath, explore,
-    fuzz, init_project, replay_trace, run_scenario, shrink_trace,
+    RunOptions, ScenarioPath, ScheduleStrategy, ShrinkMinimize, ShrinkOptions, TraceEncoding,
+    TracePath, explore, fuzz, init_project, replay_trace, run_scenario, shrink_trace,
 };
 
 fn temp_workspace(name: &str) -> PathBuf {
@@ -51,8 +51,11 @@ fn golden_run_record_replay_shrink_replay_min() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let trace = ws.join("run.trace.fozzy");
     let run = run_scenario(
@@ -66,7 +69,11 @@ fn golden_run_record_replay_shrink_replay_min() {
             record_trace_to: Some(trace.clone()),
             filter: None,
             jobs: None,
+            shard: None,
+            shard_strategy: fozzy::ShardStrategy::Count,
             fail_fast: false,
+            retries: None,
+            quarantine: None,
             record_collision: RecordCollisionPolicy::Overwrite,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             proc_backend: ProcBackend::Scripted,
@@ -87,6 +94,7 @@ fn golden_run_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("replay");
@@ -104,6 +112,9 @@ fn golden_run_record_replay_shrink_replay_min() {
             budget: None,
             aggressive: false,
             minimize: ShrinkMinimize::All,
+            preserve: fozzy::ShrinkPreserve::Status,
+            finding_match: None,
+            jobs: None,
         },
     )
     .expect("shrink");
@@ -118,6 +129,7 @@ fn golden_run_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("replay min");
@@ -144,8 +156,11 @@ fn golden_fuzz_record_replay_shrink_replay_min() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let trace = ws.join("fuzz.trace.fozzy");
     let scenario = ws.join("example.fozzy.json");
@@ -188,6 +203,7 @@ fn golden_fuzz_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("fuzz replay");
@@ -205,6 +221,9 @@ fn golden_fuzz_record_replay_shrink_replay_min() {
             budget: None,
             aggressive: false,
             minimize: ShrinkMinimize::Input,
+            preserve: fozzy::ShrinkPreserve::Status,
+            finding_match: None,
+            jobs: None,
         },
     )
     .expect("fuzz shrink");
@@ -219,6 +238,7 @@ fn golden_fuzz_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("fuzz replay min");
@@ -248,8 +268,11 @@ fn golden_explore_record_replay_shrink_replay_min() {
         mem_artifacts: false,
         profile_heap_alloc_budget: None,
         profile_heap_in_use_budget: None,
+        redact: Default::default(),
+        snapshots: Default::default(),
         mem_fragmentation_seed: None,
         mem_pressure_wave: None,
+        trace_encoding: Default::default(),
     };
     let trace = ws.join("explore.trace.fozzy");
     let run = explore(
@@ -284,6 +307,7 @@ fn golden_explore_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("explore replay");
@@ -301,6 +325,9 @@ fn golden_explore_record_replay_shrink_replay_min() {
             budget: None,
             aggressive: false,
             minimize: ShrinkMinimize::Schedule,
+            preserve: fozzy::ShrinkPreserve::Status,
+            finding_match: None,
+            jobs: None,
         },
     )
     .expect("explore shrink");
@@ -315,6 +342,7 @@ fn golden_explore_record_replay_shrink_replay_min() {
             dump_events: false,
             profile_capture: fozzy::ProfileCaptureLevel::Baseline,
             reporter: Reporter::Json,
+            start: None,
         },
     )
     .expect("explore replay min");
@@ -363,3 +391,102 @@ fn golden_init_scaffold_distributed_pass_succeeds_in_explore() {
     std::env::set_current_dir(prev).expect("restore cwd");
     assert_eq!(run.summary.status, ExitStatus::Pass);
 }
+
+#[test]
+fn golden_binary_trace_streams_while_recording_and_replays() {
+    let ws = temp_workspace("binary");
+    let scenario = ws.join("net.fozzy.json");
+    std::fs::write(
+        &scenario,
+        r#"{
+  "version": 1,
+  "name": "net-deliver",
+  "steps": [
+    { "type": "net_send", "from": "n1", "to": "n2", "payload": "hello" },
+    { "type": "net_deliver_one" },
+    { "type": "net_recv_assert", "node": "n2", "from": "n1", "payload": "hello" }
+  ]
+}"#,
+    )
+    .expect("write scenario");
+
+    let cfg = Config {
+        base_dir: ws.join(".fozzy"),
+        reporter: Reporter::Json,
+        trace_encoding: TraceEncoding::BinaryDeflate,
+        ..Config::default()
+    };
+    let trace = ws.join("net.trace.fozzy");
+    let options = RunOptions {
+        det: true,
+        seed: Some(11),
+        timeout: None,
+        reporter: Reporter::Json,
+        record_trace_to: Some(trace.clone()),
+        filter: None,
+        jobs: None,
+        shard: None,
+        shard_strategy: fozzy::ShardStrategy::Count,
+        fail_fast: false,
+        retries: None,
+        quarantine: None,
+        record_collision: RecordCollisionPolicy::Overwrite,
+        profile_capture: fozzy::ProfileCaptureLevel::Baseline,
+        proc_backend: ProcBackend::Scripted,
+        fs_backend: FsBackend::Virtual,
+        http_backend: HttpBackend::Scripted,
+        memory: fozzy::MemoryOptions::default(),
+    };
+    let run = run_scenario(&cfg, ScenarioPath::new(scenario.clone()), &options).expect("run");
+    assert_eq!(run.summary.status, ExitStatus::Pass);
+    assert!(fozzy::is_binary_trace_file(&trace));
+    assert_eq!(TraceEncoding::of_file(&trace), TraceEncoding::BinaryDeflate);
+    let report = fozzy::verify_trace_file(&trace).expect("verify");
+    assert!(report.checksum_valid);
+    let recorded = fozzy::TraceFile::read_json(&trace).expect("read trace");
+    assert!(!recorded.decisions.is_empty());
+    assert_eq!(
+        recorded.summary.identity.trace_path.as_deref(),
+        Some(trace.to_string_lossy().as_ref())
+    );
+
+    let replay = replay_trace(
+        &cfg,
+        TracePath::new(trace),
+        &ReplayOptions {
+            step: false,
+            until: None,
+            dump_events: false,
+            profile_capture: fozzy::ProfileCaptureLevel::Baseline,
+            reporter: Reporter::Json,
+            start: None,
+        },
+    )
+    .expect("replay");
+    assert_eq!(replay.summary.status, ExitStatus::Pass);
+    assert!(replay.summary.findings.is_empty());
+
+    // A passing run without --record leaves no trace behind.
+    let unrecorded = run_scenario(
+        &cfg,
+        ScenarioPath::new(scenario),
+        &RunOptions {
+            record_trace_to: None,
+            ..options
+        },
+    )
+    .expect("run");
+    let artifacts = PathBuf::from(
+        unrecorded
+            .summary
+            .identity
+            .artifacts_dir
+            .expect("artifacts dir"),
+    );
+    let leftovers: Vec<_> = std::fs::read_dir(&artifacts)
+        .expect("artifacts")
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().contains("trace"))
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+}